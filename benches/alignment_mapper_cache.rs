@@ -0,0 +1,57 @@
+use std::{str::FromStr, sync::Arc};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use hgvs::{
+    mapper::variant::{Config, Mapper},
+    parser::HgvsVariant,
+    validator::ValidationLevel,
+};
+
+mod common;
+
+use common::IdentityProvider;
+
+/// Number of distinct transcripts.
+const N_TRANSCRIPTS: usize = 10;
+/// Number of variants to project per iteration.
+const N_VARIANTS: usize = 10_000;
+
+fn build_mapper(cache_size: usize) -> Mapper {
+    let config = Config {
+        replace_reference: false,
+        prevalidation_level: ValidationLevel::Null,
+        cache_size,
+        ..Default::default()
+    };
+    Mapper::new(&config, Arc::new(IdentityProvider))
+}
+
+fn build_variants() -> Vec<HgvsVariant> {
+    (0..N_VARIANTS)
+        .map(|i| {
+            let s = format!("NM_{:06}.1:c.{}del", i % N_TRANSCRIPTS, i % 900 + 1);
+            HgvsVariant::from_str(&s).expect("invalid variant")
+        })
+        .collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let variants = build_variants();
+
+    let mut group = c.benchmark_group("c_to_n 10k variants on 10 transcripts");
+    group.sample_size(10);
+    for (name, cache_size) in [("uncached", 0), ("cached", 512)] {
+        let mapper = build_mapper(cache_size);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for var_c in &variants {
+                    mapper.c_to_n(var_c).unwrap();
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
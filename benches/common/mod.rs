@@ -0,0 +1,102 @@
+//! Shared code for the mapper benchmarks.
+
+use biocommons_bioutils::assemblies::Assembly;
+use hgvs::data::{
+    error::Error,
+    interface::{
+        GeneInfoRecord, Provider, TxExonsRecord, TxForRegionRecord, TxIdentityInfo, TxInfoRecord,
+        TxMappingOptionsRecord, TxSimilarityRecord,
+    },
+};
+use indexmap::IndexMap;
+
+/// Provider that only knows about the identity information of synthetic transcripts.
+pub struct IdentityProvider;
+
+impl Provider for IdentityProvider {
+    fn data_version(&self) -> &str {
+        "bench"
+    }
+
+    fn schema_version(&self) -> &str {
+        "1.1"
+    }
+
+    fn get_assembly_map(&self, _assembly: Assembly) -> IndexMap<String, String> {
+        panic!("for bench use only");
+    }
+
+    fn get_gene_info(&self, _hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, _tx_ac: &str) -> Result<Option<String>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_seq_part(
+        &self,
+        _ac: &str,
+        _begin: Option<usize>,
+        _end: Option<usize>,
+    ) -> Result<String, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_acs_for_protein_seq(&self, _seq: &str) -> Result<Vec<String>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_similar_transcripts(&self, _tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_exons(
+        &self,
+        _tx_ac: &str,
+        _alt_ac: &str,
+        _alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_for_gene(&self, _gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_for_region(
+        &self,
+        _alt_ac: &str,
+        _alt_aln_method: &str,
+        _start_i: i32,
+        _end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        Ok(TxIdentityInfo {
+            tx_ac: tx_ac.to_string(),
+            alt_ac: tx_ac.to_string(),
+            alt_aln_method: "transcript".to_string(),
+            cds_start_i: 100,
+            cds_end_i: 1000,
+            lengths: vec![300, 300, 600],
+            hgnc: "BENCH".to_string(),
+            translation_table: Default::default(),
+        })
+    }
+
+    fn get_tx_info(
+        &self,
+        _tx_ac: &str,
+        _alt_ac: &str,
+        _alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_mapping_options(&self, _tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        panic!("for bench use only");
+    }
+}
@@ -0,0 +1,230 @@
+use std::{str::FromStr, sync::Arc};
+
+use biocommons_bioutils::assemblies::Assembly;
+use criterion::{criterion_group, criterion_main, Criterion};
+use hgvs::{
+    data::{
+        error::Error,
+        interface::{
+            GeneInfoRecord, Provider, TxExonsRecord, TxForRegionRecord, TxIdentityInfo,
+            TxInfoRecord, TxMappingOptionsRecord, TxSimilarityRecord,
+        },
+    },
+    mapper::variant::{Config, Mapper},
+    parser::HgvsVariant,
+    validator::ValidationLevel,
+};
+use indexmap::IndexMap;
+
+/// Accession of the synthetic transcript.
+const TX_AC: &str = "NM_000001.1";
+/// Accession of the synthetic protein.
+const PRO_AC: &str = "NP_000001.1";
+/// Accession of the genome sequence the transcript is aligned to.
+const ALT_AC: &str = "NC_000001.11";
+/// Start of the alignment on the genome sequence.
+const ALT_START_I: i32 = 10_000;
+/// Number of codons of the transcript, including the stop codon.
+const N_CODONS: usize = 300;
+/// Number of variants to project per iteration.
+const N_VARIANTS: usize = 1_000;
+
+/// Return the sequence of the synthetic transcript, `Met Ala... Ter` without UTRs.
+fn tx_seq() -> String {
+    format!("ATG{}TAA", "GCC".repeat(N_CODONS - 2))
+}
+
+/// Provider serving a single transcript made of one exon aligned to the plus strand.
+struct SingleExonProvider {
+    tx_seq: String,
+}
+
+impl Provider for SingleExonProvider {
+    fn data_version(&self) -> &str {
+        "bench"
+    }
+
+    fn schema_version(&self) -> &str {
+        "1.1"
+    }
+
+    fn get_assembly_map(&self, _assembly: Assembly) -> IndexMap<String, String> {
+        panic!("for bench use only");
+    }
+
+    fn get_gene_info(&self, _hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, _tx_ac: &str) -> Result<Option<String>, Error> {
+        Ok(Some(PRO_AC.to_string()))
+    }
+
+    fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        if ac != TX_AC {
+            return Err(Error::NoSequenceRecord(ac.to_string()));
+        }
+        let end = end.unwrap_or(self.tx_seq.len());
+        Ok(self.tx_seq[begin.unwrap_or_default()..end].to_string())
+    }
+
+    fn get_acs_for_protein_seq(&self, _seq: &str) -> Result<Vec<String>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_similar_transcripts(&self, _tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        let len = self.tx_seq.len() as i32;
+        Ok(vec![TxExonsRecord {
+            hgnc: "BENCH".to_string(),
+            tx_ac: tx_ac.to_string(),
+            alt_ac: alt_ac.to_string(),
+            alt_aln_method: alt_aln_method.to_string(),
+            alt_strand: 1,
+            ord: 0,
+            tx_start_i: 0,
+            tx_end_i: len,
+            alt_start_i: ALT_START_I,
+            alt_end_i: ALT_START_I + len,
+            cigar: format!("{len}="),
+            tx_aseq: None,
+            alt_aseq: None,
+            tx_exon_set_id: 1,
+            alt_exon_set_id: 2,
+            tx_exon_id: 1,
+            alt_exon_id: 2,
+            exon_aln_id: 1,
+        }])
+    }
+
+    fn get_tx_for_gene(&self, _gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_for_region(
+        &self,
+        _alt_ac: &str,
+        _alt_aln_method: &str,
+        _start_i: i32,
+        _end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        Ok(TxIdentityInfo {
+            tx_ac: tx_ac.to_string(),
+            alt_ac: tx_ac.to_string(),
+            alt_aln_method: "transcript".to_string(),
+            cds_start_i: 0,
+            cds_end_i: self.tx_seq.len() as i32,
+            lengths: vec![self.tx_seq.len() as i32],
+            hgnc: "BENCH".to_string(),
+            translation_table: Default::default(),
+        })
+    }
+
+    fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        Ok(TxInfoRecord {
+            hgnc: "BENCH".to_string(),
+            cds_start_i: Some(0),
+            cds_end_i: Some(self.tx_seq.len() as i32),
+            tx_ac: tx_ac.to_string(),
+            alt_ac: alt_ac.to_string(),
+            alt_aln_method: alt_aln_method.to_string(),
+        })
+    }
+
+    fn get_tx_mapping_options(&self, _tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        panic!("for bench use only");
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let tx_seq = tx_seq();
+    let config = Config {
+        replace_reference: false,
+        prevalidation_level: ValidationLevel::Null,
+        renormalize_g: false,
+        ..Default::default()
+    };
+    let mapper = Mapper::new(
+        &config,
+        Arc::new(SingleExonProvider {
+            tx_seq: tx_seq.clone(),
+        }),
+    );
+
+    // Substitutions of the second base of the `GCC` codons, i.e., missense variants.
+    let vars_g = (0..N_VARIANTS)
+        .map(|i| {
+            let pos = (i % (N_CODONS - 2) + 1) * 3 + 1;
+            let reference = &tx_seq[pos..(pos + 1)];
+            let s = format!("{ALT_AC}:g.{}{reference}>A", ALT_START_I as usize + pos + 1);
+            HgvsVariant::from_str(&s).expect("invalid variant")
+        })
+        .collect::<Vec<_>>();
+    let vars_p = (0..N_VARIANTS)
+        .map(|i| {
+            let s = format!("{PRO_AC}:p.Ala{}Val", i % (N_CODONS - 2) + 2);
+            HgvsVariant::from_str(&s).expect("invalid variant")
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("g_to_p 1k variants");
+    group.bench_function("g_to_p", |b| {
+        b.iter(|| {
+            for var_g in &vars_g {
+                mapper.g_to_p(var_g, TX_AC, "splign", None).unwrap();
+            }
+        })
+    });
+    group.bench_function("g_to_c + c_to_p", |b| {
+        b.iter(|| {
+            for var_g in &vars_g {
+                let var_c = mapper.g_to_c(var_g, TX_AC, "splign").unwrap();
+                mapper.c_to_p(&var_c, None).unwrap();
+            }
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("p_to_g 1k variants");
+    group.bench_function("p_to_g", |b| {
+        b.iter(|| {
+            for var_p in &vars_p {
+                mapper.p_to_g(var_p, TX_AC, ALT_AC, "splign").unwrap();
+            }
+        })
+    });
+    group.bench_function("p_to_c + c_to_g", |b| {
+        b.iter(|| {
+            for var_p in &vars_p {
+                let var_c = mapper.p_to_c(var_p, TX_AC).unwrap();
+                mapper.c_to_g(&var_c, ALT_AC, "splign").unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
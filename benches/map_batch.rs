@@ -0,0 +1,53 @@
+use std::{str::FromStr, sync::Arc};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use hgvs::{
+    mapper::variant::{Config, Mapper},
+    parser::HgvsVariant,
+    validator::ValidationLevel,
+};
+
+mod common;
+
+use common::IdentityProvider;
+
+/// Number of variants to project per iteration.
+const N_VARIANTS: usize = 1_000;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let config = Config {
+        replace_reference: false,
+        prevalidation_level: ValidationLevel::Null,
+        ..Default::default()
+    };
+    let mapper = Mapper::new(&config, Arc::new(IdentityProvider));
+    let variants = (0..N_VARIANTS)
+        .map(|i| {
+            let s = format!("NM_{:06}.1:c.{}del", i % 10, i % 900 + 1);
+            HgvsVariant::from_str(&s).expect("invalid variant")
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("c_to_n 1k variants");
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            variants
+                .iter()
+                .map(|var_c| mapper.c_to_n(var_c))
+                .collect::<Vec<_>>()
+        })
+    });
+    for threads in [1, 2, 4, 8] {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("could not build thread pool");
+        group.bench_with_input(BenchmarkId::new("map_batch", threads), &threads, |b, _| {
+            b.iter(|| pool.install(|| mapper.c_to_n_batch(&variants)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
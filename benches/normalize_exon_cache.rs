@@ -0,0 +1,242 @@
+use std::{str::FromStr, sync::Arc};
+
+use biocommons_bioutils::assemblies::Assembly;
+use criterion::{criterion_group, criterion_main, Criterion};
+use hgvs::{
+    data::{
+        error::Error,
+        interface::{
+            GeneInfoRecord, Provider, TxExonsRecord, TxForRegionRecord, TxIdentityInfo,
+            TxInfoRecord, TxMappingOptionsRecord, TxSimilarityRecord,
+        },
+    },
+    mapper::variant::{Config, Mapper},
+    normalizer::{self, Normalizer},
+    parser::HgvsVariant,
+    validator::NullValidator,
+};
+use indexmap::IndexMap;
+
+/// Accession of the synthetic transcript.
+const TX_AC: &str = "NM_000001.1";
+/// Accession of the genome sequence the transcript is aligned to.
+const ALT_AC: &str = "NC_000001.11";
+/// Number of exons of the transcript.
+const N_EXONS: i32 = 30;
+/// Length of each exon.
+const EXON_LEN: i32 = 100;
+/// Number of variants to normalize per iteration.
+const N_VARIANTS: i32 = 1_000;
+
+/// Return a pseudo-random transcript sequence without long repeats.
+fn tx_seq() -> String {
+    let mut state = 42u32;
+    (0..N_EXONS * EXON_LEN)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            ['A', 'C', 'G', 'T'][(state >> 16) as usize % 4]
+        })
+        .collect()
+}
+
+/// Provider serving a single transcript with `N_EXONS` exons, listed in reverse order.
+struct MultiExonProvider {
+    tx_seq: String,
+}
+
+impl Provider for MultiExonProvider {
+    fn data_version(&self) -> &str {
+        "bench"
+    }
+
+    fn schema_version(&self) -> &str {
+        "1.1"
+    }
+
+    fn get_assembly_map(&self, _assembly: Assembly) -> IndexMap<String, String> {
+        panic!("for bench use only");
+    }
+
+    fn get_gene_info(&self, _hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, _tx_ac: &str) -> Result<Option<String>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        if ac != TX_AC {
+            return Err(Error::NoSequenceRecord(ac.to_string()));
+        }
+        let begin = begin.unwrap_or_default().min(self.tx_seq.len());
+        let end = end.unwrap_or(self.tx_seq.len()).min(self.tx_seq.len());
+        Ok(self.tx_seq[begin..end].to_string())
+    }
+
+    fn get_acs_for_protein_seq(&self, _seq: &str) -> Result<Vec<String>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_similar_transcripts(&self, _tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        Ok((0..N_EXONS)
+            .rev()
+            .map(|ord| {
+                let tx_start_i = ord * EXON_LEN;
+                let alt_start_i = 10_000 + ord * 2 * EXON_LEN;
+                TxExonsRecord {
+                    hgnc: "BENCH".to_string(),
+                    tx_ac: tx_ac.to_string(),
+                    alt_ac: alt_ac.to_string(),
+                    alt_aln_method: alt_aln_method.to_string(),
+                    alt_strand: 1,
+                    ord,
+                    tx_start_i,
+                    tx_end_i: tx_start_i + EXON_LEN,
+                    alt_start_i,
+                    alt_end_i: alt_start_i + EXON_LEN,
+                    cigar: format!("{EXON_LEN}="),
+                    tx_aseq: None,
+                    alt_aseq: None,
+                    tx_exon_set_id: 1,
+                    alt_exon_set_id: 2,
+                    tx_exon_id: ord,
+                    alt_exon_id: ord,
+                    exon_aln_id: ord,
+                }
+            })
+            .collect())
+    }
+
+    fn get_tx_for_gene(&self, _gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_for_region(
+        &self,
+        _alt_ac: &str,
+        _alt_aln_method: &str,
+        _start_i: i32,
+        _end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        panic!("for bench use only");
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        Ok(TxIdentityInfo {
+            tx_ac: tx_ac.to_string(),
+            alt_ac: tx_ac.to_string(),
+            alt_aln_method: "transcript".to_string(),
+            cds_start_i: 0,
+            cds_end_i: 0,
+            lengths: vec![EXON_LEN; N_EXONS as usize],
+            hgnc: "BENCH".to_string(),
+            translation_table: Default::default(),
+        })
+    }
+
+    fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        Ok(TxInfoRecord {
+            hgnc: "BENCH".to_string(),
+            cds_start_i: None,
+            cds_end_i: None,
+            tx_ac: tx_ac.to_string(),
+            alt_ac: alt_ac.to_string(),
+            alt_aln_method: alt_aln_method.to_string(),
+        })
+    }
+
+    fn get_tx_mapping_options(&self, tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        Ok(vec![TxMappingOptionsRecord {
+            tx_ac: tx_ac.to_string(),
+            alt_ac: ALT_AC.to_string(),
+            alt_aln_method: "splign".to_string(),
+        }])
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let provider = Arc::new(MultiExonProvider { tx_seq: tx_seq() });
+    let mapper = Mapper::new(
+        &Config {
+            replace_reference: false,
+            ..Default::default()
+        },
+        provider.clone(),
+    );
+    let config = normalizer::Config::builder()
+        .replace_reference(false)
+        .validate(false)
+        .build();
+    let build_normalizer = || {
+        Normalizer::new(
+            &mapper,
+            provider.clone(),
+            Arc::new(NullValidator::new()),
+            config.clone(),
+        )
+    };
+
+    // Single-base deletions in the middle of the exons.
+    let variants = (0..N_VARIANTS)
+        .map(|i| {
+            let pos = (i % N_EXONS) * EXON_LEN + EXON_LEN / 2 + i % 10;
+            HgvsVariant::from_str(&format!("{TX_AC}:n.{pos}del")).expect("invalid variant")
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("normalize 1k variants on 30 exons");
+    for (name, normalizer) in [
+        ("uncached", build_normalizer()),
+        ("exon cache", build_normalizer().with_exon_cache()),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for var in &variants {
+                    normalizer.normalize(var).unwrap();
+                }
+            })
+        });
+    }
+    group.finish();
+
+    let normalizer = build_normalizer().with_exon_cache();
+    let mut group = c.benchmark_group("normalized position of 1k variants on 30 exons");
+    group.bench_function("normalize", |b| {
+        b.iter(|| {
+            for var in &variants {
+                normalizer.normalize(var).unwrap().loc_range().unwrap();
+            }
+        })
+    });
+    group.bench_function("get_normalized_position", |b| {
+        b.iter(|| {
+            for var in &variants {
+                normalizer.get_normalized_position(var).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
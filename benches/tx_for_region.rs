@@ -0,0 +1,68 @@
+use std::ops::Range;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use hgvs::data::{interface::TxForRegionRecord, interval_tree::IntervalTree};
+
+/// Number of transcripts on the contig.
+const N_TRANSCRIPTS: i32 = 100_000;
+/// Distance between the starts of consecutive transcripts.
+const TX_DISTANCE: i32 = 1_000;
+/// Number of regions to look up per iteration.
+const N_QUERIES: i32 = 1_000;
+
+/// Return overlapping transcripts with lengths between 2 and 50 kbp.
+fn build_records() -> Vec<(Range<i32>, TxForRegionRecord)> {
+    (0..N_TRANSCRIPTS)
+        .map(|i| {
+            let start_i = i * TX_DISTANCE;
+            let end_i = start_i + 2_000 + (i * 7_919) % 48_000;
+            let record = TxForRegionRecord {
+                tx_ac: format!("NM_{:06}.1", i),
+                alt_ac: "NC_000001.11".to_string(),
+                alt_strand: 1,
+                alt_aln_method: "splign".to_string(),
+                start_i,
+                end_i,
+            };
+            (start_i..end_i, record)
+        })
+        .collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let records = build_records();
+    let tree = records.iter().cloned().collect::<IntervalTree<_, _>>();
+    let queries = (0..N_QUERIES)
+        .map(|i| {
+            let start_i = (i * 104_729) % (N_TRANSCRIPTS * TX_DISTANCE);
+            start_i..(start_i + 1)
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("get_tx_for_region 1k queries on 100k transcripts");
+    group.bench_function("linear", |b| {
+        b.iter(|| {
+            queries
+                .iter()
+                .map(|query| {
+                    records
+                        .iter()
+                        .filter(|(range, _)| range.start < query.end && query.start < range.end)
+                        .count()
+                })
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("interval tree", |b| {
+        b.iter(|| {
+            queries
+                .iter()
+                .map(|query| tree.query(query.clone()).count())
+                .sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,149 @@
+//! Conversion of HGVS genome variants to BED records.
+
+use std::io::Write;
+
+use biocommons_bioutils::assemblies::Assembly;
+
+use crate::{
+    conversion::vcf::accession_to_chrom,
+    parser::{HgvsVariant, NaEdit},
+};
+
+impl HgvsVariant {
+    /// Return the variant as a 6-column BED line without trailing newline.
+    ///
+    /// The columns are the chromosome name in `assembly`, the 0-based, half-open start and
+    /// end positions, the HGVS expression as name, the score `0`, and the strand `.`.
+    /// Insertions yield an empty interval between their flanking bases.
+    ///
+    /// Returns `None` for variants other than `g.` and `m.` variants, for variants with
+    /// unknown positions, and for accessions not in `assembly`.
+    pub fn to_bed_line(&self, assembly: Assembly) -> Option<String> {
+        let (accession, start, end, edit) = match self {
+            HgvsVariant::GenomeVariant {
+                accession,
+                loc_edit,
+                ..
+            } => (
+                accession,
+                loc_edit.loc.inner().start?,
+                loc_edit.loc.inner().end?,
+                loc_edit.edit.inner(),
+            ),
+            HgvsVariant::MtVariant {
+                accession,
+                loc_edit,
+                ..
+            } => (
+                accession,
+                loc_edit.loc.inner().start?,
+                loc_edit.loc.inner().end?,
+                loc_edit.edit.inner(),
+            ),
+            _ => return None,
+        };
+        let chrom = accession_to_chrom(accession, assembly).ok()?;
+        let (start, end) = match edit {
+            NaEdit::Ins { .. } => (start, end - 1),
+            _ => (start - 1, end),
+        };
+
+        Some(format!("{chrom}\t{start}\t{end}\t{self}\t0\t."))
+    }
+}
+
+/// Write the BED lines of `variants` to `writer`.
+///
+/// Variants without BED representation, see `HgvsVariant::to_bed_line()`, are skipped.
+pub fn to_bed_file<'a, W: Write>(
+    variants: impl Iterator<Item = &'a HgvsVariant>,
+    assembly: Assembly,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    for line in variants.filter_map(|var| var.to_bed_line(assembly)) {
+        writeln!(writer, "{line}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use biocommons_bioutils::assemblies::Assembly;
+    use pretty_assertions::assert_eq;
+
+    use crate::parser::HgvsVariant;
+
+    #[rstest::rstest]
+    #[case(
+        "NC_000001.10:g.100A>T",
+        Some("1\t99\t100\tNC_000001.10:g.100A>T\t0\t.")
+    )]
+    #[case(
+        "NC_000001.10:g.100_102del",
+        Some("1\t99\t102\tNC_000001.10:g.100_102del\t0\t.")
+    )]
+    #[case(
+        "NC_000001.10:g.100_101insAT",
+        Some("1\t100\t100\tNC_000001.10:g.100_101insAT\t0\t.")
+    )]
+    #[case(
+        "NC_000023.10:g.100_101delinsT",
+        Some("X\t99\t101\tNC_000023.10:g.100_101delinsT\t0\t.")
+    )]
+    #[case(
+        "NC_012920.1:m.8993T>G",
+        Some("MT\t8992\t8993\tNC_012920.1:m.8993T>G\t0\t.")
+    )]
+    #[case("NC_000001.11:g.100A>T", None)]
+    #[case("NC_000001.10:g.?_100del", None)]
+    #[case("NM_000088.3:c.100A>T", None)]
+    fn to_bed_line(
+        #[case] hgvs: &str,
+        #[case] expected: Option<&str>,
+    ) -> Result<(), anyhow::Error> {
+        let var = HgvsVariant::from_str(hgvs)?;
+        assert_eq!(var.to_bed_line(Assembly::Grch37p10).as_deref(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_bed_file() -> Result<(), anyhow::Error> {
+        let variants = [
+            "NC_000001.10:g.100A>T",
+            "NM_000088.3:c.100A>T",
+            "NC_000001.10:g.100_101insAT",
+        ]
+        .iter()
+        .map(|s| HgvsVariant::from_str(s))
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let mut buf = Vec::new();
+        super::to_bed_file(variants.iter(), Assembly::Grch37p10, &mut buf)?;
+        assert_eq!(
+            String::from_utf8(buf)?,
+            "1\t99\t100\tNC_000001.10:g.100A>T\t0\t.\n\
+            1\t100\t100\tNC_000001.10:g.100_101insAT\t0\t.\n"
+        );
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
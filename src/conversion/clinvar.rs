@@ -0,0 +1,275 @@
+//! Extraction of HGVS expressions from ClinVar XML files.
+//!
+//! The HGVS expressions are read from the `Attribute` elements with a `Type` starting with
+//! `HGVS` in `MeasureSet/Measure/AttributeSet`.  The expression is taken from the `Value`
+//! attribute if present and from the element text otherwise.  Each expression is returned
+//! together with the accession of the preceding `ClinVarAccession` element, e.g., the RCV
+//! accession of a reference assertion.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::Path,
+    str::FromStr,
+};
+
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use crate::{conversion::Error, parser::HgvsVariant};
+
+/// Element path from `MeasureSet` to the `AttributeSet` holding the HGVS attributes.
+const ATTRIBUTE_SET_PATH: [&[u8]; 3] = [b"MeasureSet", b"Measure", b"AttributeSet"];
+
+/// Lazily extract the HGVS expressions from the ClinVar XML read from `reader`.
+///
+/// Malformed HGVS expressions yield an `Error::InvalidHgvs` and extraction continues with
+/// the next expression.  Malformed XML yields an `Error::ClinvarXml` and ends the iteration.
+pub fn extract_hgvs_from_clinvar_xml<R: Read>(
+    reader: R,
+) -> impl Iterator<Item = Result<(String, HgvsVariant), Error>> {
+    HgvsAttributes {
+        reader: Reader::from_reader(BufReader::new(reader)),
+        buf: Vec::new(),
+        path: Vec::new(),
+        accession: String::new(),
+        text: None,
+        done: false,
+    }
+}
+
+/// Lazily extract the HGVS expressions from the ClinVar XML file at `path`, see
+/// `extract_hgvs_from_clinvar_xml()`.
+///
+/// Files ending in `.gz` are decompressed.
+pub fn extract_hgvs_from_clinvar_file(
+    path: &Path,
+) -> Result<impl Iterator<Item = Result<(String, HgvsVariant), Error>>, Error> {
+    let file = File::open(path)
+        .map_err(|e| Error::ClinvarXml(format!("cannot open {}: {}", path.display(), e)))?;
+    let reader: Box<dyn Read> = if path.extension().is_some_and(|ext| ext == "gz") {
+        Box::new(flate2::bufread::MultiGzDecoder::new(BufReader::new(file)))
+    } else {
+        Box::new(file)
+    };
+    Ok(extract_hgvs_from_clinvar_xml(reader))
+}
+
+/// Iterator over the HGVS attributes of a ClinVar XML document.
+struct HgvsAttributes<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    /// Names of the currently open elements.
+    path: Vec<Vec<u8>>,
+    /// Accession of the last `ClinVarAccession` element.
+    accession: String,
+    /// Text collected for the currently open HGVS `Attribute` element without `Value`.
+    text: Option<String>,
+    /// Whether the end of the document or an XML error was reached.
+    done: bool,
+}
+
+impl<R: BufRead> HgvsAttributes<R> {
+    /// Return whether the open elements end in `MeasureSet/Measure/AttributeSet`.
+    fn in_attribute_set(&self) -> bool {
+        self.path.len() >= ATTRIBUTE_SET_PATH.len()
+            && self.path[self.path.len() - ATTRIBUTE_SET_PATH.len()..]
+                .iter()
+                .zip(ATTRIBUTE_SET_PATH)
+                .all(|(name, expected)| name == expected)
+    }
+
+    /// Handle a start or empty element.
+    ///
+    /// Returns the HGVS expression of `Attribute` elements with `Value` and starts collecting
+    /// the text of those without.
+    fn handle_start(&mut self, e: &BytesStart, is_empty: bool) -> Result<Option<String>, Error> {
+        let attribute = |name: &str| -> Result<Option<String>, Error> {
+            e.try_get_attribute(name)
+                .map_err(|e| Error::ClinvarXml(e.to_string()))?
+                .map(|attr| {
+                    attr.unescape_value()
+                        .map(|value| value.into_owned())
+                        .map_err(|e| Error::ClinvarXml(e.to_string()))
+                })
+                .transpose()
+        };
+
+        let mut result = None;
+        match e.name().as_ref() {
+            b"ClinVarAccession" => {
+                if let Some(accession) = attribute("Acc")? {
+                    self.accession = accession;
+                }
+            }
+            b"Attribute"
+                if self.in_attribute_set()
+                    && attribute("Type")?.is_some_and(|t| t.starts_with("HGVS")) =>
+            {
+                match attribute("Value")? {
+                    Some(value) => result = Some(value),
+                    None if !is_empty => self.text = Some(String::new()),
+                    None => (),
+                }
+            }
+            _ => (),
+        }
+        if !is_empty {
+            self.path.push(e.name().as_ref().to_vec());
+        }
+        Ok(result)
+    }
+
+    /// Read events until the next HGVS expression or the end of the document.
+    fn next_expression(&mut self) -> Result<Option<String>, Error> {
+        loop {
+            self.buf.clear();
+            let event = self
+                .reader
+                .read_event_into(&mut self.buf)
+                .map_err(|e| Error::ClinvarXml(e.to_string()))?
+                .into_owned();
+            match event {
+                Event::Start(e) => {
+                    if let Some(value) = self.handle_start(&e, false)? {
+                        return Ok(Some(value));
+                    }
+                }
+                Event::Empty(e) => {
+                    if let Some(value) = self.handle_start(&e, true)? {
+                        return Ok(Some(value));
+                    }
+                }
+                Event::Text(e) => {
+                    if let Some(text) = self.text.as_mut() {
+                        let unescaped =
+                            e.unescape().map_err(|e| Error::ClinvarXml(e.to_string()))?;
+                        text.push_str(&unescaped);
+                    }
+                }
+                Event::End(_) => {
+                    self.path.pop();
+                    if let Some(text) = self.text.take() {
+                        return Ok(Some(text.trim().to_string()));
+                    }
+                }
+                Event::Eof => return Ok(None),
+                _ => (),
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for HgvsAttributes<R> {
+    type Item = Result<(String, HgvsVariant), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_expression() {
+            Ok(Some(expression)) => Some(
+                HgvsVariant::from_str(&expression)
+                    .map(|var| (self.accession.clone(), var))
+                    .map_err(|e| Error::InvalidHgvs(expression, e)),
+            ),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{extract_hgvs_from_clinvar_file, extract_hgvs_from_clinvar_xml};
+    use crate::conversion::Error;
+
+    #[test]
+    fn extract_from_file() -> Result<(), Error> {
+        let results =
+            extract_hgvs_from_clinvar_file(Path::new("tests/data/conversion/clinvar_snippet.xml"))?
+                .map(|result| result.map(|(accession, var)| (accession, format!("{var}"))))
+                .collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 6);
+        let ok = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok().cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ok,
+            vec![
+                (
+                    "RCV000000012".to_string(),
+                    "NM_014855.3:c.80_83del".to_string()
+                ),
+                (
+                    "RCV000000012".to_string(),
+                    "NC_000019.10:g.1918034_1918037del".to_string()
+                ),
+                (
+                    "RCV000000012".to_string(),
+                    "NP_055670.1:p.Gly27fsTer".to_string()
+                ),
+                (
+                    "RCV000000013".to_string(),
+                    "NM_000059.3:c.68-7T>A".to_string()
+                ),
+                (
+                    "RCV000000013".to_string(),
+                    "NC_000013.11:g.32319070T>A".to_string()
+                ),
+            ]
+        );
+        assert!(matches!(
+            &results[4],
+            Err(Error::InvalidHgvs(expression, _)) if expression == "NM_000059.3:c.68-7T/A"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn extract_malformed_xml() {
+        let xml = r#"<ReleaseSet><ClinVarSet><MeasureSet><Measure></MeasureSet>"#;
+        let results = extract_hgvs_from_clinvar_xml(xml.as_bytes()).collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Err(Error::ClinvarXml(_))));
+    }
+
+    #[test]
+    fn extract_missing_file() {
+        assert!(matches!(
+            extract_hgvs_from_clinvar_file(Path::new("tests/data/conversion/missing.xml")),
+            Err(Error::ClinvarXml(_))
+        ));
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
@@ -0,0 +1,30 @@
+//! Error type definition.
+
+use thiserror::Error;
+
+/// Error type for conversion of variants.
+#[derive(Error, Debug, Clone)]
+pub enum Error {
+    #[error("unknown chromosome {0} in assembly {1}")]
+    UnknownChromosome(String, String),
+    #[error("unknown accession {0} in assembly {1}")]
+    UnknownAccession(String, String),
+    #[error("invalid allele: {0}")]
+    InvalidAllele(String),
+    #[error("invalid position: {0}")]
+    InvalidPosition(String),
+    #[error("expected a GenomeVariant but received {0}")]
+    ExpectedGenomeVariant(String),
+    #[error("cannot convert variant with unknown positions: {0}")]
+    UnknownPosition(String),
+    #[error("cannot convert variant without reference bases: {0}")]
+    MissingReference(String),
+    #[error("cannot convert repeat without full reference stretch and exact count: {0}")]
+    UnsupportedRepeat(String),
+    #[error("problem reading ClinVar XML: {0}")]
+    ClinvarXml(String),
+    #[error("invalid HGVS expression {0}")]
+    InvalidHgvs(String, #[source] crate::parser::Error),
+    #[error("problem accessing data")]
+    DataError(#[from] crate::data::error::Error),
+}
@@ -0,0 +1,24 @@
+//! Conversion between HGVS and other variant representations.
+
+pub mod bed;
+#[cfg(feature = "clinvar")]
+pub mod clinvar;
+mod error;
+pub mod spdi;
+pub mod vcf;
+
+pub use error::Error;
+
+/// Check that `allele` is a string of nucleotides, which must be non-empty unless
+/// `allow_empty` is set.
+pub(crate) fn check_allele(allele: &str, allow_empty: bool) -> Result<(), Error> {
+    if (allow_empty || !allele.is_empty())
+        && allele
+            .chars()
+            .all(|c| matches!(c.to_ascii_uppercase(), 'A' | 'C' | 'G' | 'T' | 'N'))
+    {
+        Ok(())
+    } else {
+        Err(Error::InvalidAllele(allele.to_string()))
+    }
+}
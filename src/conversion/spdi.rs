@@ -0,0 +1,235 @@
+//! Conversion between SPDI (sequence, position, deletion, insertion) tuples and HGVS genome
+//! variants.
+//!
+//! SPDI is the representation used by the NCBI Variation Services.  Positions are 0-based
+//! interbase coordinates and the deleted sequence is always fully specified.
+
+use crate::{
+    conversion::{check_allele, Error},
+    parser::{Accession, GenomeInterval, GenomeLocEdit, HgvsVariant, Mu, NaEdit},
+    sequences::{revcomp, trim_common_prefixes, trim_common_suffixes},
+};
+
+/// Construct the SPDI tuple `(sequence_id, position, deletion, insertion)` of a genome variant.
+///
+/// As no reference sequence is available, the variant must specify the reference bases of
+/// deletions, duplications, and inversions (e.g., `delAC` rather than `del`).
+///
+/// # Args
+///
+/// * `var` -- `HgvsVariant::GenomeVariant` to convert
+pub fn g_to_spdi(var: &HgvsVariant) -> Result<(String, i64, String, String), Error> {
+    let (accession, loc_edit) = if let HgvsVariant::GenomeVariant {
+        accession,
+        loc_edit,
+        ..
+    } = var
+    {
+        (accession, loc_edit)
+    } else {
+        return Err(Error::ExpectedGenomeVariant(format!("{}", var)));
+    };
+    let (start, end) = match loc_edit.loc.inner() {
+        GenomeInterval {
+            start: Some(start),
+            end: Some(end),
+        } if *start > 0 && start <= end => (*start as i64, *end as i64),
+        _ => return Err(Error::UnknownPosition(format!("{}", var))),
+    };
+
+    // The reference bases of the variant, must span the whole interval.
+    let reference = |reference: &str| -> Result<String, Error> {
+        if reference.len() as i64 == end - start + 1 {
+            Ok(reference.to_uppercase())
+        } else {
+            Err(Error::MissingReference(format!("{}", var)))
+        }
+    };
+
+    let (pos, deletion, insertion) = match loc_edit.edit.inner() {
+        // The insertion is located between `start` and `end`.
+        NaEdit::Ins { alternative } => (start, String::new(), alternative.to_uppercase()),
+        NaEdit::RefAlt {
+            reference: del,
+            alternative,
+        } => (start - 1, reference(del)?, alternative.to_uppercase()),
+        NaEdit::DelRef { reference: del } => (start - 1, reference(del)?, String::new()),
+        NaEdit::Dup { reference: del } => {
+            let del = reference(del)?;
+            (start - 1, del.clone(), del.repeat(2))
+        }
+        NaEdit::InvRef { reference: del } => {
+            let del = reference(del)?;
+            (start - 1, del.clone(), revcomp(&del))
+        }
+        NaEdit::NumAlt { .. } | NaEdit::DelNum { .. } | NaEdit::InvNum { .. } => {
+            return Err(Error::MissingReference(format!("{}", var)))
+        }
+        NaEdit::Repeat { .. } => return Err(Error::UnsupportedRepeat(format!("{}", var))),
+    };
+
+    Ok((accession.value.clone(), pos, deletion, insertion))
+}
+
+/// Construct a genome variant from an SPDI tuple.
+///
+/// Common prefixes and suffixes of the deleted and inserted sequence are trimmed, such that
+/// the result is a substitution, deletion, insertion, or deletion-insertion.
+///
+/// # Args
+///
+/// * `seq_id` -- accession of the sequence, e.g., `"NC_000012.12"`
+/// * `pos` -- 0-based interbase position of the deletion or insertion
+/// * `del` -- deleted sequence
+/// * `ins` -- inserted sequence
+pub fn spdi_to_g(seq_id: &str, pos: i64, del: &str, ins: &str) -> Result<HgvsVariant, Error> {
+    check_allele(del, true)?;
+    check_allele(ins, true)?;
+    if del.is_empty() && ins.is_empty() {
+        return Err(Error::InvalidAllele(format!("{seq_id}:{pos}::")));
+    }
+    let pos = i32::try_from(pos)
+        .ok()
+        .filter(|pos| *pos >= 0)
+        .ok_or_else(|| Error::InvalidPosition(pos.to_string()))?;
+    let del = del.to_uppercase();
+    let ins = ins.to_uppercase();
+
+    let (start, end, edit) = if del == ins {
+        (
+            pos + 1,
+            pos + del.len() as i32,
+            NaEdit::RefAlt {
+                reference: del.clone(),
+                alternative: del,
+            },
+        )
+    } else {
+        let (prefix, reference, alternative) = trim_common_prefixes(&del, &ins);
+        let (_, reference, alternative) = trim_common_suffixes(&reference, &alternative);
+        let start = pos + prefix as i32 + 1;
+        let end = start + reference.len() as i32 - 1;
+        match (reference.len(), alternative.len()) {
+            // HGVS cannot describe an insertion before the first base of a sequence.
+            (0, _) if start == 1 => return Err(Error::InvalidPosition(pos.to_string())),
+            (0, _) => (start - 1, start, NaEdit::Ins { alternative }),
+            (_, 0) => (start, end, NaEdit::DelRef { reference }),
+            (_, _) => (
+                start,
+                end,
+                NaEdit::RefAlt {
+                    reference,
+                    alternative,
+                },
+            ),
+        }
+    };
+
+    Ok(HgvsVariant::GenomeVariant {
+        accession: Accession::from(seq_id.to_string()),
+        gene_symbol: None,
+        loc_edit: GenomeLocEdit {
+            loc: Mu::Certain(GenomeInterval {
+                start: Some(start),
+                end: Some(end),
+            }),
+            edit: Mu::Certain(edit),
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use anyhow::Error;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::parser::HgvsVariant;
+
+    use super::{g_to_spdi, spdi_to_g};
+
+    #[rstest]
+    // rs671 (ALDH2) on GRCh38.
+    #[case("NC_000012.12:g.111803962G>A", "NC_000012.12", 111803961, "G", "A")]
+    #[case("NC_000007.13:g.101_103delCGT", "NC_000007.13", 100, "CGT", "")]
+    #[case("NC_000007.13:g.100_101insCG", "NC_000007.13", 100, "", "CG")]
+    #[case("NC_000007.13:g.100_101delACinsGT", "NC_000007.13", 99, "AC", "GT")]
+    #[case("NC_000007.13:g.100A=", "NC_000007.13", 99, "A", "A")]
+    fn roundtrip(
+        #[case] hgvs_g: &str,
+        #[case] seq_id: &str,
+        #[case] pos: i64,
+        #[case] del: &str,
+        #[case] ins: &str,
+    ) -> Result<(), Error> {
+        let var_g = HgvsVariant::from_str(hgvs_g)?;
+        assert_eq!(
+            g_to_spdi(&var_g)?,
+            (seq_id.to_string(), pos, del.to_string(), ins.to_string())
+        );
+        assert_eq!(spdi_to_g(seq_id, pos, del, ins)?, var_g);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("NC_000007.13:g.100dupA", "NC_000007.13", 99, "A", "AA")]
+    #[case("NC_000007.13:g.100_101invAC", "NC_000007.13", 99, "AC", "GT")]
+    fn g_to_spdi_expands(
+        #[case] hgvs_g: &str,
+        #[case] seq_id: &str,
+        #[case] pos: i64,
+        #[case] del: &str,
+        #[case] ins: &str,
+    ) -> Result<(), Error> {
+        let var_g = HgvsVariant::from_str(hgvs_g)?;
+        assert_eq!(
+            g_to_spdi(&var_g)?,
+            (seq_id.to_string(), pos, del.to_string(), ins.to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn spdi_to_g_trims() -> Result<(), Error> {
+        let var = spdi_to_g("NC_000007.13", 99, "ACG", "ATG")?;
+        assert_eq!(format!("{}", &var), "NC_000007.13:g.101C>T");
+        let var = spdi_to_g("NC_000007.13", 99, "AA", "AAA")?;
+        assert_eq!(format!("{}", &var), "NC_000007.13:g.101_102insA");
+
+        Ok(())
+    }
+
+    #[test]
+    fn fails() -> Result<(), Error> {
+        assert!(g_to_spdi(&HgvsVariant::from_str("NM_001637.3:c.1582G>A")?).is_err());
+        assert!(g_to_spdi(&HgvsVariant::from_str("NC_000007.13:g.100_102del")?).is_err());
+        assert!(g_to_spdi(&HgvsVariant::from_str("NC_000007.13:g.100_102delAC")?).is_err());
+        assert!(spdi_to_g("NC_000007.13", -1, "A", "G").is_err());
+        assert!(spdi_to_g("NC_000007.13", 0, "", "G").is_err());
+        assert!(spdi_to_g("NC_000007.13", 100, "", "").is_err());
+        assert!(spdi_to_g("NC_000007.13", 100, "A", "<DEL>").is_err());
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+// Copyright 2014 Bioutils Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
@@ -0,0 +1,521 @@
+//! Conversion between VCF records and HGVS genome variants.
+
+use biocommons_bioutils::assemblies::Assembly;
+
+use crate::{
+    conversion::{check_allele, Error},
+    data::interface::Provider,
+    mapper::assembly::{chrom_name_to_refseq_ac, refseq_ac_to_chrom_name},
+    parser::{
+        Accession, GenomeInterval, GenomeLocEdit, HgvsVariant, Mu, NaEdit, UncertainLengthChange,
+    },
+    sequences::{revcomp, trim_common_prefixes, trim_common_suffixes},
+};
+
+/// Return the RefSeq accession of chromosome `chrom` in `assembly`, see
+/// `mapper::assembly::chrom_name_to_refseq_ac()`.
+fn chrom_to_accession(chrom: &str, assembly: Assembly) -> Result<String, Error> {
+    chrom_name_to_refseq_ac(chrom, assembly)
+        .ok_or_else(|| Error::UnknownChromosome(chrom.to_string(), format!("{:?}", assembly)))
+}
+
+/// Return the chromosome name of RefSeq accession `ac` in `assembly`, see
+/// `mapper::assembly::refseq_ac_to_chrom_name()`.
+pub(super) fn accession_to_chrom(ac: &str, assembly: Assembly) -> Result<String, Error> {
+    refseq_ac_to_chrom_name(ac, assembly)
+        .ok_or_else(|| Error::UnknownAccession(ac.to_string(), format!("{:?}", assembly)))
+}
+
+/// Construct a genome variant from the fields of a VCF record.
+///
+/// Common prefixes and suffixes of the alleles are trimmed, such that the result is a
+/// substitution, deletion, insertion, or deletion-insertion.  No shuffling is performed;
+/// use the normalizer to obtain the 3'-most representation.
+///
+/// # Args
+///
+/// * `chrom` -- chromosome name, e.g., `"1"` or `"chr1"`
+/// * `pos` -- 1-based position of the first base of `ref_allele`
+/// * `ref_allele` -- reference allele, including the VCF anchor base for indels
+/// * `alt_allele` -- alternative allele, including the VCF anchor base for indels
+/// * `assembly` -- assembly to look up the chromosome accession in
+pub fn vcf_to_g(
+    chrom: &str,
+    pos: u64,
+    ref_allele: &str,
+    alt_allele: &str,
+    assembly: Assembly,
+) -> Result<HgvsVariant, Error> {
+    check_allele(ref_allele, false)?;
+    check_allele(alt_allele, false)?;
+    let accession = chrom_to_accession(chrom, assembly)?;
+    let pos = i32::try_from(pos)
+        .ok()
+        .filter(|pos| *pos > 0)
+        .ok_or_else(|| Error::InvalidPosition(pos.to_string()))?;
+    let ref_allele = ref_allele.to_uppercase();
+    let alt_allele = alt_allele.to_uppercase();
+
+    let (start, end, edit) = if ref_allele == alt_allele {
+        let end = pos + ref_allele.len() as i32 - 1;
+        (
+            pos,
+            end,
+            NaEdit::RefAlt {
+                reference: ref_allele.clone(),
+                alternative: ref_allele,
+            },
+        )
+    } else {
+        let (prefix, reference, alternative) = trim_common_prefixes(&ref_allele, &alt_allele);
+        let (_, reference, alternative) = trim_common_suffixes(&reference, &alternative);
+        let start = pos + prefix as i32;
+        let end = start + reference.len() as i32 - 1;
+        match (reference.len(), alternative.len()) {
+            // The insertion is located between the last common base and the next one.
+            (0, _) => (start - 1, start, NaEdit::Ins { alternative }),
+            (_, 0) => (start, end, NaEdit::DelRef { reference }),
+            (_, _) => (
+                start,
+                end,
+                NaEdit::RefAlt {
+                    reference,
+                    alternative,
+                },
+            ),
+        }
+    };
+
+    Ok(HgvsVariant::GenomeVariant {
+        accession: Accession::from(accession),
+        gene_symbol: None,
+        loc_edit: GenomeLocEdit {
+            loc: Mu::Certain(GenomeInterval {
+                start: Some(start),
+                end: Some(end),
+            }),
+            edit: Mu::Certain(edit),
+        },
+    })
+}
+
+/// Construct the VCF fields `(chrom, pos, ref, alt)` of a genome variant.
+///
+/// The reference sequence is taken from `provider` rather than the variant, so variants
+/// without reference bases (e.g., `del`) are supported.  Common prefixes and suffixes of
+/// the alleles are trimmed and empty alleles are left-anchored with the preceding base as
+/// required by VCF (or right-anchored at the first base of the sequence).
+///
+/// # Args
+///
+/// * `var` -- `HgvsVariant::GenomeVariant` to convert
+/// * `assembly` -- assembly to look up the chromosome name in
+/// * `provider` -- provider for the reference sequence
+pub fn g_to_vcf(
+    var: &HgvsVariant,
+    assembly: Assembly,
+    provider: &dyn Provider,
+) -> Result<(String, u64, String, String), Error> {
+    let (accession, loc_edit) = if let HgvsVariant::GenomeVariant {
+        accession,
+        loc_edit,
+        ..
+    } = var
+    {
+        (accession, loc_edit)
+    } else {
+        return Err(Error::ExpectedGenomeVariant(format!("{}", var)));
+    };
+    let chrom = accession_to_chrom(&accession.value, assembly)?;
+    let (start, end) = match loc_edit.loc.inner() {
+        GenomeInterval {
+            start: Some(start),
+            end: Some(end),
+        } if *start > 0 && start <= end => (*start, *end),
+        _ => return Err(Error::UnknownPosition(format!("{}", var))),
+    };
+
+    // Fetch reference sequence of the 0-based, half-open range `[begin, end)`.
+    let get_seq = |begin: i32, end: i32| -> Result<String, Error> {
+        Ok(provider
+            .get_seq_part(&accession.value, Some(begin as usize), Some(end as usize))?
+            .to_uppercase())
+    };
+
+    // Reference and alternative allele at the 0-based position `pos`.
+    let (pos, reference, alternative) = match loc_edit.edit.inner() {
+        NaEdit::Ins { alternative } => (start, String::new(), alternative.to_uppercase()),
+        NaEdit::Repeat {
+            sequence,
+            count: UncertainLengthChange::Known(count),
+        } if !sequence.is_empty() && start < end => (
+            start - 1,
+            get_seq(start - 1, end)?,
+            sequence.to_uppercase().repeat(*count as usize),
+        ),
+        NaEdit::Repeat { .. } => return Err(Error::UnsupportedRepeat(format!("{}", var))),
+        edit => {
+            let reference = get_seq(start - 1, end)?;
+            let alternative = match edit {
+                NaEdit::RefAlt { alternative, .. } | NaEdit::NumAlt { alternative, .. } => {
+                    alternative.to_uppercase()
+                }
+                NaEdit::DelRef { .. } | NaEdit::DelNum { .. } => String::new(),
+                NaEdit::Dup { .. } => reference.repeat(2),
+                NaEdit::InvRef { .. } | NaEdit::InvNum { .. } => revcomp(&reference),
+                NaEdit::Ins { .. } | NaEdit::Repeat { .. } => unreachable!(),
+            };
+            (start - 1, reference, alternative)
+        }
+    };
+
+    if reference == alternative {
+        return Ok((chrom, pos as u64 + 1, reference.clone(), alternative));
+    }
+
+    let (_, reference, alternative) = trim_common_suffixes(&reference, &alternative);
+    let (prefix, reference, alternative) = trim_common_prefixes(&reference, &alternative);
+    let pos = pos + prefix as i32;
+
+    if !reference.is_empty() && !alternative.is_empty() {
+        Ok((chrom, pos as u64 + 1, reference, alternative))
+    } else if pos > 0 {
+        let anchor = get_seq(pos - 1, pos)?;
+        Ok((
+            chrom,
+            pos as u64,
+            format!("{anchor}{reference}"),
+            format!("{anchor}{alternative}"),
+        ))
+    } else {
+        let end = pos + reference.len() as i32;
+        let anchor = get_seq(end, end + 1)?;
+        Ok((
+            chrom,
+            1,
+            format!("{reference}{anchor}"),
+            format!("{alternative}{anchor}"),
+        ))
+    }
+}
+
+impl NaEdit {
+    /// Construct the edit described by the reference and alternative allele of a VCF record.
+    ///
+    /// Alleles of equal length give a substitution, or an inversion if the alternative allele
+    /// is the reverse complement of the reference allele.  If one allele extends the other
+    /// (i.e., left-anchored as usual in VCF), the result is an insertion, a duplication (if
+    /// the inserted bases repeat the bases before), or a deletion, and the anchor is not part
+    /// of the edit.  All other alleles give a deletion-insertion.
+    ///
+    /// # Args
+    ///
+    /// * `ref_allele` -- reference allele of the VCF record
+    /// * `alt_allele` -- alternative allele of the VCF record
+    pub fn from_vcf_fields(ref_allele: &str, alt_allele: &str) -> Result<NaEdit, Error> {
+        check_allele(ref_allele, false)?;
+        check_allele(alt_allele, false)?;
+        let reference = ref_allele.to_uppercase();
+        let alternative = alt_allele.to_uppercase();
+
+        Ok(if reference == alternative {
+            NaEdit::RefAlt {
+                reference,
+                alternative,
+            }
+        } else if reference.len() == alternative.len() {
+            if reference.len() > 1 && alternative == revcomp(&reference) {
+                NaEdit::InvRef { reference }
+            } else {
+                NaEdit::RefAlt {
+                    reference,
+                    alternative,
+                }
+            }
+        } else if let Some(inserted) = alternative.strip_prefix(&reference) {
+            if reference.ends_with(inserted) {
+                NaEdit::Dup {
+                    reference: inserted.to_string(),
+                }
+            } else {
+                NaEdit::Ins {
+                    alternative: inserted.to_string(),
+                }
+            }
+        } else if let Some(deleted) = reference.strip_prefix(&alternative) {
+            NaEdit::DelRef {
+                reference: deleted.to_string(),
+            }
+        } else {
+            NaEdit::RefAlt {
+                reference,
+                alternative,
+            }
+        })
+    }
+
+    /// Return the reference and alternative allele of a VCF record describing the edit.
+    ///
+    /// Insertions and deletions are anchored with `ref_seq_context`, the reference bases
+    /// immediately before the edit, so that no allele is empty.  The context is also put
+    /// before duplications, such that this is the inverse of `NaEdit::from_vcf_fields()`.
+    ///
+    /// Fails for edits that do not give their bases, e.g., `del3`, and for repeats.
+    ///
+    /// # Args
+    ///
+    /// * `ref_seq_context` -- reference bases before the edit, required for insertions
+    ///   and deletions
+    pub fn to_vcf_fields(&self, ref_seq_context: Option<&str>) -> Result<(String, String), Error> {
+        let missing_reference = || Error::MissingReference(format!("{self}"));
+        let anchor = || {
+            ref_seq_context
+                .filter(|context| !context.is_empty())
+                .ok_or_else(missing_reference)
+        };
+        let context = ref_seq_context.unwrap_or_default();
+
+        match self {
+            NaEdit::RefAlt {
+                reference,
+                alternative,
+            } if !reference.is_empty() && !alternative.is_empty() => {
+                Ok((reference.clone(), alternative.clone()))
+            }
+            NaEdit::RefAlt {
+                reference,
+                alternative,
+            } if !reference.is_empty() || !alternative.is_empty() => {
+                let anchor = anchor()?;
+                Ok((
+                    format!("{anchor}{reference}"),
+                    format!("{anchor}{alternative}"),
+                ))
+            }
+            NaEdit::DelRef { reference } if !reference.is_empty() => {
+                let anchor = anchor()?;
+                Ok((format!("{anchor}{reference}"), anchor.to_string()))
+            }
+            NaEdit::Ins { alternative } if !alternative.is_empty() => {
+                let anchor = anchor()?;
+                Ok((anchor.to_string(), format!("{anchor}{alternative}")))
+            }
+            NaEdit::Dup { reference } if !reference.is_empty() => Ok((
+                format!("{context}{reference}"),
+                format!("{context}{reference}{reference}"),
+            )),
+            NaEdit::InvRef { reference } if !reference.is_empty() => {
+                Ok((reference.clone(), revcomp(reference)))
+            }
+            NaEdit::Repeat { .. } => Err(Error::UnsupportedRepeat(format!("{self}"))),
+            _ => Err(missing_reference()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use anyhow::Error;
+    use biocommons_bioutils::assemblies::Assembly;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::{
+        data::uta_sr::test_helpers::build_provider,
+        parser::{HgvsVariant, NaEdit, NoRef, UncertainLengthChange},
+    };
+
+    use super::{accession_to_chrom, g_to_vcf, vcf_to_g};
+
+    #[rstest]
+    #[case("7", 36561662, "C", "T", "NC_000007.13:g.36561662C>T")]
+    #[case("chr7", 36561662, "c", "t", "NC_000007.13:g.36561662C>T")]
+    #[case("7", 100, "ACGT", "A", "NC_000007.13:g.101_103delCGT")]
+    #[case("7", 100, "A", "ACG", "NC_000007.13:g.100_101insCG")]
+    #[case("7", 100, "AC", "GT", "NC_000007.13:g.100_101delACinsGT")]
+    #[case("7", 100, "ACG", "AT", "NC_000007.13:g.101_102delCGinsT")]
+    #[case("7", 100, "A", "A", "NC_000007.13:g.100=")]
+    #[case("chrM", 100, "A", "G", "NC_001807.4:g.100A>G")]
+    #[case("MT", 100, "A", "G", "NC_012920.1:g.100A>G")]
+    fn vcf_to_g_grch37(
+        #[case] chrom: &str,
+        #[case] pos: u64,
+        #[case] ref_allele: &str,
+        #[case] alt_allele: &str,
+        #[case] expected: &str,
+    ) -> Result<(), Error> {
+        let var = vcf_to_g(chrom, pos, ref_allele, alt_allele, Assembly::Grch37p10)?;
+        assert_eq!(format!("{}", &var), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn vcf_to_g_fails() {
+        assert!(vcf_to_g("99", 100, "A", "G", Assembly::Grch37p10).is_err());
+        assert!(vcf_to_g("1", 0, "A", "G", Assembly::Grch37p10).is_err());
+        assert!(vcf_to_g("1", 100, "", "G", Assembly::Grch37p10).is_err());
+        assert!(vcf_to_g("1", 100, "A", "<DEL>", Assembly::Grch37p10).is_err());
+    }
+
+    #[rstest]
+    #[case("C", "T", None, "C>T")]
+    #[case("A", "A", None, "=")]
+    #[case("AT", "AT", None, "=")]
+    #[case("CG", "TA", None, "delCGinsTA")]
+    #[case("ACG", "CGT", None, "invACG")]
+    #[case("CAT", "C", Some("C"), "delAT")]
+    #[case("C", "CGG", Some("C"), "insGG")]
+    #[case("CAT", "CATAT", Some("C"), "dupAT")]
+    #[case("AT", "ATAT", None, "dupAT")]
+    #[case("CAT", "G", None, "delCATinsG")]
+    #[case("CA", "GTT", None, "delCAinsGTT")]
+    fn na_edit_vcf_fields(
+        #[case] ref_allele: &str,
+        #[case] alt_allele: &str,
+        #[case] context: Option<&str>,
+        #[case] expected: &str,
+    ) -> Result<(), Error> {
+        let edit = NaEdit::from_vcf_fields(ref_allele, alt_allele)?;
+        assert_eq!(format!("{edit}"), expected);
+        assert_eq!(
+            edit.to_vcf_fields(context)?,
+            (ref_allele.to_string(), alt_allele.to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn na_edit_vcf_fields_fails() {
+        assert!(NaEdit::from_vcf_fields("", "A").is_err());
+        assert!(NaEdit::from_vcf_fields("A", "<DEL>").is_err());
+
+        let ins = NaEdit::Ins {
+            alternative: "GG".to_string(),
+        };
+        assert!(ins.to_vcf_fields(None).is_err());
+        assert!(ins.to_vcf_fields(Some("")).is_err());
+        assert!(NaEdit::DelNum { count: 3 }
+            .to_vcf_fields(Some("C"))
+            .is_err());
+        assert!(NaEdit::Repeat {
+            sequence: "CA".to_string(),
+            count: UncertainLengthChange::Known(3),
+        }
+        .to_vcf_fields(Some("C"))
+        .is_err());
+    }
+
+    #[rstest]
+    #[case("NC_000007.13:g.36561662C>T")]
+    #[case("NC_000007.13:g.21940852_21940908del")]
+    fn g_to_vcf_roundtrip(#[case] hgvs_g: &str) -> Result<(), Error> {
+        let provider = build_provider()?;
+        let var_g = HgvsVariant::from_str(hgvs_g)?;
+
+        let (chrom, pos, ref_allele, alt_allele) =
+            g_to_vcf(&var_g, Assembly::Grch37p10, provider.as_ref())?;
+        assert_eq!(chrom, "7");
+        let var = vcf_to_g(&chrom, pos, &ref_allele, &alt_allele, Assembly::Grch37p10)?;
+        assert_eq!(format!("{}", &NoRef(&var)), hgvs_g);
+
+        Ok(())
+    }
+
+    #[test]
+    fn g_to_vcf_deletion_is_left_anchored() -> Result<(), Error> {
+        let provider = build_provider()?;
+        let var_g = HgvsVariant::from_str("NC_000007.13:g.21940852_21940908del")?;
+
+        let (_, pos, ref_allele, alt_allele) =
+            g_to_vcf(&var_g, Assembly::Grch37p10, provider.as_ref())?;
+        assert_eq!(pos, 21940851);
+        assert_eq!(ref_allele.len(), 58);
+        assert_eq!(alt_allele.len(), 1);
+        assert!(ref_allele.starts_with(&alt_allele));
+
+        Ok(())
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct DbSnpRecord {
+        #[serde(alias = "HGVSg")]
+        hgvs_g: String,
+    }
+
+    /// Round-trip the substitutions, deletions, and insertions of the dbSNP entries in the
+    /// GCP test data through VCF.
+    #[rstest]
+    #[case("tests/data/mapper/gcp/ADRA2B-dbSNP.tsv")]
+    #[case("tests/data/mapper/gcp/BAHCC1-dbSNP.tsv")]
+    #[case("tests/data/mapper/gcp/DNAH11-dbSNP.tsv")]
+    #[case("tests/data/mapper/gcp/FOLR3-dbSNP.tsv")]
+    #[case("tests/data/mapper/gcp/JRK-dbSNP.tsv")]
+    #[case("tests/data/mapper/gcp/NEFL-dbSNP.tsv")]
+    #[case("tests/data/mapper/gcp/ORAI1-dbSNP.tsv")]
+    #[case("tests/data/mapper/gcp/ZCCHC3-dbSNP.tsv")]
+    fn g_to_vcf_roundtrip_dbsnp(#[case] path: &str) -> Result<(), Error> {
+        let provider = build_provider()?;
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .has_headers(true)
+            .flexible(true)
+            .comment(Some(b'#'))
+            .from_path(path)?;
+
+        for record in rdr.deserialize() {
+            let record: DbSnpRecord = record?;
+            let var_g = HgvsVariant::from_str(&record.hgvs_g)?;
+            let HgvsVariant::GenomeVariant { loc_edit, .. } = &var_g else {
+                panic!("not a genome variant: {}", &record.hgvs_g);
+            };
+            let is_supported = match loc_edit.edit.inner() {
+                NaEdit::RefAlt {
+                    reference,
+                    alternative,
+                } => reference.len() == 1 && alternative.len() == 1 && reference != alternative,
+                NaEdit::DelRef { .. } | NaEdit::DelNum { .. } | NaEdit::Ins { .. } => true,
+                _ => false,
+            };
+            if !is_supported {
+                continue;
+            }
+
+            let assembly = [Assembly::Grch37p10, Assembly::Grch38]
+                .into_iter()
+                .find(|assembly| accession_to_chrom(var_g.accession(), *assembly).is_ok())
+                .expect("accession of unknown assembly");
+            let (chrom, pos, ref_allele, alt_allele) =
+                g_to_vcf(&var_g, assembly, provider.as_ref())?;
+            let var = vcf_to_g(&chrom, pos, &ref_allele, &alt_allele, assembly)?;
+            assert_eq!(
+                format!("{}", &NoRef(&var)),
+                format!("{}", &NoRef(&var_g)),
+                "{}",
+                &record.hgvs_g
+            );
+        }
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+// Copyright 2014 Bioutils Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
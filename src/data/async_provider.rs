@@ -0,0 +1,517 @@
+//! Async access to providers for use from async runtimes.
+//!
+//! The providers of this crate perform blocking I/O (e.g., database queries or file access).
+//! `AsyncProviderAdapter` runs the queries of a wrapped provider on the blocking thread pool
+//! of `tokio` such that they can be awaited without blocking the executor.
+//!
+//! Only available with the `async` feature.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use biocommons_bioutils::assemblies::Assembly;
+use indexmap::IndexMap;
+
+use crate::data::{
+    error::Error,
+    interface::{
+        GeneInfoRecord, Provider, ProviderCapabilities, TranscriptSummary, TxExonsRecord,
+        TxForRegionRecord, TxIdentityInfo, TxInfoRecord, TxMappingOptionsRecord,
+        TxSimilarityRecord,
+    },
+};
+
+/// Async variant of `data::interface::Provider`.
+///
+/// See the synchronous trait for the documentation of the individual methods.
+#[async_trait]
+pub trait AsyncProvider: Send + Sync {
+    /// Return the data version, e.g., `uta_20210129`.
+    fn data_version(&self) -> &str;
+
+    /// Return the schema version, e.g., `"1.1"`.
+    fn schema_version(&self) -> &str;
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::all()
+    }
+
+    async fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String>;
+
+    async fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error>;
+
+    async fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error>;
+
+    async fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        Ok(self
+            .get_pro_ac_for_tx_ac(tx_ac)
+            .await?
+            .into_iter()
+            .collect())
+    }
+
+    async fn get_tx_ac_for_pro_ac(&self, _pro_ac: &str) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+
+    async fn get_transcript_versions(&self, _base_ac: &str) -> Result<Vec<String>, Error> {
+        Ok(Vec::new())
+    }
+
+    async fn get_latest_transcript_version(&self, base_ac: &str) -> Result<Option<String>, Error> {
+        Ok(self.get_transcript_versions(base_ac).await?.pop())
+    }
+
+    async fn get_seq(&self, ac: &str) -> Result<String, Error> {
+        self.get_seq_part(ac, None, None).await
+    }
+
+    async fn get_tx_full_seq(&self, tx_ac: &str) -> Result<String, Error> {
+        let id_info = self.get_tx_identity_info(tx_ac).await?;
+        let seq = self.get_seq(tx_ac).await?;
+        let tx_len = id_info.lengths.iter().sum::<i32>() as usize;
+        if !id_info.lengths.is_empty() && seq.len() != tx_len {
+            return Err(Error::SequenceLengthMismatch(
+                tx_ac.to_string(),
+                tx_len,
+                seq.len(),
+            ));
+        }
+        Ok(seq)
+    }
+
+    async fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error>;
+
+    async fn batch_get_seq_parts(
+        &self,
+        queries: &[(String, Option<usize>, Option<usize>)],
+    ) -> Result<Vec<String>, Error>;
+
+    async fn get_acs_for_protein_seq(&self, seq: &str) -> Result<Vec<String>, Error>;
+
+    async fn get_similar_transcripts(&self, tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error>;
+
+    async fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error>;
+
+    async fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, Error>;
+
+    async fn get_all_tx_for_gene(&self, gene: &str) -> Result<Vec<TranscriptSummary>, Error> {
+        let mut result = Vec::new();
+        for record in self.get_tx_for_gene(gene).await? {
+            result.push(TranscriptSummary {
+                pro_ac: self.get_pro_ac_for_tx_ac(&record.tx_ac).await?,
+                tx_ac: record.tx_ac,
+                alt_ac: record.alt_ac,
+                alt_aln_method: record.alt_aln_method,
+                cds_start_i: record.cds_start_i,
+                cds_end_i: record.cds_end_i,
+            });
+        }
+        Ok(result)
+    }
+
+    async fn get_transcript_count(&self, gene: &str) -> Result<usize, Error> {
+        Ok(self.get_tx_for_gene(gene).await?.len())
+    }
+
+    async fn get_mane_select_for_gene(&self, _hgnc: &str) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+
+    async fn get_tx_for_region(
+        &self,
+        alt_ac: &str,
+        alt_aln_method: &str,
+        start_i: i32,
+        end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error>;
+
+    async fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error>;
+
+    async fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error>;
+
+    async fn get_tx_mapping_options(
+        &self,
+        tx_ac: &str,
+    ) -> Result<Vec<TxMappingOptionsRecord>, Error>;
+}
+
+/// Adapter that makes a synchronous `Provider` usable as an `AsyncProvider`.
+///
+/// The adapter also implements `Provider` by calling the wrapped provider directly, such
+/// that it can be passed to the mapper, normalizer, and validator as well.
+pub struct AsyncProviderAdapter<P: Provider + Send + Sync + 'static> {
+    provider: Arc<P>,
+}
+
+impl<P: Provider + Send + Sync + 'static> AsyncProviderAdapter<P> {
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider: Arc::new(provider),
+        }
+    }
+
+    /// Run `f` with the wrapped provider on the blocking thread pool.
+    async fn spawn_blocking<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&P) -> Result<T, Error> + Send + 'static,
+    {
+        let provider = self.provider.clone();
+        tokio::task::spawn_blocking(move || f(provider.as_ref()))
+            .await
+            .map_err(|e| Error::BlockingTaskFailed(e.to_string()))?
+    }
+}
+
+#[async_trait]
+impl<P: Provider + Send + Sync + 'static> AsyncProvider for AsyncProviderAdapter<P> {
+    fn data_version(&self) -> &str {
+        self.provider.data_version()
+    }
+
+    fn schema_version(&self) -> &str {
+        self.provider.schema_version()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.provider.capabilities()
+    }
+
+    async fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
+        self.provider.get_assembly_map(assembly)
+    }
+
+    async fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        let hgnc = hgnc.to_string();
+        self.spawn_blocking(move |p| p.get_gene_info(&hgnc)).await
+    }
+
+    async fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error> {
+        let tx_ac = tx_ac.to_string();
+        self.spawn_blocking(move |p| p.get_pro_ac_for_tx_ac(&tx_ac))
+            .await
+    }
+
+    async fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        let tx_ac = tx_ac.to_string();
+        self.spawn_blocking(move |p| p.get_all_pro_ac_for_tx_ac(&tx_ac))
+            .await
+    }
+
+    async fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        let pro_ac = pro_ac.to_string();
+        self.spawn_blocking(move |p| p.get_tx_ac_for_pro_ac(&pro_ac))
+            .await
+    }
+
+    async fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        let base_ac = base_ac.to_string();
+        self.spawn_blocking(move |p| p.get_transcript_versions(&base_ac))
+            .await
+    }
+
+    async fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        let ac = ac.to_string();
+        self.spawn_blocking(move |p| p.get_seq_part(&ac, begin, end))
+            .await
+    }
+
+    async fn batch_get_seq_parts(
+        &self,
+        queries: &[(String, Option<usize>, Option<usize>)],
+    ) -> Result<Vec<String>, Error> {
+        let queries = queries.to_vec();
+        self.spawn_blocking(move |p| p.batch_get_seq_parts(&queries))
+            .await
+    }
+
+    async fn get_acs_for_protein_seq(&self, seq: &str) -> Result<Vec<String>, Error> {
+        let seq = seq.to_string();
+        self.spawn_blocking(move |p| p.get_acs_for_protein_seq(&seq))
+            .await
+    }
+
+    async fn get_similar_transcripts(&self, tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        let tx_ac = tx_ac.to_string();
+        self.spawn_blocking(move |p| p.get_similar_transcripts(&tx_ac))
+            .await
+    }
+
+    async fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        let (tx_ac, alt_ac, alt_aln_method) = (
+            tx_ac.to_string(),
+            alt_ac.to_string(),
+            alt_aln_method.to_string(),
+        );
+        self.spawn_blocking(move |p| p.get_tx_exons(&tx_ac, &alt_ac, &alt_aln_method))
+            .await
+    }
+
+    async fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        let gene = gene.to_string();
+        self.spawn_blocking(move |p| p.get_tx_for_gene(&gene)).await
+    }
+
+    async fn get_all_tx_for_gene(&self, gene: &str) -> Result<Vec<TranscriptSummary>, Error> {
+        let gene = gene.to_string();
+        self.spawn_blocking(move |p| p.get_all_tx_for_gene(&gene))
+            .await
+    }
+
+    async fn get_transcript_count(&self, gene: &str) -> Result<usize, Error> {
+        let gene = gene.to_string();
+        self.spawn_blocking(move |p| p.get_transcript_count(&gene))
+            .await
+    }
+
+    async fn get_mane_select_for_gene(&self, hgnc: &str) -> Result<Option<String>, Error> {
+        let hgnc = hgnc.to_string();
+        self.spawn_blocking(move |p| p.get_mane_select_for_gene(&hgnc))
+            .await
+    }
+
+    async fn get_tx_for_region(
+        &self,
+        alt_ac: &str,
+        alt_aln_method: &str,
+        start_i: i32,
+        end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        let (alt_ac, alt_aln_method) = (alt_ac.to_string(), alt_aln_method.to_string());
+        self.spawn_blocking(move |p| p.get_tx_for_region(&alt_ac, &alt_aln_method, start_i, end_i))
+            .await
+    }
+
+    async fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        let tx_ac = tx_ac.to_string();
+        self.spawn_blocking(move |p| p.get_tx_identity_info(&tx_ac))
+            .await
+    }
+
+    async fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        let (tx_ac, alt_ac, alt_aln_method) = (
+            tx_ac.to_string(),
+            alt_ac.to_string(),
+            alt_aln_method.to_string(),
+        );
+        self.spawn_blocking(move |p| p.get_tx_info(&tx_ac, &alt_ac, &alt_aln_method))
+            .await
+    }
+
+    async fn get_tx_mapping_options(
+        &self,
+        tx_ac: &str,
+    ) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        let tx_ac = tx_ac.to_string();
+        self.spawn_blocking(move |p| p.get_tx_mapping_options(&tx_ac))
+            .await
+    }
+}
+
+impl<P: Provider + Send + Sync + 'static> Provider for AsyncProviderAdapter<P> {
+    fn data_version(&self) -> &str {
+        self.provider.data_version()
+    }
+
+    fn schema_version(&self) -> &str {
+        self.provider.schema_version()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.provider.capabilities()
+    }
+
+    fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
+        self.provider.get_assembly_map(assembly)
+    }
+
+    fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        self.provider.get_gene_info(hgnc)
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error> {
+        self.provider.get_pro_ac_for_tx_ac(tx_ac)
+    }
+
+    fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        self.provider.get_all_pro_ac_for_tx_ac(tx_ac)
+    }
+
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        self.provider.get_tx_ac_for_pro_ac(pro_ac)
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        self.provider.get_transcript_versions(base_ac)
+    }
+
+    fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        self.provider.get_seq_part(ac, begin, end)
+    }
+
+    fn batch_get_seq_parts(
+        &self,
+        queries: &[(String, Option<usize>, Option<usize>)],
+    ) -> Result<Vec<String>, Error> {
+        self.provider.batch_get_seq_parts(queries)
+    }
+
+    fn get_acs_for_protein_seq(&self, seq: &str) -> Result<Vec<String>, Error> {
+        self.provider.get_acs_for_protein_seq(seq)
+    }
+
+    fn get_similar_transcripts(&self, tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        self.provider.get_similar_transcripts(tx_ac)
+    }
+
+    fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        self.provider.get_tx_exons(tx_ac, alt_ac, alt_aln_method)
+    }
+
+    fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        self.provider.get_tx_for_gene(gene)
+    }
+
+    fn get_all_tx_for_gene(&self, gene: &str) -> Result<Vec<TranscriptSummary>, Error> {
+        self.provider.get_all_tx_for_gene(gene)
+    }
+
+    fn get_transcript_count(&self, gene: &str) -> Result<usize, Error> {
+        self.provider.get_transcript_count(gene)
+    }
+
+    fn get_mane_select_for_gene(&self, hgnc: &str) -> Result<Option<String>, Error> {
+        self.provider.get_mane_select_for_gene(hgnc)
+    }
+
+    fn get_tx_for_region(
+        &self,
+        alt_ac: &str,
+        alt_aln_method: &str,
+        start_i: i32,
+        end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        self.provider
+            .get_tx_for_region(alt_ac, alt_aln_method, start_i, end_i)
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        self.provider.get_tx_identity_info(tx_ac)
+    }
+
+    fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        self.provider.get_tx_info(tx_ac, alt_ac, alt_aln_method)
+    }
+
+    fn get_tx_mapping_options(&self, tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        self.provider.get_tx_mapping_options(tx_ac)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::{
+        data::gff3::{Config, Provider},
+        mapper::variant::{Config as MapperConfig, Mapper},
+        parser::HgvsVariant,
+    };
+
+    use super::{AsyncProvider, AsyncProviderAdapter};
+
+    fn build_adapter() -> Result<AsyncProviderAdapter<Provider>, anyhow::Error> {
+        Ok(AsyncProviderAdapter::new(Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/single_exon.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?))
+    }
+
+    #[tokio::test]
+    async fn async_queries() -> Result<(), anyhow::Error> {
+        let adapter = build_adapter()?;
+
+        let info = AsyncProvider::get_tx_identity_info(&adapter, "TX1.1").await?;
+        assert_eq!(info.cds_start_i, 20);
+        assert_eq!(
+            AsyncProvider::get_seq_part(&adapter, "chrT", Some(30), Some(36)).await?,
+            "ATGAAA"
+        );
+        assert!(AsyncProvider::get_seq(&adapter, "chrX").await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mapper_c_to_g() -> Result<(), anyhow::Error> {
+        let mapper = Mapper::new(&MapperConfig::default(), Arc::new(build_adapter()?));
+
+        let var_c = HgvsVariant::from_str("TX1.1:c.4A>G")?;
+        let var_g = mapper.c_to_g(&var_c, "chrT", "splign")?;
+        assert_eq!(format!("{}", &var_g), "chrT:g.34A>G");
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
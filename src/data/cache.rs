@@ -0,0 +1,387 @@
+//! Caching of sequence queries in front of another provider.
+
+use std::sync::{Arc, Mutex};
+
+use biocommons_bioutils::assemblies::Assembly;
+use cached::{Cached, SizedCache};
+use indexmap::IndexMap;
+
+use crate::data::{
+    error::Error,
+    interface::{
+        GeneInfoRecord, Provider, ProviderCapabilities, TranscriptSummary, TxExonsRecord,
+        TxForRegionRecord, TxIdentityInfo, TxInfoRecord, TxMappingOptionsRecord,
+        TxSimilarityRecord,
+    },
+};
+
+/// Key of the sequence cache: `(ac, begin, end)` with `begin`/`end` defaulting to `0` and
+/// `usize::MAX`.
+type SeqPartKey = (String, usize, usize);
+
+/// Provider that keeps the results of recent `get_seq_part()` calls in an LRU cache.
+///
+/// All other queries are passed through to the wrapped provider.
+pub struct CachingProvider {
+    /// The wrapped provider.
+    provider: Arc<dyn Provider + Send + Sync>,
+    /// LRU cache of sequence parts.
+    seq_parts: Mutex<SizedCache<SeqPartKey, String>>,
+}
+
+impl CachingProvider {
+    /// Wrap `provider`, keeping the results of up to `capacity` sequence queries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is `0`.
+    pub fn new(provider: Arc<dyn Provider + Send + Sync>, capacity: usize) -> Self {
+        Self {
+            provider,
+            seq_parts: Mutex::new(SizedCache::with_size(capacity)),
+        }
+    }
+
+    fn key(ac: &str, begin: Option<usize>, end: Option<usize>) -> SeqPartKey {
+        (
+            ac.to_string(),
+            begin.unwrap_or_default(),
+            end.unwrap_or(usize::MAX),
+        )
+    }
+
+    fn cache_get(&self, key: &SeqPartKey) -> Option<String> {
+        self.seq_parts
+            .lock()
+            .expect("sequence cache poisoned")
+            .cache_get(key)
+            .cloned()
+    }
+
+    fn cache_set(&self, key: SeqPartKey, seq: String) {
+        self.seq_parts
+            .lock()
+            .expect("sequence cache poisoned")
+            .cache_set(key, seq);
+    }
+}
+
+impl Provider for CachingProvider {
+    fn data_version(&self) -> &str {
+        self.provider.data_version()
+    }
+
+    fn schema_version(&self) -> &str {
+        self.provider.schema_version()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.provider.capabilities()
+    }
+
+    fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
+        self.provider.get_assembly_map(assembly)
+    }
+
+    fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        self.provider.get_gene_info(hgnc)
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error> {
+        self.provider.get_pro_ac_for_tx_ac(tx_ac)
+    }
+
+    fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        self.provider.get_all_pro_ac_for_tx_ac(tx_ac)
+    }
+
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        self.provider.get_tx_ac_for_pro_ac(pro_ac)
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        self.provider.get_transcript_versions(base_ac)
+    }
+
+    fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        let key = Self::key(ac, begin, end);
+        if let Some(seq) = self.cache_get(&key) {
+            return Ok(seq);
+        }
+        let seq = self.provider.get_seq_part(ac, begin, end)?;
+        self.cache_set(key, seq.clone());
+        Ok(seq)
+    }
+
+    fn batch_get_seq_parts(
+        &self,
+        queries: &[(String, Option<usize>, Option<usize>)],
+    ) -> Result<Vec<String>, Error> {
+        let mut result = queries
+            .iter()
+            .map(|(ac, begin, end)| self.cache_get(&Self::key(ac, *begin, *end)))
+            .collect::<Vec<_>>();
+
+        // Fetch all missing parts with a single query to the wrapped provider.
+        let missing = queries
+            .iter()
+            .zip(result.iter())
+            .filter(|(_, seq)| seq.is_none())
+            .map(|(query, _)| query.clone())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let mut fetched = self
+                .provider
+                .batch_get_seq_parts(&missing)?
+                .into_iter()
+                .zip(missing);
+            for seq in result.iter_mut().filter(|seq| seq.is_none()) {
+                let (value, (ac, begin, end)) =
+                    fetched.next().expect("provider returned too few sequences");
+                self.cache_set(Self::key(&ac, begin, end), value.clone());
+                *seq = Some(value);
+            }
+        }
+
+        Ok(result.into_iter().flatten().collect())
+    }
+
+    fn get_acs_for_protein_seq(&self, seq: &str) -> Result<Vec<String>, Error> {
+        self.provider.get_acs_for_protein_seq(seq)
+    }
+
+    fn get_similar_transcripts(&self, tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        self.provider.get_similar_transcripts(tx_ac)
+    }
+
+    fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        self.provider.get_tx_exons(tx_ac, alt_ac, alt_aln_method)
+    }
+
+    fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        self.provider.get_tx_for_gene(gene)
+    }
+
+    fn get_all_tx_for_gene(&self, gene: &str) -> Result<Vec<TranscriptSummary>, Error> {
+        self.provider.get_all_tx_for_gene(gene)
+    }
+
+    fn get_transcript_count(&self, gene: &str) -> Result<usize, Error> {
+        self.provider.get_transcript_count(gene)
+    }
+
+    fn get_mane_select_for_gene(&self, hgnc: &str) -> Result<Option<String>, Error> {
+        self.provider.get_mane_select_for_gene(hgnc)
+    }
+
+    fn get_tx_for_region(
+        &self,
+        alt_ac: &str,
+        alt_aln_method: &str,
+        start_i: i32,
+        end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        self.provider
+            .get_tx_for_region(alt_ac, alt_aln_method, start_i, end_i)
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        self.provider.get_tx_identity_info(tx_ac)
+    }
+
+    fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        self.provider.get_tx_info(tx_ac, alt_ac, alt_aln_method)
+    }
+
+    fn get_tx_mapping_options(&self, tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        self.provider.get_tx_mapping_options(tx_ac)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use biocommons_bioutils::assemblies::Assembly;
+    use indexmap::IndexMap;
+
+    use crate::data::{
+        error::Error,
+        interface::{
+            GeneInfoRecord, Provider, TxExonsRecord, TxForRegionRecord, TxIdentityInfo,
+            TxInfoRecord, TxMappingOptionsRecord, TxSimilarityRecord,
+        },
+    };
+
+    use super::CachingProvider;
+
+    /// Provider returning the accession as the sequence, counting the sequence queries.
+    #[derive(Default)]
+    struct CountingProvider {
+        seq_part_calls: AtomicUsize,
+    }
+
+    impl Provider for CountingProvider {
+        fn data_version(&self) -> &str {
+            "test"
+        }
+
+        fn schema_version(&self) -> &str {
+            "test"
+        }
+
+        fn get_assembly_map(&self, _assembly: Assembly) -> IndexMap<String, String> {
+            IndexMap::new()
+        }
+
+        fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error> {
+            Err(Error::NoGeneFound(hgnc.to_string()))
+        }
+
+        fn get_pro_ac_for_tx_ac(&self, _tx_ac: &str) -> Result<Option<String>, Error> {
+            Ok(None)
+        }
+
+        fn get_seq_part(
+            &self,
+            ac: &str,
+            begin: Option<usize>,
+            end: Option<usize>,
+        ) -> Result<String, Error> {
+            self.seq_part_calls.fetch_add(1, Ordering::SeqCst);
+            let end = end.unwrap_or(ac.len()).min(ac.len());
+            Ok(ac[begin.unwrap_or_default().min(end)..end].to_string())
+        }
+
+        fn get_acs_for_protein_seq(&self, _seq: &str) -> Result<Vec<String>, Error> {
+            Ok(vec![])
+        }
+
+        fn get_similar_transcripts(&self, _tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+            Ok(vec![])
+        }
+
+        fn get_tx_exons(
+            &self,
+            _tx_ac: &str,
+            _alt_ac: &str,
+            _alt_aln_method: &str,
+        ) -> Result<Vec<TxExonsRecord>, Error> {
+            Ok(vec![])
+        }
+
+        fn get_tx_for_gene(&self, _gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+            Ok(vec![])
+        }
+
+        fn get_tx_for_region(
+            &self,
+            _alt_ac: &str,
+            _alt_aln_method: &str,
+            _start_i: i32,
+            _end_i: i32,
+        ) -> Result<Vec<TxForRegionRecord>, Error> {
+            Ok(vec![])
+        }
+
+        fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+            Err(Error::NoTranscriptFound(tx_ac.to_string()))
+        }
+
+        fn get_tx_info(
+            &self,
+            tx_ac: &str,
+            _alt_ac: &str,
+            _alt_aln_method: &str,
+        ) -> Result<TxInfoRecord, Error> {
+            Err(Error::NoTranscriptFound(tx_ac.to_string()))
+        }
+
+        fn get_tx_mapping_options(
+            &self,
+            _tx_ac: &str,
+        ) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn get_seq_part_cached() -> Result<(), Error> {
+        let counting = Arc::new(CountingProvider::default());
+        let provider = CachingProvider::new(counting.clone(), 2);
+
+        assert_eq!(provider.get_seq_part("ACGT", Some(1), Some(3))?, "CG");
+        assert_eq!(provider.get_seq_part("ACGT", Some(1), Some(3))?, "CG");
+        assert_eq!(counting.seq_part_calls.load(Ordering::SeqCst), 1);
+
+        assert_eq!(provider.get_seq("ACGT")?, "ACGT");
+        assert_eq!(provider.get_seq_part("ACGT", Some(0), None)?, "ACGT");
+        assert_eq!(counting.seq_part_calls.load(Ordering::SeqCst), 2);
+
+        // Evicts the least recently used entry `("ACGT", 1, 3)`.
+        assert_eq!(provider.get_seq("TTT")?, "TTT");
+        assert_eq!(provider.get_seq_part("ACGT", Some(1), Some(3))?, "CG");
+        assert_eq!(counting.seq_part_calls.load(Ordering::SeqCst), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn batch_get_seq_parts() -> Result<(), Error> {
+        let counting = Arc::new(CountingProvider::default());
+        let provider = CachingProvider::new(counting.clone(), 10);
+
+        assert_eq!(provider.get_seq_part("ACGT", None, Some(2))?, "AC");
+        let queries = vec![
+            (String::from("ACGT"), Some(2), None),
+            (String::from("ACGT"), None, Some(2)),
+            (String::from("TTT"), None, None),
+        ];
+        assert_eq!(
+            provider.batch_get_seq_parts(&queries)?,
+            vec!["GT", "AC", "TTT"]
+        );
+        assert_eq!(counting.seq_part_calls.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            provider.batch_get_seq_parts(&queries)?,
+            vec!["GT", "AC", "TTT"]
+        );
+        assert_eq!(counting.seq_part_calls.load(Ordering::SeqCst), 3);
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
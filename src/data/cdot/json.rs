@@ -2,19 +2,20 @@
 //!
 //! https://github.com/SACGF/cdot
 
-use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Instant};
+use std::{collections::HashMap, ops::Range, path::PathBuf, sync::Arc, time::Instant};
 
 use crate::{
     data::error::Error,
     data::interface::{
-        self, GeneInfoRecord, TxExonsRecord, TxForRegionRecord, TxIdentityInfo, TxInfoRecord,
-        TxMappingOptionsRecord, TxSimilarityRecord,
+        self, GeneInfoRecord, ProviderCapabilities, TxExonsRecord, TxForRegionRecord,
+        TxIdentityInfo, TxInfoRecord, TxMappingOptionsRecord, TxSimilarityRecord,
     },
+    data::interval_tree::IntervalTree,
+    parser::Accession,
     sequences::TranslationTable,
 };
 use biocommons_bioutils::assemblies::{Assembly, ASSEMBLY_INFOS};
 
-use bio::data_structures::interval_tree::ArrayBackedIntervalTree;
 use chrono::NaiveDateTime;
 use indexmap::IndexMap;
 use seqrepo::{self, SeqRepo};
@@ -103,6 +104,10 @@ impl interface::Provider for Provider {
         self.inner.schema_version()
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::all() - ProviderCapabilities::HAS_SIMILAR_TX
+    }
+
     fn get_assembly_map(
         &self,
         assembly: biocommons_bioutils::assemblies::Assembly,
@@ -118,6 +123,14 @@ impl interface::Provider for Provider {
         self.inner.get_pro_ac_for_tx_ac(tx_ac)
     }
 
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        self.inner.get_tx_ac_for_pro_ac(pro_ac)
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        self.inner.get_transcript_versions(base_ac)
+    }
+
     fn get_seq_part(
         &self,
         ac: &str,
@@ -565,9 +578,6 @@ pub mod models {
     }
 }
 
-/// Type alias for interval trees.
-type IntervalTree = ArrayBackedIntervalTree<i32, String>;
-
 /// Internal implementation of the transcript provider.
 struct TxProvider {
     /// Genes by HGNC symbol.
@@ -577,7 +587,7 @@ struct TxProvider {
     /// Transcript identifiers for each gene HGNC symbol.
     transcript_ids_for_gene: HashMap<String, Vec<String>>,
     /// Interval tree for each alternative contig.
-    interval_trees: HashMap<String, IntervalTree>,
+    interval_trees: HashMap<String, IntervalTree<i32, String>>,
 }
 
 /// "Normal" associated functions and methods.
@@ -700,18 +710,13 @@ impl TxProvider {
 
     fn build_interval_trees(
         transcripts: &HashMap<String, models::Transcript>,
-    ) -> HashMap<String, IntervalTree> {
+    ) -> HashMap<String, IntervalTree<i32, String>> {
         let start = Instant::now();
         log::debug!("Building interval trees...");
 
-        let mut result = HashMap::new();
+        let mut spans: HashMap<String, Vec<(Range<i32>, String)>> = HashMap::new();
         for transcript in transcripts.values() {
             for genome_alignment in transcript.genome_builds.values() {
-                let contig = &genome_alignment.contig;
-                result.entry(contig.clone()).or_insert(IntervalTree::new());
-                let tree = result
-                    .get_mut(contig)
-                    .expect("should not happen; just inserted");
                 let alt_start_i = genome_alignment
                     .exons
                     .iter()
@@ -725,13 +730,16 @@ impl TxProvider {
                     .map(|exon| exon.alt_end_i)
                     .max()
                     .expect("should not happen; must have at least one exon");
-                tree.insert(alt_start_i..alt_end_i, transcript.id.clone());
+                spans
+                    .entry(genome_alignment.contig.clone())
+                    .or_default()
+                    .push((alt_start_i..alt_end_i, transcript.id.clone()));
             }
         }
-
-        for tree in result.values_mut() {
-            tree.index();
-        }
+        let result = spans
+            .into_iter()
+            .map(|(contig, spans)| (contig, spans.into_iter().collect()))
+            .collect();
 
         log::debug!("Built interval trees in {:?}", start.elapsed());
         result
@@ -795,6 +803,31 @@ impl TxProvider {
         Ok(transcript.protein.clone())
     }
 
+    /// Return the transcript encoding `pro_ac`.
+    ///
+    /// This scans all transcripts as there is no index by protein accession.  If several
+    /// transcripts encode the protein, the smallest accession is returned.
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        Ok(self
+            .transcripts
+            .iter()
+            .filter(|(_, tx)| tx.protein.as_deref() == Some(pro_ac))
+            .map(|(tx_ac, _)| tx_ac)
+            .min()
+            .cloned())
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        let mut result = self
+            .transcripts
+            .keys()
+            .map(|tx_ac| Accession::new(tx_ac))
+            .filter(|ac| ac.base() == base_ac && ac.is_versioned())
+            .collect::<Vec<_>>();
+        result.sort_by_key(|ac| ac.version());
+        Ok(result.into_iter().map(|ac| ac.value).collect())
+    }
+
     /// Note from the original cdot Python code.
     ///
     /// This is not implemented. The only caller has comment: 'TODO: drop get_acs_for_protein_seq'
@@ -937,8 +970,7 @@ impl TxProvider {
         if let Some(contig_itv_tree) = self.interval_trees.get(alt_ac) {
             let mut tmp = Vec::new();
 
-            for entry in contig_itv_tree.find((start_i - 1)..(end_i)) {
-                let tx_ac = entry.data();
+            for tx_ac in contig_itv_tree.query((start_i - 1)..(end_i)) {
                 let tx = self
                     .transcripts
                     .get(tx_ac)
@@ -1009,11 +1041,12 @@ impl TxProvider {
             .expect("cannot happen; transcripts without gene_name not imported")
             .clone();
 
-        let mut tmp = tx
+        let genome_alignment = tx
             .genome_builds
             .values()
             .next()
-            .expect("should not happen; must have at least one alignment remaining")
+            .expect("should not happen; must have at least one alignment remaining");
+        let mut tmp = genome_alignment
             .exons
             .iter()
             .map(|exon| (exon.ord, exon.alt_cds_end_i + 1 - exon.alt_cds_start_i))
@@ -1029,11 +1062,10 @@ impl TxProvider {
             cds_end_i: tx.stop_codon.unwrap_or_default(),
             lengths,
             hgnc,
-            translation_table: if is_selenoprotein {
-                TranslationTable::Selenocysteine
-            } else {
-                TranslationTable::Standard
-            },
+            translation_table: TranslationTable::for_alt_ac(
+                &genome_alignment.contig,
+                is_selenoprotein,
+            ),
         })
     }
 
@@ -0,0 +1,828 @@
+//! Access to transcripts through the Ensembl REST API, e.g., for model organisms.
+//!
+//! Transcripts are looked up with `GET /lookup/id/{id}?expand=1` and their sequences are
+//! fetched with `GET /sequence/id/{id}`.  Genomic sequences are fetched with
+//! `GET /sequence/region/{species}/{region}`.  Requests are spaced out to at most
+//! `MAX_REQUESTS_PER_SECOND` to respect the Ensembl rate limit.
+//!
+//! Transcripts are aligned to the Ensembl sequence region name, e.g., `13`.  If an assembly is
+//! configured, the RefSeq accession of the chromosome, e.g., `NC_000013.11`, can be used as
+//! well.  As the provider performs a network request for each sequence query, it should be
+//! wrapped into a `data::cache::CachingProvider`:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use hgvs::data::{
+//!     cache::CachingProvider,
+//!     ensembl_rest::{Config, EnsemblRestProvider},
+//! };
+//! use hgvs::mapper::variant::Mapper;
+//!
+//! let provider = EnsemblRestProvider::new(Config {
+//!     species: String::from("mus_musculus"),
+//!     assembly: None,
+//!     ..Default::default()
+//! })?;
+//! let provider = Arc::new(CachingProvider::new(Arc::new(provider), 1_000));
+//! let mapper = Mapper::new(&Default::default(), provider);
+//! # Ok::<(), hgvs::data::error::Error>(())
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use biocommons_bioutils::assemblies::{Assembly, ASSEMBLY_INFOS};
+use indexmap::IndexMap;
+use reqwest::{blocking::Client, header, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    data::{
+        error::Error,
+        gff3::Transcript,
+        interface::{
+            GeneInfoRecord, Provider, ProviderCapabilities, TxExonsRecord, TxForRegionRecord,
+            TxIdentityInfo, TxInfoRecord, TxMappingOptionsRecord, TxSimilarityRecord,
+        },
+    },
+    sequences::TranslationTable,
+};
+
+/// URL of the public Ensembl REST API.
+pub static DEFAULT_BASE_URL: &str = "https://rest.ensembl.org";
+
+/// What is returned by `Provider::schema_version()` and `Provider::data_version()`.
+pub static REQUIRED_VERSION: &str = "1.1";
+
+/// The alignment method returned for all Ensembl transcripts.
+pub static ENSEMBL_ALN_METHOD: &str = "splign";
+
+/// Maximal number of requests per second, as allowed by the Ensembl REST API.
+pub const MAX_REQUESTS_PER_SECOND: u32 = 15;
+
+/// Number of attempts for requests that are rejected because of the rate limit.
+const MAX_ATTEMPTS: usize = 3;
+
+/// Longest wait before retrying a request, regardless of the `Retry-After` header.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Configuration for the `data::ensembl_rest::EnsemblRestProvider`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Config {
+    /// Base URL of the REST API, e.g., `https://rest.ensembl.org`.
+    pub base_url: String,
+    /// Species name as used by Ensembl, e.g., `homo_sapiens`.
+    pub species: String,
+    /// Assembly used for translating between RefSeq chromosome accessions and Ensembl
+    /// sequence region names, if any.
+    pub assembly: Option<Assembly>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            species: String::from("homo_sapiens"),
+            assembly: Some(Assembly::Grch38),
+        }
+    }
+}
+
+/// Exon in the response of `GET /lookup/id/{id}`.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct LookupExon {
+    /// 1-based start position.
+    start: i32,
+    /// 1-based end position (inclusive).
+    end: i32,
+}
+
+/// Translation in the response of `GET /lookup/id/{id}`.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct LookupTranslation {
+    id: String,
+    version: Option<u32>,
+    /// 1-based genomic start position of the CDS.
+    start: i32,
+    /// 1-based genomic end position of the CDS (inclusive), including the stop codon.
+    end: i32,
+}
+
+/// Response of `GET /lookup/id/{id}?expand=1` for a transcript.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct LookupTranscript {
+    id: String,
+    version: Option<u32>,
+    /// Transcript name, e.g., `BRCA2-201`.
+    display_name: Option<String>,
+    seq_region_name: String,
+    strand: i16,
+    #[serde(rename = "Exon", default)]
+    exons: Vec<LookupExon>,
+    #[serde(rename = "Translation")]
+    translation: Option<LookupTranslation>,
+}
+
+/// Response of `GET /sequence/id/{id}` and `GET /sequence/region/{species}/{region}`.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct SequenceResponse {
+    version: Option<u32>,
+    seq: String,
+}
+
+/// Return the versioned accession for Ensembl `id` and `version`.
+fn versioned(id: &str, version: Option<u32>) -> String {
+    match version {
+        Some(version) => format!("{id}.{version}"),
+        None => id.to_string(),
+    }
+}
+
+/// Split the accession `ac` into the identifier and the version, if any.
+fn split_version(ac: &str) -> (&str, Option<u32>) {
+    match ac.split_once('.') {
+        Some((id, version)) => match version.parse() {
+            Ok(version) => (id, Some(version)),
+            Err(_) => (ac, None),
+        },
+        None => (ac, None),
+    }
+}
+
+/// Return whether `id` is an Ensembl stable identifier, e.g., `ENST00000380152` or
+/// `ENSMUSP00000000001`, and the type letter (`G`, `T`, `P`, or `E`).
+fn stable_id_type(id: &str) -> Option<char> {
+    let rest = id.strip_prefix("ENS")?;
+    let digits = rest.find(|c: char| c.is_ascii_digit())?;
+    if digits == 0 || !rest[digits..].chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    rest[..digits].chars().last()
+}
+
+/// Return the gene symbol for the transcript name, e.g., `BRCA2` for `BRCA2-201`.
+fn gene_symbol(display_name: &str) -> &str {
+    match display_name.rsplit_once('-') {
+        Some((symbol, number))
+            if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            symbol
+        }
+        _ => display_name,
+    }
+}
+
+/// A transcript as looked up from the REST API.
+#[derive(Debug, Clone)]
+struct EnsemblTranscript {
+    /// Transcript with exons and CDS on the Ensembl sequence region.
+    tx: Transcript,
+    /// RefSeq accession of the sequence region in the configured assembly, if any.
+    refseq_ac: Option<String>,
+}
+
+impl EnsemblTranscript {
+    /// Construct from the `lookup` response, see `EnsemblRestProvider::refseq_ac()` for
+    /// `refseq_ac`.
+    fn from_lookup(lookup: LookupTranscript, refseq_ac: Option<String>) -> Result<Self, Error> {
+        let tx_ac = versioned(&lookup.id, lookup.version);
+        if lookup.exons.is_empty() || !matches!(lookup.strand, 1 | -1) {
+            return Err(Error::EnsemblRest(format!(
+                "invalid exons or strand for {tx_ac}"
+            )));
+        }
+        let mut exons = lookup
+            .exons
+            .iter()
+            .map(|exon| (exon.start - 1)..exon.end)
+            .collect::<Vec<_>>();
+        exons.sort_by_key(|exon| exon.start);
+
+        Ok(Self {
+            tx: Transcript {
+                tx_ac,
+                hgnc: lookup
+                    .display_name
+                    .as_deref()
+                    .map(gene_symbol)
+                    .unwrap_or_default()
+                    .to_string(),
+                pro_ac: lookup
+                    .translation
+                    .as_ref()
+                    .map(|translation| versioned(&translation.id, translation.version)),
+                alt_ac: lookup.seq_region_name,
+                alt_aln_method: ENSEMBL_ALN_METHOD.to_string(),
+                strand: lookup.strand,
+                exons,
+                cds: lookup
+                    .translation
+                    .as_ref()
+                    .map(|translation| (translation.start - 1)..translation.end),
+                is_selenoprotein: false,
+            },
+            refseq_ac,
+        })
+    }
+
+    /// Return whether the transcript is aligned to `alt_ac`.
+    fn is_aligned_to(&self, alt_ac: &str) -> bool {
+        self.tx.alt_ac == alt_ac || self.refseq_ac.as_deref() == Some(alt_ac)
+    }
+
+    /// Return the translation table; `MT` is the Ensembl name of the mitochondrial genome.
+    fn translation_table(&self) -> TranslationTable {
+        match self.tx.alt_ac.as_str() {
+            "MT" => TranslationTable::VertebrateMitochondrial,
+            _ => TranslationTable::for_alt_ac(
+                self.refseq_ac.as_deref().unwrap_or_default(),
+                self.tx.is_selenoprotein,
+            ),
+        }
+    }
+}
+
+/// Spaces out requests to at most `MAX_REQUESTS_PER_SECOND`.
+#[derive(Debug)]
+struct RateLimiter {
+    /// Earliest time of the next request.
+    next: Mutex<Instant>,
+    /// Minimal time between two requests.
+    interval: Duration,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: u32) -> Self {
+        Self {
+            next: Mutex::new(Instant::now()),
+            interval: Duration::from_secs(1) / requests_per_second,
+        }
+    }
+
+    /// Block until the next request may be sent.
+    fn wait(&self) {
+        let mut next = self.next.lock().expect("poisoned");
+        let now = Instant::now();
+        if *next > now {
+            thread::sleep(*next - now);
+        }
+        *next = (*next).max(now) + self.interval;
+    }
+}
+
+/// This provider provides information from the Ensembl REST API.
+///
+/// # Remarks
+///
+/// Only the current version of each transcript is available; looking up other versions
+/// yields `Error::NoTranscriptFound`.  The gene symbol is derived from the transcript name,
+/// e.g., `BRCA2` for `BRCA2-201`.  The methods `get_gene_info()`, `get_tx_for_gene()`,
+/// `get_tx_for_region()`, `get_acs_for_protein_seq()`, and `get_similar_transcripts()` are
+/// not implemented and return errors or empty results.
+///
+/// The method `get_tx_exons()` returns `None` for record entries `tx_aseq`, and `alt_aseq`
+/// and `i32::MAX` for `tx_exon-set_id`, `alt_exon_set_id`, `tx_exon_id`, `alt_exon_id`,
+/// `exon_aln_id`.
+pub struct EnsemblRestProvider {
+    /// Base URL of the REST API.
+    base_url: String,
+    /// Species name as used by Ensembl.
+    species: String,
+    /// Assembly for translating RefSeq chromosome accessions, if any.
+    assembly: Option<Assembly>,
+    /// HTTP client.
+    client: Client,
+    /// Limiter for the request rate.
+    rate_limiter: RateLimiter,
+    /// Transcripts looked up so far, by versioned accession.
+    transcripts: Mutex<HashMap<String, Arc<EnsemblTranscript>>>,
+}
+
+impl EnsemblRestProvider {
+    pub fn new(config: Config) -> Result<Self, Error> {
+        let client = Client::builder()
+            .user_agent(concat!("hgvs-rs/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| Error::EnsemblRest(e.to_string()))?;
+        Ok(Self {
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            species: config.species,
+            assembly: config.assembly,
+            client,
+            rate_limiter: RateLimiter::new(MAX_REQUESTS_PER_SECOND),
+            transcripts: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Send a `GET` request for `path` and deserialize the JSON response.
+    ///
+    /// Returns `None` if the API does not know the requested object.
+    fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<Option<T>, Error> {
+        let url = format!("{}{}", self.base_url, path);
+        for _ in 0..MAX_ATTEMPTS {
+            self.rate_limiter.wait();
+            let response = self
+                .client
+                .get(&url)
+                .query(query)
+                .header(header::ACCEPT, "application/json")
+                .send()
+                .map_err(|e| Error::EnsemblRest(format!("{url}: {e}")))?;
+            match response.status() {
+                StatusCode::TOO_MANY_REQUESTS => {
+                    let retry_after = retry_after(
+                        response
+                            .headers()
+                            .get(header::RETRY_AFTER)
+                            .and_then(|value| value.to_str().ok()),
+                    );
+                    log::debug!("rate limit exceeded for {url}, retrying in {retry_after:?}");
+                    thread::sleep(retry_after);
+                }
+                // Ensembl answers unknown identifiers with "400 Bad Request".
+                StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND => return Ok(None),
+                _ => {
+                    return response
+                        .error_for_status()
+                        .and_then(|response| response.json())
+                        .map(Some)
+                        .map_err(|e| Error::EnsemblRest(format!("{url}: {e}")));
+                }
+            }
+        }
+        Err(Error::EnsemblRest(format!(
+            "{url}: rate limit exceeded {MAX_ATTEMPTS} times"
+        )))
+    }
+
+    /// Return the RefSeq accession of the Ensembl sequence region `name` in the configured
+    /// assembly, if any.
+    fn refseq_ac(&self, name: &str) -> Option<String> {
+        ASSEMBLY_INFOS[self.assembly?]
+            .sequences
+            .iter()
+            .find(|record| record.name == name)
+            .map(|record| record.refseq_ac.clone())
+    }
+
+    /// Return the Ensembl sequence region name for `alt_ac`, which may be a RefSeq accession
+    /// of the configured assembly.
+    fn region_name<'a>(&self, alt_ac: &'a str) -> &'a str {
+        self.assembly
+            .and_then(|assembly| {
+                ASSEMBLY_INFOS[assembly]
+                    .sequences
+                    .iter()
+                    .find(|record| record.refseq_ac == alt_ac)
+            })
+            .map(|record| record.name.as_str())
+            .unwrap_or(alt_ac)
+    }
+
+    /// Return the transcript `tx_ac`, looking it up on first access.
+    fn get_transcript(&self, tx_ac: &str) -> Result<Arc<EnsemblTranscript>, Error> {
+        if let Some(tx) = self.transcripts.lock().expect("poisoned").get(tx_ac) {
+            return Ok(tx.clone());
+        }
+
+        let (id, version) = split_version(tx_ac);
+        let lookup: LookupTranscript = self
+            .get_json(
+                &format!("/lookup/id/{id}"),
+                &[
+                    ("expand", String::from("1")),
+                    ("species", self.species.clone()),
+                ],
+            )?
+            .ok_or_else(|| Error::NoTranscriptFound(tx_ac.to_string()))?;
+        if version.is_some() && lookup.version != version {
+            log::debug!(
+                "Ensembl only knows {} for {}",
+                versioned(&lookup.id, lookup.version),
+                tx_ac
+            );
+            return Err(Error::NoTranscriptFound(tx_ac.to_string()));
+        }
+
+        let refseq_ac = self.refseq_ac(&lookup.seq_region_name);
+        let tx = Arc::new(EnsemblTranscript::from_lookup(lookup, refseq_ac)?);
+        self.transcripts
+            .lock()
+            .expect("poisoned")
+            .insert(tx_ac.to_string(), tx.clone());
+        Ok(tx)
+    }
+
+    /// Return the transcript `tx_ac` if aligned to `alt_ac`.
+    fn get_aligned_transcript(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+    ) -> Result<Arc<EnsemblTranscript>, Error> {
+        let tx = self.get_transcript(tx_ac)?;
+        if !tx.is_aligned_to(alt_ac) {
+            return Err(Error::NoAlignmentFound(
+                tx_ac.to_string(),
+                alt_ac.to_string(),
+            ));
+        }
+        Ok(tx)
+    }
+}
+
+impl Provider for EnsemblRestProvider {
+    fn data_version(&self) -> &str {
+        REQUIRED_VERSION
+    }
+
+    fn schema_version(&self) -> &str {
+        REQUIRED_VERSION
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::all()
+            - ProviderCapabilities::HAS_GENE_INFO
+            - ProviderCapabilities::HAS_SIMILAR_TX
+    }
+
+    fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
+        IndexMap::from_iter(
+            ASSEMBLY_INFOS[assembly]
+                .sequences
+                .iter()
+                .map(|record| (record.refseq_ac.clone(), record.name.clone())),
+        )
+    }
+
+    fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        log::warn!("EnsemblRestProvider::get_gene_info() has not been implemented");
+        Err(Error::NoGeneFound(hgnc.to_string()))
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error> {
+        Ok(self.get_transcript(tx_ac)?.tx.pro_ac.clone())
+    }
+
+    fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        let begin = begin.unwrap_or_default();
+        if end.is_some_and(|end| end <= begin) {
+            return Ok(String::new());
+        }
+
+        let (id, version) = split_version(ac);
+        let response: SequenceResponse = match stable_id_type(id) {
+            Some(id_type) => {
+                // Coordinates are 1-based and relative to the transcript or protein.
+                let mut query = vec![(
+                    "type",
+                    String::from(if id_type == 'P' { "protein" } else { "cdna" }),
+                )];
+                if let Some(end) = end {
+                    query.push(("start", (begin + 1).to_string()));
+                    query.push(("end", end.to_string()));
+                }
+                let response: SequenceResponse = self
+                    .get_json(&format!("/sequence/id/{id}"), &query)?
+                    .ok_or_else(|| Error::NoSequenceRecord(ac.to_string()))?;
+                if version.is_some() && response.version.is_some() && response.version != version {
+                    return Err(Error::NoSequenceRecord(ac.to_string()));
+                }
+                if end.is_none() {
+                    SequenceResponse {
+                        seq: response.seq.get(begin..).unwrap_or_default().to_string(),
+                        ..response
+                    }
+                } else {
+                    response
+                }
+            }
+            None => {
+                let end = end.ok_or_else(|| {
+                    Error::EnsemblRest(format!("sequence of {ac} requires an end position"))
+                })?;
+                self.get_json(
+                    &format!(
+                        "/sequence/region/{}/{}:{}..{}:1",
+                        self.species,
+                        self.region_name(ac),
+                        begin + 1,
+                        end
+                    ),
+                    &[],
+                )?
+                .ok_or_else(|| Error::NoSequenceRecord(ac.to_string()))?
+            }
+        };
+
+        Ok(response.seq)
+    }
+
+    fn get_acs_for_protein_seq(&self, _seq: &str) -> Result<Vec<String>, Error> {
+        log::warn!("EnsemblRestProvider::get_acs_for_protein_seq() has not been implemented");
+        Ok(vec![])
+    }
+
+    fn get_similar_transcripts(&self, _tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        log::warn!("EnsemblRestProvider::get_similar_transcripts() has not been implemented");
+        Ok(vec![])
+    }
+
+    fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        let tx = self.get_aligned_transcript(tx_ac, alt_ac)?;
+
+        let mut tx_start_i = 0;
+        let mut result = tx
+            .tx
+            .exons_tx_order()
+            .into_iter()
+            .enumerate()
+            .map(|(ord, exon)| {
+                let length = exon.end - exon.start;
+                let record = TxExonsRecord {
+                    hgnc: tx.tx.hgnc.clone(),
+                    tx_ac: tx_ac.to_string(),
+                    alt_ac: alt_ac.to_string(),
+                    alt_aln_method: alt_aln_method.to_string(),
+                    alt_strand: tx.tx.strand,
+                    ord: ord as i32,
+                    tx_start_i,
+                    tx_end_i: tx_start_i + length,
+                    alt_start_i: exon.start,
+                    alt_end_i: exon.end,
+                    cigar: format!("{}M", length),
+                    tx_aseq: None,
+                    alt_aseq: None,
+                    tx_exon_set_id: i32::MAX,
+                    alt_exon_set_id: i32::MAX,
+                    tx_exon_id: i32::MAX,
+                    alt_exon_id: i32::MAX,
+                    exon_aln_id: i32::MAX,
+                };
+                tx_start_i += length;
+                record
+            })
+            .collect::<Vec<_>>();
+        result.sort_by_key(|record| record.alt_start_i);
+
+        Ok(result)
+    }
+
+    fn get_tx_for_gene(&self, _gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        log::warn!("EnsemblRestProvider::get_tx_for_gene() has not been implemented");
+        Ok(vec![])
+    }
+
+    fn get_tx_for_region(
+        &self,
+        _alt_ac: &str,
+        _alt_aln_method: &str,
+        _start_i: i32,
+        _end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        log::warn!("EnsemblRestProvider::get_tx_for_region() has not been implemented");
+        Ok(vec![])
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        let tx = self.get_transcript(tx_ac)?;
+        let cds = tx.tx.cds_tx();
+
+        Ok(TxIdentityInfo {
+            tx_ac: tx_ac.to_string(),
+            alt_ac: tx_ac.to_string(), // sic(!)
+            alt_aln_method: String::from("transcript"),
+            cds_start_i: cds.as_ref().map(|cds| cds.start).unwrap_or_default(),
+            cds_end_i: cds.as_ref().map(|cds| cds.end).unwrap_or_default(),
+            lengths: tx.tx.lengths(),
+            hgnc: tx.tx.hgnc.clone(),
+            translation_table: tx.translation_table(),
+        })
+    }
+
+    fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        let tx = self.get_aligned_transcript(tx_ac, alt_ac)?;
+        let cds = tx.tx.cds_tx();
+
+        Ok(TxInfoRecord {
+            hgnc: tx.tx.hgnc.clone(),
+            cds_start_i: cds.as_ref().map(|cds| cds.start),
+            cds_end_i: cds.as_ref().map(|cds| cds.end),
+            tx_ac: tx_ac.to_string(),
+            alt_ac: alt_ac.to_string(),
+            alt_aln_method: alt_aln_method.to_string(),
+        })
+    }
+
+    fn get_tx_mapping_options(&self, tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        let tx = self.get_transcript(tx_ac)?;
+
+        Ok(tx
+            .refseq_ac
+            .iter()
+            .chain(std::iter::once(&tx.tx.alt_ac))
+            .map(|alt_ac| TxMappingOptionsRecord {
+                tx_ac: tx_ac.to_string(),
+                alt_ac: alt_ac.clone(),
+                alt_aln_method: tx.tx.alt_aln_method.clone(),
+            })
+            .collect())
+    }
+}
+
+/// Return the time to wait given the value of the `Retry-After` header in seconds.
+///
+/// Missing or invalid values, e.g., negative numbers, give one second; the result is at
+/// most `MAX_RETRY_AFTER`.
+fn retry_after(value: Option<&str>) -> Duration {
+    value
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|secs| !secs.is_nan())
+        .and_then(|secs| Duration::try_from_secs_f64(secs.min(MAX_RETRY_AFTER.as_secs_f64())).ok())
+        .unwrap_or(Duration::from_secs(1))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{str::FromStr, sync::Arc, time::Duration};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        data::{cache::CachingProvider, interface::Provider as _},
+        mapper::variant::{Config as MapperConfig, Mapper},
+        parser::HgvsVariant,
+    };
+
+    use super::{
+        gene_symbol, retry_after, split_version, stable_id_type, Config, EnsemblRestProvider,
+        EnsemblTranscript, LookupTranscript, MAX_RETRY_AFTER,
+    };
+
+    // Lookup response for a transcript on the minus strand of chromosome 7 with the exons at
+    // `7:g.1001_1300` and `7:g.1801_2000`, and the CDS at `7:g.1101_1900`.
+    static LOOKUP_JSON: &str = r#"{
+        "object_type": "Transcript",
+        "id": "ENST00000000001",
+        "version": 2,
+        "display_name": "GENE1-201",
+        "species": "homo_sapiens",
+        "assembly_name": "GRCh38",
+        "seq_region_name": "7",
+        "start": 1001,
+        "end": 2000,
+        "strand": -1,
+        "Exon": [
+            {"object_type": "Exon", "id": "ENSE00000000001", "start": 1801, "end": 2000, "strand": -1},
+            {"object_type": "Exon", "id": "ENSE00000000002", "start": 1001, "end": 1300, "strand": -1}
+        ],
+        "Translation": {
+            "object_type": "Translation",
+            "id": "ENSP00000000001",
+            "version": 2,
+            "start": 1101,
+            "end": 1900,
+            "length": 99
+        }
+    }"#;
+
+    fn build_provider() -> Result<EnsemblRestProvider, anyhow::Error> {
+        let provider = EnsemblRestProvider::new(Config::default())?;
+        let lookup: LookupTranscript = serde_json::from_str(LOOKUP_JSON)?;
+        let refseq_ac = provider.refseq_ac(&lookup.seq_region_name);
+        provider.transcripts.lock().unwrap().insert(
+            String::from("ENST00000000001.2"),
+            Arc::new(EnsemblTranscript::from_lookup(lookup, refseq_ac)?),
+        );
+        Ok(provider)
+    }
+
+    #[test]
+    fn helpers() {
+        assert_eq!(
+            split_version("ENST00000380152.8"),
+            ("ENST00000380152", Some(8))
+        );
+        assert_eq!(split_version("ENST00000380152"), ("ENST00000380152", None));
+        assert_eq!(stable_id_type("ENST00000380152"), Some('T'));
+        assert_eq!(stable_id_type("ENSMUSP00000000001"), Some('P'));
+        assert_eq!(stable_id_type("NM_000059"), None);
+        assert_eq!(stable_id_type("ENS"), None);
+        assert_eq!(gene_symbol("BRCA2-201"), "BRCA2");
+        assert_eq!(gene_symbol("HLA-A"), "HLA-A");
+
+        assert_eq!(retry_after(Some("2.5")), Duration::from_millis(2500));
+        assert_eq!(retry_after(None), Duration::from_secs(1));
+        assert_eq!(retry_after(Some("-1")), Duration::from_secs(1));
+        assert_eq!(retry_after(Some("NaN")), Duration::from_secs(1));
+        assert_eq!(retry_after(Some("1e300")), MAX_RETRY_AFTER);
+        assert_eq!(retry_after(Some("inf")), MAX_RETRY_AFTER);
+    }
+
+    #[test]
+    fn transcript_from_lookup() -> Result<(), anyhow::Error> {
+        let provider = build_provider()?;
+
+        let info = provider.get_tx_identity_info("ENST00000000001.2")?;
+        assert_eq!(info.cds_start_i, 100);
+        assert_eq!(info.cds_end_i, 400);
+        assert_eq!(info.lengths, vec![200, 300]);
+        assert_eq!(info.hgnc, "GENE1");
+        assert_eq!(
+            provider.get_pro_ac_for_tx_ac("ENST00000000001.2")?,
+            Some(String::from("ENSP00000000001.2"))
+        );
+
+        let options = provider.get_tx_mapping_options("ENST00000000001.2")?;
+        assert_eq!(
+            options
+                .iter()
+                .map(|option| option.alt_ac.as_str())
+                .collect::<Vec<_>>(),
+            vec!["NC_000007.14", "7"]
+        );
+
+        for alt_ac in ["7", "NC_000007.14"] {
+            let exons = provider.get_tx_exons("ENST00000000001.2", alt_ac, "splign")?;
+            assert_eq!(
+                exons
+                    .iter()
+                    .map(|exon| (
+                        exon.ord,
+                        exon.tx_start_i,
+                        exon.tx_end_i,
+                        exon.alt_start_i,
+                        exon.alt_end_i
+                    ))
+                    .collect::<Vec<_>>(),
+                vec![(1, 200, 500, 1000, 1300), (0, 0, 200, 1800, 2000)]
+            );
+            assert!(exons.iter().all(|exon| exon.alt_strand == -1));
+        }
+        assert!(provider
+            .get_tx_exons("ENST00000000001.2", "8", "splign")
+            .is_err());
+
+        let info = provider.get_tx_info("ENST00000000001.2", "7", "splign")?;
+        assert_eq!((info.cds_start_i, info.cds_end_i), (Some(100), Some(400)));
+
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "requires access to rest.ensembl.org"]
+    fn map_brca2_variant() -> Result<(), anyhow::Error> {
+        let provider = EnsemblRestProvider::new(Config::default())?;
+        let provider = Arc::new(CachingProvider::new(Arc::new(provider), 100));
+        let mapper = Mapper::new(&MapperConfig::default(), provider);
+
+        let var_c = HgvsVariant::from_str("ENST00000380152.8:c.68-7T>A")?;
+        let var_g = mapper.c_to_g(&var_c, "NC_000013.11", "splign")?;
+        assert_eq!(format!("{var_g}"), "NC_000013.11:g.32319070T>A");
+        assert_eq!(
+            format!("{}", mapper.g_to_c(&var_g, "ENST00000380152.8", "splign")?),
+            "ENST00000380152.8:c.68-7T>A"
+        );
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
@@ -22,6 +22,16 @@ pub enum Error {
     CdotJsonOpen(String),
     #[error("could not parse cdot JSON file: {0}")]
     CdotJsonParse(String),
+    #[error("could not open GFF3 file: {0}")]
+    Gff3Open(String),
+    #[error("could not parse GFF3 file: {0}")]
+    Gff3Parse(String),
+    #[error("could not open transcript TSV file: {0}")]
+    TsvOpen(String),
+    #[error("could not parse transcript TSV file: {0}")]
+    TsvParse(String),
+    #[error("could not open indexed FASTA file: {0}")]
+    FastaOpen(String),
     #[error("no gene found for {0}")]
     NoGeneFound(String),
     #[error("no transcript found for {0}")]
@@ -30,4 +40,14 @@ pub enum Error {
     NoAlignmentFound(String, String),
     #[error("found no sequence record for accession {0}")]
     NoSequenceRecord(String),
+    #[error("blocking provider task failed: {0}")]
+    BlockingTaskFailed(String),
+    #[error("Ensembl REST request failed: {0}")]
+    EnsemblRest(String),
+    #[error("position {1} out of range for sequence {0}")]
+    PositionOutOfRange(String, i64),
+    #[error("sequence of {0} has length {2} but its exons have a total length of {1}")]
+    SequenceLengthMismatch(String, usize, usize),
+    #[error("provider does not support {0}")]
+    CapabilityNotSupported(String),
 }
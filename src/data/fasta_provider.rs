@@ -0,0 +1,434 @@
+//! Access to transcripts from a local tab-separated file with sequences from an indexed FASTA
+//! file.
+//!
+//! This allows for using the library without access to a database or a `seqrepo`.  The FASTA
+//! file must be indexed, e.g., with `samtools faidx`.  Transcript sequences are spliced from
+//! the genome sequence.
+//!
+//! The first line of the transcript file is a header naming the columns, the order of the
+//! columns is arbitrary.  The following columns are required:
+//!
+//! - `tx_ac` -- transcript accession
+//! - `alt_ac` -- accession of the contig as named in the FASTA file
+//! - `alt_aln_method` -- alignment method, e.g., `splign`
+//! - `cds_start_i`, `cds_end_i` -- CDS in transcript coordinates (0-based, half-open), empty
+//!   for non-coding transcripts
+//! - `exon_starts`, `exon_ends` -- comma-separated exon positions on the contig (0-based,
+//!   half-open)
+//!
+//! The optional columns `alt_strand` (`1` or `-1`, default `1`), `hgnc` (gene symbol), and
+//! `pro_ac` (protein accession) may be given in addition.  For example:
+//!
+//! ```text
+//! tx_ac   alt_ac  alt_aln_method  alt_strand  hgnc   pro_ac  cds_start_i  cds_end_i  exon_starts  exon_ends
+//! TX1.1   chrT    splign          1           GENE1  PX1.1   20           86         10           110
+//! TX2.1   chrT    splign          -1          GENE2                                  10,60        40,110
+//! ```
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+use biocommons_bioutils::assemblies::Assembly;
+use indexmap::IndexMap;
+
+use crate::data::{
+    error::Error,
+    gff3::{self, Transcript},
+    interface::{
+        self, GeneInfoRecord, ProviderCapabilities, TxExonsRecord, TxForRegionRecord,
+        TxIdentityInfo, TxInfoRecord, TxMappingOptionsRecord, TxSimilarityRecord,
+    },
+};
+
+/// Configuration for the `data::fasta_provider::FastaProvider`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Config {
+    /// Path to the tab-separated file with the transcripts.
+    pub tsv_path: String,
+    /// Path to the FASTA file with the genome sequence; the `.fai` index must be next to it.
+    pub fasta_path: String,
+}
+
+/// Parse comma-separated positions, ignoring a trailing comma.
+fn parse_positions(value: &str) -> Option<Vec<i32>> {
+    value
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().ok())
+        .collect()
+}
+
+/// This provider provides information from a transcript TSV file and an indexed FASTA file.
+///
+/// # Remarks
+///
+/// All transcripts are assumed to align to the genome without gaps.  The methods
+/// `get_acs_for_protein_seq()` and `get_similar_transcripts()` are not implemented and return
+/// empty results.
+///
+/// The method `get_tx_exons()` returns `None` for record entries `tx_aseq`, and `alt_aseq`
+/// and `i32::MAX` for `tx_exon-set_id`, `alt_exon_set_id`, `tx_exon_id`, `alt_exon_id`,
+/// `exon_aln_id`.
+pub struct FastaProvider {
+    inner: gff3::Provider,
+}
+
+impl FastaProvider {
+    pub fn new(config: Config) -> Result<Self, Error> {
+        let transcripts = Self::load_transcripts(&config.tsv_path)?;
+        Ok(Self {
+            inner: gff3::Provider::with_transcripts(transcripts, &config.fasta_path)?,
+        })
+    }
+
+    /// Load the transcripts from the TSV file at `path`.
+    fn load_transcripts(path: &str) -> Result<IndexMap<String, Transcript>, Error> {
+        let file = File::open(path).map_err(|e| Error::TsvOpen(format!("{}: {}", path, e)))?;
+        let mut lines = BufReader::new(file).lines();
+
+        let header = lines
+            .next()
+            .transpose()
+            .map_err(|e| Error::TsvParse(format!("{}: {}", path, e)))?
+            .unwrap_or_default();
+        let columns = header.split('\t').collect::<Vec<_>>();
+        let column = |name: &str| columns.iter().position(|column| *column == name);
+        let required = |name: &str| {
+            column(name)
+                .ok_or_else(|| Error::TsvParse(format!("{}: missing column {}", path, name)))
+        };
+        let col_tx_ac = required("tx_ac")?;
+        let col_alt_ac = required("alt_ac")?;
+        let col_alt_aln_method = required("alt_aln_method")?;
+        let col_cds_start_i = required("cds_start_i")?;
+        let col_cds_end_i = required("cds_end_i")?;
+        let col_exon_starts = required("exon_starts")?;
+        let col_exon_ends = required("exon_ends")?;
+        let col_alt_strand = column("alt_strand");
+        let col_hgnc = column("hgnc");
+        let col_pro_ac = column("pro_ac");
+
+        let mut result = IndexMap::new();
+        for (i, line) in lines.enumerate() {
+            let line = line.map_err(|e| Error::TsvParse(format!("{}: {}", path, e)))?;
+            if line.is_empty() {
+                continue;
+            }
+            let err = |msg: &str| Error::TsvParse(format!("{}:{}: {}", path, i + 2, msg));
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let field = |col: usize| {
+                fields
+                    .get(col)
+                    .copied()
+                    .ok_or_else(|| err("too few columns"))
+            };
+            let optional = |col: Option<usize>| col.and_then(|col| fields.get(col).copied());
+
+            let strand = match optional(col_alt_strand).unwrap_or("1") {
+                "1" | "+" | "" => 1,
+                "-1" | "-" => -1,
+                _ => return Err(err("invalid alt_strand")),
+            };
+            let starts = parse_positions(field(col_exon_starts)?)
+                .ok_or_else(|| err("invalid exon_starts"))?;
+            let ends =
+                parse_positions(field(col_exon_ends)?).ok_or_else(|| err("invalid exon_ends"))?;
+            if starts.is_empty()
+                || starts.len() != ends.len()
+                || starts
+                    .iter()
+                    .zip(ends.iter())
+                    .any(|(start, end)| start >= end)
+            {
+                return Err(err("invalid exons"));
+            }
+            let mut exons = starts
+                .into_iter()
+                .zip(ends)
+                .map(|(start, end)| start..end)
+                .collect::<Vec<_>>();
+            exons.sort_by_key(|exon| exon.start);
+
+            let mut tx = Transcript {
+                tx_ac: field(col_tx_ac)?.to_string(),
+                hgnc: optional(col_hgnc).unwrap_or_default().to_string(),
+                pro_ac: optional(col_pro_ac)
+                    .filter(|pro_ac| !pro_ac.is_empty())
+                    .map(str::to_string),
+                alt_ac: field(col_alt_ac)?.to_string(),
+                alt_aln_method: field(col_alt_aln_method)?.to_string(),
+                strand,
+                exons,
+                cds: None,
+                is_selenoprotein: false,
+            };
+
+            // Project the CDS from transcript to contig coordinates.
+            match (field(col_cds_start_i)?, field(col_cds_end_i)?) {
+                ("", "") => (),
+                (start, end) => {
+                    let start = start
+                        .parse::<i32>()
+                        .map_err(|_| err("invalid cds_start_i"))?;
+                    let end = end.parse::<i32>().map_err(|_| err("invalid cds_end_i"))?;
+                    let first = tx.n_to_g(start);
+                    let last = tx.n_to_g(end - 1);
+                    match first.zip(last) {
+                        Some((first, last)) if start < end => {
+                            tx.cds = Some(first.min(last)..(first.max(last) + 1));
+                        }
+                        _ => return Err(err("CDS outside of transcript")),
+                    }
+                }
+            }
+
+            result.insert(tx.tx_ac.clone(), tx);
+        }
+
+        log::debug!(
+            "fasta_provider::FastaProvider -- #transcripts = {}",
+            result.len()
+        );
+
+        Ok(result)
+    }
+}
+
+impl interface::Provider for FastaProvider {
+    fn data_version(&self) -> &str {
+        self.inner.data_version()
+    }
+
+    fn schema_version(&self) -> &str {
+        self.inner.schema_version()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
+        self.inner.get_assembly_map(assembly)
+    }
+
+    fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        self.inner.get_gene_info(hgnc)
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error> {
+        self.inner.get_pro_ac_for_tx_ac(tx_ac)
+    }
+
+    fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        self.inner.get_all_pro_ac_for_tx_ac(tx_ac)
+    }
+
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        self.inner.get_tx_ac_for_pro_ac(pro_ac)
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        self.inner.get_transcript_versions(base_ac)
+    }
+
+    fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        self.inner.get_seq_part(ac, begin, end)
+    }
+
+    fn get_acs_for_protein_seq(&self, seq: &str) -> Result<Vec<String>, Error> {
+        self.inner.get_acs_for_protein_seq(seq)
+    }
+
+    fn get_similar_transcripts(&self, tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        self.inner.get_similar_transcripts(tx_ac)
+    }
+
+    fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        self.inner.get_tx_exons(tx_ac, alt_ac, alt_aln_method)
+    }
+
+    fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        self.inner.get_tx_for_gene(gene)
+    }
+
+    fn get_tx_for_region(
+        &self,
+        alt_ac: &str,
+        alt_aln_method: &str,
+        start_i: i32,
+        end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        self.inner
+            .get_tx_for_region(alt_ac, alt_aln_method, start_i, end_i)
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        self.inner.get_tx_identity_info(tx_ac)
+    }
+
+    fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        self.inner.get_tx_info(tx_ac, alt_ac, alt_aln_method)
+    }
+
+    fn get_tx_mapping_options(&self, tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        self.inner.get_tx_mapping_options(tx_ac)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::{
+        data::{error::Error, interface::Provider as _},
+        mapper::variant::{Config as MapperConfig, Mapper},
+        parser::HgvsVariant,
+    };
+
+    use super::{Config, FastaProvider};
+
+    // The transcript file describes `TX1.1` (as in the `gff3` tests) with a single exon at
+    // `chrT:g.11_110` on the plus strand, and the non-coding `TX2.1` with the exons
+    // `chrT:g.11_40` and `chrT:g.61_110` on the minus strand, aligned with `blat`.
+    fn build_provider() -> Result<FastaProvider, anyhow::Error> {
+        Ok(FastaProvider::new(Config {
+            tsv_path: String::from("tests/data/data/fasta/transcripts.txt"),
+            fasta_path: String::from("tests/data/data/fasta/single_exon.fa"),
+        })?)
+    }
+
+    #[test]
+    fn get_tx_identity_info() -> Result<(), anyhow::Error> {
+        let provider = build_provider()?;
+
+        let info = provider.get_tx_identity_info("TX1.1")?;
+        assert_eq!(info.cds_start_i, 20);
+        assert_eq!(info.cds_end_i, 86);
+        assert_eq!(info.lengths, vec![100]);
+        assert_eq!(info.hgnc, "GENE1");
+        assert_eq!(
+            provider.get_pro_ac_for_tx_ac("TX1.1")?,
+            Some("PX1.1".to_string())
+        );
+
+        let info = provider.get_tx_identity_info("TX2.1")?;
+        assert_eq!(info.cds_start_i, 0);
+        assert_eq!(info.cds_end_i, 0);
+        assert_eq!(info.lengths, vec![50, 30]);
+        assert_eq!(provider.get_pro_ac_for_tx_ac("TX2.1")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_tx_exons() -> Result<(), anyhow::Error> {
+        let provider = build_provider()?;
+
+        let exons = provider.get_tx_exons("TX2.1", "chrT", "blat")?;
+        assert_eq!(exons.len(), 2);
+        assert_eq!(
+            (exons[0].tx_start_i, exons[0].tx_end_i, exons[0].alt_start_i),
+            (50, 80, 10)
+        );
+        assert_eq!(
+            (exons[1].tx_start_i, exons[1].tx_end_i, exons[1].alt_start_i),
+            (0, 50, 60)
+        );
+        assert_eq!(exons[0].alt_strand, -1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_tx_for_region() -> Result<(), anyhow::Error> {
+        let provider = build_provider()?;
+
+        let records = provider.get_tx_for_region("chrT", "splign", 50, 51)?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].tx_ac, "TX1.1");
+        let records = provider.get_tx_for_region("chrT", "blat", 50, 51)?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].tx_ac, "TX2.1");
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_seq_part() -> Result<(), anyhow::Error> {
+        let provider = build_provider()?;
+
+        assert_eq!(
+            provider.get_seq_part("TX1.1", Some(20), Some(26))?,
+            "ATGAAA"
+        );
+        assert_eq!(provider.get_seq("TX2.1")?.len(), 80);
+        assert!(matches!(
+            provider.get_seq("chrX"),
+            Err(Error::NoSequenceRecord(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn g_to_c_and_g_to_n() -> Result<(), anyhow::Error> {
+        let mapper = Mapper::new(&MapperConfig::default(), Arc::new(build_provider()?));
+
+        let var_g = HgvsVariant::from_str("chrT:g.34A>G")?;
+        let var_c = mapper.g_to_c(&var_g, "TX1.1", "splign")?;
+        assert_eq!(format!("{}", &var_c), "TX1.1:c.4A>G");
+        assert_eq!(mapper.c_to_g(&var_c, "chrT", "splign")?, var_g);
+        let var_p = mapper.c_to_p(&var_c, None)?;
+        assert_eq!(format!("{}", &var_p), "PX1.1:p.Lys2Glu");
+
+        let var_g = HgvsVariant::from_str("chrT:g.100C>T")?;
+        let var_n = mapper.g_to_n(&var_g, "TX2.1", "blat")?;
+        assert_eq!(format!("{}", &var_n), "TX2.1:n.11G>A");
+
+        Ok(())
+    }
+
+    #[test]
+    fn fails_on_missing_column() {
+        assert!(matches!(
+            FastaProvider::new(Config {
+                tsv_path: String::from("tests/data/data/fasta/single_exon.fa.fai"),
+                fasta_path: String::from("tests/data/data/fasta/single_exon.fa"),
+            }),
+            Err(Error::TsvParse(_))
+        ));
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
@@ -0,0 +1,161 @@
+//! Lookup of gene names for variants without inline gene symbol.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    data::{error::Error, interface::Provider},
+    parser::HgvsVariant,
+};
+
+impl HgvsVariant {
+    /// Return the gene symbol of the variant.
+    ///
+    /// This is the inline gene symbol, if any.  Otherwise, the `hgnc` field of
+    /// `Provider::get_tx_identity_info()` is used for transcript (c., n., r.) variants.
+    /// Other variants, e.g., g. and m. variants, have no transcript to look up and yield
+    /// `None`.
+    pub fn gene_name(&self, provider: &dyn Provider) -> Result<Option<String>, Error> {
+        if let Some(gene_symbol) = self.gene_symbol() {
+            return Ok(Some(gene_symbol.value.clone()));
+        }
+        if !self.is_transcript() {
+            return Ok(None);
+        }
+
+        let hgnc = provider.get_tx_identity_info(self.accession())?.hgnc;
+        Ok((!hgnc.is_empty()).then_some(hgnc))
+    }
+}
+
+/// Set the gene symbols of transcript variants in `vars` that have none, see
+/// `HgvsVariant::gene_name()`.
+///
+/// Each distinct accession is looked up only once.
+pub fn add_gene_names(vars: &mut [HgvsVariant], provider: &dyn Provider) -> Result<(), Error> {
+    add_gene_names_with(vars, |tx_acs| {
+        tx_acs
+            .into_iter()
+            .map(|tx_ac| {
+                let hgnc = provider.get_tx_identity_info(&tx_ac)?.hgnc;
+                Ok((tx_ac, hgnc))
+            })
+            .collect()
+    })
+}
+
+/// Set the gene symbols of transcript variants in `vars` that have none.
+///
+/// `lookup` is called once with the distinct accessions of these variants and returns the
+/// `hgnc` value of `Provider::get_tx_identity_info()` for each of them.  Empty values are not
+/// set.
+pub(crate) fn add_gene_names_with<E>(
+    vars: &mut [HgvsVariant],
+    lookup: impl FnOnce(HashSet<String>) -> Result<HashMap<String, String>, E>,
+) -> Result<(), E> {
+    let needs_gene_name = |var: &HgvsVariant| var.gene_symbol().is_none() && var.is_transcript();
+
+    let tx_acs = vars
+        .iter()
+        .filter(|var| needs_gene_name(var))
+        .map(|var| var.accession().value.clone())
+        .collect::<HashSet<_>>();
+    let hgncs = lookup(tx_acs)?;
+
+    for var in vars.iter_mut() {
+        match hgncs.get(&var.accession().value) {
+            Some(hgnc) if needs_gene_name(var) && !hgnc.is_empty() => {
+                *var = var.clone().with_gene_symbol(hgnc.clone());
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use crate::{
+        data::gff3::{Config, Provider},
+        parser::HgvsVariant,
+    };
+
+    use super::add_gene_names;
+
+    fn provider() -> Result<Provider, anyhow::Error> {
+        Ok(Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?)
+    }
+
+    #[test]
+    fn gene_name() -> Result<(), anyhow::Error> {
+        let provider = provider()?;
+
+        for (s, expected) in [
+            ("TX1.1:c.4A>G", Some("GENE1")),
+            ("TX1.1(OTHER):c.4A>G", Some("OTHER")),
+            ("TX2.1:n.5del", Some("GENE2")),
+            ("TX1.1:c.[4A>G;5del]", Some("GENE1")),
+            ("chrT:g.34A>G", None),
+            ("chrT(GENE1):g.34A>G", Some("GENE1")),
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            assert_eq!(var.gene_name(&provider)?.as_deref(), expected, "{s}");
+        }
+
+        let var = HgvsVariant::from_str("TX9.1:c.4A>G")?;
+        assert!(var.gene_name(&provider).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_gene_names_batch() -> Result<(), anyhow::Error> {
+        let provider = provider()?;
+
+        let mut vars = [
+            "TX1.1:c.4A>G",
+            "TX1.2:c.-19A>G",
+            "TX1.1:c.5del",
+            "TX1.1(OTHER):c.4A>G",
+            "chrT:g.34A>G",
+        ]
+        .iter()
+        .map(|s| HgvsVariant::from_str(s))
+        .collect::<Result<Vec<_>, _>>()?;
+        add_gene_names(&mut vars, &provider)?;
+
+        assert_eq!(
+            vars.iter().map(|var| format!("{var}")).collect::<Vec<_>>(),
+            vec![
+                "TX1.1(GENE1):c.4A>G",
+                "TX1.2(GENE1):c.-19A>G",
+                "TX1.1(GENE1):c.5del",
+                "TX1.1(OTHER):c.4A>G",
+                "chrT:g.34A>G",
+            ]
+        );
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
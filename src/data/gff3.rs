@@ -0,0 +1,871 @@
+//! Access to transcripts from a local GFF3 file with sequences from an indexed FASTA file.
+//!
+//! Exon and CDS features are grouped by their `Parent` transcript.  The transcript accession
+//! is taken from the `transcript_id` attribute of the transcript feature (falling back to its
+//! `ID`), the protein accession from the `protein_id` attribute of the CDS features, and the
+//! gene symbol from the `gene` attribute of the transcript feature (falling back to the `Name`
+//! of the parent gene feature).
+//!
+//! The CDS of a transcript spans all its CDS features and is expected to include the stop
+//! codon as in RefSeq GFF3 files.  Files with CDS features excluding the stop codon must
+//! provide it as `stop_codon` feature, as GENCODE does, which is then added to the CDS.
+//!
+//! The FASTA file must be indexed, e.g., with `samtools faidx`.  Transcript sequences are
+//! spliced from the genome sequence.
+
+use std::{collections::HashMap, fs::File, ops::Range, sync::Mutex};
+
+use bio::io::{fasta, gff};
+use biocommons_bioutils::assemblies::{Assembly, ASSEMBLY_INFOS};
+use indexmap::IndexMap;
+
+use crate::{
+    data::error::Error,
+    data::interface::{
+        self, GeneInfoRecord, ProviderCapabilities, TxExonsRecord, TxForRegionRecord,
+        TxIdentityInfo, TxInfoRecord, TxMappingOptionsRecord, TxSimilarityRecord,
+    },
+    data::interval_tree::IntervalTree,
+    parser::Accession,
+    sequences::{revcomp, TranslationTable},
+};
+
+/// Configuration for the `data::gff3::Provider`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Config {
+    /// Path to the GFF3 file with the transcript annotation.
+    pub gff3_path: String,
+    /// Path to the FASTA file with the genome sequence; the `.fai` index must be next to it.
+    pub fasta_path: String,
+}
+
+/// What is returned by `Provider::schema_version()` and `Provider::data_version()`.
+pub static REQUIRED_VERSION: &str = "1.1";
+
+/// The alignment method returned for all GFF3 transcripts.
+pub static GFF3_ALN_METHOD: &str = "splign";
+
+/// A transcript as extracted from the GFF3 file.
+#[derive(Debug, Clone)]
+pub(crate) struct Transcript {
+    /// Transcript accession.
+    pub(crate) tx_ac: String,
+    /// Gene symbol, empty if unknown.
+    pub(crate) hgnc: String,
+    /// Accession of the encoded protein, if any.
+    pub(crate) pro_ac: Option<String>,
+    /// Accession of the contig.
+    pub(crate) alt_ac: String,
+    /// Alignment method of the transcript to the contig.
+    pub(crate) alt_aln_method: String,
+    /// `1` for forward, `-1` for reverse strand.
+    pub(crate) strand: i16,
+    /// Exons on the contig (0-based, half-open), sorted by position.
+    pub(crate) exons: Vec<Range<i32>>,
+    /// CDS on the contig (0-based, half-open), if coding.
+    pub(crate) cds: Option<Range<i32>>,
+    /// Whether a stop codon is recoded as selenocysteine.
+    pub(crate) is_selenoprotein: bool,
+}
+
+impl Transcript {
+    /// Return the exons in transcript order.
+    pub(crate) fn exons_tx_order(&self) -> Vec<&Range<i32>> {
+        let mut exons = self.exons.iter().collect::<Vec<_>>();
+        if self.strand == -1 {
+            exons.reverse();
+        }
+        exons
+    }
+
+    /// Return exon lengths in transcript order.
+    pub(crate) fn lengths(&self) -> Vec<i32> {
+        self.exons_tx_order()
+            .into_iter()
+            .map(|exon| exon.end - exon.start)
+            .collect()
+    }
+
+    /// Project the 0-based contig position `pos` to the transcript, if exonic.
+    fn g_to_n(&self, pos: i32) -> Option<i32> {
+        let mut tx_start_i = 0;
+        for exon in self.exons_tx_order() {
+            if exon.contains(&pos) {
+                return Some(if self.strand == -1 {
+                    tx_start_i + exon.end - 1 - pos
+                } else {
+                    tx_start_i + pos - exon.start
+                });
+            }
+            tx_start_i += exon.end - exon.start;
+        }
+        None
+    }
+
+    /// Project the 0-based transcript position `pos` to the contig, if within the transcript.
+    pub(crate) fn n_to_g(&self, pos: i32) -> Option<i32> {
+        let mut tx_start_i = 0;
+        for exon in self.exons_tx_order() {
+            let length = exon.end - exon.start;
+            if (tx_start_i..(tx_start_i + length)).contains(&pos) {
+                return Some(if self.strand == -1 {
+                    exon.end - 1 - (pos - tx_start_i)
+                } else {
+                    exon.start + (pos - tx_start_i)
+                });
+            }
+            tx_start_i += length;
+        }
+        None
+    }
+
+    /// Return the CDS in transcript coordinates (0-based, half-open), if coding.
+    pub(crate) fn cds_tx(&self) -> Option<Range<i32>> {
+        let cds = self.cds.as_ref()?;
+        let first = self.g_to_n(cds.start)?;
+        let last = self.g_to_n(cds.end - 1)?;
+        Some(first.min(last)..(first.max(last) + 1))
+    }
+
+    /// Return the span of the transcript on the contig.
+    pub(crate) fn span(&self) -> Range<i32> {
+        let start = self
+            .exons
+            .first()
+            .map(|exon| exon.start)
+            .unwrap_or_default();
+        let end = self.exons.last().map(|exon| exon.end).unwrap_or_default();
+        start..end
+    }
+}
+
+/// Exons and CDS features collected for one `Parent` while reading the GFF3 file.
+#[derive(Debug, Default)]
+struct Parts {
+    /// Accession of the contig.
+    alt_ac: String,
+    /// `1` for forward, `-1` for reverse strand.
+    strand: i16,
+    /// Exon features (0-based, half-open).
+    exons: Vec<Range<i32>>,
+    /// CDS features (0-based, half-open).
+    cds: Vec<Range<i32>>,
+    /// Stop codon features (0-based, half-open), for files with CDS excluding them.
+    stop_codons: Vec<Range<i32>>,
+    /// Value of the `protein_id` attribute of the CDS features.
+    pro_ac: Option<String>,
+    /// Whether any CDS feature has a `transl_except` to selenocysteine.
+    is_selenoprotein: bool,
+}
+
+/// This provider provides information from a GFF3 file and an indexed FASTA file.
+///
+/// # Remarks
+///
+/// All transcripts are assumed to align to the genome without gaps and are reported with the
+/// alignment method `GFF3_ALN_METHOD`.  The methods `get_acs_for_protein_seq()` and
+/// `get_similar_transcripts()` are not implemented and return empty results.
+///
+/// The method `get_tx_exons()` returns `None` for record entries `tx_aseq`, and `alt_aseq`
+/// and `i32::MAX` for `tx_exon-set_id`, `alt_exon_set_id`, `tx_exon_id`, `alt_exon_id`,
+/// `exon_aln_id`.
+pub struct Provider {
+    /// Transcripts by accession.
+    transcripts: HashMap<String, Transcript>,
+    /// Transcript accessions for each gene symbol.
+    tx_acs_for_gene: HashMap<String, Vec<String>>,
+    /// Interval tree of transcripts for each contig.
+    interval_trees: HashMap<String, IntervalTree<i32, String>>,
+    /// Length of each contig in the FASTA file.
+    contig_lengths: HashMap<String, u64>,
+    /// Reader for the FASTA file.
+    fasta: Mutex<fasta::IndexedReader<File>>,
+}
+
+impl Provider {
+    pub fn new(config: Config) -> Result<Self, Error> {
+        let transcripts = Self::load_transcripts(&config.gff3_path)?;
+        Self::with_transcripts(transcripts, &config.fasta_path)
+    }
+
+    /// Construct from already loaded transcripts and the path to the indexed FASTA file.
+    pub(crate) fn with_transcripts(
+        transcripts: IndexMap<String, Transcript>,
+        fasta_path: &str,
+    ) -> Result<Self, Error> {
+        let mut tx_acs_for_gene: HashMap<String, Vec<String>> = HashMap::new();
+        let mut spans: HashMap<String, Vec<(Range<i32>, String)>> = HashMap::new();
+        for tx in transcripts.values() {
+            tx_acs_for_gene
+                .entry(tx.hgnc.clone())
+                .or_default()
+                .push(tx.tx_ac.clone());
+            spans
+                .entry(tx.alt_ac.clone())
+                .or_default()
+                .push((tx.span(), tx.tx_ac.clone()));
+        }
+        let interval_trees = spans
+            .into_iter()
+            .map(|(alt_ac, spans)| (alt_ac, spans.into_iter().collect()))
+            .collect();
+
+        let fasta = fasta::IndexedReader::from_file(&fasta_path)
+            .map_err(|e| Error::FastaOpen(format!("{}: {}", fasta_path, e)))?;
+        let contig_lengths = fasta
+            .index
+            .sequences()
+            .into_iter()
+            .map(|seq| (seq.name, seq.len))
+            .collect();
+
+        log::debug!(
+            "gff3::Provider -- #transcripts = {}, #genes = {}",
+            transcripts.len(),
+            tx_acs_for_gene.len()
+        );
+
+        Ok(Self {
+            transcripts: transcripts.into_iter().collect(),
+            tx_acs_for_gene,
+            interval_trees,
+            contig_lengths,
+            fasta: Mutex::new(fasta),
+        })
+    }
+
+    /// Load the transcripts from the GFF3 file at `path`.
+    fn load_transcripts(path: &str) -> Result<IndexMap<String, Transcript>, Error> {
+        let mut reader = gff::Reader::from_file(path, gff::GffType::GFF3)
+            .map_err(|e| Error::Gff3Open(format!("{}: {}", path, e)))?;
+
+        // Features that are not exons, CDS, or stop codons by their `ID`, e.g., genes and
+        // transcripts.
+        let mut features: HashMap<String, gff::Record> = HashMap::new();
+        // Exons, CDS, and stop codons by their `Parent`, in the order of the file.
+        let mut parts: IndexMap<String, Parts> = IndexMap::new();
+
+        for record in reader.records() {
+            let record = record.map_err(|e| Error::Gff3Parse(format!("{}: {}", path, e)))?;
+            let feature_type = record.feature_type();
+            if !["exon", "CDS", "stop_codon"].contains(&feature_type) {
+                if let Some(id) = record.attributes().get("ID") {
+                    features.insert(id.clone(), record.clone());
+                }
+                continue;
+            }
+
+            let range = (*record.start() as i32 - 1)..(*record.end() as i32);
+            let parents = record
+                .attributes()
+                .get("Parent")
+                .map(|parents| parents.split(',').map(str::to_string).collect::<Vec<_>>())
+                .unwrap_or_default();
+            for parent in parents {
+                let entry = parts.entry(parent).or_insert_with(|| Parts {
+                    alt_ac: record.seqname().to_string(),
+                    strand: match record.strand() {
+                        Some(strand) if strand.strand_symbol() == "-" => -1,
+                        _ => 1,
+                    },
+                    ..Default::default()
+                });
+                if feature_type == "exon" {
+                    entry.exons.push(range.clone());
+                } else if feature_type == "stop_codon" {
+                    entry.stop_codons.push(range.clone());
+                } else {
+                    entry.cds.push(range.clone());
+                    if let Some(protein_id) = record.attributes().get("protein_id") {
+                        entry.pro_ac = Some(protein_id.clone());
+                    }
+                    if let Some(transl_except) = record.attributes().get("transl_except") {
+                        entry.is_selenoprotein |= transl_except.contains("Sec");
+                    }
+                }
+            }
+        }
+
+        let mut result = IndexMap::new();
+        for (parent, mut parts) in parts {
+            if parts.exons.is_empty() {
+                log::warn!("Skipping {} which has no exon features", &parent);
+                continue;
+            }
+            parts.exons.sort_by_key(|exon| exon.start);
+
+            let tx = features.get(&parent);
+            let tx_attr = |key: &str| tx.and_then(|tx| tx.attributes().get(key)).cloned();
+            let gene = tx_attr("Parent").and_then(|gene_id| features.get(&gene_id));
+            let tx_ac = tx_attr("transcript_id").unwrap_or_else(|| parent.clone());
+            let hgnc = tx_attr("gene")
+                .or_else(|| gene.and_then(|gene| gene.attributes().get("Name").cloned()))
+                .unwrap_or_default();
+            let cds = if parts.cds.is_empty() {
+                None
+            } else {
+                let cds = || parts.cds.iter().chain(parts.stop_codons.iter());
+                let start = cds().map(|cds| cds.start).min();
+                let end = cds().map(|cds| cds.end).max();
+                start.zip(end).map(|(start, end)| start..end)
+            };
+
+            result.insert(
+                tx_ac.clone(),
+                Transcript {
+                    tx_ac,
+                    hgnc,
+                    pro_ac: parts.pro_ac,
+                    alt_ac: parts.alt_ac,
+                    alt_aln_method: GFF3_ALN_METHOD.to_string(),
+                    strand: parts.strand,
+                    exons: parts.exons,
+                    cds,
+                    is_selenoprotein: parts.is_selenoprotein,
+                },
+            );
+        }
+
+        Ok(result)
+    }
+
+    fn get_transcript(&self, tx_ac: &str) -> Result<&Transcript, Error> {
+        self.transcripts
+            .get(tx_ac)
+            .ok_or(Error::NoTranscriptFound(tx_ac.to_string()))
+    }
+
+    /// Fetch the sequence of the contig `ac` from the FASTA file.
+    fn fetch_contig_seq(&self, ac: &str, begin: u64, end: u64) -> Result<String, Error> {
+        let mut seq = Vec::new();
+        let mut fasta = self.fasta.lock().expect("poisoned");
+        fasta
+            .fetch(ac, begin, end)
+            .map_err(|_e| Error::NoSequenceRecord(ac.to_string()))?;
+        fasta
+            .read(&mut seq)
+            .map_err(|_e| Error::NoSequenceRecord(ac.to_string()))?;
+        Ok(String::from_utf8_lossy(&seq).to_uppercase())
+    }
+
+    /// Splice the part `begin..end` (0-based, clamped to the transcript length) of the
+    /// sequence of the transcript `tx` from the contig sequence.
+    ///
+    /// Only the exon segments overlapping the part are read from the FASTA file.
+    fn fetch_tx_seq_part(
+        &self,
+        tx: &Transcript,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        let tx_len = tx
+            .exons
+            .iter()
+            .map(|exon| (exon.end - exon.start) as usize)
+            .sum::<usize>();
+        let end = end.unwrap_or(tx_len).min(tx_len);
+        let begin = begin.unwrap_or_default().min(end);
+        // The exons are in contig order, so the part of a minus strand transcript is taken
+        // from the end of the spliced contig sequence.
+        let (begin, end) = if tx.strand == -1 {
+            (tx_len - end, tx_len - begin)
+        } else {
+            (begin, end)
+        };
+
+        let mut seq = String::new();
+        let mut offset = 0;
+        for exon in &tx.exons {
+            let exon_len = (exon.end - exon.start) as usize;
+            let overlap_begin = begin.max(offset);
+            let overlap_end = end.min(offset + exon_len);
+            if overlap_begin < overlap_end {
+                let contig_begin = exon.start as u64 + (overlap_begin - offset) as u64;
+                let contig_end = exon.start as u64 + (overlap_end - offset) as u64;
+                seq.push_str(&self.fetch_contig_seq(&tx.alt_ac, contig_begin, contig_end)?);
+            }
+            offset += exon_len;
+        }
+        Ok(if tx.strand == -1 { revcomp(&seq) } else { seq })
+    }
+}
+
+impl interface::Provider for Provider {
+    fn data_version(&self) -> &str {
+        REQUIRED_VERSION
+    }
+
+    fn schema_version(&self) -> &str {
+        REQUIRED_VERSION
+    }
+
+    /// Gene information is limited to the symbol and there are neither protein sequences
+    /// nor transcript similarities.
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::HAS_GENOME_SEQ
+            | ProviderCapabilities::HAS_TX_SEQ
+            | ProviderCapabilities::HAS_TX_MAPPING
+    }
+
+    fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
+        IndexMap::from_iter(
+            ASSEMBLY_INFOS[assembly]
+                .sequences
+                .iter()
+                .map(|record| (record.refseq_ac.clone(), record.name.clone())),
+        )
+    }
+
+    fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        if hgnc.is_empty() || !self.tx_acs_for_gene.contains_key(hgnc) {
+            return Err(Error::NoGeneFound(hgnc.to_string()));
+        }
+
+        Ok(GeneInfoRecord {
+            hgnc: hgnc.to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error> {
+        Ok(self.get_transcript(tx_ac)?.pro_ac.clone())
+    }
+
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        Ok(self
+            .transcripts
+            .iter()
+            .filter(|(_, tx)| tx.pro_ac.as_deref() == Some(pro_ac))
+            .map(|(tx_ac, _)| tx_ac)
+            .min()
+            .cloned())
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        let mut result = self
+            .transcripts
+            .keys()
+            .map(|tx_ac| Accession::new(tx_ac))
+            .filter(|ac| ac.base() == base_ac && ac.is_versioned())
+            .collect::<Vec<_>>();
+        result.sort_by_key(|ac| ac.version());
+        Ok(result.into_iter().map(|ac| ac.value).collect())
+    }
+
+    fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        if let Some(tx) = self.transcripts.get(ac) {
+            self.fetch_tx_seq_part(tx, begin, end)
+        } else {
+            let len = *self
+                .contig_lengths
+                .get(ac)
+                .ok_or(Error::NoSequenceRecord(ac.to_string()))? as usize;
+            let end = end.unwrap_or(len).min(len);
+            let begin = begin.unwrap_or_default().min(end);
+            if begin == end {
+                Ok(String::new())
+            } else {
+                self.fetch_contig_seq(ac, begin as u64, end as u64)
+            }
+        }
+    }
+
+    fn get_acs_for_protein_seq(&self, _seq: &str) -> Result<Vec<String>, Error> {
+        log::warn!("gff3::Provider::get_acs_for_protein_seq() has not been implemented");
+        Ok(vec![])
+    }
+
+    fn get_similar_transcripts(&self, _tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        log::warn!("gff3::Provider::get_similar_transcripts() has not been implemented");
+        Ok(vec![])
+    }
+
+    fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        let tx = self.get_transcript(tx_ac)?;
+        if tx.alt_ac != alt_ac {
+            return Err(Error::NoAlignmentFound(
+                tx_ac.to_string(),
+                alt_ac.to_string(),
+            ));
+        }
+
+        let mut tx_start_i = 0;
+        let mut result = tx
+            .exons_tx_order()
+            .into_iter()
+            .enumerate()
+            .map(|(ord, exon)| {
+                let length = exon.end - exon.start;
+                let record = TxExonsRecord {
+                    hgnc: tx.hgnc.clone(),
+                    tx_ac: tx_ac.to_string(),
+                    alt_ac: alt_ac.to_string(),
+                    alt_aln_method: alt_aln_method.to_string(),
+                    alt_strand: tx.strand,
+                    ord: ord as i32,
+                    tx_start_i,
+                    tx_end_i: tx_start_i + length,
+                    alt_start_i: exon.start,
+                    alt_end_i: exon.end,
+                    cigar: format!("{}M", length),
+                    tx_aseq: None,
+                    alt_aseq: None,
+                    tx_exon_set_id: i32::MAX,
+                    alt_exon_set_id: i32::MAX,
+                    tx_exon_id: i32::MAX,
+                    alt_exon_id: i32::MAX,
+                    exon_aln_id: i32::MAX,
+                };
+                tx_start_i += length;
+                record
+            })
+            .collect::<Vec<_>>();
+        result.sort_by_key(|record| record.alt_start_i);
+
+        Ok(result)
+    }
+
+    fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        let mut tmp = self
+            .tx_acs_for_gene
+            .get(gene)
+            .map(|tx_acs| {
+                tx_acs
+                    .iter()
+                    .map(|tx_ac| {
+                        let tx = &self.transcripts[tx_ac];
+                        let cds = tx.cds_tx();
+                        let span = tx.span();
+                        let rec = TxInfoRecord {
+                            hgnc: gene.to_string(),
+                            cds_start_i: cds.as_ref().map(|cds| cds.start),
+                            cds_end_i: cds.as_ref().map(|cds| cds.end),
+                            tx_ac: tx_ac.clone(),
+                            alt_ac: tx.alt_ac.clone(),
+                            alt_aln_method: tx.alt_aln_method.clone(),
+                        };
+                        (span.end - span.start, rec)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        // Sorted by length in descending order.
+        tmp.sort_by_key(|x| std::cmp::Reverse(x.0));
+
+        Ok(tmp.into_iter().map(|x| x.1).collect())
+    }
+
+    fn get_tx_for_region(
+        &self,
+        alt_ac: &str,
+        alt_aln_method: &str,
+        start_i: i32,
+        end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        let mut tmp = Vec::new();
+        if let Some(tree) = self.interval_trees.get(alt_ac) {
+            for tx_ac in tree.query(start_i..(end_i.max(start_i + 1))) {
+                let tx = &self.transcripts[tx_ac];
+                if tx.alt_aln_method != alt_aln_method {
+                    continue;
+                }
+                let span = tx.span();
+                tmp.push((
+                    (span.end - span.start, tx.tx_ac.clone()),
+                    TxForRegionRecord {
+                        tx_ac: tx.tx_ac.clone(),
+                        alt_ac: alt_ac.to_string(),
+                        alt_strand: tx.strand,
+                        alt_aln_method: alt_aln_method.to_string(),
+                        start_i: span.start,
+                        end_i: span.end,
+                    },
+                ));
+            }
+        }
+
+        // Sorted by length in descending order, break tie by tx accession.
+        tmp.sort_by(|a, b| b.0.cmp(&a.0));
+
+        Ok(tmp.into_iter().map(|x| x.1).collect())
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        let tx = self.get_transcript(tx_ac)?;
+        let cds = tx.cds_tx();
+
+        Ok(TxIdentityInfo {
+            tx_ac: tx_ac.to_string(),
+            alt_ac: tx_ac.to_string(), // sic(!)
+            alt_aln_method: String::from("transcript"),
+            cds_start_i: cds.as_ref().map(|cds| cds.start).unwrap_or_default(),
+            cds_end_i: cds.as_ref().map(|cds| cds.end).unwrap_or_default(),
+            lengths: tx.lengths(),
+            hgnc: tx.hgnc.clone(),
+            translation_table: TranslationTable::for_alt_ac(&tx.alt_ac, tx.is_selenoprotein),
+        })
+    }
+
+    fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        let tx = self.get_transcript(tx_ac)?;
+        if tx.alt_ac != alt_ac {
+            return Err(Error::NoAlignmentFound(
+                tx_ac.to_string(),
+                alt_ac.to_string(),
+            ));
+        }
+        let cds = tx.cds_tx();
+
+        Ok(TxInfoRecord {
+            hgnc: tx.hgnc.clone(),
+            cds_start_i: cds.as_ref().map(|cds| cds.start),
+            cds_end_i: cds.as_ref().map(|cds| cds.end),
+            tx_ac: tx_ac.to_string(),
+            alt_ac: alt_ac.to_string(),
+            alt_aln_method: alt_aln_method.to_string(),
+        })
+    }
+
+    fn get_tx_mapping_options(&self, tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        let tx = self.get_transcript(tx_ac)?;
+
+        Ok(vec![TxMappingOptionsRecord {
+            tx_ac: tx_ac.to_string(),
+            alt_ac: tx.alt_ac.clone(),
+            alt_aln_method: tx.alt_aln_method.clone(),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{str::FromStr, sync::Arc};
+
+    use crate::{
+        data::interface::Provider as _,
+        mapper::variant::{Config as MapperConfig, Mapper},
+        parser::HgvsVariant,
+        sequences::revcomp,
+    };
+
+    use super::{Config, Provider};
+
+    // The GFF3 file describes the single-exon transcript `TX1.1` at `chrT:g.11_110` (plus
+    // strand) with the CDS at `chrT:g.31_96`, encoding `PX1.1` which starts with `MetLys`.
+    fn build_provider() -> Result<Provider, anyhow::Error> {
+        Ok(Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/single_exon.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?)
+    }
+
+    #[test]
+    fn get_tx_identity_info() -> Result<(), anyhow::Error> {
+        let provider = build_provider()?;
+
+        let info = provider.get_tx_identity_info("TX1.1")?;
+        assert_eq!(info.cds_start_i, 20);
+        assert_eq!(info.cds_end_i, 86);
+        assert_eq!(info.lengths, vec![100]);
+        assert_eq!(info.hgnc, "GENE1");
+        assert_eq!(
+            provider.get_pro_ac_for_tx_ac("TX1.1")?,
+            Some("PX1.1".to_string())
+        );
+        assert_eq!(provider.get_all_pro_ac_for_tx_ac("TX1.1")?, vec!["PX1.1"]);
+        assert_eq!(
+            provider.get_tx_ac_for_pro_ac("PX1.1")?,
+            Some("TX1.1".to_string())
+        );
+        assert_eq!(provider.get_tx_ac_for_pro_ac("PX9.1")?, None);
+        assert!(provider.get_tx_identity_info("TX2.1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_tx_ac_for_shared_pro_ac() -> Result<(), anyhow::Error> {
+        // `TX1.2`, `TX1.1`, and `TX3.1` all encode `PX1.1`.
+        let provider = Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/shared_protein.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+
+        assert_eq!(
+            provider.get_tx_ac_for_pro_ac("PX1.1")?,
+            Some("TX1.1".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn stop_codon_feature() -> Result<(), anyhow::Error> {
+        // Same as `single_exon.gff3` but with the stop codon as separate feature.
+        let provider = Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/stop_codon.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+
+        let info = provider.get_tx_identity_info("TX1.1")?;
+        assert_eq!(info.cds_start_i, 20);
+        assert_eq!(info.cds_end_i, 86);
+        assert_eq!(
+            provider.get_pro_ac_for_tx_ac("TX1.1")?,
+            Some("PX1.1".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_transcript_versions() -> Result<(), anyhow::Error> {
+        let provider = Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+
+        assert_eq!(
+            provider.get_transcript_versions("TX1")?,
+            vec!["TX1.1", "TX1.2"]
+        );
+        assert_eq!(
+            provider.get_latest_transcript_version("TX1")?,
+            Some("TX1.2".to_string())
+        );
+        assert!(provider.get_transcript_versions("TX3")?.is_empty());
+        assert_eq!(provider.get_latest_transcript_version("TX3")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_tx_exons() -> Result<(), anyhow::Error> {
+        let provider = build_provider()?;
+
+        let exons = provider.get_tx_exons("TX1.1", "chrT", "splign")?;
+        assert_eq!(exons.len(), 1);
+        assert_eq!(exons[0].tx_start_i, 0);
+        assert_eq!(exons[0].tx_end_i, 100);
+        assert_eq!(exons[0].alt_start_i, 10);
+        assert_eq!(exons[0].alt_end_i, 110);
+        assert_eq!(exons[0].alt_strand, 1);
+        assert!(provider.get_tx_exons("TX1.1", "chrX", "splign").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_seq_part() -> Result<(), anyhow::Error> {
+        let provider = build_provider()?;
+
+        assert_eq!(
+            provider.get_seq_part("TX1.1", Some(20), Some(26))?,
+            "ATGAAA"
+        );
+        assert_eq!(provider.get_seq_part("chrT", Some(30), Some(36))?, "ATGAAA");
+        assert_eq!(provider.get_seq("TX1.1")?.len(), 100);
+        assert_eq!(provider.get_seq("chrT")?.len(), 150);
+        assert!(provider.get_seq("chrX").is_err());
+
+        assert_eq!(
+            provider.get_seq_part_i64("chrT", Some(30), Some(36))?,
+            "ATGAAA"
+        );
+        assert!(matches!(
+            provider.get_seq_part_i64("chrT", Some(-1), Some(36)),
+            Err(crate::data::error::Error::PositionOutOfRange(_, -1))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_seq_part_spliced() -> Result<(), anyhow::Error> {
+        let provider = Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/two_exons_minus.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+
+        // `TX3.1` consists of `chrT:g.11_40` and `chrT:g.61_110` on the minus strand.
+        let spliced = format!(
+            "{}{}",
+            provider.get_seq_part("chrT", Some(10), Some(40))?,
+            provider.get_seq_part("chrT", Some(60), Some(110))?
+        );
+        let seq = provider.get_seq("TX3.1")?;
+        assert_eq!(seq, revcomp(&spliced));
+        for begin in 0..=seq.len() {
+            for end in begin..=seq.len() + 1 {
+                assert_eq!(
+                    provider.get_seq_part("TX3.1", Some(begin), Some(end))?,
+                    seq[begin..end.min(seq.len())],
+                    "{begin}..{end}"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_tx_for_region() -> Result<(), anyhow::Error> {
+        let provider = build_provider()?;
+
+        let records = provider.get_tx_for_region("chrT", "splign", 50, 51)?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].tx_ac, "TX1.1");
+        assert!(provider
+            .get_tx_for_region("chrT", "splign", 120, 130)?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn g_to_c_and_c_to_p() -> Result<(), anyhow::Error> {
+        let mapper = Mapper::new(&MapperConfig::default(), Arc::new(build_provider()?));
+
+        let var_g = HgvsVariant::from_str("chrT:g.34A>G")?;
+        let var_c = mapper.g_to_c(&var_g, "TX1.1", "splign")?;
+        assert_eq!(format!("{}", &var_c), "TX1.1:c.4A>G");
+        assert_eq!(mapper.c_to_g(&var_c, "chrT", "splign")?, var_g);
+
+        let var_p = mapper.c_to_p(&var_c, None)?;
+        assert_eq!(format!("{}", &var_p), "PX1.1:p.Lys2Glu");
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
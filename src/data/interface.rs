@@ -1,4 +1,29 @@
 //! Definition of the interface for accessing the transcript database.
+//!
+//! The mappers and the normalizer only access transcript and sequence data through the
+//! [`Provider`] trait.  Implementations shipped with this crate are
+//! [`crate::data::uta::Provider`], [`crate::data::uta_sr::Provider`],
+//! [`crate::data::cdot::json::Provider`], and [`crate::data::gff3::Provider`].
+//!
+//! # Coordinate conventions
+//!
+//! All `*_i` fields in the records below are interbase (0-based, half-open)
+//! coordinates as used by UTA.  For example, `cds_start_i = 283` and `cds_end_i = 1003`
+//! describe the CDS `n.284_1003` in 1-based HGVS notation, i.e., `c.1` is at
+//! `n.(cds_start_i + 1)` and the last base of the stop codon is at `n.cds_end_i`.
+//!
+//! # Implementing a custom provider
+//!
+//! A minimal provider for `c.`/`n.` to `p.` projection needs `get_tx_identity_info()`,
+//! `get_seq_part()`, and `get_pro_ac_for_tx_ac()`.  Mapping to and from the genome
+//! additionally needs `get_tx_info()` and `get_tx_exons()`, and the assembly mapper uses
+//! `get_assembly_map()`, `get_tx_for_region()`, and `get_tx_mapping_options()`.
+//!
+//! For testing, a small in-memory implementation is usually sufficient; see the
+//! `sanity_mock` module in the tests of [`crate::mapper::variant`], which reads its
+//! transcripts from a TSV file and panics in all methods that are not needed.  Such partial
+//! providers should override `Provider::capabilities()` so that the mappers and the
+//! normalizer report an unsupported operation as an error rather than calling into them.
 
 use chrono::NaiveDateTime;
 use indexmap::IndexMap;
@@ -18,11 +43,17 @@ use biocommons_bioutils::assemblies::Assembly;
 /// ```
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct GeneInfoRecord {
+    /// HGNC gene symbol.
     pub hgnc: String,
+    /// Cytogenetic location.
     pub maploc: String,
+    /// Short description of the gene.
     pub descr: String,
+    /// Longer summary of the gene's function.
     pub summary: String,
+    /// Alias symbols of the gene.
     pub aliases: Vec<String>,
+    /// Time that the record was added to the database.
     pub added: NaiveDateTime,
 }
 
@@ -51,6 +82,7 @@ pub struct TxSimilarityRecord {
     pub tx_ac1: String,
     /// Accession of second transcript.
     pub tx_ac2: String,
+    /// Whether both transcripts belong to the same gene.
     pub hgnc_eq: bool,
     /// Whether CDS sequences are identical.
     pub cds_eq: bool,
@@ -59,9 +91,17 @@ pub struct TxSimilarityRecord {
     /// Whether the cds-clipped portions of the exon structures are identical
     /// (i.e., ecluding. UTR).
     pub cds_es_fp_eq: bool,
+    /// Whether the lengths of the cds-clipped exons are identical (the coordinates
+    /// may differ).
     pub cds_exon_lengths_fp_eq: bool,
 }
 
+/// Alignment of a single exon of a transcript to a reference sequence.
+///
+/// The alignment mapper concatenates the `cigar` strings of all exons of a
+/// transcript (ordered by `ord`, reverse-complemented for `alt_strand == -1`) to build
+/// the [`crate::mapper::cigar::CigarMapper`] between transcript and genome.
+///
 ///```text
 /// hgnc            | TGDS
 /// tx_ac           | NM_001304430.1
@@ -84,26 +124,48 @@ pub struct TxSimilarityRecord {
 ///```
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct TxExonsRecord {
+    /// HGNC gene symbol.
     pub hgnc: String,
+    /// Transcript accession with version.
     pub tx_ac: String,
+    /// Accession of the reference sequence that the exon is aligned to.
     pub alt_ac: String,
+    /// Alignment method, e.g., `splign` or `blat`.
     pub alt_aln_method: String,
+    /// Strand of the alignment on `alt_ac`, either `1` or `-1`.
     pub alt_strand: i16,
+    /// 0-based rank of the exon in transcript order.
     pub ord: i32,
+    /// Exon start on the transcript (interbase).
     pub tx_start_i: i32,
+    /// Exon end on the transcript (interbase).
     pub tx_end_i: i32,
+    /// Exon start on `alt_ac` (interbase, always less than `alt_end_i`).
     pub alt_start_i: i32,
+    /// Exon end on `alt_ac` (interbase).
     pub alt_end_i: i32,
+    /// CIGAR string of the transcript-to-reference alignment of the exon, using
+    /// `=`/`X` for (mis)matches and `I`/`D` for indels.
     pub cigar: String,
+    /// Aligned transcript sequence, if available.
     pub tx_aseq: Option<String>,
+    /// Aligned reference sequence, if available.
     pub alt_aseq: Option<String>,
+    /// Database identifier of the transcript's exon set.
     pub tx_exon_set_id: i32,
+    /// Database identifier of the reference's exon set.
     pub alt_exon_set_id: i32,
+    /// Database identifier of the transcript exon.
     pub tx_exon_id: i32,
+    /// Database identifier of the reference exon.
     pub alt_exon_id: i32,
+    /// Database identifier of the exon alignment.
     pub exon_aln_id: i32,
 }
 
+/// Transcript alignment overlapping a queried region, see
+/// [`Provider::get_tx_for_region`].
+///
 /// ```text
 /// tx_ac          | NM_001304430.2
 /// alt_ac         | NC_000013.10
@@ -114,14 +176,22 @@ pub struct TxExonsRecord {
 /// ```
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct TxForRegionRecord {
+    /// Transcript accession with version.
     pub tx_ac: String,
+    /// Accession of the reference sequence.
     pub alt_ac: String,
+    /// Strand of the alignment on `alt_ac`, either `1` or `-1`.
     pub alt_strand: i16,
+    /// Alignment method, e.g., `splign`.
     pub alt_aln_method: String,
+    /// Start of the transcript alignment on `alt_ac` (interbase).
     pub start_i: i32,
+    /// End of the transcript alignment on `alt_ac` (interbase).
     pub end_i: i32,
 }
 
+/// Transcript-intrinsic information, independent of any genome alignment.
+///
 /// ```text
 /// tx_ac          | NM_199425.2
 /// alt_ac         | NM_199425.2
@@ -133,17 +203,30 @@ pub struct TxForRegionRecord {
 /// ```
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct TxIdentityInfo {
+    /// Transcript accession with version.
     pub tx_ac: String,
+    /// Same as `tx_ac`, the transcript is "aligned" to itself.
     pub alt_ac: String,
+    /// Always `"transcript"`.
     pub alt_aln_method: String,
+    /// Start of the CDS on the transcript (interbase); `c.1` is `n.(cds_start_i + 1)`.
     pub cds_start_i: i32,
+    /// End of the CDS on the transcript (interbase), including the stop codon.
     pub cds_end_i: i32,
+    /// Lengths of the exons in transcript order.
+    ///
+    /// The transcript length is the sum of the entries.  The per-exon lengths are kept
+    /// so that exon boundaries on the transcript can be derived without a genome
+    /// alignment, e.g., for the normalizer's boundary checks.
     pub lengths: Vec<i32>,
+    /// HGNC gene symbol.
     pub hgnc: String,
     /// The translation table to use for this transcript.
     pub translation_table: TranslationTable,
 }
 
+/// Information about a transcript's alignment to a reference sequence.
+///
 /// ```text
 /// hgnc           | ATM
 /// cds_start_i    | 385
@@ -154,14 +237,51 @@ pub struct TxIdentityInfo {
 /// ```
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct TxInfoRecord {
+    /// HGNC gene symbol.
     pub hgnc: String,
+    /// Start of the CDS on the transcript (interbase), `None` for non-coding transcripts.
     pub cds_start_i: Option<i32>,
+    /// End of the CDS on the transcript (interbase, including the stop codon), `None`
+    /// for non-coding transcripts.
     pub cds_end_i: Option<i32>,
+    /// Transcript accession with version.
+    pub tx_ac: String,
+    /// Accession of the reference sequence.
+    pub alt_ac: String,
+    /// Alignment method, e.g., `splign`.
+    pub alt_aln_method: String,
+}
+
+/// Summary of a transcript's alignment together with its protein product, see
+/// [`Provider::get_all_tx_for_gene`].
+///
+/// ```text
+/// tx_ac          | NM_000051.3
+/// alt_ac         | AC_000143.1
+/// alt_aln_method | splign
+/// cds_start_i    | 385
+/// cds_end_i      | 9556
+/// pro_ac         | NP_000042.3
+/// ```
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct TranscriptSummary {
+    /// Transcript accession with version.
     pub tx_ac: String,
+    /// Accession of the reference sequence.
     pub alt_ac: String,
+    /// Alignment method, e.g., `splign`.
     pub alt_aln_method: String,
+    /// Start of the CDS on the transcript (interbase), `None` for non-coding transcripts.
+    pub cds_start_i: Option<i32>,
+    /// End of the CDS on the transcript (interbase, including the stop codon), `None`
+    /// for non-coding transcripts.
+    pub cds_end_i: Option<i32>,
+    /// Accession of the protein product, `None` for non-coding transcripts.
+    pub pro_ac: Option<String>,
 }
 
+/// Available alignment of a transcript, see [`Provider::get_tx_mapping_options`].
+///
 /// ```text
 /// -[ RECORD 1 ]--+----------------
 /// tx_ac          | ENST00000000233
@@ -174,19 +294,90 @@ pub struct TxInfoRecord {
 /// ```
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct TxMappingOptionsRecord {
+    /// Transcript accession with version.
     pub tx_ac: String,
+    /// Accession of the reference sequence.
     pub alt_ac: String,
+    /// Alignment method, e.g., `splign`.
     pub alt_aln_method: String,
 }
 
+bitflags::bitflags! {
+    /// The kinds of data that a `Provider` can serve.
+    ///
+    /// Mappers and normalizers check these before calling into the provider so that an
+    /// unsupported operation fails with `Error::CapabilityNotSupported`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ProviderCapabilities: u32 {
+        /// Sequences of genomic contigs via `get_seq_part()`.
+        const HAS_GENOME_SEQ = 1 << 0;
+        /// Sequences of transcripts via `get_seq_part()`.
+        const HAS_TX_SEQ = 1 << 1;
+        /// Sequences of proteins via `get_seq_part()`.
+        const HAS_PROTEIN_SEQ = 1 << 2;
+        /// Alignments of transcripts to the genome via `get_tx_info()` and `get_tx_exons()`.
+        const HAS_TX_MAPPING = 1 << 3;
+        /// Gene information via `get_gene_info()`.
+        const HAS_GENE_INFO = 1 << 4;
+        /// Similar transcripts via `get_similar_transcripts()`.
+        const HAS_SIMILAR_TX = 1 << 5;
+    }
+}
+
+impl ProviderCapabilities {
+    /// Return an error naming the flags of `required` that are not in `self`.
+    pub fn require(self, required: ProviderCapabilities) -> Result<(), Error> {
+        let missing = required.difference(self);
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::CapabilityNotSupported(
+                missing
+                    .iter_names()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>()
+                    .join(" | "),
+            ))
+        }
+    }
+}
+
 /// Interface for data providers.
-pub trait Provider {
+///
+/// All methods return [`Error`] if the underlying data source fails; methods that look
+/// up a single record should also return an error if the record does not exist unless
+/// documented otherwise.
+///
+/// Providers are shared between mappers, normalizers, and validators behind an `Arc` and
+/// thus must be `Send + Sync`.
+pub trait Provider: Send + Sync {
     /// Return the data version, e.g., `uta_20210129`.
+    ///
+    /// Used together with `schema_version()` as part of the key of the reference transcript
+    /// data cache of `c_to_p()`, so providers with different data do not share entries.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// // Cached data is only valid for the same data and schema version.
+    /// let cache_key = format!("{}/{}", provider.data_version(), provider.schema_version());
+    /// # Ok(())
+    /// # }
+    /// ```
     fn data_version(&self) -> &str;
 
     /// Return the schema version, e.g., `"1.1"`.
     fn schema_version(&self) -> &str;
 
+    /// Return the kinds of data that this provider can serve.
+    ///
+    /// The default implementation returns all capabilities.
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::all()
+    }
+
     /// Return a map from accession to chromosome name for the given assembly
     ///
     /// For example, when `assembly_name = "GRCh38.p5"`, the value for `"NC_000001.11"`
@@ -195,6 +386,22 @@ pub trait Provider {
     /// # Arguments
     ///
     /// * `assembly` - The assembly to build the map for.
+    ///
+    /// Used by the assembly mapper to select the reference sequences of the assembly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// use biocommons_bioutils::assemblies::Assembly;
+    ///
+    /// // Accession to chromosome name, e.g., "NC_000001.11" to "1".
+    /// let map = provider.get_assembly_map(Assembly::Grch38);
+    /// let chrom_1 = map.iter().find(|(_, name)| name.as_str() == "1");
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String>;
 
     /// Returns the basic information about the gene.
@@ -202,6 +409,17 @@ pub trait Provider {
     /// # Arguments
     ///
     /// * `hgnc` - HGNC gene name
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// let info = provider.get_gene_info("ATM")?;
+    /// println!("{} at {}: {}", info.hgnc, info.maploc, info.descr);
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error>;
 
     /// Return the (single) associated protein accession for a given transcript accession,
@@ -210,8 +428,68 @@ pub trait Provider {
     /// # Arguments
     ///
     /// * `tx_ac` -- transcript accession with version (e.g., 'NM_000051.3')
+    ///
+    /// Used by `c_to_p()` when no protein accession is given explicitly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// // `None` for non-coding transcripts.
+    /// let pro_ac = provider.get_pro_ac_for_tx_ac("NM_000051.3")?;
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error>;
 
+    /// Return all protein accessions associated with a given transcript accession.
+    ///
+    /// The default implementation returns the result of `get_pro_ac_for_tx_ac()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx_ac` -- transcript accession with version (e.g., 'NM_000051.3')
+    fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        Ok(self.get_pro_ac_for_tx_ac(tx_ac)?.into_iter().collect())
+    }
+
+    /// Return the transcript accession that encodes the given protein accession, or None if
+    /// not found.
+    ///
+    /// The default implementation always returns None.
+    ///
+    /// # Arguments
+    ///
+    /// * `pro_ac` -- protein accession with version (e.g., 'NP_000042.3')
+    fn get_tx_ac_for_pro_ac(&self, _pro_ac: &str) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+
+    /// Return all versions of the transcript with the given unversioned accession, ordered
+    /// by increasing version.
+    ///
+    /// The default implementation always returns an empty list.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_ac` -- transcript accession without version (e.g., 'NM_000088')
+    fn get_transcript_versions(&self, _base_ac: &str) -> Result<Vec<String>, Error> {
+        Ok(Vec::new())
+    }
+
+    /// Return the highest version of the transcript with the given unversioned accession,
+    /// or None if not found.
+    ///
+    /// The default implementation returns the last result of `get_transcript_versions()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_ac` -- transcript accession without version (e.g., 'NM_000088')
+    fn get_latest_transcript_version(&self, base_ac: &str) -> Result<Option<String>, Error> {
+        Ok(self.get_transcript_versions(base_ac)?.pop())
+    }
+
     /// Return full sequence for the given accession.
     ///
     /// # Arguments
@@ -221,13 +499,52 @@ pub trait Provider {
         self.get_seq_part(ac, None, None)
     }
 
+    /// Return the full sequence of the given transcript.
+    ///
+    /// Other than `get_seq()`, the length of the sequence is checked against the total exon
+    /// length from `get_tx_identity_info()`, unless the exon lengths are unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx_ac` -- transcript accession
+    fn get_tx_full_seq(&self, tx_ac: &str) -> Result<String, Error> {
+        let id_info = self.get_tx_identity_info(tx_ac)?;
+        let seq = self.get_seq(tx_ac)?;
+        let tx_len = id_info.lengths.iter().sum::<i32>() as usize;
+        if !id_info.lengths.is_empty() && seq.len() != tx_len {
+            return Err(Error::SequenceLengthMismatch(
+                tx_ac.to_string(),
+                tx_len,
+                seq.len(),
+            ));
+        }
+        Ok(seq)
+    }
+
     /// Return sequence part for the given accession.
     ///
     /// # Arguments
     ///
     /// * `ac` -- accession
-    /// * `start` -- start position (0-based, start of sequence if missing)
-    /// * `end` -- end position (0-based, end of sequence if missing)
+    /// * `begin` -- start position (0-based, start of sequence if missing)
+    /// * `end` -- end position (0-based, exclusive, end of sequence if missing)
+    ///
+    /// Used, e.g., by the normalizer to fetch the sequence window around a variant and
+    /// when replacing reference bases.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// // The first codon of a transcript, i.e., `c.1_3`, is at `n.(cds_start_i + 1)`.
+    /// let info = provider.get_tx_identity_info("NM_000051.3")?;
+    /// let start = info.cds_start_i as usize;
+    /// let codon = provider.get_seq_part("NM_000051.3", Some(start), Some(start + 3))?;
+    /// assert_eq!(codon.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_seq_part(
         &self,
         ac: &str,
@@ -235,10 +552,62 @@ pub trait Provider {
         end: Option<usize>,
     ) -> Result<String, Error>;
 
+    /// Return the sequence part like `get_seq_part()` but with `i64` positions.
+    ///
+    /// The default implementation converts the positions to `usize` and fails with
+    /// `Error::PositionOutOfRange` for positions that cannot be represented, e.g., negative
+    /// ones.
+    fn get_seq_part_i64(
+        &self,
+        ac: &str,
+        begin: Option<i64>,
+        end: Option<i64>,
+    ) -> Result<String, Error> {
+        let convert = |pos: Option<i64>| {
+            pos.map(|pos| {
+                usize::try_from(pos).map_err(|_| Error::PositionOutOfRange(ac.to_string(), pos))
+            })
+            .transpose()
+        };
+        self.get_seq_part(ac, convert(begin)?, convert(end)?)
+    }
+
+    /// Return sequence parts for multiple regions at once.
+    ///
+    /// The default implementation calls `get_seq_part()` for each query; providers that
+    /// access sequences over the network may override this to issue a single request.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` -- `(ac, begin, end)` triples as passed to `get_seq_part()`
+    fn batch_get_seq_parts(
+        &self,
+        queries: &[(String, Option<usize>, Option<usize>)],
+    ) -> Result<Vec<String>, Error> {
+        queries
+            .iter()
+            .map(|(ac, begin, end)| self.get_seq_part(ac, *begin, *end))
+            .collect()
+    }
+
     /// Returns a list of protein accessions for a given sequence.
     ///
     /// The list is guaranteed to contain at least one element with the MD5-based accession
     /// (MD5_01234abc..def56789) at the end of the list.
+    ///
+    /// Used by `c_to_p()` as a fallback if the transcript has no associated protein.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// let acs = provider.get_acs_for_protein_seq("MSLVLLSLAALCRSAVPREPTVQCGSETGPSPEWMLQHDLIPGDLRDLRVEPVTTS")?;
+    /// // The MD5-based accession is always the last entry.
+    /// assert!(acs.last().is_some_and(|ac| ac.starts_with("MD5_")));
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_acs_for_protein_seq(&self, seq: &str) -> Result<Vec<String>, Error>;
 
     /// Return a list of transcripts that are similar to the given transcript, with relevant
@@ -247,6 +616,23 @@ pub trait Provider {
     /// # Arguments
     ///
     /// * `tx_ac` -- transcript accession with version (e.g., 'NM_000051.3')
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// // Transcripts with the same CDS exon structure are candidates for replacing a transcript
+    /// // that is not aligned to the genome.
+    /// let candidates = provider
+    ///     .get_similar_transcripts("NM_000051.3")?
+    ///     .into_iter()
+    ///     .filter(|record| record.cds_es_fp_eq)
+    ///     .map(|record| record.tx_ac2)
+    ///     .collect::<Vec<_>>();
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_similar_transcripts(&self, tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error>;
 
     /// Return transcript exon info for supplied accession (tx_ac, alt_ac, alt_aln_method),
@@ -257,6 +643,23 @@ pub trait Provider {
     /// * `tx_ac` -- transcript accession with version (e.g., 'NM_000051.3')
     /// * `alt_ac` -- specific genomic sequence (e.g., NC_000011.4)
     /// * `alt_aln_method` -- sequence alignment method (e.g., splign, blat)
+    ///
+    /// Used to build the alignment mapper for genome/transcript projection.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// let exons = provider.get_tx_exons("NM_000051.3", "NC_000011.10", "splign")?;
+    /// // `tx_*_i` and `alt_*_i` are interbase, gaps in the alignment are given by `cigar`.
+    /// for exon in &exons {
+    ///     println!("{}..{} -> {}..{} ({})", exon.tx_start_i, exon.tx_end_i,
+    ///              exon.alt_start_i, exon.alt_end_i, exon.cigar);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_tx_exons(
         &self,
         tx_ac: &str,
@@ -269,16 +672,92 @@ pub trait Provider {
     /// # Arguments
     ///
     /// * `gene` - HGNC gene name
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// // The longest transcript of the gene comes first.
+    /// let longest = provider.get_tx_for_gene("ATM")?.into_iter().next();
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, Error>;
 
+    /// Return summaries of all transcripts of the supplied gene including their protein
+    /// accessions, in the order of `get_tx_for_gene()`.
+    ///
+    /// The default implementation combines `get_tx_for_gene()` and `get_pro_ac_for_tx_ac()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `gene` - HGNC gene name
+    fn get_all_tx_for_gene(&self, gene: &str) -> Result<Vec<TranscriptSummary>, Error> {
+        self.get_tx_for_gene(gene)?
+            .into_iter()
+            .map(|record| {
+                Ok(TranscriptSummary {
+                    pro_ac: self.get_pro_ac_for_tx_ac(&record.tx_ac)?,
+                    tx_ac: record.tx_ac,
+                    alt_ac: record.alt_ac,
+                    alt_aln_method: record.alt_aln_method,
+                    cds_start_i: record.cds_start_i,
+                    cds_end_i: record.cds_end_i,
+                })
+            })
+            .collect()
+    }
+
+    /// Return the number of transcript records of the supplied gene, i.e., the length of the
+    /// result of `get_tx_for_gene()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `gene` - HGNC gene name
+    fn get_transcript_count(&self, gene: &str) -> Result<usize, Error> {
+        Ok(self.get_tx_for_gene(gene)?.len())
+    }
+
+    /// Return the accession of the MANE Select transcript of the given gene, or None if not
+    /// known.
+    ///
+    /// The default implementation always returns None, see `data::mane::ManeProvider`
+    /// for adding MANE Select information to any provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `hgnc` -- HGNC gene name
+    fn get_mane_select_for_gene(&self, _hgnc: &str) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+
     /// Return transcripts that overlap given region.
     ///
     /// # Arguments
     ///
-    // * `alt_ac` -- reference sequence (e.g., NC_000007.13)
-    // * `alt_aln_method` -- alignment method (e.g., splign)
-    // * `start_i` -- 5' bound of region
-    // * `end_i` -- 3' bound of region
+    /// * `alt_ac` -- reference sequence (e.g., NC_000007.13)
+    /// * `alt_aln_method` -- alignment method (e.g., splign)
+    /// * `start_i` -- 5' bound of region (interbase)
+    /// * `end_i` -- 3' bound of region (interbase)
+    ///
+    /// Used by the assembly mapper to find the transcripts relevant for a `g.` variant.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// // Transcripts overlapping `NC_000011.10:g.108227625`, i.e., the interbase interval
+    /// // `108227624..108227625`.
+    /// let tx_acs = provider
+    ///     .get_tx_for_region("NC_000011.10", "splign", 108227624, 108227625)?
+    ///     .into_iter()
+    ///     .map(|record| record.tx_ac)
+    ///     .collect::<Vec<_>>();
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_tx_for_region(
         &self,
         alt_ac: &str,
@@ -292,6 +771,22 @@ pub trait Provider {
     /// # Arguments
     ///
     /// * `tx_ac` -- transcript accession with version (e.g., 'NM_199425.2')
+    ///
+    /// Used for transcript-only projections (`c.` to `n.` and `p.`) and by the
+    /// normalizer.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// let info = provider.get_tx_identity_info("NM_000051.3")?;
+    /// // The transcript length is the sum of the exon lengths; the CDS includes the stop codon.
+    /// let tx_len = info.lengths.iter().sum::<i32>();
+    /// assert!(info.cds_start_i < info.cds_end_i && info.cds_end_i <= tx_len);
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error>;
 
     /// Return a single transcript info for supplied accession (tx_ac, alt_ac, alt_aln_method), or None if not found.
@@ -299,8 +794,21 @@ pub trait Provider {
     /// # Arguments
     ///
     /// * `tx_ac` -- transcript accession with version (e.g., 'NM_000051.3')
-    /// * `alt_ac -- specific genomic sequence (e.g., NC_000011.4)
+    /// * `alt_ac` -- specific genomic sequence (e.g., NC_000011.4)
     /// * `alt_aln_method` -- sequence alignment method (e.g., splign, blat)
+    ///
+    /// Used together with `get_tx_exons()` to build the alignment mapper.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// let info = provider.get_tx_info("NM_000051.3", "NC_000011.10", "splign")?;
+    /// assert_eq!(info.alt_ac, "NC_000011.10");
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_tx_info(
         &self,
         tx_ac: &str,
@@ -316,6 +824,21 @@ pub trait Provider {
     /// # Arguments
     ///
     /// * `tx_ac` -- transcript accession with version (e.g., 'NM_000051.3')
+    ///
+    /// Used by the assembly mapper to find the chromosome of a transcript.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hgvs::data::{error::Error, interface::Provider};
+    /// # fn example(provider: &dyn Provider) -> Result<(), Error> {
+    /// // The genome sequences and alignment methods that `NM_000051.3` can be projected to.
+    /// for option in provider.get_tx_mapping_options("NM_000051.3")? {
+    ///     println!("{} ({})", option.alt_ac, option.alt_aln_method);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     fn get_tx_mapping_options(&self, tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error>;
 }
 
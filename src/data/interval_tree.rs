@@ -0,0 +1,113 @@
+//! Interval tree for looking up transcripts by region.
+
+use std::ops::Range;
+
+use bio::data_structures::interval_tree::ArrayBackedIntervalTree;
+
+/// Static interval tree from half-open ranges to values.
+///
+/// The tree is indexed when built from an iterator and cannot be modified afterwards.
+/// Queries take `O(log n + k)` time for `k` results.
+///
+/// # Example
+///
+/// ```
+/// use hgvs::data::{interface::TxForRegionRecord, interval_tree::IntervalTree};
+///
+/// let record = |tx_ac: &str, start_i, end_i| TxForRegionRecord {
+///     tx_ac: tx_ac.to_string(),
+///     alt_ac: "NC_000001.11".to_string(),
+///     alt_strand: 1,
+///     alt_aln_method: "splign".to_string(),
+///     start_i,
+///     end_i,
+/// };
+/// let records = [record("TX1.1", 10, 110), record("TX2.1", 100, 200)];
+/// let tree = records
+///     .iter()
+///     .map(|rec| (rec.start_i..rec.end_i, rec.clone()))
+///     .collect::<IntervalTree<_, _>>();
+///
+/// let tx_acs = tree.query(50..51).map(|rec| rec.tx_ac.as_str()).collect::<Vec<_>>();
+/// assert_eq!(tx_acs, vec!["TX1.1"]);
+/// assert_eq!(tree.query(105..106).count(), 2);
+/// assert_eq!(tree.query(300..400).count(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntervalTree<K: Ord + Copy, V> {
+    tree: ArrayBackedIntervalTree<K, V>,
+}
+
+impl<K: Ord + Copy, V: Clone> IntervalTree<K, V> {
+    /// Return the values of all ranges overlapping `range`.
+    ///
+    /// Empty ranges do not overlap anything.
+    pub fn query(&self, range: Range<K>) -> impl Iterator<Item = &V> {
+        let entries = if range.start < range.end {
+            self.tree.find(range)
+        } else {
+            Vec::new()
+        };
+        entries.into_iter().map(|entry| entry.data())
+    }
+}
+
+impl<K: Ord + Copy, V: Clone> Default for IntervalTree<K, V> {
+    fn default() -> Self {
+        std::iter::empty().collect()
+    }
+}
+
+impl<K: Ord + Copy, V: Clone> FromIterator<(Range<K>, V)> for IntervalTree<K, V> {
+    fn from_iter<T: IntoIterator<Item = (Range<K>, V)>>(iter: T) -> Self {
+        Self {
+            tree: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IntervalTree;
+
+    #[test]
+    fn query() {
+        let tree = [(10..20, "a"), (15..30, "b"), (40..50, "c"), (15..30, "d")]
+            .into_iter()
+            .collect::<IntervalTree<i32, &str>>();
+
+        let query = |start, end| {
+            let mut result = tree.query(start..end).copied().collect::<Vec<_>>();
+            result.sort();
+            result
+        };
+        assert_eq!(query(0, 10), Vec::<&str>::new());
+        assert_eq!(query(0, 11), vec!["a"]);
+        assert_eq!(query(19, 20), vec!["a", "b", "d"]);
+        assert_eq!(query(20, 40), vec!["b", "d"]);
+        assert_eq!(query(30, 40), Vec::<&str>::new());
+        assert_eq!(query(0, 100), vec!["a", "b", "c", "d"]);
+        assert_eq!(query(15, 15), Vec::<&str>::new());
+        assert_eq!(query(20, 10), Vec::<&str>::new());
+        assert_eq!(
+            IntervalTree::<i32, &str>::default().query(0..100).count(),
+            0
+        );
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
@@ -0,0 +1,284 @@
+//! Logging of provider calls.
+
+use std::{fmt, time::Instant};
+
+use biocommons_bioutils::assemblies::Assembly;
+use indexmap::IndexMap;
+
+use crate::data::{
+    error::Error,
+    interface::{
+        GeneInfoRecord, Provider, ProviderCapabilities, TranscriptSummary, TxExonsRecord,
+        TxForRegionRecord, TxIdentityInfo, TxInfoRecord, TxMappingOptionsRecord,
+        TxSimilarityRecord,
+    },
+};
+
+/// Provider that logs each call to the wrapped provider together with its duration.
+///
+/// A message naming the method and its key argument is written before the call, another
+/// one with the elapsed time in microseconds afterwards.
+pub struct LoggingProvider<P: Provider> {
+    /// The wrapped provider.
+    inner: P,
+    /// Level of the log messages.
+    log_level: log::Level,
+    /// Receiver of the log messages instead of the `log` crate, used in tests.
+    sink: Option<fn(log::Level, String)>,
+}
+
+impl<P: Provider> LoggingProvider<P> {
+    /// Wrap `inner`, logging at `log::Level::Debug`.
+    pub fn new(inner: P) -> Self {
+        Self::with_level(inner, log::Level::Debug)
+    }
+
+    /// Wrap `inner`, logging at `level`.
+    pub fn with_level(inner: P, level: log::Level) -> Self {
+        Self {
+            inner,
+            log_level: level,
+            sink: None,
+        }
+    }
+
+    /// Write the message `args`.
+    fn log(&self, args: fmt::Arguments) {
+        match self.sink {
+            Some(sink) => sink(self.log_level, args.to_string()),
+            None => log::log!(self.log_level, "{}", args),
+        }
+    }
+
+    /// Call `f` on the wrapped provider and log the call of `method` with argument `key`.
+    fn call<T>(&self, method: &str, key: &str, f: impl FnOnce(&P) -> T) -> T {
+        self.log(format_args!("{}({}) started", method, key));
+        let start = Instant::now();
+        let result = f(&self.inner);
+        self.log(format_args!(
+            "{}({}) finished in {} µs",
+            method,
+            key,
+            start.elapsed().as_micros()
+        ));
+        result
+    }
+}
+
+impl<P: Provider> Provider for LoggingProvider<P> {
+    fn data_version(&self) -> &str {
+        self.inner.data_version()
+    }
+
+    fn schema_version(&self) -> &str {
+        self.inner.schema_version()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
+        self.call("get_assembly_map", &format!("{:?}", assembly), |p| {
+            p.get_assembly_map(assembly)
+        })
+    }
+
+    fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        self.call("get_gene_info", hgnc, |p| p.get_gene_info(hgnc))
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error> {
+        self.call("get_pro_ac_for_tx_ac", tx_ac, |p| {
+            p.get_pro_ac_for_tx_ac(tx_ac)
+        })
+    }
+
+    fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        self.call("get_all_pro_ac_for_tx_ac", tx_ac, |p| {
+            p.get_all_pro_ac_for_tx_ac(tx_ac)
+        })
+    }
+
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        self.call("get_tx_ac_for_pro_ac", pro_ac, |p| {
+            p.get_tx_ac_for_pro_ac(pro_ac)
+        })
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        self.call("get_transcript_versions", base_ac, |p| {
+            p.get_transcript_versions(base_ac)
+        })
+    }
+
+    fn get_seq(&self, ac: &str) -> Result<String, Error> {
+        self.call("get_seq", ac, |p| p.get_seq(ac))
+    }
+
+    fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        self.call("get_seq_part", ac, |p| p.get_seq_part(ac, begin, end))
+    }
+
+    fn batch_get_seq_parts(
+        &self,
+        queries: &[(String, Option<usize>, Option<usize>)],
+    ) -> Result<Vec<String>, Error> {
+        let key = queries
+            .iter()
+            .map(|(ac, _, _)| ac.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.call("batch_get_seq_parts", &key, |p| {
+            p.batch_get_seq_parts(queries)
+        })
+    }
+
+    fn get_acs_for_protein_seq(&self, seq: &str) -> Result<Vec<String>, Error> {
+        self.call("get_acs_for_protein_seq", seq, |p| {
+            p.get_acs_for_protein_seq(seq)
+        })
+    }
+
+    fn get_similar_transcripts(&self, tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        self.call("get_similar_transcripts", tx_ac, |p| {
+            p.get_similar_transcripts(tx_ac)
+        })
+    }
+
+    fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        self.call("get_tx_exons", tx_ac, |p| {
+            p.get_tx_exons(tx_ac, alt_ac, alt_aln_method)
+        })
+    }
+
+    fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        self.call("get_tx_for_gene", gene, |p| p.get_tx_for_gene(gene))
+    }
+
+    fn get_all_tx_for_gene(&self, gene: &str) -> Result<Vec<TranscriptSummary>, Error> {
+        self.call("get_all_tx_for_gene", gene, |p| p.get_all_tx_for_gene(gene))
+    }
+
+    fn get_transcript_count(&self, gene: &str) -> Result<usize, Error> {
+        self.call("get_transcript_count", gene, |p| {
+            p.get_transcript_count(gene)
+        })
+    }
+
+    fn get_mane_select_for_gene(&self, hgnc: &str) -> Result<Option<String>, Error> {
+        self.call("get_mane_select_for_gene", hgnc, |p| {
+            p.get_mane_select_for_gene(hgnc)
+        })
+    }
+
+    fn get_tx_for_region(
+        &self,
+        alt_ac: &str,
+        alt_aln_method: &str,
+        start_i: i32,
+        end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        self.call("get_tx_for_region", alt_ac, |p| {
+            p.get_tx_for_region(alt_ac, alt_aln_method, start_i, end_i)
+        })
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        self.call("get_tx_identity_info", tx_ac, |p| {
+            p.get_tx_identity_info(tx_ac)
+        })
+    }
+
+    fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        self.call("get_tx_info", tx_ac, |p| {
+            p.get_tx_info(tx_ac, alt_ac, alt_aln_method)
+        })
+    }
+
+    fn get_tx_mapping_options(&self, tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        self.call("get_tx_mapping_options", tx_ac, |p| {
+            p.get_tx_mapping_options(tx_ac)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+
+    use crate::data::{
+        gff3::{Config, Provider},
+        interface::Provider as _,
+    };
+
+    use super::LoggingProvider;
+
+    thread_local! {
+        /// Records written by `LoggingProvider` in the current thread.
+        static RECORDS: RefCell<Vec<(log::Level, String)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Sink collecting the records per thread so that tests do not interfere.
+    fn capture(level: log::Level, message: String) {
+        RECORDS.with(|records| records.borrow_mut().push((level, message)));
+    }
+
+    #[test]
+    fn get_seq_part_logged() -> Result<(), anyhow::Error> {
+        let mut provider = LoggingProvider::with_level(
+            Provider::new(Config {
+                gff3_path: String::from("tests/data/data/gff3/single_exon.gff3"),
+                fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+            })?,
+            log::Level::Info,
+        );
+        provider.sink = Some(capture);
+        assert_eq!(provider.get_seq_part("TX1.1", Some(0), Some(3))?.len(), 3);
+
+        let records = RECORDS.with(|records| records.take());
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0],
+            (
+                log::Level::Info,
+                String::from("get_seq_part(TX1.1) started")
+            )
+        );
+        assert_eq!(records[1].0, log::Level::Info);
+        assert!(records[1].1.starts_with("get_seq_part(TX1.1) finished in"));
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
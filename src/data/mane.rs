@@ -0,0 +1,454 @@
+//! MANE Select transcripts from the MANE summary file.
+//!
+//! The Matched Annotation from NCBI and EMBL-EBI (MANE) project designates one transcript per
+//! protein-coding gene as MANE Select.  The project distributes a summary file, e.g.,
+//! `MANE.GRCh38.v1.3.summary.txt.gz` from `https://ftp.ncbi.nlm.nih.gov/refseq/MANE/`, that
+//! is tab-separated with a header line starting with `#` and the following columns.
+//!
+//! - `#NCBI_GeneID` -- NCBI gene identifier, e.g., `GeneID:672`
+//! - `Ensembl_Gene` -- Ensembl gene identifier, e.g., `ENSG00000012048.23`
+//! - `HGNC_ID` -- HGNC identifier, e.g., `HGNC:1100`
+//! - `symbol` -- HGNC gene symbol, e.g., `BRCA1`
+//! - `name` -- gene name
+//! - `RefSeq_nuc` -- RefSeq transcript accession, e.g., `NM_007294.4`
+//! - `RefSeq_prot` -- RefSeq protein accession, e.g., `NP_009225.1`
+//! - `Ensembl_nuc` -- Ensembl transcript accession, e.g., `ENST00000357654.9`
+//! - `Ensembl_prot` -- Ensembl protein accession, e.g., `ENSP00000350283.3`
+//! - `MANE_status` -- `MANE Select` or `MANE Plus Clinical`
+//! - `GRCh38_chr`, `chr_start`, `chr_end`, `chr_strand` -- location on GRCh38
+//!
+//! The columns `#NCBI_GeneID`, `Ensembl_Gene`, `HGNC_ID`, `symbol`, `RefSeq_nuc`,
+//! `RefSeq_prot`, and `MANE_status` are used and the file must be decompressed.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use biocommons_bioutils::assemblies::Assembly;
+use indexmap::IndexMap;
+
+use crate::data::{
+    error::Error,
+    interface::{
+        GeneInfoRecord, Provider, ProviderCapabilities, TranscriptSummary, TxExonsRecord,
+        TxForRegionRecord, TxIdentityInfo, TxInfoRecord, TxMappingOptionsRecord,
+        TxSimilarityRecord,
+    },
+};
+
+/// Status of a transcript in MANE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManeStatus {
+    /// `MANE Select`, the one representative transcript of the gene.
+    Select,
+    /// `MANE Plus Clinical`, an additional transcript needed to report clinical variants.
+    PlusClinical,
+}
+
+/// One record from the MANE summary file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManeEntry {
+    /// HGNC identifier, e.g., `HGNC:1100`.
+    pub hgnc_id: String,
+    /// HGNC gene symbol, e.g., `BRCA1`.
+    pub symbol: String,
+    /// NCBI gene identifier, e.g., `672` for `GeneID:672`.
+    pub ncbi_gene_id: u64,
+    /// Ensembl gene identifier, e.g., `ENSG00000012048.23`.
+    pub ensembl_gene_id: String,
+    /// MANE status of the transcript.
+    pub mane_status: ManeStatus,
+    /// RefSeq transcript accession, e.g., `NM_007294.4`.
+    pub refseq_nuc: String,
+    /// RefSeq protein accession, e.g., `NP_009225.1`.
+    pub refseq_prot: String,
+}
+
+/// Load the records of the MANE summary file at `path`.
+pub fn load_mane_from_tsv(path: &Path) -> Result<Vec<ManeEntry>, Error> {
+    let file =
+        File::open(path).map_err(|e| Error::TsvOpen(format!("{}: {}", path.display(), e)))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header = lines
+        .next()
+        .transpose()
+        .map_err(|e| Error::TsvParse(format!("{}: {}", path.display(), e)))?
+        .unwrap_or_default();
+    let columns = header
+        .trim_start_matches('#')
+        .split('\t')
+        .collect::<Vec<_>>();
+    let required = |name: &str| {
+        columns
+            .iter()
+            .position(|column| *column == name)
+            .ok_or_else(|| Error::TsvParse(format!("{}: missing column {}", path.display(), name)))
+    };
+    let col_ncbi_gene_id = required("NCBI_GeneID")?;
+    let col_ensembl_gene = required("Ensembl_Gene")?;
+    let col_hgnc_id = required("HGNC_ID")?;
+    let col_symbol = required("symbol")?;
+    let col_refseq_nuc = required("RefSeq_nuc")?;
+    let col_refseq_prot = required("RefSeq_prot")?;
+    let col_mane_status = required("MANE_status")?;
+
+    let mut result = Vec::new();
+    for (i, line) in lines.enumerate() {
+        let line = line.map_err(|e| Error::TsvParse(format!("{}: {}", path.display(), e)))?;
+        if line.is_empty() {
+            continue;
+        }
+        let invalid = |msg: &str| Error::TsvParse(format!("{}:{}: {}", path.display(), i + 2, msg));
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let field = |col: usize| {
+            fields
+                .get(col)
+                .copied()
+                .ok_or_else(|| invalid("too few columns"))
+        };
+
+        let ncbi_gene_id = field(col_ncbi_gene_id)?;
+        let mane_status = field(col_mane_status)?;
+        result.push(ManeEntry {
+            hgnc_id: field(col_hgnc_id)?.to_string(),
+            symbol: field(col_symbol)?.to_string(),
+            ncbi_gene_id: ncbi_gene_id
+                .trim_start_matches("GeneID:")
+                .parse()
+                .map_err(|_| invalid(&format!("invalid NCBI gene ID {}", ncbi_gene_id)))?,
+            ensembl_gene_id: field(col_ensembl_gene)?.to_string(),
+            mane_status: match mane_status {
+                "MANE Select" => ManeStatus::Select,
+                "MANE Plus Clinical" => ManeStatus::PlusClinical,
+                _ => return Err(invalid(&format!("invalid MANE status {}", mane_status))),
+            },
+            refseq_nuc: field(col_refseq_nuc)?.to_string(),
+            refseq_prot: field(col_refseq_prot)?.to_string(),
+        });
+    }
+
+    Ok(result)
+}
+
+/// Provider that adds the transcripts and proteins from the MANE summary file to the
+/// wrapped provider.
+///
+/// The MANE table is consulted first for the MANE Select transcript of a gene and the
+/// protein of a transcript, genes and transcripts not listed in the file are looked up in
+/// the wrapped provider.  All other calls are passed through.
+pub struct ManeProvider<P: Provider> {
+    /// The wrapped provider.
+    inner: P,
+    /// The records from the MANE summary file.
+    entries: Vec<ManeEntry>,
+    /// Index of the MANE Select record by gene symbol.
+    select_by_symbol: HashMap<String, usize>,
+    /// Index of the record by RefSeq transcript accession.
+    by_refseq_nuc: HashMap<String, usize>,
+}
+
+impl<P: Provider> ManeProvider<P> {
+    /// Wrap `inner`, reading the MANE table from the summary file at `path`.
+    pub fn new(inner: P, path: impl AsRef<Path>) -> Result<Self, Error> {
+        Ok(Self::with_entries(
+            inner,
+            load_mane_from_tsv(path.as_ref())?,
+        ))
+    }
+
+    /// Wrap `inner` with the given MANE table, see `load_mane_from_tsv()`.
+    pub fn with_entries(inner: P, entries: Vec<ManeEntry>) -> Self {
+        let select_by_symbol = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.mane_status == ManeStatus::Select)
+            .map(|(idx, entry)| (entry.symbol.clone(), idx))
+            .collect();
+        let by_refseq_nuc = entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| (entry.refseq_nuc.clone(), idx))
+            .collect();
+        Self {
+            inner,
+            entries,
+            select_by_symbol,
+            by_refseq_nuc,
+        }
+    }
+
+    /// Return the records of the MANE table.
+    pub fn entries(&self) -> &[ManeEntry] {
+        &self.entries
+    }
+
+    /// Return the RefSeq accession of the MANE Select transcript of the gene with the given
+    /// symbol, if listed in the MANE table.
+    pub fn mane_select_for_gene(&self, gene: &str) -> Option<&str> {
+        self.select_by_symbol
+            .get(gene)
+            .map(|&idx| self.entries[idx].refseq_nuc.as_str())
+    }
+
+    /// Return the RefSeq accessions of the MANE Plus Clinical transcripts of the gene with
+    /// the given symbol.
+    pub fn mane_plus_clinical_for_gene(&self, gene: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.mane_status == ManeStatus::PlusClinical && entry.symbol == gene)
+            .map(|entry| entry.refseq_nuc.as_str())
+            .collect()
+    }
+
+    /// Return the MANE record of the transcript with the given RefSeq accession, if any.
+    pub fn mane_entry_for_tx(&self, tx_ac: &str) -> Option<&ManeEntry> {
+        self.by_refseq_nuc.get(tx_ac).map(|&idx| &self.entries[idx])
+    }
+}
+
+impl<P: Provider> Provider for ManeProvider<P> {
+    fn data_version(&self) -> &str {
+        self.inner.data_version()
+    }
+
+    fn schema_version(&self) -> &str {
+        self.inner.schema_version()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
+        self.inner.get_assembly_map(assembly)
+    }
+
+    fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, Error> {
+        self.inner.get_gene_info(hgnc)
+    }
+
+    fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, Error> {
+        match self.mane_entry_for_tx(tx_ac) {
+            Some(entry) => Ok(Some(entry.refseq_prot.clone())),
+            None => self.inner.get_pro_ac_for_tx_ac(tx_ac),
+        }
+    }
+
+    fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        self.inner.get_all_pro_ac_for_tx_ac(tx_ac)
+    }
+
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        self.inner.get_tx_ac_for_pro_ac(pro_ac)
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        self.inner.get_transcript_versions(base_ac)
+    }
+
+    fn get_seq(&self, ac: &str) -> Result<String, Error> {
+        self.inner.get_seq(ac)
+    }
+
+    fn get_seq_part(
+        &self,
+        ac: &str,
+        begin: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<String, Error> {
+        self.inner.get_seq_part(ac, begin, end)
+    }
+
+    fn get_seq_part_i64(
+        &self,
+        ac: &str,
+        begin: Option<i64>,
+        end: Option<i64>,
+    ) -> Result<String, Error> {
+        self.inner.get_seq_part_i64(ac, begin, end)
+    }
+
+    fn batch_get_seq_parts(
+        &self,
+        queries: &[(String, Option<usize>, Option<usize>)],
+    ) -> Result<Vec<String>, Error> {
+        self.inner.batch_get_seq_parts(queries)
+    }
+
+    fn get_acs_for_protein_seq(&self, seq: &str) -> Result<Vec<String>, Error> {
+        self.inner.get_acs_for_protein_seq(seq)
+    }
+
+    fn get_similar_transcripts(&self, tx_ac: &str) -> Result<Vec<TxSimilarityRecord>, Error> {
+        self.inner.get_similar_transcripts(tx_ac)
+    }
+
+    fn get_tx_exons(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<Vec<TxExonsRecord>, Error> {
+        self.inner.get_tx_exons(tx_ac, alt_ac, alt_aln_method)
+    }
+
+    fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, Error> {
+        self.inner.get_tx_for_gene(gene)
+    }
+
+    fn get_all_tx_for_gene(&self, gene: &str) -> Result<Vec<TranscriptSummary>, Error> {
+        self.inner.get_all_tx_for_gene(gene)
+    }
+
+    fn get_transcript_count(&self, gene: &str) -> Result<usize, Error> {
+        self.inner.get_transcript_count(gene)
+    }
+
+    fn get_mane_select_for_gene(&self, hgnc: &str) -> Result<Option<String>, Error> {
+        match self.mane_select_for_gene(hgnc) {
+            Some(tx_ac) => Ok(Some(tx_ac.to_string())),
+            None => self.inner.get_mane_select_for_gene(hgnc),
+        }
+    }
+
+    fn get_tx_for_region(
+        &self,
+        alt_ac: &str,
+        alt_aln_method: &str,
+        start_i: i32,
+        end_i: i32,
+    ) -> Result<Vec<TxForRegionRecord>, Error> {
+        self.inner
+            .get_tx_for_region(alt_ac, alt_aln_method, start_i, end_i)
+    }
+
+    fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, Error> {
+        self.inner.get_tx_identity_info(tx_ac)
+    }
+
+    fn get_tx_info(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<TxInfoRecord, Error> {
+        self.inner.get_tx_info(tx_ac, alt_ac, alt_aln_method)
+    }
+
+    fn get_tx_mapping_options(&self, tx_ac: &str) -> Result<Vec<TxMappingOptionsRecord>, Error> {
+        self.inner.get_tx_mapping_options(tx_ac)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::data::{
+        gff3::{Config, Provider},
+        interface::Provider as _,
+    };
+
+    use super::{load_mane_from_tsv, ManeEntry, ManeProvider, ManeStatus};
+
+    #[test]
+    fn load_mane() -> Result<(), anyhow::Error> {
+        let entries = load_mane_from_tsv("tests/data/data/mane/mane_summary.txt".as_ref())?;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0],
+            ManeEntry {
+                hgnc_id: String::from("HGNC:548"),
+                symbol: String::from("AOAH"),
+                ncbi_gene_id: 313,
+                ensembl_gene_id: String::from("ENSG00000136250.12"),
+                mane_status: ManeStatus::Select,
+                refseq_nuc: String::from("NM_001637.3"),
+                refseq_prot: String::from("NP_001628.1"),
+            }
+        );
+        assert_eq!(entries[2].mane_status, ManeStatus::PlusClinical);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_mane_select_for_gene() -> Result<(), anyhow::Error> {
+        let inner = Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+        assert_eq!(inner.get_mane_select_for_gene("GENE1")?, None);
+
+        let provider = ManeProvider::new(inner, "tests/data/data/mane/mane_summary.txt")?;
+        // `TX1.2` is listed as MANE Plus Clinical only.
+        assert_eq!(
+            provider.get_mane_select_for_gene("GENE1")?,
+            Some(String::from("TX1.1"))
+        );
+        assert_eq!(
+            provider.get_mane_select_for_gene("AOAH")?,
+            Some(String::from("NM_001637.3"))
+        );
+        assert_eq!(provider.get_mane_select_for_gene("GENE2")?, None);
+        assert_eq!(provider.get_tx_for_gene("GENE1")?.len(), 2);
+
+        assert_eq!(provider.mane_select_for_gene("GENE1"), Some("TX1.1"));
+        assert_eq!(provider.mane_plus_clinical_for_gene("GENE1"), vec!["TX1.2"]);
+        assert!(provider.mane_plus_clinical_for_gene("AOAH").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_pro_ac_for_tx_ac() -> Result<(), anyhow::Error> {
+        let inner = Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+        assert!(inner.get_pro_ac_for_tx_ac("NM_001637.3").is_err());
+
+        let provider = ManeProvider::new(inner, "tests/data/data/mane/mane_summary.txt")?;
+        assert_eq!(
+            provider.get_pro_ac_for_tx_ac("NM_001637.3")?,
+            Some(String::from("NP_001628.1"))
+        );
+        assert_eq!(
+            provider.get_pro_ac_for_tx_ac("TX1.2")?,
+            Some(String::from("PX1.2"))
+        );
+        // Not in the MANE table, looked up in the wrapped provider.
+        assert_eq!(provider.get_pro_ac_for_tx_ac("TX2.1")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_file() -> Result<(), anyhow::Error> {
+        let inner = Provider::new(Config {
+            gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+        assert!(ManeProvider::new(inner, "tests/data/data/mane/missing.txt").is_err());
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
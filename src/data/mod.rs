@@ -1,7 +1,18 @@
 //! Datatypes, interfaces, and data acess.
 
+#[cfg(feature = "async")]
+pub mod async_provider;
+pub mod cache;
 pub mod cdot;
+#[cfg(feature = "ensembl-rest")]
+pub mod ensembl_rest;
 pub mod error;
+pub mod fasta_provider;
+pub mod gene_name;
+pub mod gff3;
 pub mod interface;
+pub mod interval_tree;
+pub mod logging;
+pub mod mane;
 pub mod uta;
 pub mod uta_sr;
@@ -8,13 +8,15 @@ use quick_cache::sync::Cache;
 use std::fmt::Debug;
 use std::sync::{Arc, Mutex};
 
+use crate::parser::Accession;
 use crate::sequences::{seq_md5, TranslationTable};
 use biocommons_bioutils::assemblies::{Assembly, ASSEMBLY_INFOS};
 
 use crate::data::{
-    error::Error, interface, interface::GeneInfoRecord, interface::TxExonsRecord,
-    interface::TxForRegionRecord, interface::TxIdentityInfo, interface::TxInfoRecord,
-    interface::TxMappingOptionsRecord, interface::TxSimilarityRecord,
+    error::Error, interface, interface::GeneInfoRecord, interface::ProviderCapabilities,
+    interface::TranscriptSummary, interface::TxExonsRecord, interface::TxForRegionRecord,
+    interface::TxIdentityInfo, interface::TxInfoRecord, interface::TxMappingOptionsRecord,
+    interface::TxSimilarityRecord,
 };
 
 /// Configuration for the `data::uta::Provider`.
@@ -137,11 +139,10 @@ impl TryFrom<Row> for TxIdentityInfo {
             lengths: row.try_get("lengths").map_err(Arc::new)?,
             hgnc: hgnc.to_string(),
             // UTA database does not support selenoproteins (yet).
-            translation_table: if is_selenoprotein {
-                TranslationTable::Selenocysteine
-            } else {
-                TranslationTable::Standard
-            },
+            translation_table: TranslationTable::for_alt_ac(
+                row.try_get("alt_ac").map_err(Arc::new)?,
+                is_selenoprotein,
+            ),
         })
     }
 }
@@ -161,6 +162,21 @@ impl TryFrom<Row> for TxInfoRecord {
     }
 }
 
+impl TryFrom<Row> for TranscriptSummary {
+    type Error = Error;
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tx_ac: row.try_get("tx_ac").map_err(Arc::new)?,
+            alt_ac: row.try_get("alt_ac").map_err(Arc::new)?,
+            alt_aln_method: row.try_get("alt_aln_method").map_err(Arc::new)?,
+            cds_start_i: row.try_get("cds_start_i").map_err(Arc::new)?,
+            cds_end_i: row.try_get("cds_end_i").map_err(Arc::new)?,
+            pro_ac: row.try_get("pro_ac").map_err(Arc::new)?,
+        })
+    }
+}
+
 impl TryFrom<Row> for TxMappingOptionsRecord {
     type Error = Error;
 
@@ -177,10 +193,15 @@ impl TryFrom<Row> for TxMappingOptionsRecord {
 struct ProviderCaches {
     get_gene_info: Cache<String, GeneInfoRecord>,
     get_pro_ac_for_tx_ac: Cache<String, Option<String>>,
+    get_all_pro_ac_for_tx_ac: Cache<String, Vec<String>>,
+    get_tx_ac_for_pro_ac: Cache<String, Option<String>>,
+    get_transcript_versions: Cache<String, Vec<String>>,
     get_acs_for_protein_seq: Cache<String, Vec<String>>,
     get_similar_transcripts: Cache<String, Vec<TxSimilarityRecord>>,
     get_tx_exons: Cache<(String, String, String), Vec<TxExonsRecord>>,
     get_tx_for_gene: Cache<String, Vec<TxInfoRecord>>,
+    get_all_tx_for_gene: Cache<String, Vec<TranscriptSummary>>,
+    get_transcript_count: Cache<String, usize>,
     get_tx_for_region: Cache<(String, String, i32, i32), Vec<TxForRegionRecord>>,
     get_tx_identity_info: Cache<String, TxIdentityInfo>,
     get_tx_info: Cache<(String, String, String), TxInfoRecord>,
@@ -192,10 +213,15 @@ impl ProviderCaches {
         Self {
             get_gene_info: Cache::new(items_capacity),
             get_pro_ac_for_tx_ac: Cache::new(items_capacity),
+            get_all_pro_ac_for_tx_ac: Cache::new(items_capacity),
+            get_tx_ac_for_pro_ac: Cache::new(items_capacity),
+            get_transcript_versions: Cache::new(items_capacity),
             get_acs_for_protein_seq: Cache::new(items_capacity),
             get_similar_transcripts: Cache::new(items_capacity),
             get_tx_exons: Cache::new(items_capacity),
             get_tx_for_gene: Cache::new(items_capacity),
+            get_all_tx_for_gene: Cache::new(items_capacity),
+            get_transcript_count: Cache::new(items_capacity),
             get_tx_for_region: Cache::new(items_capacity),
             get_tx_identity_info: Cache::new(items_capacity),
             get_tx_info: Cache::new(items_capacity),
@@ -260,6 +286,11 @@ impl interface::Provider for Provider {
         &self.schema_version
     }
 
+    /// The UTA database does not contain the sequences of genomic contigs.
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::all() - ProviderCapabilities::HAS_GENOME_SEQ
+    }
+
     fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
         IndexMap::from_iter(
             ASSEMBLY_INFOS[assembly]
@@ -324,6 +355,101 @@ impl interface::Provider for Provider {
         }
     }
 
+    fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        if let Some(result) = self.caches.get_all_pro_ac_for_tx_ac.get(tx_ac) {
+            return Ok(result);
+        }
+
+        let sql = format!(
+            "SELECT DISTINCT pro_ac FROM {}.associated_accessions \
+            WHERE tx_ac = $1 ORDER BY pro_ac DESC",
+            self.config.db_schema
+        );
+        let mut result = Vec::new();
+        for row in self
+            .conn
+            .lock()
+            .expect("cannot obtain connection lock")
+            .query(&sql, &[&tx_ac])
+            .map_err(Arc::new)?
+        {
+            result.push(row.try_get("pro_ac").map_err(Arc::new)?);
+        }
+
+        self.caches
+            .get_all_pro_ac_for_tx_ac
+            .insert(tx_ac.to_string(), result.clone());
+        Ok(result)
+    }
+
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        if let Some(result) = self.caches.get_tx_ac_for_pro_ac.get(pro_ac) {
+            return Ok(result);
+        }
+
+        let sql = format!(
+            "SELECT tx_ac FROM {}.associated_accessions \
+            WHERE pro_ac = $1 ORDER BY tx_ac DESC",
+            self.config.db_schema
+        );
+        let result = match (self
+            .conn
+            .lock()
+            .expect("cannot obtain connection lock")
+            .query(&sql, &[&pro_ac])
+            .map_err(Arc::new)?)
+        .into_iter()
+        .next()
+        {
+            Some(row) => Some(row.try_get("tx_ac").map_err(Arc::new)?),
+            None => None,
+        };
+
+        self.caches
+            .get_tx_ac_for_pro_ac
+            .insert(pro_ac.to_string(), result.clone());
+        Ok(result)
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        if let Some(result) = self.caches.get_transcript_versions.get(base_ac) {
+            return Ok(result);
+        }
+
+        // Escape the LIKE wildcards, e.g., the underscore in `NM_000088`.
+        let pattern = format!(
+            "{}.%",
+            base_ac
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        let sql = format!(
+            "SELECT ac FROM {}.transcript WHERE ac LIKE $1",
+            self.config.db_schema
+        );
+        let mut result = Vec::new();
+        for row in self
+            .conn
+            .lock()
+            .expect("cannot obtain connection lock")
+            .query(&sql, &[&pattern])
+            .map_err(Arc::new)?
+        {
+            let ac: String = row.try_get("ac").map_err(Arc::new)?;
+            if let Some(version) = Accession::new(&ac).version() {
+                result.push((version, ac));
+            }
+        }
+        result.sort();
+        let result: Vec<_> = result.into_iter().map(|(_, ac)| ac).collect();
+
+        self.caches
+            .get_transcript_versions
+            .insert(base_ac.to_string(), result.clone());
+        Ok(result)
+    }
+
     fn get_seq_part(
         &self,
         ac: &str,
@@ -494,6 +620,70 @@ impl interface::Provider for Provider {
         Ok(result)
     }
 
+    fn get_all_tx_for_gene(&self, gene: &str) -> Result<Vec<TranscriptSummary>, Error> {
+        if let Some(result) = self.caches.get_all_tx_for_gene.get(gene) {
+            return Ok(result);
+        }
+
+        // The protein accession is chosen as in `get_pro_ac_for_tx_ac()`.
+        let sql = format!(
+            "SELECT ES.tx_ac, ES.alt_ac, ES.alt_aln_method, T.cds_start_i, T.cds_end_i, \
+            AA.pro_ac \
+            FROM {}.transcript T \
+            JOIN {}.exon_set ES ON T.ac=ES.tx_ac \
+            LEFT JOIN ( \
+                SELECT tx_ac, MAX(pro_ac) AS pro_ac FROM {}.associated_accessions \
+                GROUP BY tx_ac \
+            ) AA ON T.ac=AA.tx_ac \
+            WHERE alt_aln_method != 'transcript' AND hgnc = $1 \
+            ORDER BY hgnc, cds_start_i, cds_end_i, tx_ac, alt_ac, alt_aln_method",
+            self.config.db_schema, self.config.db_schema, self.config.db_schema,
+        );
+        let mut result = Vec::new();
+        for row in self
+            .conn
+            .lock()
+            .expect("cannot obtain connection lock")
+            .query(&sql, &[&gene])
+            .map_err(Arc::new)?
+        {
+            result.push(row.try_into()?);
+        }
+
+        self.caches
+            .get_all_tx_for_gene
+            .insert(gene.to_string(), result.clone());
+        Ok(result)
+    }
+
+    fn get_transcript_count(&self, gene: &str) -> Result<usize, Error> {
+        if let Some(result) = self.caches.get_transcript_count.get(gene) {
+            return Ok(result);
+        }
+
+        let sql = format!(
+            "SELECT COUNT(*) AS count \
+            FROM {}.transcript T \
+            JOIN {}.exon_set ES ON T.ac=ES.tx_ac WHERE alt_aln_method != 'transcript' \
+            AND hgnc = $1",
+            self.config.db_schema, self.config.db_schema,
+        );
+        let count: i64 = self
+            .conn
+            .lock()
+            .expect("cannot obtain connection lock")
+            .query_one(&sql, &[&gene])
+            .map_err(Arc::new)?
+            .try_get("count")
+            .map_err(Arc::new)?;
+        let result = count as usize;
+
+        self.caches
+            .get_transcript_count
+            .insert(gene.to_string(), result);
+        Ok(result)
+    }
+
     fn get_tx_for_region(
         &self,
         alt_ac: &str,
@@ -554,13 +744,21 @@ impl interface::Provider for Provider {
             ORDER BY tx_ac, alt_ac, alt_aln_method, cds_start_i, cds_end_i, lengths, hgnc",
             self.config.db_schema
         );
-        let result: TxIdentityInfo = self
+        let mut result: TxIdentityInfo = self
             .conn
             .lock()
             .expect("cannot obtain connection lock")
             .query_one(&sql, &[&tx_ac])
             .map_err(Arc::new)?
             .try_into()?;
+        // The summary view only has the transcript itself as `alt_ac`, so look at the
+        // alignments to find transcripts on the mitochondrial genome.
+        if self.get_tx_mapping_options(tx_ac)?.iter().any(|option| {
+            TranslationTable::for_alt_ac(&option.alt_ac, false)
+                == TranslationTable::VertebrateMitochondrial
+        }) {
+            result.translation_table = TranslationTable::VertebrateMitochondrial;
+        }
 
         self.caches
             .get_tx_identity_info
@@ -710,6 +908,51 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn get_all_pro_ac_for_tx_ac() -> Result<(), Error> {
+        let provider = Provider::with_config(&get_config())?;
+
+        assert_eq!(
+            provider.get_all_pro_ac_for_tx_ac("NM_130831.2")?,
+            vec!["NP_570844.1".to_string()]
+        );
+        assert!(provider.get_all_pro_ac_for_tx_ac("NM_130831.x")?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_tx_ac_for_pro_ac() -> Result<(), Error> {
+        let provider = Provider::with_config(&get_config())?;
+
+        assert_eq!(
+            provider.get_tx_ac_for_pro_ac("NP_000079.2")?,
+            Some("NM_000088.3".to_string())
+        );
+        assert_eq!(provider.get_tx_ac_for_pro_ac("NP_000079.x")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_transcript_versions() -> Result<(), Error> {
+        let provider = Provider::with_config(&get_config())?;
+
+        let versions = provider.get_transcript_versions("NM_000088")?;
+        assert!(versions.contains(&"NM_000088.3".to_string()));
+        assert!(versions.iter().all(|ac| ac.starts_with("NM_000088.")));
+        assert_eq!(
+            provider
+                .get_latest_transcript_version("NM_000088")?
+                .as_ref(),
+            versions.last()
+        );
+        assert!(provider.get_transcript_versions("NM_000088x")?.is_empty());
+        assert_eq!(provider.get_latest_transcript_version("NM_000088x")?, None);
+
+        Ok(())
+    }
+
     #[test]
     fn get_seq() -> Result<(), Error> {
         let provider = Provider::with_config(&get_config())?;
@@ -787,6 +1030,25 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn get_all_tx_for_gene() -> Result<(), Error> {
+        let provider = Provider::with_config(&get_config())?;
+
+        let records = provider.get_all_tx_for_gene("OMA1")?;
+        let tx_records = provider.get_tx_for_gene("OMA1")?;
+
+        assert_eq!(provider.get_transcript_count("OMA1")?, tx_records.len());
+        assert_eq!(records.len(), tx_records.len());
+        for (record, tx_record) in records.iter().zip(tx_records.iter()) {
+            assert_eq!(record.tx_ac, tx_record.tx_ac);
+            assert_eq!(record.alt_ac, tx_record.alt_ac);
+            assert_eq!(record.pro_ac, provider.get_pro_ac_for_tx_ac(&record.tx_ac)?);
+        }
+        assert_eq!(provider.get_transcript_count("NO-SUCH-GENE")?, 0);
+
+        Ok(())
+    }
+
     #[test]
     fn get_tx_for_region() -> Result<(), Error> {
         let provider = Provider::with_config(&get_config())?;
@@ -9,9 +9,9 @@ use std::sync::Arc;
 
 use crate::data::uta;
 use crate::data::{
-    error::Error, interface, interface::GeneInfoRecord, interface::TxExonsRecord,
-    interface::TxForRegionRecord, interface::TxIdentityInfo, interface::TxInfoRecord,
-    interface::TxMappingOptionsRecord, interface::TxSimilarityRecord,
+    error::Error, interface, interface::GeneInfoRecord, interface::TranscriptSummary,
+    interface::TxExonsRecord, interface::TxForRegionRecord, interface::TxIdentityInfo,
+    interface::TxInfoRecord, interface::TxMappingOptionsRecord, interface::TxSimilarityRecord,
 };
 use seqrepo::{self, AliasOrSeqId, SeqRepo};
 
@@ -105,6 +105,18 @@ impl interface::Provider for Provider {
         self.inner.get_pro_ac_for_tx_ac(tx_ac)
     }
 
+    fn get_all_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Vec<String>, Error> {
+        self.inner.get_all_pro_ac_for_tx_ac(tx_ac)
+    }
+
+    fn get_tx_ac_for_pro_ac(&self, pro_ac: &str) -> Result<Option<String>, Error> {
+        self.inner.get_tx_ac_for_pro_ac(pro_ac)
+    }
+
+    fn get_transcript_versions(&self, base_ac: &str) -> Result<Vec<String>, Error> {
+        self.inner.get_transcript_versions(base_ac)
+    }
+
     fn get_seq_part(
         &self,
         ac: &str,
@@ -141,6 +153,14 @@ impl interface::Provider for Provider {
         self.inner.get_tx_for_gene(gene)
     }
 
+    fn get_all_tx_for_gene(&self, gene: &str) -> Result<Vec<TranscriptSummary>, Error> {
+        self.inner.get_all_tx_for_gene(gene)
+    }
+
+    fn get_transcript_count(&self, gene: &str) -> Result<usize, Error> {
+        self.inner.get_transcript_count(gene)
+    }
+
     fn get_tx_for_region(
         &self,
         alt_ac: &str,
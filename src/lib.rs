@@ -1,6 +1,8 @@
+pub mod conversion;
 pub mod data;
 pub mod mapper;
 pub mod normalizer;
 pub mod parser;
 pub mod sequences;
+pub mod static_data;
 pub mod validator;
@@ -23,10 +23,11 @@
 //    g.   ... 123   124   125   126   127   128   129   130   131   132   133 ...
 
 use std::iter::once;
+use std::ops::Range;
 use std::sync::Arc;
 
 use crate::{
-    data::interface::{Provider, TxExonsRecord},
+    data::interface::{Provider, ProviderCapabilities, TxExonsRecord},
     mapper::Error,
     parser::{CdsInterval, CdsPos, GenomeInterval, Mu, TxInterval, TxPos},
 };
@@ -120,8 +121,8 @@ impl Default for Config {
 pub struct Mapper {
     /// Configuration for alignment mapping.
     pub config: Config,
-    /// Data provider to use for the mapping.
-    pub provider: Arc<dyn Provider + Sync + Send>,
+    /// Data provider used for building the mapper, `None` if built with `from_cigar()`.
+    pub provider: Option<Arc<dyn Provider + Sync + Send>>,
 
     /// The transcript accession.
     pub tx_ac: String,
@@ -149,6 +150,9 @@ impl Mapper {
         let (strand, gc_offset, cds_start_i, cds_end_i, tgt_len, cigar_mapper) = if alt_aln_method
             != "transcript"
         {
+            provider
+                .capabilities()
+                .require(ProviderCapabilities::HAS_TX_MAPPING)?;
             let tx_info = provider.get_tx_info(tx_ac, alt_ac, alt_aln_method)?;
             let tx_exons = {
                 let tx_exons = provider.get_tx_exons(tx_ac, alt_ac, alt_aln_method)?;
@@ -230,7 +234,7 @@ impl Mapper {
 
         Ok(Mapper {
             config,
-            provider,
+            provider: Some(provider),
             tx_ac: tx_ac.to_string(),
             alt_ac: alt_ac.to_string(),
             alt_aln_method: alt_aln_method.to_string(),
@@ -243,6 +247,44 @@ impl Mapper {
         })
     }
 
+    /// Construct a mapper from an alignment given as CIGAR string, without a provider.
+    ///
+    /// # Args
+    ///
+    /// * `config` -- configuration for mapping
+    /// * `tx_ac` -- transcript accession
+    /// * `alt_ac` -- reference sequence accession
+    /// * `strand` -- strand of the transcript on the reference sequence, `1` or `-1`
+    /// * `alt_start_i` -- start of the alignment on the reference sequence (0-based)
+    /// * `cigar` -- alignment in reference sequence order with the introns as `N`, e.g.,
+    ///   `30=20N50=`
+    /// * `cds` -- CDS in transcript coordinates (0-based, half-open), `None` for non-coding
+    ///   transcripts
+    pub fn from_cigar(
+        config: &Config,
+        tx_ac: &str,
+        alt_ac: &str,
+        strand: i16,
+        alt_start_i: i32,
+        cigar: &str,
+        cds: Option<Range<i32>>,
+    ) -> Result<Mapper, Error> {
+        let cigar_mapper = CigarMapper::new(&parse_cigar_string(cigar)?);
+        Ok(Mapper {
+            config: config.clone(),
+            provider: None,
+            tx_ac: tx_ac.to_string(),
+            alt_ac: alt_ac.to_string(),
+            alt_aln_method: String::new(),
+            strand,
+            gc_offset: alt_start_i,
+            cds_start_i: cds.as_ref().map(|cds| cds.start),
+            cds_end_i: cds.as_ref().map(|cds| cds.end),
+            tgt_len: cigar_mapper.tgt_len,
+            cigar_mapper,
+        })
+    }
+
     /// Convert a genomic (g.) interval to a transcript (n.) interval.
     pub fn g_to_n(&self, g_interval: &GenomeInterval) -> Result<Mu<TxInterval>, Error> {
         if let GenomeInterval {
@@ -351,32 +393,15 @@ impl Mapper {
         ))
     }
 
-    fn pos_n_to_c(&self, pos: &TxPos) -> CdsPos {
+    fn pos_n_to_c(&self, pos: &TxPos) -> Result<CdsPos, Error> {
         let cds_start_i = self
             .cds_start_i
             .expect("cannot convert n. to c. without CDS positions");
         let cds_end_i = self
             .cds_end_i
             .expect("cannot convert n. to c. without CDS positions");
-        if pos.base <= cds_start_i {
-            CdsPos {
-                base: pos.base - cds_start_i - (if pos.base > 0 { 1 } else { 0 }),
-                offset: pos.offset,
-                cds_from: crate::parser::CdsFrom::Start,
-            }
-        } else if pos.base > cds_start_i && pos.base <= cds_end_i {
-            CdsPos {
-                base: pos.base - cds_start_i,
-                offset: pos.offset,
-                cds_from: crate::parser::CdsFrom::Start,
-            }
-        } else {
-            CdsPos {
-                base: pos.base - cds_end_i,
-                offset: pos.offset,
-                cds_from: crate::parser::CdsFrom::End,
-            }
-        }
+        pos.to_cds_pos(cds_start_i, cds_end_i)
+            .ok_or_else(|| Error::CoordinateOutOfBounds(format!("{:?}", pos)))
     }
 
     /// Convert a transcript (n.) interval to a CDS (c.) interval.
@@ -392,8 +417,8 @@ impl Mapper {
         }
 
         Ok(CdsInterval {
-            start: self.pos_n_to_c(&n_interval.start),
-            end: self.pos_n_to_c(&n_interval.end),
+            start: self.pos_n_to_c(&n_interval.start)?,
+            end: self.pos_n_to_c(&n_interval.end)?,
         })
     }
 
@@ -405,33 +430,12 @@ impl Mapper {
             .cds_end_i
             .expect("cannot convert n. to c. without CDS positions");
 
-        let n = match pos.cds_from {
-            crate::parser::CdsFrom::Start => {
-                let n = pos.base + cds_start_i;
-                if pos.base < 0 {
-                    // correct for lack of c.0 coordinate
-                    n + 1
-                } else {
-                    n
-                }
-            }
-            crate::parser::CdsFrom::End => pos.base + cds_end_i,
-        };
+        let n = pos.to_tx_pos(cds_start_i, cds_end_i);
 
-        let n = if n <= 0 {
-            // correct for lack of n.0 coordinate
-            n - 1
-        } else {
-            n
-        };
-
-        if (n <= 0 || n > self.tgt_len) && self.config.strict_bounds {
+        if (n.base <= 0 || n.base > self.tgt_len) && self.config.strict_bounds {
             Err(Error::CoordinateOutOfBounds(format!("{:?}", pos)))
         } else {
-            Ok(TxPos {
-                base: n,
-                offset: pos.offset,
-            })
+            Ok(n)
         }
     }
 
@@ -490,12 +494,16 @@ impl Mapper {
 #[cfg(test)]
 mod test {
     use anyhow::Error;
-    use std::str::FromStr;
+    use std::{str::FromStr, sync::Arc};
 
     use pretty_assertions::assert_eq;
 
     use crate::{
-        data::{interface::TxExonsRecord, uta_sr::test_helpers::build_provider},
+        data::{
+            fasta_provider::{Config as FastaConfig, FastaProvider},
+            interface::TxExonsRecord,
+            uta_sr::test_helpers::build_provider,
+        },
         parser::{CdsFrom, CdsInterval, CdsPos, GenomeInterval, Mu, TxInterval, TxPos},
     };
 
@@ -564,6 +572,109 @@ mod test {
         Ok(())
     }
 
+    /// Mappers built with `from_cigar()` must agree with the ones built from the transcript
+    /// file of the `fasta_provider` tests, see there for the transcripts.
+    #[test]
+    fn from_cigar() -> Result<(), Error> {
+        let provider = Arc::new(FastaProvider::new(FastaConfig {
+            tsv_path: String::from("tests/data/data/fasta/transcripts.txt"),
+            fasta_path: String::from("tests/data/data/fasta/single_exon.fa"),
+        })?);
+
+        for (tx_ac, alt_aln_method, strand, cigar, cds) in [
+            ("TX1.1", "splign", 1, "100=", Some(20..86)),
+            ("TX2.1", "blat", -1, "30=20N50=", None),
+        ] {
+            let expected = Mapper::new(
+                &Default::default(),
+                provider.clone(),
+                tx_ac,
+                "chrT",
+                alt_aln_method,
+            )?;
+            let mapper =
+                Mapper::from_cigar(&Default::default(), tx_ac, "chrT", strand, 10, cigar, cds)?;
+            assert!(mapper.provider.is_none());
+            assert_eq!(mapper.tgt_len, expected.tgt_len);
+            assert_eq!(
+                mapper.is_coding_transcript(),
+                expected.is_coding_transcript()
+            );
+
+            for (start, end) in [(11, 11), (30, 45), (39, 42), (100, 110)] {
+                let g_interval = GenomeInterval {
+                    start: Some(start),
+                    end: Some(end),
+                };
+                let n_interval = mapper.g_to_n(&g_interval)?;
+                assert_eq!(n_interval, expected.g_to_n(&g_interval)?, "{tx_ac}");
+                assert_eq!(
+                    mapper.n_to_g(n_interval.inner())?,
+                    expected.n_to_g(n_interval.inner())?,
+                    "{tx_ac}"
+                );
+                if mapper.is_coding_transcript() {
+                    assert_eq!(
+                        mapper.g_to_c(&g_interval)?,
+                        expected.g_to_c(&g_interval)?,
+                        "{tx_ac}"
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The standalone conversions on the positions must agree with the mapper.
+    #[test]
+    fn c_to_n_standalone() -> Result<(), Error> {
+        let mapper = Mapper::from_cigar(
+            &Default::default(),
+            "TX1.1",
+            "chrT",
+            1,
+            10,
+            "100=",
+            Some(20..86),
+        )?;
+
+        for (base, offset, cds_from) in [
+            (-20, None, CdsFrom::Start),
+            (-1, Some(-3), CdsFrom::Start),
+            (1, None, CdsFrom::Start),
+            (33, Some(2), CdsFrom::Start),
+            (66, None, CdsFrom::Start),
+            (1, None, CdsFrom::End),
+            (14, Some(-1), CdsFrom::End),
+        ] {
+            let pos = CdsPos {
+                base,
+                offset,
+                cds_from,
+            };
+            let c_interval = CdsInterval {
+                start: pos.clone(),
+                end: pos,
+            };
+            let n_interval = mapper.c_to_n(&c_interval)?;
+            assert_eq!(c_interval.to_tx_interval(20, 86), n_interval);
+            assert_eq!(
+                n_interval.to_cds_interval(20, 86),
+                Some(mapper.n_to_c(&n_interval)?)
+            );
+            assert_eq!(n_interval.to_cds_interval(20, 86), Some(c_interval));
+        }
+
+        let n0 = TxPos {
+            base: 0,
+            offset: None,
+        };
+        assert_eq!(n0.to_cds_pos(20, 86), None);
+
+        Ok(())
+    }
+
     #[test]
     fn run_none_if_default() {
         assert_eq!(none_if_default(0u32), None);
@@ -62,7 +62,7 @@ impl RefTranscriptData {
         pro_ac: Option<&str>,
     ) -> Result<Self, Error> {
         let tx_info = provider.as_ref().get_tx_identity_info(tx_ac)?;
-        let transcript_sequence = provider.as_ref().get_seq(tx_ac)?;
+        let transcript_sequence = provider.as_ref().get_tx_full_seq(tx_ac)?;
 
         // Use 1-based HGVS coordinates.
         let cds_start = tx_info.cds_start_i + 1;
@@ -108,6 +108,24 @@ impl RefTranscriptData {
             translation_table: tx_info.translation_table,
         })
     }
+
+    /// Return the reference data with the amino acid sequence translated using `table`.
+    pub(crate) fn with_translation_table(self, table: TranslationTable) -> Result<Self, Error> {
+        if table == self.translation_table {
+            return Ok(self);
+        }
+        let aa_sequence = translate_cds(
+            &self.transcript_sequence[((self.cds_start - 1) as usize)..(self.cds_stop as usize)],
+            true,
+            "*",
+            table,
+        )?;
+        Ok(Self {
+            aa_sequence,
+            translation_table: table,
+            ..self
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -288,6 +306,9 @@ impl AltSeqBuilder {
                 | NaEdit::Ins { .. } => EditType::NaRefAlt,
                 NaEdit::Dup { .. } => EditType::Dup,
                 NaEdit::InvRef { .. } | NaEdit::InvNum { .. } => EditType::Inv,
+                NaEdit::Repeat { .. } => {
+                    return Err(Error::RepeatNotSupported(format!("{}", self.var_c)))
+                }
             },
             VariantLocation::Intron
             // NB: the following comment is from the original Python code
@@ -6,10 +6,11 @@ use std::ops::Range;
 use std::sync::Arc;
 
 use crate::mapper::error::Error;
-use crate::mapper::variant;
+use crate::mapper::{liftover, variant};
 use crate::parser::HgvsVariant;
+use crate::static_data::{get_assembly_for_accession, InfoExt, HG19_CHRM_ACCESSION};
 use crate::{data::interface::Provider, validator::ValidationLevel};
-use biocommons_bioutils::assemblies::Assembly;
+use biocommons_bioutils::assemblies::{Assembly, ASSEMBLY_INFOS};
 
 #[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
 pub enum InParAssume {
@@ -29,6 +30,46 @@ impl InParAssume {
     }
 }
 
+/// Return the assembly that contains the sequence with accession `ac`, e.g., `NC_000001.10`.
+///
+/// See `static_data::get_assembly_for_accession()` for how sequences shared between
+/// assemblies are resolved.
+pub fn infer_assembly(ac: &str) -> Option<Assembly> {
+    get_assembly_for_accession(ac)
+}
+
+/// Return the RefSeq accession of chromosome `chrom` in `assembly`, e.g., `NC_000001.11`
+/// for `"1"` or `"chr1"` on GRCh38.
+pub fn chrom_name_to_refseq_ac(chrom: &str, assembly: Assembly) -> Option<String> {
+    ASSEMBLY_INFOS[assembly]
+        .accession_for_chrom(chrom)
+        .map(str::to_string)
+}
+
+/// Return the chromosome name (without `chr` prefix) of RefSeq accession `ac` in `assembly`.
+///
+/// `HG19_CHRM_ACCESSION` is named `"M"` on GRCh37, the inverse of `chrom_name_to_refseq_ac()`.
+pub fn refseq_ac_to_chrom_name(ac: &str, assembly: Assembly) -> Option<String> {
+    if ac == HG19_CHRM_ACCESSION && assembly != Assembly::Grch38 {
+        return Some(String::from("M"));
+    }
+    ASSEMBLY_INFOS[assembly]
+        .sequences
+        .iter()
+        .find(|record| record.refseq_ac == ac)
+        .map(|record| record.name.clone())
+}
+
+/// Return the RefSeq accession of the sequence with GenBank accession `ac` in `assembly`,
+/// e.g., `NC_000001.11` for `CM000663.2` on GRCh38.
+pub fn genbank_ac_to_refseq_ac(ac: &str, assembly: Assembly) -> Option<String> {
+    ASSEMBLY_INFOS[assembly]
+        .sequences
+        .iter()
+        .find(|record| record.genbank_ac == ac)
+        .map(|record| record.refseq_ac.clone())
+}
+
 /// Configuration for `Assemblymapper`.
 #[derive(Debug)]
 pub struct Config {
@@ -111,6 +152,7 @@ impl Mapper {
             strict_bounds: config.strict_bounds,
             renormalize_g: config.renormalize_g,
             genome_seq_available: config.genome_seq_available,
+            ..Default::default()
         };
         let inner = variant::Mapper::new(&inner_config, provider.clone());
         let asm_accessions = provider
@@ -218,6 +260,29 @@ impl Mapper {
         self.maybe_normalize(&var)
     }
 
+    /// Lift-over variant from the configured assembly to assembly `to`.
+    ///
+    /// Transcript variants (c. or n.) are projected to the genome first.
+    ///
+    /// # Args
+    ///
+    /// * `var` -- `HgvsVariant::GenomeVariant`, `HgvsVariant::CdsVariant`, or
+    ///   `HgvsVariant::TxVariant` to lift over
+    /// * `to` -- assembly to lift over to
+    /// * `chain` -- alignments from the configured assembly to `to`
+    pub fn lift_over(
+        &self,
+        var: &HgvsVariant,
+        to: Assembly,
+        chain: &liftover::Chain,
+    ) -> Result<HgvsVariant, Error> {
+        let var_g = match var {
+            HgvsVariant::GenomeVariant { .. } => var.clone(),
+            _ => self.t_to_g(var)?,
+        };
+        liftover::lift_over(&var_g, self.config.assembly, to, chain)
+    }
+
     /// Convert from CDS variant (c.) to transcript variant (n.).
     ///
     /// # Args
@@ -370,13 +435,24 @@ impl Mapper {
     pub fn replace_reference(&self, var: HgvsVariant) -> Result<HgvsVariant, Error> {
         self.inner.replace_reference(var)
     }
+
+    /// Return the assembly of the genomic sequence of `var_g`, see `infer_assembly()`.
+    ///
+    /// This may differ from the configured assembly, e.g., to detect variants that were
+    /// described on the other assembly.
+    pub fn default_assembly(&self, var_g: &HgvsVariant) -> Option<Assembly> {
+        infer_assembly(var_g.accession())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::data::uta_sr::test_helpers::build_provider;
+    use crate::parser::HgvsVariant;
     use anyhow::Error;
     use biocommons_bioutils::assemblies::Assembly;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
 
     use super::{Config, Mapper};
 
@@ -412,6 +488,88 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn infer_assembly() {
+        assert_eq!(
+            super::infer_assembly("NC_000001.10"),
+            Some(Assembly::Grch37)
+        );
+        assert_eq!(
+            super::infer_assembly("NC_000001.11"),
+            Some(Assembly::Grch38)
+        );
+        assert_eq!(
+            super::infer_assembly("NC_000023.10"),
+            Some(Assembly::Grch37)
+        );
+        assert_eq!(super::infer_assembly("NC_000001.12"), None);
+        assert_eq!(super::infer_assembly("NM_000088.3"), None);
+    }
+
+    #[test]
+    fn chrom_accession_lookup() {
+        let names = (1..=22)
+            .map(|i| i.to_string())
+            .chain(["X".to_string(), "Y".to_string()]);
+        for (i, name) in names.enumerate() {
+            for (assembly, genbank_version) in [(Assembly::Grch37, 1), (Assembly::Grch38, 2)] {
+                let refseq_ac = super::chrom_name_to_refseq_ac(&name, assembly)
+                    .unwrap_or_else(|| panic!("no accession for {name} in {assembly:?}"));
+                assert!(refseq_ac.starts_with(&format!("NC_{:06}.", i + 1)));
+                assert_eq!(
+                    super::chrom_name_to_refseq_ac(&format!("chr{name}"), assembly),
+                    Some(refseq_ac.clone())
+                );
+                assert_eq!(
+                    super::refseq_ac_to_chrom_name(&refseq_ac, assembly),
+                    Some(name.clone())
+                );
+                let genbank_ac = format!("CM{:06}.{}", 663 + i, genbank_version);
+                assert_eq!(
+                    super::genbank_ac_to_refseq_ac(&genbank_ac, assembly),
+                    Some(refseq_ac)
+                );
+            }
+        }
+
+        assert_eq!(
+            super::chrom_name_to_refseq_ac("chr25", Assembly::Grch38),
+            None
+        );
+        assert_eq!(
+            super::chrom_name_to_refseq_ac("chrM", Assembly::Grch37),
+            Some(String::from("NC_001807.4"))
+        );
+        assert_eq!(
+            super::refseq_ac_to_chrom_name("NC_001807.4", Assembly::Grch37),
+            Some(String::from("M"))
+        );
+        assert_eq!(
+            super::refseq_ac_to_chrom_name("NC_001807.4", Assembly::Grch38),
+            None
+        );
+        assert_eq!(
+            super::refseq_ac_to_chrom_name("NC_000001.10", Assembly::Grch38),
+            None
+        );
+        assert_eq!(
+            super::genbank_ac_to_refseq_ac("CM000663.1", Assembly::Grch38),
+            None
+        );
+    }
+
+    #[test]
+    fn default_assembly() -> Result<(), Error> {
+        let mapper = build_mapper_38(false)?;
+
+        let var_g = HgvsVariant::from_str("NC_000017.10:g.41197701G>C")?;
+        assert_eq!(mapper.default_assembly(&var_g), Some(Assembly::Grch37));
+        let var_c = HgvsVariant::from_str("NM_007294.3:c.5586G>C")?;
+        assert_eq!(mapper.default_assembly(&var_c), None);
+
+        Ok(())
+    }
+
     /// The following is a port of `Test_variant::Mapper` in
     /// `test_hgvs_variant::Mapper_near_discrepancies.py` (sic!)
     mod cases {
@@ -1,6 +1,6 @@
 //! Code supporting the `CigarMapper`
 
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use crate::mapper::Error;
 use nom::{combinator::all_consuming, multi::many0};
@@ -179,6 +179,14 @@ pub fn parse_cigar_string(input: &str) -> Result<CigarString, Error> {
     ))
 }
 
+impl FromStr for CigarString {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_cigar_string(s)
+    }
+}
+
 /// Provide coordinate mapping between two sequences whose alignment is given by a CIGAR string.
 ///
 /// CIGAR is about alignments between positions in two sequences.  It is base-centric.
@@ -341,7 +349,25 @@ mod test {
     use anyhow::Error;
     use pretty_assertions::assert_eq;
 
-    use super::{parse_cigar_string, CigarElement, CigarMapper, CigarMapperResult, CigarOp};
+    use super::{
+        parse_cigar_string, CigarElement, CigarMapper, CigarMapperResult, CigarOp, CigarString,
+    };
+
+    #[test]
+    fn cigar_string_from_str() -> Result<(), Error> {
+        for (cigar, expected) in [
+            ("30=20N50=", "30=20N50="),
+            ("5M1I4M", "5MI4M"),
+            ("2X1D3=", "2XD3="),
+        ] {
+            let cigar_string = cigar.parse::<CigarString>()?;
+            assert_eq!(cigar_string, parse_cigar_string(cigar)?);
+            assert_eq!(format!("{}", &cigar_string), expected);
+        }
+        assert!("5M1Q".parse::<CigarString>().is_err());
+
+        Ok(())
+    }
 
     #[test]
     fn parse_cigar_string_simple() -> Result<(), Error> {
@@ -21,6 +21,10 @@ pub enum Error {
     ExpectedTxVariant(String),
     #[error("expected a CdsVariant but received {0}")]
     ExpectedCdsVariant(String),
+    #[error("expected a RnaVariant but received {0}")]
+    ExpectedRnaVariant(String),
+    #[error("expected a MtVariant but received {0}")]
+    NotMtVariant(String),
     #[error("no NAEdit in HGVS.c variant: {0}")]
     NoNAEditInHgvsC(String),
     #[error("must have ProtVariant")]
@@ -33,6 +37,10 @@ pub enum Error {
     NaEditMissing,
     #[error("can only update reference for c, g, m, n, r")]
     CannotUpdateReference,
+    #[error("allele does not contain any variant")]
+    EmptyAllele,
+    #[error("repeat notation is not supported here: {0}")]
+    RepeatNotSupported(String),
     #[error("invalid CIGAR value: {0}")]
     InvalidCigarValue(char),
     #[error("invalid CIGAR value: {0}")]
@@ -99,6 +107,28 @@ pub enum Error {
     CannotConvertIntervalStart(i32),
     #[error("cannot convert interval end: {0} to usize")]
     CannotConvertIntervalEnd(i32),
+    #[error("cannot project frameshift back to c.: {0}")]
+    CannotBackProjectFrameshift(String),
+    #[error("cannot project protein edit back to c.: {0}")]
+    CannotBackProjectProteinEdit(String),
+    #[error("reference amino acids of {0} do not match protein sequence {1}")]
+    ProteinReferenceMismatch(String, String),
+    #[error("no codon encodes amino acid {0}")]
+    NoCodonForAminoAcid(String),
+    #[error("invalid chain file: {0}")]
+    InvalidChainFile(String),
+    #[error("cannot lift over {0}")]
+    LiftOverFailed(String),
+    #[error("cannot lift over {0} as it spans a chain block boundary")]
+    LiftOverBlockBoundary(String),
+    #[error("cannot remap {0} to {1} as it is not aligned to the same region")]
+    AccessionRemapFailed(String, String),
+    #[error("no transcripts overlapping {0}")]
+    NoOverlappingTranscripts(String),
+    #[error("protein accession {found} does not match {expected} of the transcript")]
+    ProteinAccessionMismatch { expected: String, found: String },
+    #[error("no protein accession known for transcript {0}")]
+    NoProteinAccession(String),
     #[error("general mapper error")]
     General,
 }
@@ -0,0 +1,376 @@
+//! Lift-over of genome variants between assemblies using UCSC chain files.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use biocommons_bioutils::assemblies::{Assembly, ASSEMBLY_INFOS};
+
+use crate::mapper::Error;
+use crate::parser::{Accession, GenomeInterval, GenomeLocEdit, HgvsVariant, Mu};
+
+/// Ungapped block of a chain, in 0-based coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Block {
+    /// Start position on the source sequence.
+    t_start: i32,
+    /// Start position on the target sequence, on the strand of the chain.
+    q_start: i32,
+    /// Length of the block.
+    size: i32,
+}
+
+/// One chain, i.e., an alignment between a source and a target sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChainRecord {
+    score: i64,
+    t_start: i32,
+    t_end: i32,
+    q_name: String,
+    q_size: i32,
+    q_reverse: bool,
+    /// Blocks sorted by `t_start`.
+    blocks: Vec<Block>,
+}
+
+impl ChainRecord {
+    /// Return index of the block containing the 0-based source position `pos`, if any.
+    fn block_index(&self, pos: i32) -> Option<usize> {
+        if pos < self.t_start || pos >= self.t_end {
+            return None;
+        }
+        let idx = self.blocks.partition_point(|block| block.t_start <= pos);
+        idx.checked_sub(1)
+            .filter(|idx| pos < self.blocks[*idx].t_start + self.blocks[*idx].size)
+    }
+
+    /// Map the 0-based source position `pos` within block `idx` to the forward strand of the
+    /// target sequence.
+    fn map_pos(&self, idx: usize, pos: i32) -> i32 {
+        let block = &self.blocks[idx];
+        let q_pos = block.q_start + (pos - block.t_start);
+        if self.q_reverse {
+            self.q_size - q_pos - 1
+        } else {
+            q_pos
+        }
+    }
+}
+
+/// Alignments between two assemblies as loaded from a UCSC chain file.
+///
+/// Sequence names are stored without the `chr` prefix and with `M` replaced by `MT` so they
+/// can be looked up in the assembly information.
+#[derive(Debug, Clone, Default)]
+pub struct Chain {
+    /// Chains by source sequence name, sorted by decreasing score.
+    chains: HashMap<String, Vec<ChainRecord>>,
+}
+
+/// Normalize UCSC sequence names to the names used in the assembly information.
+fn normalize_name(name: &str) -> String {
+    let name = name.strip_prefix("chr").unwrap_or(name);
+    if name == "M" {
+        "MT".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+impl Chain {
+    /// Load chain file at `path`, files ending in `.gz` are decompressed.
+    pub fn from_file<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            Error::InvalidChainFile(format!("cannot open {}: {}", path.display(), e))
+        })?;
+        if path.extension().map(|ext| ext == "gz").unwrap_or(false) {
+            Self::from_reader(BufReader::new(flate2::bufread::MultiGzDecoder::new(
+                BufReader::new(file),
+            )))
+        } else {
+            Self::from_reader(BufReader::new(file))
+        }
+    }
+
+    /// Load chain file from `reader`.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, Error> {
+        let mut result = Self::default();
+        // Name of the source sequence, the current chain, and the current positions.
+        let mut current: Option<(String, ChainRecord, i32, i32)> = None;
+
+        for line in reader.lines() {
+            let line = line.map_err(|e| Error::InvalidChainFile(e.to_string()))?;
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let invalid = || Error::InvalidChainFile(line.clone());
+            let int = |s: &str| s.parse::<i32>().map_err(|_| invalid());
+
+            match fields.as_slice() {
+                [] => continue,
+                // chain score tName tSize tStrand tStart tEnd qName qSize qStrand qStart qEnd id
+                ["chain", header @ ..] if header.len() >= 11 && header[3] == "+" => {
+                    if let Some((name, record, _, _)) = current.take() {
+                        result.chains.entry(name).or_default().push(record);
+                    }
+                    let record = ChainRecord {
+                        score: header[0].parse().map_err(|_| invalid())?,
+                        t_start: int(header[4])?,
+                        t_end: int(header[5])?,
+                        q_name: normalize_name(header[6]),
+                        q_size: int(header[7])?,
+                        q_reverse: match header[8] {
+                            "+" => false,
+                            "-" => true,
+                            _ => return Err(invalid()),
+                        },
+                        blocks: Vec::new(),
+                    };
+                    let (t_pos, q_pos) = (record.t_start, int(header[9])?);
+                    current = Some((normalize_name(header[1]), record, t_pos, q_pos));
+                }
+                [size, gaps @ ..] if gaps.is_empty() || gaps.len() == 2 => {
+                    let (_, record, t_pos, q_pos) = current.as_mut().ok_or_else(invalid)?;
+                    let size = int(size)?;
+                    record.blocks.push(Block {
+                        t_start: *t_pos,
+                        q_start: *q_pos,
+                        size,
+                    });
+                    *t_pos += size;
+                    *q_pos += size;
+                    if let [dt, dq] = gaps {
+                        *t_pos += int(dt)?;
+                        *q_pos += int(dq)?;
+                    }
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        if let Some((name, record, _, _)) = current.take() {
+            result.chains.entry(name).or_default().push(record);
+        }
+
+        for records in result.chains.values_mut() {
+            records.sort_by_key(|record| std::cmp::Reverse(record.score));
+        }
+        Ok(result)
+    }
+
+    /// Map the 1-based inclusive interval `[start, end]` on sequence `name`.
+    ///
+    /// Returns the target sequence name, the 1-based inclusive interval on it, and whether
+    /// the target is on the reverse strand.
+    fn map_interval(
+        &self,
+        name: &str,
+        start: i32,
+        end: i32,
+    ) -> Result<(String, i32, i32, bool), Error> {
+        let records = self
+            .chains
+            .get(name)
+            .ok_or_else(|| Error::LiftOverFailed(format!("{name}:{start}-{end}")))?;
+        for record in records {
+            match (record.block_index(start - 1), record.block_index(end - 1)) {
+                (Some(i), Some(j)) if i == j => {
+                    let (q_start, q_end) =
+                        (record.map_pos(i, start - 1), record.map_pos(j, end - 1));
+                    let (q_start, q_end) = if record.q_reverse {
+                        (q_end, q_start)
+                    } else {
+                        (q_start, q_end)
+                    };
+                    return Ok((
+                        record.q_name.clone(),
+                        q_start + 1,
+                        q_end + 1,
+                        record.q_reverse,
+                    ));
+                }
+                (Some(_), _) | (_, Some(_)) => {
+                    return Err(Error::LiftOverBlockBoundary(format!(
+                        "{name}:{start}-{end}"
+                    )))
+                }
+                (None, None) => continue,
+            }
+        }
+        Err(Error::LiftOverFailed(format!("{name}:{start}-{end}")))
+    }
+}
+
+/// Lift-over of a genome variant from assembly `from` to assembly `to`.
+///
+/// Variants on other sequences must be projected to the genome first, e.g., with
+/// `assembly::Mapper::t_to_g()`.  Variants whose interval spans the boundary of an ungapped
+/// chain block cannot be lifted over.  If the target is on the reverse strand, the sequences
+/// of the edit are reverse-complemented.
+///
+/// # Args
+///
+/// * `var` -- `HgvsVariant::GenomeVariant` to lift over
+/// * `from` -- assembly of `var`
+/// * `to` -- assembly to lift over to
+/// * `chain` -- alignments from `from` to `to`
+pub fn lift_over(
+    var: &HgvsVariant,
+    from: Assembly,
+    to: Assembly,
+    chain: &Chain,
+) -> Result<HgvsVariant, Error> {
+    let HgvsVariant::GenomeVariant {
+        accession,
+        gene_symbol,
+        loc_edit,
+    } = var
+    else {
+        return Err(Error::ExpectedGenomeVariant(format!("{}", var)));
+    };
+    let (start, end) = match loc_edit.loc.inner() {
+        GenomeInterval {
+            start: Some(start),
+            end: Some(end),
+        } => (*start, *end),
+        _ => return Err(Error::MissingGenomeIntervalPosition(format!("{}", var))),
+    };
+
+    let name = ASSEMBLY_INFOS[from]
+        .sequences
+        .iter()
+        .find(|record| record.refseq_ac == accession.value)
+        .map(|record| record.name.clone())
+        .ok_or_else(|| Error::LiftOverFailed(format!("{} not in {:?}", accession.value, from)))?;
+    let (name, start, end, reverse) = chain.map_interval(&name, start, end)?;
+    let to_ac = ASSEMBLY_INFOS[to]
+        .sequences
+        .iter()
+        .find(|record| record.name == name)
+        .map(|record| record.refseq_ac.clone())
+        .ok_or_else(|| Error::LiftOverFailed(format!("{} not in {:?}", name, to)))?;
+
+    let edit = if reverse {
+        Mu::from(
+            loc_edit.edit.inner().reverse_complement(),
+            loc_edit.edit.is_certain(),
+        )
+    } else {
+        loc_edit.edit.clone()
+    };
+    Ok(HgvsVariant::GenomeVariant {
+        accession: Accession::new(&to_ac),
+        gene_symbol: gene_symbol.clone(),
+        loc_edit: GenomeLocEdit {
+            loc: Mu::from(
+                GenomeInterval {
+                    start: Some(start),
+                    end: Some(end),
+                },
+                loc_edit.loc.is_certain(),
+            ),
+            edit,
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use anyhow::Error;
+    use biocommons_bioutils::assemblies::Assembly;
+    use pretty_assertions::assert_eq;
+
+    use crate::parser::HgvsVariant;
+
+    use super::{lift_over, Chain};
+
+    /// Chains on chr7 with a gap and on chr1 onto the reverse strand of chr12.
+    const CHAIN: &str = "\
+chain 1000 chr7 159138663 + 1000 1310 chr7 159345973 + 2000 2300 1
+100 10 0
+200
+
+chain 500 chr1 249250621 + 100 200 chr12 133275309 - 1000 1100 2
+100
+";
+
+    fn lift(chain: &Chain, hgvs: &str) -> Result<String, Error> {
+        let var = HgvsVariant::from_str(hgvs)?;
+        let var = lift_over(&var, Assembly::Grch37p10, Assembly::Grch38, chain)?;
+        Ok(format!("{}", &var))
+    }
+
+    #[test]
+    fn lift_over_rs671() -> Result<(), Error> {
+        let chain = Chain::from_file("tests/data/mapper/liftover/hg19ToHg38.rs671.chain")?;
+
+        assert_eq!(
+            lift(&chain, "NC_000012.11:g.112241766G>A")?,
+            "NC_000012.12:g.111803962G>A"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lift_over_blocks() -> Result<(), Error> {
+        let chain = Chain::from_reader(CHAIN.as_bytes())?;
+
+        // First and second block of the chr7 chain.
+        assert_eq!(
+            lift(&chain, "NC_000007.13:g.1001_1100del")?,
+            "NC_000007.14:g.2001_2100del"
+        );
+        assert_eq!(
+            lift(&chain, "NC_000007.13:g.1111A>T")?,
+            "NC_000007.14:g.2101A>T"
+        );
+        // Reverse strand.
+        assert_eq!(
+            lift(&chain, "NC_000001.10:g.101_102delAC")?,
+            "NC_000012.12:g.133274308_133274309delGT"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lift_over_fails() -> Result<(), Error> {
+        let chain = Chain::from_reader(CHAIN.as_bytes())?;
+
+        // Spans the gap between the blocks.
+        assert!(lift(&chain, "NC_000007.13:g.1100_1111del").is_err());
+        // In the gap between the blocks.
+        assert!(lift(&chain, "NC_000007.13:g.1105A>T").is_err());
+        // Not covered by any chain.
+        assert!(lift(&chain, "NC_000007.13:g.100A>T").is_err());
+        // Not a genome variant.
+        assert!(lift(&chain, "NM_001637.3:c.1582G>A").is_err());
+
+        assert!(Chain::from_reader("chain x".as_bytes()).is_err());
+        assert!(Chain::from_reader("100 10 0".as_bytes()).is_err());
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+// Copyright 2014 Bioutils Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
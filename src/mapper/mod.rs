@@ -5,6 +5,7 @@ pub(crate) mod altseq;
 pub mod assembly;
 pub mod cigar;
 mod error;
+pub mod liftover;
 pub mod variant;
 
 pub use error::Error;
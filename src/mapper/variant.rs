@@ -1,26 +1,39 @@
 //! Code for mapping variants between sequences.
 
+use std::collections::HashMap;
 use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::{ops::Range, sync::Arc};
 
-use cached::proc_macro::cached;
-use cached::SizedCache;
-use log::{debug, info};
+use biocommons_bioutils::assemblies::Assembly;
+use cached::{Cached, SizedCache};
+use indexmap::IndexSet;
+use log::{debug, info, warn};
+use rayon::prelude::*;
 
 use crate::{
-    data::interface::Provider,
+    data::{
+        gene_name::add_gene_names_with,
+        interface::{Provider, ProviderCapabilities},
+    },
     mapper::Error,
     normalizer::{self, Normalizer},
     parser::{
-        Accession, CdsInterval, CdsLocEdit, CdsPos, GeneSymbol, GenomeInterval, GenomeLocEdit,
-        HgvsVariant, Mu, NaEdit, TxInterval, TxLocEdit, TxPos,
+        Accession, CdsFrom, CdsInterval, CdsLocEdit, CdsPos, GeneSymbol, GenomeInterval,
+        GenomeLocEdit, HgvsVariant, Mu, NaEdit, ProtLocEdit, ProteinConsequence, ProteinEdit,
+        RnaInterval, RnaLocEdit, RnaPos, TxInterval, TxLocEdit, TxPos, SPLICE_SITE_WINDOW,
     },
-    sequences::revcomp,
+    sequences::{aa_to_aa1, revcomp, translate_cds, TranslationTable},
+    static_data::get_assembly_for_accession,
     validator::{ValidationLevel, Validator},
 };
 
 use super::alignment;
 
+/// Transcript accession that makes `Mapper::g_to_t()` select the transcript itself.
+pub const AUTO_TX_AC: &str = "auto";
+
 /// Configuration for Mapper.
 ///
 /// Defaults are taken from `hgvs` Python library.
@@ -37,6 +50,15 @@ pub struct Config {
     /// Use the genome sequence in case of uncertain g-to-n projections.  This
     /// can be switched off so genome sequence does not have to be available.
     pub genome_seq_available: bool,
+    /// Number of `alignment::Mapper` objects to keep in the LRU cache, `0` disables
+    /// caching.
+    pub cache_size: usize,
+    /// Retry with the most similar transcript from `Provider::get_similar_transcripts()` if
+    /// the alignment of a transcript cannot be loaded.
+    pub fallback_to_similar: bool,
+    /// Prefer the MANE Select transcript from `Provider::get_mane_select_for_gene()` when
+    /// `g_to_t()` selects the transcript with `AUTO_TX_AC`.
+    pub prefer_mane_select: bool,
 }
 
 impl Default for Config {
@@ -49,15 +71,160 @@ impl Default for Config {
             strict_bounds: true,
             renormalize_g: true,
             genome_seq_available: true,
+            cache_size: 512,
+            fallback_to_similar: false,
+            prefer_mane_select: false,
         }
     }
 }
 
+impl Config {
+    /// Return a builder starting from the default configuration.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+/// Builder for `Config`, starting from `Config::default()`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// Set whether reference bases of projected variants are filled in from the provider,
+    /// enabled by default.
+    pub fn replace_reference(mut self, replace_reference: bool) -> Self {
+        self.config.replace_reference = replace_reference;
+        self
+    }
+
+    /// Set whether failed validation is an error rather than a logged warning, disabled by
+    /// default.
+    pub fn strict_validation(mut self, strict_validation: bool) -> Self {
+        self.config.strict_validation = strict_validation;
+        self
+    }
+
+    /// Set the validation performed on input variants, `ValidationLevel::Full` by default.
+    pub fn prevalidation_level(mut self, prevalidation_level: ValidationLevel) -> Self {
+        self.config.prevalidation_level = prevalidation_level;
+        self
+    }
+
+    /// Set whether the gene symbol is added to projected variants, disabled by default.
+    pub fn add_gene_symbol(mut self, add_gene_symbol: bool) -> Self {
+        self.config.add_gene_symbol = add_gene_symbol;
+        self
+    }
+
+    /// Set whether positions outside of the transcript are rejected, enabled by default.
+    pub fn strict_bounds(mut self, strict_bounds: bool) -> Self {
+        self.config.strict_bounds = strict_bounds;
+        self
+    }
+
+    /// Set whether out of bounds genome variants on the minus strand are re-normalized,
+    /// enabled by default.  Disable if the provider has no genome sequence.
+    pub fn renormalize_g(mut self, renormalize_g: bool) -> Self {
+        self.config.renormalize_g = renormalize_g;
+        self
+    }
+
+    /// Set whether the genome sequence is used for uncertain g-to-n projections, enabled by
+    /// default.  Disable if the provider has no genome sequence.
+    pub fn genome_seq_available(mut self, genome_seq_available: bool) -> Self {
+        self.config.genome_seq_available = genome_seq_available;
+        self
+    }
+
+    /// Set the number of alignment mappers kept in the LRU cache, `512` by default.  `0`
+    /// disables caching.
+    pub fn cache_size(mut self, cache_size: usize) -> Self {
+        self.config.cache_size = cache_size;
+        self
+    }
+
+    /// Set whether to fall back to the most similar transcript if the alignment of a
+    /// transcript cannot be loaded, disabled by default.
+    pub fn fallback_to_similar(mut self, fallback_to_similar: bool) -> Self {
+        self.config.fallback_to_similar = fallback_to_similar;
+        self
+    }
+
+    /// Set whether `g_to_t()` with `AUTO_TX_AC` prefers the MANE Select transcript, disabled
+    /// by default.
+    pub fn prefer_mane_select(mut self, prefer_mane_select: bool) -> Self {
+        self.config.prefer_mane_select = prefer_mane_select;
+        self
+    }
+
+    /// Return the configuration.
+    pub fn build(self) -> Config {
+        self.config
+    }
+}
+
+/// Counters of successful and failed calls to the mapping methods of `Mapper`.
+///
+/// Only the entry point is counted, i.e., the public mapping method called by the user;
+/// the projections it performs internally are not, e.g., `g_to_p` does not count a
+/// `g_to_c` and a `c_to_p`.  Methods without a counter pair of their own, such as
+/// `g_to_all_transcripts()` or `remap_accession()`, are not counted at all.  The
+/// counters can be shared between several mappers.
+#[derive(Default, Debug)]
+pub struct MapperStats {
+    pub g_to_t_ok: AtomicU64,
+    pub g_to_t_err: AtomicU64,
+    pub t_to_g_ok: AtomicU64,
+    pub t_to_g_err: AtomicU64,
+    pub g_to_n_ok: AtomicU64,
+    pub g_to_n_err: AtomicU64,
+    pub n_to_g_ok: AtomicU64,
+    pub n_to_g_err: AtomicU64,
+    pub g_to_c_ok: AtomicU64,
+    pub g_to_c_err: AtomicU64,
+    pub c_to_g_ok: AtomicU64,
+    pub c_to_g_err: AtomicU64,
+    pub g_to_r_ok: AtomicU64,
+    pub g_to_r_err: AtomicU64,
+    pub r_to_g_ok: AtomicU64,
+    pub r_to_g_err: AtomicU64,
+    pub c_to_n_ok: AtomicU64,
+    pub c_to_n_err: AtomicU64,
+    pub n_to_c_ok: AtomicU64,
+    pub n_to_c_err: AtomicU64,
+    pub c_to_r_ok: AtomicU64,
+    pub c_to_r_err: AtomicU64,
+    pub r_to_c_ok: AtomicU64,
+    pub r_to_c_err: AtomicU64,
+    pub c_to_p_ok: AtomicU64,
+    pub c_to_p_err: AtomicU64,
+    pub p_to_c_ok: AtomicU64,
+    pub p_to_c_err: AtomicU64,
+    pub g_to_p_ok: AtomicU64,
+    pub g_to_p_err: AtomicU64,
+    pub p_to_g_ok: AtomicU64,
+    pub p_to_g_err: AtomicU64,
+    pub m_to_p_ok: AtomicU64,
+    pub m_to_p_err: AtomicU64,
+}
+
+/// Key of the alignment mapper cache: `(tx_ac, alt_ac, alt_aln_method)`.
+type AlignmentMapperKey = (String, String, String);
+
 /// Projects variants between sequences using `alignment::Mapper`.
 pub struct Mapper {
     config: Config,
     provider: Arc<dyn Provider + Send + Sync>,
     validator: Arc<dyn Validator + Send + Sync>,
+    /// LRU cache of alignment mappers; `None` if caching is disabled.
+    ///
+    /// The cache is bound to `provider` and `config.strict_bounds` which are fixed for the
+    /// lifetime of the mapper.
+    alignment_mapper_cache: Option<Mutex<SizedCache<AlignmentMapperKey, alignment::Mapper>>>,
+    /// Call statistics; `None` if not collected.
+    stats: Option<Arc<MapperStats>>,
 }
 
 /// Maps SequenceVariant objects between g., n., r., c., and p. representations.
@@ -105,18 +272,73 @@ impl Mapper {
             config: config.clone(),
             provider: provider.clone(),
             validator: validator.clone(),
+            alignment_mapper_cache: Self::new_alignment_mapper_cache(config.cache_size),
+            stats: None,
+        }
+    }
+
+    /// Return the mapper with an alignment mapper cache of the given size.
+    ///
+    /// The cache is emptied; a `cache_size` of `0` disables caching.
+    pub fn with_cache_size(self, cache_size: usize) -> Mapper {
+        Mapper {
+            config: Config {
+                cache_size,
+                ..self.config
+            },
+            alignment_mapper_cache: Self::new_alignment_mapper_cache(cache_size),
+            ..self
+        }
+    }
+
+    /// Return the mapper collecting call statistics into `stats`.
+    pub fn with_stats(self, stats: Arc<MapperStats>) -> Mapper {
+        Mapper {
+            stats: Some(stats),
+            ..self
         }
     }
 
+    fn new_alignment_mapper_cache(
+        cache_size: usize,
+    ) -> Option<Mutex<SizedCache<AlignmentMapperKey, alignment::Mapper>>> {
+        (cache_size > 0).then(|| Mutex::new(SizedCache::with_size(cache_size)))
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
 
+    /// Return the call statistics, if collected.
+    pub fn stats(&self) -> Option<&MapperStats> {
+        self.stats.as_deref()
+    }
+
+    /// Run `f` and count its outcome in the counters selected by `counters`.
+    fn record<T>(
+        &self,
+        counters: impl FnOnce(&MapperStats) -> (&AtomicU64, &AtomicU64),
+        f: impl FnOnce() -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let result = f();
+        if let Some(stats) = &self.stats {
+            let (ok, err) = counters(stats);
+            let counter = if result.is_ok() { ok } else { err };
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
     /// Return a copy of the internal provider.
     pub fn provider(&self) -> Arc<dyn Provider + Send + Sync> {
         self.provider.clone()
     }
 
+    /// Return a copy of the internal validator.
+    pub fn validator(&self) -> Arc<dyn Validator + Send + Sync> {
+        self.validator.clone()
+    }
+
     /// Obtain new `alignment::Mapper` for the given arguments, possibly caching results.
     fn build_alignment_mapper(
         &self,
@@ -124,23 +346,138 @@ impl Mapper {
         alt_ac: &str,
         alt_aln_method: &str,
     ) -> Result<alignment::Mapper, Error> {
-        // // TODO: implement caching
-        // alignment::Mapper::new(
-        //     &alignment::Config {
-        //         strict_bounds: self.config.strict_bounds,
-        //     },
-        //     self.provider.clone(),
-        //     tx_ac,
-        //     alt_ac,
-        //     alt_aln_method,
-        // )
-        build_alignment_mapper_cached(
+        let key = (
+            tx_ac.to_string(),
+            alt_ac.to_string(),
+            alt_aln_method.to_string(),
+        );
+        if let Some(cache) = &self.alignment_mapper_cache {
+            let mut cache = cache.lock().expect("alignment mapper cache poisoned");
+            if let Some(mapper) = cache.cache_get(&key) {
+                return Ok(mapper.clone());
+            }
+        }
+
+        // Build outside of the lock so concurrent lookups of other transcripts do not block
+        // on the provider.
+        let mapper = alignment::Mapper::new(
+            &alignment::Config {
+                strict_bounds: self.config.strict_bounds,
+            },
             self.provider.clone(),
-            self.config.strict_bounds,
             tx_ac,
             alt_ac,
             alt_aln_method,
-        )
+        )?;
+
+        if let Some(cache) = &self.alignment_mapper_cache {
+            cache
+                .lock()
+                .expect("alignment mapper cache poisoned")
+                .cache_set(key, mapper.clone());
+        }
+
+        Ok(mapper)
+    }
+
+    /// Return the accession of the transcript to use instead of `tx_ac`.
+    ///
+    /// This is `tx_ac` unless `config.fallback_to_similar` is set and the alignment of `tx_ac`
+    /// to `alt_ac` cannot be loaded while the one of a similar transcript can, see
+    /// `find_similar_transcript()`.
+    fn resolve_tx_ac(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<String, Error> {
+        if !self.config.fallback_to_similar
+            || !self
+                .provider
+                .capabilities()
+                .contains(ProviderCapabilities::HAS_SIMILAR_TX)
+        {
+            return Ok(tx_ac.to_string());
+        }
+        match self.build_alignment_mapper(tx_ac, alt_ac, alt_aln_method) {
+            Ok(_) => Ok(tx_ac.to_string()),
+            Err(e) => match self.find_similar_transcript(tx_ac)? {
+                Some(similar_tx_ac) => {
+                    self.build_alignment_mapper(&similar_tx_ac, alt_ac, alt_aln_method)?;
+                    warn!("using similar transcript {similar_tx_ac} instead of {tx_ac}: {e}");
+                    Ok(similar_tx_ac)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Return `var_t` with the accession replaced as by `resolve_tx_ac()`.
+    fn resolve_tx_variant(
+        &self,
+        var_t: &HgvsVariant,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        let tx_ac = self.resolve_tx_ac(var_t.accession(), alt_ac, alt_aln_method)?;
+        if tx_ac == var_t.accession().value {
+            return Ok(var_t.clone());
+        }
+        Ok(match var_t.clone() {
+            HgvsVariant::CdsVariant {
+                gene_symbol,
+                loc_edit,
+                ..
+            } => HgvsVariant::CdsVariant {
+                accession: Accession::new(&tx_ac),
+                gene_symbol,
+                loc_edit,
+            },
+            HgvsVariant::TxVariant {
+                gene_symbol,
+                loc_edit,
+                ..
+            } => HgvsVariant::TxVariant {
+                accession: Accession::new(&tx_ac),
+                gene_symbol,
+                loc_edit,
+            },
+            var_t => var_t,
+        })
+    }
+
+    /// Return the transcript most similar to `tx_ac`, if any.
+    ///
+    /// Only transcripts of the same gene are considered.  They are ranked by identical CDS
+    /// sequence, identical CDS exon structure, identical full exon structure, and identical
+    /// CDS exon lengths, in this order.  Ties are resolved in favour of the transcript listed
+    /// first by `Provider::get_similar_transcripts()`.
+    pub fn find_similar_transcript(&self, tx_ac: &str) -> Result<Option<String>, Error> {
+        self.provider
+            .capabilities()
+            .require(ProviderCapabilities::HAS_SIMILAR_TX)?;
+        Ok(self
+            .provider
+            .get_similar_transcripts(tx_ac)?
+            .into_iter()
+            .filter(|record| record.hgnc_eq)
+            .filter_map(|record| {
+                let other = if record.tx_ac1 == tx_ac {
+                    record.tx_ac2.clone()
+                } else {
+                    record.tx_ac1.clone()
+                };
+                let score = (
+                    record.cds_eq,
+                    record.cds_es_fp_eq,
+                    record.es_fp_eq,
+                    record.cds_exon_lengths_fp_eq,
+                );
+                (other != tx_ac).then_some((score, other))
+            })
+            .rev()
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, other)| other))
     }
 
     /// Construct a new normalizer for the variant mapper.
@@ -156,26 +493,279 @@ impl Mapper {
         ))
     }
 
-    /// Convert from genome (g.) variant to transcript variant (g. or n.).
+    /// Validate the variant with the configured validator and normalize it.
+    ///
+    /// Returns the normalized variant or the first error encountered.
+    pub fn validate_and_normalize(&self, var: &HgvsVariant) -> Result<HgvsVariant, Error> {
+        self.validator.validate(var)?;
+        Ok(self.normalizer()?.normalize(var)?)
+    }
+
+    /// Validate and normalize the genome (g.) variant, then project it to a CDS variant (c.).
+    ///
+    /// See `validate_and_normalize()` and `g_to_c()`.
+    pub fn validate_normalize_and_map_g_to_c(
+        &self,
+        var_g: &HgvsVariant,
+        tx_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        let var_g = self.validate_and_normalize(var_g)?;
+        self.g_to_c_inner(&var_g, tx_ac, alt_aln_method)
+    }
+
+    /// Return whether the CDS variant overlaps the stop codon of its transcript.
+    ///
+    /// The position of the stop codon is derived from the CDS start and end of the transcript
+    /// as given by the provider.
+    pub fn affects_stop_codon(&self, var_c: &HgvsVariant) -> Result<bool, Error> {
+        let HgvsVariant::CdsVariant { accession, .. } = var_c else {
+            return Err(Error::ExpectedCdsVariant(format!("{}", var_c)));
+        };
+        let id_info = self.provider.as_ref().get_tx_identity_info(accession)?;
+        Ok(var_c.affects_stop_codon(id_info.cds_end_i - id_info.cds_start_i))
+    }
+
+    /// Classify the consequence of a coding variant on the protein.
+    ///
+    /// Variants at the canonical splice sites (see `SPLICE_SITE_WINDOW`) are reported as
+    /// `ProteinConsequence::SpliceSite`, all others are projected with `c_to_p()` first.
+    pub fn coding_consequence(&self, var_c: &HgvsVariant) -> Result<ProteinConsequence, Error> {
+        if !matches!(var_c, HgvsVariant::CdsVariant { .. }) {
+            return Err(Error::ExpectedCdsVariant(format!("{}", var_c)));
+        }
+        if var_c.spans_intron() && var_c.is_splicing(SPLICE_SITE_WINDOW) {
+            return Ok(ProteinConsequence::SpliceSite);
+        }
+        Ok(self
+            .c_to_p_with_table(var_c, None, None)?
+            .protein_consequence())
+    }
+
+    /// Return the assembly of the sequence `var` is described on, see
+    /// `static_data::get_assembly_for_accession()`.
+    ///
+    /// Returns `None` for variants on transcripts and proteins.
+    pub fn assembly_for_variant(&self, var: &HgvsVariant) -> Option<Assembly> {
+        get_assembly_for_accession(var.accession())
+    }
+
+    /// Convert from genome (g.) variant to transcript variant (c., n., or r.).
+    ///
+    /// Coding transcripts yield c. variants, RNA transcripts (`NR_` accessions) yield r.
+    /// variants, and all other non-coding transcripts yield n. variants.
+    ///
+    /// If `tx_ac` is `AUTO_TX_AC`, the variant is projected to all overlapping transcripts
+    /// with `g_to_all_transcripts()`.  The MANE Select transcript is returned if
+    /// `config.prefer_mane_select` is set and it is among them, otherwise the first
+    /// successful projection.
     ///
     /// # Args
     ///
     /// * `var_g` -- `HgvsVariant::GenomeVariant` to project
-    /// * `tx_ac` -- accession of transcript to project to
+    /// * `tx_ac` -- accession of transcript to project to, or `AUTO_TX_AC`
     /// * `alt_al_method` -- alignment method, e.g., `splign`
     pub fn g_to_t(
         &self,
         var_g: &HgvsVariant,
         tx_ac: &str,
         alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        if tx_ac == AUTO_TX_AC {
+            return self.g_to_auto_transcript(var_g, alt_aln_method);
+        }
+        self.record(
+            |stats| (&stats.g_to_t_ok, &stats.g_to_t_err),
+            || self.g_to_t_inner(var_g, tx_ac, alt_aln_method),
+        )
+    }
+
+    /// Implementation of `g_to_t()` that does not record statistics.
+    fn g_to_t_inner(
+        &self,
+        var_g: &HgvsVariant,
+        tx_ac: &str,
+        alt_aln_method: &str,
     ) -> Result<HgvsVariant, Error> {
         self.validator.validate(var_g)?;
+        let tx_ac = &self.resolve_tx_ac(tx_ac, var_g.accession(), alt_aln_method)?;
         let mapper = self.build_alignment_mapper(tx_ac, var_g.accession(), alt_aln_method)?;
         if mapper.is_coding_transcript() {
-            self.g_to_c(var_g, tx_ac, alt_aln_method)
+            self.g_to_c_inner(var_g, tx_ac, alt_aln_method)
+        } else if tx_ac.starts_with("NR_") {
+            self.g_to_r_inner(var_g, tx_ac, alt_aln_method)
         } else {
-            self.g_to_n(var_g, tx_ac, alt_aln_method)
+            self.g_to_n_inner(var_g, tx_ac, alt_aln_method)
+        }
+    }
+
+    /// Project `var_g` to the transcript selected for `AUTO_TX_AC`, see `g_to_t()`.
+    fn g_to_auto_transcript(
+        &self,
+        var_g: &HgvsVariant,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        let mut results = self.g_to_all_transcripts(var_g, alt_aln_method)?;
+
+        let mut selected = None;
+        if self.config.prefer_mane_select {
+            for (idx, result) in results.iter().enumerate() {
+                if let Ok(var_t) = result {
+                    if self.is_mane_select(&var_t.accession().value)? {
+                        selected = Some(idx);
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Fall back to the first successful projection or the first error.
+        match selected.or_else(|| results.iter().position(Result::is_ok)) {
+            Some(idx) => results.swap_remove(idx),
+            None if !results.is_empty() => results.swap_remove(0),
+            None => Err(Error::NoOverlappingTranscripts(format!("{var_g}"))),
+        }
+    }
+
+    /// Return whether `tx_ac` is the MANE Select transcript of its gene.
+    fn is_mane_select(&self, tx_ac: &str) -> Result<bool, Error> {
+        let hgnc = self.provider.get_tx_identity_info(tx_ac)?.hgnc;
+        Ok(self.provider.get_mane_select_for_gene(&hgnc)?.as_deref() == Some(tx_ac))
+    }
+
+    /// Convert from genome (g.) variant to all transcripts overlapping its position.
+    ///
+    /// The overlapping transcripts are obtained with `Provider::get_tx_for_region()` and each
+    /// projection is done with `g_to_t()`.  The result contains one entry per transcript so
+    /// that a failure for one transcript does not prevent the others from being mapped.
+    ///
+    /// # Args
+    ///
+    /// * `var_g` -- `HgvsVariant::GenomeVariant` to project
+    /// * `alt_al_method` -- alignment method, e.g., `splign`
+    pub fn g_to_all_transcripts(
+        &self,
+        var_g: &HgvsVariant,
+        alt_aln_method: &str,
+    ) -> Result<Vec<Result<HgvsVariant, Error>>, Error> {
+        Ok(self
+            .overlapping_tx_acs(var_g, alt_aln_method)?
+            .into_iter()
+            .map(|tx_ac| self.g_to_t_inner(var_g, &tx_ac, alt_aln_method))
+            .collect())
+    }
+
+    /// Convert from genome (g.) variant to all overlapping transcripts accepted by `predicate`.
+    ///
+    /// In contrast to `g_to_all_transcripts()`, the first failing projection is returned as
+    /// an error.
+    ///
+    /// # Args
+    ///
+    /// * `var_g` -- `HgvsVariant::GenomeVariant` to project
+    /// * `alt_al_method` -- alignment method, e.g., `splign`
+    /// * `predicate` -- called with the transcript accession, e.g., `|ac| ac.starts_with("NM_")`
+    pub fn g_to_all_transcripts_filtered(
+        &self,
+        var_g: &HgvsVariant,
+        alt_aln_method: &str,
+        predicate: impl Fn(&str) -> bool,
+    ) -> Result<Vec<HgvsVariant>, Error> {
+        self.overlapping_tx_acs(var_g, alt_aln_method)?
+            .into_iter()
+            .filter(|tx_ac| predicate(tx_ac))
+            .map(|tx_ac| self.g_to_t_inner(var_g, &tx_ac, alt_aln_method))
+            .collect()
+    }
+
+    /// Return accessions of the transcripts overlapping the location of `var_g`, without
+    /// duplicates and in the order of the provider.
+    fn overlapping_tx_acs(
+        &self,
+        var_g: &HgvsVariant,
+        alt_aln_method: &str,
+    ) -> Result<Vec<String>, Error> {
+        let range = match var_g {
+            HgvsVariant::GenomeVariant { .. } => var_g.loc_range(),
+            _ => None,
         }
+        .ok_or_else(|| Error::ExpectedGenomeVariant(format!("{}", &var_g)))?;
+        let tx_acs = self
+            .provider
+            .get_tx_for_region(
+                &var_g.accession().value,
+                alt_aln_method,
+                range.start,
+                range.end,
+            )?
+            .into_iter()
+            .filter(|record| record.alt_aln_method == alt_aln_method)
+            .map(|record| record.tx_ac)
+            .collect::<IndexSet<_>>();
+        Ok(tx_acs.into_iter().collect())
+    }
+
+    /// Convert from genome (g.) variant to RNA variant (r.).
+    ///
+    /// This is the same as `g_to_n()` but the result uses the RNA alphabet.
+    ///
+    /// # Args
+    ///
+    /// * `var_g` -- `HgvsVariant::GenomeVariant` to project
+    /// * `tx_ac` -- accession of transcript to project to
+    /// * `alt_al_method` -- alignment method, e.g., `splign`
+    pub fn g_to_r(
+        &self,
+        var_g: &HgvsVariant,
+        tx_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.g_to_r_ok, &stats.g_to_r_err),
+            || self.g_to_r_inner(var_g, tx_ac, alt_aln_method),
+        )
+    }
+
+    /// Implementation of `g_to_r()` that does not record statistics.
+    fn g_to_r_inner(
+        &self,
+        var_g: &HgvsVariant,
+        tx_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        let var_n = self.g_to_n_inner(var_g, tx_ac, alt_aln_method)?;
+        n_to_r(var_n)
+    }
+
+    /// Convert from RNA variant (r.) to genome variant (g.).
+    ///
+    /// # Args
+    ///
+    /// * `var_r` -- `HgvsVariant::RnaVariant` to project
+    /// * `alt_ac` -- alternative contig accession
+    /// * `alt_al_method` -- alignment method, e.g., `splign`
+    pub fn r_to_g(
+        &self,
+        var_r: &HgvsVariant,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.r_to_g_ok, &stats.r_to_g_err),
+            || self.r_to_g_inner(var_r, alt_ac, alt_aln_method),
+        )
+    }
+
+    /// Implementation of `r_to_g()` that does not record statistics.
+    fn r_to_g_inner(
+        &self,
+        var_r: &HgvsVariant,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        self.validator.validate(var_r)?;
+        let var_n = r_to_n(var_r.clone())?;
+        self.n_to_g_inner(&var_n, alt_ac, alt_aln_method)
     }
 
     /// Convert from genome (g.) variant to transcript variant (n.).
@@ -190,6 +780,19 @@ impl Mapper {
         var_g: &HgvsVariant,
         tx_ac: &str,
         alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.g_to_n_ok, &stats.g_to_n_err),
+            || self.g_to_n_inner(var_g, tx_ac, alt_aln_method),
+        )
+    }
+
+    /// Implementation of `g_to_n()` that does not record statistics.
+    fn g_to_n_inner(
+        &self,
+        var_g: &HgvsVariant,
+        tx_ac: &str,
+        alt_aln_method: &str,
     ) -> Result<HgvsVariant, Error> {
         self.validator.validate(var_g)?;
         let var_g = if self.config.replace_reference {
@@ -203,6 +806,7 @@ impl Mapper {
             gene_symbol,
         } = &var_g
         {
+            let tx_ac = &self.resolve_tx_ac(tx_ac, &accession.value, alt_aln_method)?;
             let mapper = self.build_alignment_mapper(tx_ac, &accession.value, alt_aln_method)?;
 
             let var_g = if mapper.strand == -1
@@ -309,6 +913,20 @@ impl Mapper {
         alt_ac: &str,
         alt_aln_method: &str,
     ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.n_to_g_ok, &stats.n_to_g_err),
+            || self.n_to_g_inner(var_n, alt_ac, alt_aln_method),
+        )
+    }
+
+    /// Implementation of `n_to_g()` that does not record statistics.
+    fn n_to_g_inner(
+        &self,
+        var_n: &HgvsVariant,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        let var_n = &self.resolve_tx_variant(var_n, alt_ac, alt_aln_method)?;
         self.validator.validate(var_n)?;
         let var_n = self.replace_reference(var_n.clone())?;
         if let HgvsVariant::TxVariant {
@@ -397,6 +1015,19 @@ impl Mapper {
         var_g: &HgvsVariant,
         tx_ac: &str,
         alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.g_to_c_ok, &stats.g_to_c_err),
+            || self.g_to_c_inner(var_g, tx_ac, alt_aln_method),
+        )
+    }
+
+    /// Implementation of `g_to_c()` that does not record statistics.
+    fn g_to_c_inner(
+        &self,
+        var_g: &HgvsVariant,
+        tx_ac: &str,
+        alt_aln_method: &str,
     ) -> Result<HgvsVariant, Error> {
         self.validator.validate(var_g)?;
         let var_g = if self.config.replace_reference {
@@ -410,6 +1041,7 @@ impl Mapper {
             loc_edit,
         } = &var_g
         {
+            let tx_ac = &self.resolve_tx_ac(tx_ac, &accession.value, alt_aln_method)?;
             let mapper = self.build_alignment_mapper(tx_ac, &accession.value, alt_aln_method)?;
             let pos_c = mapper.g_to_c(loc_edit.loc.inner())?;
 
@@ -489,6 +1121,20 @@ impl Mapper {
         alt_ac: &str,
         alt_aln_method: &str,
     ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.c_to_g_ok, &stats.c_to_g_err),
+            || self.c_to_g_inner(var_c, alt_ac, alt_aln_method),
+        )
+    }
+
+    /// Implementation of `c_to_g()` that does not record statistics.
+    fn c_to_g_inner(
+        &self,
+        var_c: &HgvsVariant,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        let var_c = &self.resolve_tx_variant(var_c, alt_ac, alt_aln_method)?;
         self.validator.validate(var_c)?;
         let var_c = if self.config.replace_reference {
             self.replace_reference(var_c.clone())?
@@ -577,11 +1223,12 @@ impl Mapper {
         }
     }
 
-    /// Convert from transcript (c. or n.) to genome (g.) variant.
+    /// Convert from transcript (c., n., or r.) to genome (g.) variant.
     ///
     /// # Args
     ///
-    /// * `var_t` -- `HgvsVariant::TxVariant` or `HgvsVariant::CdsVariant` to project
+    /// * `var_t` -- `HgvsVariant::TxVariant`, `HgvsVariant::CdsVariant`, or
+    ///   `HgvsVariant::RnaVariant` to project
     /// * `alt_ac` -- accession of alternativ esequence
     /// * `alt_al_method` -- alignment method, e.g., `"splign"`
     pub fn t_to_g(
@@ -589,6 +1236,19 @@ impl Mapper {
         var_t: &HgvsVariant,
         alt_ac: &str,
         alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.t_to_g_ok, &stats.t_to_g_err),
+            || self.t_to_g_inner(var_t, alt_ac, alt_aln_method),
+        )
+    }
+
+    /// Implementation of `t_to_g()` that does not record statistics.
+    fn t_to_g_inner(
+        &self,
+        var_t: &HgvsVariant,
+        alt_ac: &str,
+        alt_aln_method: &str,
     ) -> Result<HgvsVariant, Error> {
         self.validator.validate(var_t)?;
         let var_t = if self.config.replace_reference {
@@ -597,8 +1257,9 @@ impl Mapper {
             var_t.clone()
         };
         match var_t {
-            HgvsVariant::TxVariant { .. } => self.n_to_g(&var_t, alt_ac, alt_aln_method),
-            HgvsVariant::CdsVariant { .. } => self.c_to_g(&var_t, alt_ac, alt_aln_method),
+            HgvsVariant::TxVariant { .. } => self.n_to_g_inner(&var_t, alt_ac, alt_aln_method),
+            HgvsVariant::CdsVariant { .. } => self.c_to_g_inner(&var_t, alt_ac, alt_aln_method),
+            HgvsVariant::RnaVariant { .. } => self.r_to_g_inner(&var_t, alt_ac, alt_aln_method),
             _ => Err(Error::ExpectedCdsVariant(format!("{}", &var_t))),
         }
     }
@@ -609,6 +1270,14 @@ impl Mapper {
     ///
     /// * `var_c` -- `HgvsVariant::CdsVariant` to project
     pub fn c_to_n(&self, var_c: &HgvsVariant) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.c_to_n_ok, &stats.c_to_n_err),
+            || self.c_to_n_inner(var_c),
+        )
+    }
+
+    /// Implementation of `c_to_n()` that does not record statistics.
+    fn c_to_n_inner(&self, var_c: &HgvsVariant) -> Result<HgvsVariant, Error> {
         log::debug!("c_to_n({})", var_c);
         self.validator.validate(var_c)?;
         let var_c = if self.config.replace_reference {
@@ -654,6 +1323,14 @@ impl Mapper {
     ///
     /// * `var_n` -- `HgvsVariant::TxVariant` to project
     pub fn n_to_c(&self, var_n: &HgvsVariant) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.n_to_c_ok, &stats.n_to_c_err),
+            || self.n_to_c_inner(var_n),
+        )
+    }
+
+    /// Implementation of `n_to_c()` that does not record statistics.
+    fn n_to_c_inner(&self, var_n: &HgvsVariant) -> Result<HgvsVariant, Error> {
         self.validator.validate(var_n)?;
         let var_n = if self.config.replace_reference {
             self.replace_reference(var_n.clone())?
@@ -691,6 +1368,45 @@ impl Mapper {
         }
     }
 
+    /// Convert from CDS variant (c.) to RNA variant (r.).
+    ///
+    /// This is the same as `c_to_n()` but the result uses the RNA alphabet.
+    ///
+    /// # Args
+    ///
+    /// * `var_c` -- `HgvsVariant::CdsVariant` to project
+    pub fn c_to_r(&self, var_c: &HgvsVariant) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.c_to_r_ok, &stats.c_to_r_err),
+            || self.c_to_r_inner(var_c),
+        )
+    }
+
+    /// Implementation of `c_to_r()` that does not record statistics.
+    fn c_to_r_inner(&self, var_c: &HgvsVariant) -> Result<HgvsVariant, Error> {
+        let var_n = self.c_to_n_inner(var_c)?;
+        n_to_r(var_n)
+    }
+
+    /// Convert from RNA variant (r.) to CDS variant (c.).
+    ///
+    /// # Args
+    ///
+    /// * `var_r` -- `HgvsVariant::RnaVariant` to project
+    pub fn r_to_c(&self, var_r: &HgvsVariant) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.r_to_c_ok, &stats.r_to_c_err),
+            || self.r_to_c_inner(var_r),
+        )
+    }
+
+    /// Implementation of `r_to_c()` that does not record statistics.
+    fn r_to_c_inner(&self, var_r: &HgvsVariant) -> Result<HgvsVariant, Error> {
+        self.validator.validate(var_r)?;
+        let var_n = r_to_n(var_r.clone())?;
+        self.n_to_c_inner(&var_n)
+    }
+
     /// Convert from CDS variant (c.) to protein variant (p.).
     ///
     /// # Args
@@ -698,6 +1414,20 @@ impl Mapper {
     /// * `var_c` -- `HgvsVariant::TxVariant` to project
     /// * `pro_ac` -- Protein accession
     pub fn c_to_p(&self, var_c: &HgvsVariant, prot_ac: Option<&str>) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.c_to_p_ok, &stats.c_to_p_err),
+            || self.c_to_p_with_table(var_c, prot_ac, None),
+        )
+    }
+
+    /// Implementation of `c_to_p()` that does not record statistics, translating with
+    /// `translation_table` if given instead of the translation table of the transcript.
+    fn c_to_p_with_table(
+        &self,
+        var_c: &HgvsVariant,
+        prot_ac: Option<&str>,
+        translation_table: Option<TranslationTable>,
+    ) -> Result<HgvsVariant, Error> {
         use super::altseq::*;
 
         if let HgvsVariant::CdsVariant {
@@ -707,6 +1437,9 @@ impl Mapper {
         } = &var_c
         {
             self.validator.validate(var_c)?;
+            self.provider
+                .capabilities()
+                .require(ProviderCapabilities::HAS_TX_SEQ)?;
 
             let var_c = if self.config.replace_reference {
                 self.replace_reference(var_c.clone())?
@@ -719,6 +1452,10 @@ impl Mapper {
                 accession.deref(),
                 prot_ac.map(|s| s.to_string()).as_deref(),
             )?;
+            let reference_data = match translation_table {
+                Some(table) => reference_data.with_translation_table(table)?,
+                None => reference_data,
+            };
             let builder = AltSeqBuilder::new(var_c, reference_data.clone());
 
             // NB: the following comment is from the original code.
@@ -760,12 +1497,514 @@ impl Mapper {
         }
     }
 
+    /// Convert from CDS variant (c.) to protein variant (p.) on the protein accession that
+    /// the provider records for the transcript, see `c_to_p()`.
+    pub fn c_to_p_auto(&self, var_c: &HgvsVariant) -> Result<HgvsVariant, Error> {
+        let HgvsVariant::CdsVariant { accession, .. } = var_c else {
+            return Err(Error::ExpectedCdsVariant(format!("{}", var_c)));
+        };
+        let prot_ac = self.provider.get_pro_ac_for_tx_ac(&accession.value)?;
+        self.c_to_p_with_table(var_c, prot_ac.as_deref(), None)
+    }
+
+    /// Convert from CDS variant (c.) to protein variant (p.) after checking that `prot_ac`
+    /// is the protein accession that the provider records for the transcript, see `c_to_p()`.
+    pub fn c_to_p_verified(
+        &self,
+        var_c: &HgvsVariant,
+        prot_ac: &str,
+    ) -> Result<HgvsVariant, Error> {
+        let HgvsVariant::CdsVariant { accession, .. } = var_c else {
+            return Err(Error::ExpectedCdsVariant(format!("{}", var_c)));
+        };
+        match self.provider.get_pro_ac_for_tx_ac(&accession.value)? {
+            Some(expected) if expected == prot_ac => {
+                self.c_to_p_with_table(var_c, Some(prot_ac), None)
+            }
+            Some(expected) => Err(Error::ProteinAccessionMismatch {
+                expected,
+                found: prot_ac.to_string(),
+            }),
+            None => Err(Error::NoProteinAccession(accession.value.clone())),
+        }
+    }
+
+    /// Convert from protein variant (p.) to CDS variant (c.).
+    ///
+    /// Only substitutions and deletions can be projected back.  The resulting variant spans
+    /// all affected codons.  For substitutions, the alternative codon is the one with the
+    /// fewest differences to the reference codon.
+    ///
+    /// # Args
+    ///
+    /// * `var_p` -- `HgvsVariant::ProtVariant` to project
+    /// * `tx_ac` -- accession of the transcript encoding the protein
+    pub fn p_to_c(&self, var_p: &HgvsVariant, tx_ac: &str) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.p_to_c_ok, &stats.p_to_c_err),
+            || self.p_to_c_inner(var_p, tx_ac),
+        )
+    }
+
+    /// Implementation of `p_to_c()` that does not record statistics.
+    fn p_to_c_inner(&self, var_p: &HgvsVariant, tx_ac: &str) -> Result<HgvsVariant, Error> {
+        let (loc, edit) = if let HgvsVariant::ProtVariant {
+            loc_edit: ProtLocEdit::Ordinary { loc, edit },
+            ..
+        } = var_p
+        {
+            (loc, edit)
+        } else {
+            return Err(Error::NotProtVariant);
+        };
+        if let ProteinEdit::Fs { .. } = edit.inner() {
+            return Err(Error::CannotBackProjectFrameshift(format!("{}", var_p)));
+        }
+
+        let interval = loc.inner();
+        if interval.start.number < 1 || interval.end.number < interval.start.number {
+            return Err(Error::CannotBackProjectProteinEdit(format!("{}", var_p)));
+        }
+        let base_start = (interval.start.number - 1) * 3 + 1;
+        let base_end = interval.end.number * 3;
+
+        let id_info = self.provider.as_ref().get_tx_identity_info(tx_ac)?;
+        let begin = id_info.cds_start_i + base_start - 1;
+        let end = id_info.cds_start_i + base_end;
+        if end > id_info.cds_end_i {
+            return Err(Error::CoordinateOutOfBounds(format!(
+                "{base_start}_{base_end}"
+            )));
+        }
+        self.provider
+            .capabilities()
+            .require(ProviderCapabilities::HAS_TX_SEQ)?;
+        let reference =
+            self.provider
+                .as_ref()
+                .get_seq_part(tx_ac, Some(begin as usize), Some(end as usize))?;
+
+        // Check the reference amino acids given in 1- or 3-letter notation.
+        let ref_aa = translate_cds(&reference, true, "*", id_info.translation_table)?;
+        for pos in [&interval.start, &interval.end] {
+            let aa = aa_to_aa1(&pos.aa)?;
+            let i = (pos.number - interval.start.number) as usize;
+            if ref_aa.get(i..(i + 1)) != Some(aa.as_str()) {
+                return Err(Error::ProteinReferenceMismatch(
+                    format!("{}", var_p),
+                    ref_aa.clone(),
+                ));
+            }
+        }
+
+        let na_edit = match edit.inner() {
+            ProteinEdit::Subst { alternative }
+                if interval.start == interval.end && !alternative.is_empty() =>
+            {
+                let alt_aa = aa_to_aa1(alternative)?;
+                NaEdit::RefAlt {
+                    alternative: closest_codon(&reference, &alt_aa, id_info.translation_table)?,
+                    reference,
+                }
+            }
+            ProteinEdit::Del => NaEdit::DelRef { reference },
+            _ => return Err(Error::CannotBackProjectProteinEdit(format!("{}", var_p))),
+        };
+
+        let var_c = HgvsVariant::CdsVariant {
+            accession: Accession::new(tx_ac),
+            gene_symbol: self.fetch_gene_symbol(tx_ac, var_p.gene_symbol())?,
+            loc_edit: CdsLocEdit {
+                loc: Mu::from(
+                    CdsInterval {
+                        start: CdsPos {
+                            base: base_start,
+                            offset: None,
+                            cds_from: CdsFrom::Start,
+                        },
+                        end: CdsPos {
+                            base: base_end,
+                            offset: None,
+                            cds_from: CdsFrom::Start,
+                        },
+                    },
+                    loc.is_certain(),
+                ),
+                edit: Mu::from(na_edit, edit.is_certain()),
+            },
+        };
+
+        debug!("p_to_c({}) = {}", var_p, &var_c);
+        Ok(var_c)
+    }
+
+    /// Convert from genome variant (g.) to protein variant (p.), see `g_to_c()` and
+    /// `c_to_p()`.
+    ///
+    /// # Args
+    ///
+    /// * `var_g` -- `HgvsVariant::GenomeVariant` to project
+    /// * `tx_ac` -- accession of the coding transcript to project over
+    /// * `alt_aln_method` -- alignment method, e.g., `splign`
+    /// * `prot_ac` -- protein accession, looked up for `tx_ac` if `None`
+    pub fn g_to_p(
+        &self,
+        var_g: &HgvsVariant,
+        tx_ac: &str,
+        alt_aln_method: &str,
+        prot_ac: Option<&str>,
+    ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.g_to_p_ok, &stats.g_to_p_err),
+            || self.g_to_p_inner(var_g, tx_ac, alt_aln_method, prot_ac),
+        )
+    }
+
+    /// Implementation of `g_to_p()` that does not record statistics.
+    fn g_to_p_inner(
+        &self,
+        var_g: &HgvsVariant,
+        tx_ac: &str,
+        alt_aln_method: &str,
+        prot_ac: Option<&str>,
+    ) -> Result<HgvsVariant, Error> {
+        let var_c = self.g_to_c_inner(var_g, tx_ac, alt_aln_method)?;
+        self.c_to_p_with_table(&var_c, prot_ac, None)
+    }
+
+    /// Convert from mitochondrial variant (m.) to protein variant (p.).
+    ///
+    /// The variant is projected to the coding transcript overlapping it, see `g_to_c()`,
+    /// and translated with the vertebrate mitochondrial code regardless of the translation
+    /// table the provider reports for the transcript.
+    ///
+    /// # Args
+    ///
+    /// * `var_m` -- `HgvsVariant::MtVariant` to project
+    /// * `alt_aln_method` -- alignment method, e.g., `splign`
+    /// * `prot_ac` -- protein accession; if given, the transcript encoding it is used,
+    ///   otherwise the first overlapping coding transcript
+    pub fn m_to_p(
+        &self,
+        var_m: &HgvsVariant,
+        alt_aln_method: &str,
+        prot_ac: Option<&str>,
+    ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.m_to_p_ok, &stats.m_to_p_err),
+            || self.m_to_p_inner(var_m, alt_aln_method, prot_ac),
+        )
+    }
+
+    /// Implementation of `m_to_p()` that does not record statistics.
+    fn m_to_p_inner(
+        &self,
+        var_m: &HgvsVariant,
+        alt_aln_method: &str,
+        prot_ac: Option<&str>,
+    ) -> Result<HgvsVariant, Error> {
+        let HgvsVariant::MtVariant {
+            accession,
+            gene_symbol,
+            loc_edit,
+        } = var_m
+        else {
+            return Err(Error::NotMtVariant(format!("{}", var_m)));
+        };
+        let loc = loc_edit.loc.inner();
+        let var_g = HgvsVariant::GenomeVariant {
+            accession: accession.clone(),
+            gene_symbol: gene_symbol.clone(),
+            loc_edit: GenomeLocEdit {
+                loc: Mu::from(
+                    GenomeInterval {
+                        start: loc.start,
+                        end: loc.end,
+                    },
+                    loc_edit.loc.is_certain(),
+                ),
+                edit: loc_edit.edit.clone(),
+            },
+        };
+
+        let mut tx_ac = None;
+        for candidate in self.overlapping_tx_acs(&var_g, alt_aln_method)? {
+            let candidate_pro_ac = self.provider.get_pro_ac_for_tx_ac(&candidate)?;
+            let matches = match (prot_ac, candidate_pro_ac) {
+                (Some(prot_ac), Some(candidate_pro_ac)) => prot_ac == candidate_pro_ac,
+                (None, Some(_)) => true,
+                (_, None) => false,
+            };
+            if matches {
+                tx_ac = Some(candidate);
+                break;
+            }
+        }
+        let tx_ac = tx_ac.ok_or_else(|| Error::NoOverlappingTranscripts(format!("{}", var_m)))?;
+
+        let var_c = self.g_to_c_inner(&var_g, &tx_ac, alt_aln_method)?;
+        self.c_to_p_with_table(
+            &var_c,
+            prot_ac,
+            Some(TranslationTable::VertebrateMitochondrial),
+        )
+    }
+
+    /// Convert from protein variant (p.) to genome variant (g.), see `p_to_c()` and
+    /// `c_to_g()`.
+    ///
+    /// # Args
+    ///
+    /// * `var_p` -- `HgvsVariant::ProtVariant` to project
+    /// * `tx_ac` -- accession of the transcript encoding the protein
+    /// * `alt_ac` -- accession of the genome sequence to project to
+    /// * `alt_aln_method` -- alignment method, e.g., `splign`
+    pub fn p_to_g(
+        &self,
+        var_p: &HgvsVariant,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        self.record(
+            |stats| (&stats.p_to_g_ok, &stats.p_to_g_err),
+            || self.p_to_g_inner(var_p, tx_ac, alt_ac, alt_aln_method),
+        )
+    }
+
+    /// Implementation of `p_to_g()` that does not record statistics.
+    fn p_to_g_inner(
+        &self,
+        var_p: &HgvsVariant,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        let var_c = self.p_to_c_inner(var_p, tx_ac)?;
+        self.c_to_g_inner(&var_c, alt_ac, alt_aln_method)
+    }
+
+    /// Project a transcript variant (c., n., or r.) to another transcript, e.g., a newer
+    /// version of the same transcript.
+    ///
+    /// The variant is projected to the genome with `t_to_g()` and from there to `new_tx_ac`
+    /// with `g_to_t()`, so differences in UTR length and CDS start between the transcripts
+    /// are accounted for.
+    ///
+    /// # Args
+    ///
+    /// * `var` -- transcript variant to project
+    /// * `new_tx_ac` -- accession of the transcript to project to
+    /// * `alt_ac` -- accession of the genome sequence both transcripts are aligned to
+    /// * `alt_aln_method` -- alignment method, e.g., `splign`
+    pub fn remap_accession(
+        &self,
+        var: &HgvsVariant,
+        new_tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Result<HgvsVariant, Error> {
+        let var_g = self.t_to_g_inner(var, alt_ac, alt_aln_method)?;
+        let remap_failed =
+            || Error::AccessionRemapFailed(format!("{}", var), new_tx_ac.to_string());
+
+        // The new transcript must be aligned to the genome sequence and cover the variant.
+        let exons = self
+            .provider
+            .as_ref()
+            .get_tx_exons(new_tx_ac, alt_ac, alt_aln_method)
+            .map_err(|_| remap_failed())?;
+        let tx_start_i = exons.iter().map(|exon| exon.alt_start_i).min();
+        let tx_end_i = exons.iter().map(|exon| exon.alt_end_i).max();
+        let (start, end) = match &var_g {
+            HgvsVariant::GenomeVariant { loc_edit, .. } => {
+                (loc_edit.loc.inner().start, loc_edit.loc.inner().end)
+            }
+            _ => return Err(Error::NotGenomeVariant(format!("{}", &var_g))),
+        };
+        match (tx_start_i, tx_end_i, start, end) {
+            (Some(tx_start_i), Some(tx_end_i), Some(start), Some(end))
+                if start - 1 < tx_end_i && end > tx_start_i => {}
+            _ => return Err(remap_failed()),
+        }
+
+        self.g_to_t_inner(&var_g, new_tx_ac, alt_aln_method)
+    }
+
+    /// Apply `f` to all `variants` in parallel.
+    ///
+    /// The results are returned in the order of `variants`.  Alleles are mapped variant by
+    /// variant.  Use the `*_batch()` functions for the common projections.
+    ///
+    /// # Args
+    ///
+    /// * `variants` -- variants to map
+    /// * `f` -- function to apply to each variant, e.g., `|m, v| m.c_to_n(v)`
+    pub fn map_batch<F>(&self, variants: &[HgvsVariant], f: F) -> Vec<Result<HgvsVariant, Error>>
+    where
+        F: Fn(&Mapper, &HgvsVariant) -> Result<HgvsVariant, Error> + Sync,
+    {
+        variants
+            .par_iter()
+            .map(|var| match var {
+                HgvsVariant::Allele { variants, .. } => self.map_allele(variants, &f),
+                _ => f(self, var),
+            })
+            .collect()
+    }
+
+    /// Map each variant of an allele with `f` and return the resulting allele.
+    fn map_allele<F>(&self, variants: &[HgvsVariant], f: &F) -> Result<HgvsVariant, Error>
+    where
+        F: Fn(&Mapper, &HgvsVariant) -> Result<HgvsVariant, Error>,
+    {
+        let variants = variants
+            .iter()
+            .map(|var| f(self, var))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (accession, gene_symbol) = match variants.first() {
+            Some(var) => (var.accession().clone(), var.gene_symbol().clone()),
+            None => return Err(Error::EmptyAllele),
+        };
+        Ok(HgvsVariant::Allele {
+            accession,
+            gene_symbol,
+            variants,
+        })
+    }
+
+    /// Convert genome variants (g.) to CDS variants (c.) in parallel, see `g_to_c()`.
+    pub fn g_to_c_batch(
+        &self,
+        variants: &[HgvsVariant],
+        tx_ac: &str,
+        alt_aln_method: &str,
+    ) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.g_to_c(v, tx_ac, alt_aln_method))
+    }
+
+    /// Convert genome variants (g.) to transcript variants (n.) in parallel, see `g_to_n()`.
+    pub fn g_to_n_batch(
+        &self,
+        variants: &[HgvsVariant],
+        tx_ac: &str,
+        alt_aln_method: &str,
+    ) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.g_to_n(v, tx_ac, alt_aln_method))
+    }
+
+    /// Convert genome variants (g.) to c. or n. variants in parallel, see `g_to_t()`.
+    pub fn g_to_t_batch(
+        &self,
+        variants: &[HgvsVariant],
+        tx_ac: &str,
+        alt_aln_method: &str,
+    ) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.g_to_t(v, tx_ac, alt_aln_method))
+    }
+
+    /// Convert CDS variants (c.) to genome variants (g.) in parallel, see `c_to_g()`.
+    pub fn c_to_g_batch(
+        &self,
+        variants: &[HgvsVariant],
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.c_to_g(v, alt_ac, alt_aln_method))
+    }
+
+    /// Convert transcript variants (n.) to genome variants (g.) in parallel, see `n_to_g()`.
+    pub fn n_to_g_batch(
+        &self,
+        variants: &[HgvsVariant],
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.n_to_g(v, alt_ac, alt_aln_method))
+    }
+
+    /// Convert genome variants (g.) to RNA variants (r.) in parallel, see `g_to_r()`.
+    pub fn g_to_r_batch(
+        &self,
+        variants: &[HgvsVariant],
+        tx_ac: &str,
+        alt_aln_method: &str,
+    ) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.g_to_r(v, tx_ac, alt_aln_method))
+    }
+
+    /// Convert RNA variants (r.) to genome variants (g.) in parallel, see `r_to_g()`.
+    pub fn r_to_g_batch(
+        &self,
+        variants: &[HgvsVariant],
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.r_to_g(v, alt_ac, alt_aln_method))
+    }
+
+    /// Convert c., n., or r. variants to genome variants (g.) in parallel, see `t_to_g()`.
+    pub fn t_to_g_batch(
+        &self,
+        variants: &[HgvsVariant],
+        alt_ac: &str,
+        alt_aln_method: &str,
+    ) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.t_to_g(v, alt_ac, alt_aln_method))
+    }
+
+    /// Convert CDS variants (c.) to transcript variants (n.) in parallel, see `c_to_n()`.
+    pub fn c_to_n_batch(&self, variants: &[HgvsVariant]) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.c_to_n(v))
+    }
+
+    /// Convert transcript variants (n.) to CDS variants (c.) in parallel, see `n_to_c()`.
+    pub fn n_to_c_batch(&self, variants: &[HgvsVariant]) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.n_to_c(v))
+    }
+
+    /// Convert CDS variants (c.) to protein variants (p.) in parallel, see `c_to_p()`.
+    pub fn c_to_p_batch(
+        &self,
+        variants: &[HgvsVariant],
+        prot_ac: Option<&str>,
+    ) -> Vec<Result<HgvsVariant, Error>> {
+        self.map_batch(variants, |m, v| m.c_to_p(v, prot_ac))
+    }
+
+    /// Return `variants` with the gene symbols added to transcript (c., n., r.) variants.
+    ///
+    /// The gene symbols are taken from the `hgnc` field of `Provider::get_tx_identity_info()`.
+    /// Each distinct accession is looked up only once and the lookups run in parallel.
+    /// Variants that already have a gene symbol and other types of variants are returned
+    /// unchanged.  Other than `Config::add_gene_symbol`, this is done regardless of the
+    /// configuration.
+    pub fn add_gene_symbols_batch(
+        &self,
+        variants: &[HgvsVariant],
+    ) -> Result<Vec<HgvsVariant>, Error> {
+        let mut variants = variants.to_vec();
+        add_gene_names_with(&mut variants, |tx_acs| {
+            tx_acs
+                .into_par_iter()
+                .map(|tx_ac| {
+                    let hgnc = self.provider.get_tx_identity_info(&tx_ac)?.hgnc;
+                    Ok((tx_ac, hgnc))
+                })
+                .collect::<Result<HashMap<_, _>, Error>>()
+        })?;
+        Ok(variants)
+    }
+
     fn get_altered_sequence(
         &self,
         strand: i16,
         interval: Range<i32>,
         var: &HgvsVariant,
     ) -> Result<String, Error> {
+        self.provider
+            .capabilities()
+            .require(required_seq_capability(var))?;
         let mut seq = self.provider.as_ref().get_seq_part(
             var.accession(),
             Some(
@@ -805,6 +2044,7 @@ impl Mapper {
                 let rc = revcomp(&seq[r.clone()]);
                 seq.replace_range(r, &rc);
             }
+            NaEdit::Repeat { .. } => return Err(Error::RepeatNotSupported(format!("{var}"))),
         }
 
         Ok(if strand == -1 { revcomp(&seq) } else { seq })
@@ -819,39 +2059,29 @@ impl Mapper {
         let result = if strand == 1 {
             edit.inner().clone()
         } else {
-            match edit.inner() {
-                NaEdit::RefAlt {
-                    reference,
-                    alternative,
-                } => NaEdit::RefAlt {
-                    reference: revcomp(reference),
-                    alternative: revcomp(alternative),
-                },
-                NaEdit::NumAlt { count, alternative } => NaEdit::NumAlt {
-                    count: *count,
-                    alternative: revcomp(alternative),
-                },
-                NaEdit::DelRef { reference } => NaEdit::DelRef {
-                    reference: revcomp(reference),
-                },
-                NaEdit::Ins { alternative } => NaEdit::Ins {
-                    alternative: revcomp(alternative),
-                },
-                NaEdit::Dup { reference } => NaEdit::Dup {
-                    reference: revcomp(reference),
-                },
-                NaEdit::InvRef { reference } => NaEdit::InvRef {
-                    reference: revcomp(reference),
-                },
-                NaEdit::DelNum { count } => NaEdit::DelNum { count: *count },
-                NaEdit::InvNum { count } => NaEdit::InvNum { count: *count },
-            }
+            edit.inner().reverse_complement()
         };
         Ok(Mu::from(result, edit.is_certain()))
     }
 
     /// Fetch reference sequence for variant and return updated `HgvsVariant` if necessary.
     pub fn replace_reference(&self, var: HgvsVariant) -> Result<HgvsVariant, Error> {
+        if let HgvsVariant::Allele {
+            accession,
+            gene_symbol,
+            variants,
+        } = var
+        {
+            return Ok(HgvsVariant::Allele {
+                accession,
+                gene_symbol,
+                variants: variants
+                    .into_iter()
+                    .map(|v| self.replace_reference(v))
+                    .collect::<Result<Vec<_>, _>>()?,
+            });
+        }
+
         match &var {
             HgvsVariant::ProtVariant { .. } => Err(Error::CannotUpdateReference),
             _ => Ok(()),
@@ -861,6 +2091,10 @@ impl Mapper {
             // Insertions have no reference sequence (zero-width); return as-is.
             return Ok(var);
         }
+        if let Some(NaEdit::Repeat { .. }) = var.na_edit() {
+            // Repeats give the repeat unit rather than the reference sequence; return as-is.
+            return Ok(var);
+        }
 
         if var.spans_intron() {
             debug!(
@@ -890,6 +2124,11 @@ impl Mapper {
                 loc_edit,
                 ..
             } => (loc_edit.loc.inner().clone().try_into()?, accession),
+            HgvsVariant::CircularVariant {
+                accession,
+                loc_edit,
+                ..
+            } => (loc_edit.loc.inner().clone().try_into()?, accession),
             HgvsVariant::TxVariant {
                 accession,
                 loc_edit,
@@ -909,6 +2148,9 @@ impl Mapper {
             // This is an out-of-bounds variant.
             return Ok(var);
         }
+        self.provider
+            .capabilities()
+            .require(required_seq_capability(&var))?;
         log::debug!("get_seq_part({}, {}, {})", ac, r.start, r.end);
         let seq = self.provider.as_ref().get_seq_part(
             ac,
@@ -951,82 +2193,444 @@ impl Mapper {
     }
 }
 
-/// A LRU cached version of `alignment::Mapper::new`.
-/// The indirection here is due to the fact that `cached` cannot deal with `self` arguments.
-/// The `convert` argument constructs the key to be used in the cache.
-/// All of this function's arguments contribute to the key;
-/// that is why the supplied provider's `data_version` and `schema_version`
-/// should return sensible values which allow distinguishing them from other providers.
-///
-/// Because the cached value must implement `Clone`
-/// and the type is `Result<alignment::Mapper, Error>`,
-/// `Error`, too, must implement `Clone`.
-/// Sadly, postgres Errors do not do that, so either:
-/// 1. wrap non-clonable errors in `Arc`
-/// 2. convert the result to an option instead
-/// 3. return a generic error instead
-#[cached(
-    ty = "SizedCache<String, Result<alignment::Mapper, Error>>",
-    create = "{ SizedCache::with_size(1000) }",
-    convert = r#"{ format!("{}{}{}{}{}{}",
-                       provider.data_version(),
-                       provider.schema_version(),
-                       strict_bounds,
-                       tx_ac,
-                       alt_ac,
-                       alt_aln_method) }"#
-)]
-fn build_alignment_mapper_cached(
-    provider: Arc<dyn Provider + Send + Sync>,
-    strict_bounds: bool,
-    tx_ac: &str,
-    alt_ac: &str,
-    alt_aln_method: &str,
-) -> Result<alignment::Mapper, Error> {
-    alignment::Mapper::new(
-        &alignment::Config { strict_bounds },
-        provider,
-        tx_ac,
-        alt_ac,
-        alt_aln_method,
-    )
+/// Return the provider capability needed for reading the reference sequence of `var`.
+pub(crate) fn required_seq_capability(var: &HgvsVariant) -> ProviderCapabilities {
+    match var {
+        HgvsVariant::GenomeVariant { .. }
+        | HgvsVariant::MtVariant { .. }
+        | HgvsVariant::CircularVariant { .. } => ProviderCapabilities::HAS_GENOME_SEQ,
+        HgvsVariant::ProtVariant { .. } => ProviderCapabilities::HAS_PROTEIN_SEQ,
+        _ => ProviderCapabilities::HAS_TX_SEQ,
+    }
 }
+
+/// Return `edit` with all contained sequences converted by `f`.
+fn map_na_edit_seqs(edit: &Mu<NaEdit>, f: impl Fn(&str) -> String) -> Mu<NaEdit> {
+    let result = match edit.inner() {
+        NaEdit::RefAlt {
+            reference,
+            alternative,
+        } => NaEdit::RefAlt {
+            reference: f(reference),
+            alternative: f(alternative),
+        },
+        NaEdit::NumAlt { count, alternative } => NaEdit::NumAlt {
+            count: *count,
+            alternative: f(alternative),
+        },
+        NaEdit::DelRef { reference } => NaEdit::DelRef {
+            reference: f(reference),
+        },
+        NaEdit::Ins { alternative } => NaEdit::Ins {
+            alternative: f(alternative),
+        },
+        NaEdit::Dup { reference } => NaEdit::Dup {
+            reference: f(reference),
+        },
+        NaEdit::InvRef { reference } => NaEdit::InvRef {
+            reference: f(reference),
+        },
+        NaEdit::Repeat { sequence, count } => NaEdit::Repeat {
+            sequence: f(sequence),
+            count: count.clone(),
+        },
+        NaEdit::DelNum { .. } | NaEdit::InvNum { .. } => edit.inner().clone(),
+    };
+    Mu::from(result, edit.is_certain())
+}
+
+/// Convert transcript variant (n.) to RNA variant (r.), switching to the RNA alphabet.
+fn n_to_r(var_n: HgvsVariant) -> Result<HgvsVariant, Error> {
+    if let HgvsVariant::TxVariant {
+        accession,
+        gene_symbol,
+        loc_edit,
+    } = var_n
+    {
+        let to_rna_pos = |pos: &TxPos| RnaPos {
+            base: pos.base,
+            offset: pos.offset,
+        };
+        let loc = loc_edit.loc.inner();
+        Ok(HgvsVariant::RnaVariant {
+            accession,
+            gene_symbol,
+            loc_edit: RnaLocEdit {
+                loc: Mu::from(
+                    RnaInterval {
+                        start: to_rna_pos(&loc.start),
+                        end: to_rna_pos(&loc.end),
+                    },
+                    loc_edit.loc.is_certain(),
+                ),
+                edit: map_na_edit_seqs(&loc_edit.edit, |seq| seq.to_lowercase().replace('t', "u")),
+            },
+        })
+    } else {
+        Err(Error::ExpectedTxVariant(format!("{}", &var_n)))
+    }
+}
+
+/// Convert RNA variant (r.) to transcript variant (n.), switching to the DNA alphabet.
+fn r_to_n(var_r: HgvsVariant) -> Result<HgvsVariant, Error> {
+    if let HgvsVariant::RnaVariant {
+        accession,
+        gene_symbol,
+        loc_edit,
+    } = var_r
+    {
+        let to_tx_pos = |pos: &RnaPos| TxPos {
+            base: pos.base,
+            offset: pos.offset,
+        };
+        let loc = loc_edit.loc.inner();
+        Ok(HgvsVariant::TxVariant {
+            accession,
+            gene_symbol,
+            loc_edit: TxLocEdit {
+                loc: Mu::from(
+                    TxInterval {
+                        start: to_tx_pos(&loc.start),
+                        end: to_tx_pos(&loc.end),
+                    },
+                    loc_edit.loc.is_certain(),
+                ),
+                edit: map_na_edit_seqs(&loc_edit.edit, |seq| seq.to_uppercase().replace('U', "T")),
+            },
+        })
+    } else {
+        Err(Error::ExpectedRnaVariant(format!("{}", &var_r)))
+    }
+}
+
+/// Return the codon encoding the 1-letter amino acid `aa` that differs least from `codon`.
+fn closest_codon(
+    codon: &str,
+    aa: &str,
+    translation_table: TranslationTable,
+) -> Result<String, Error> {
+    const BASES: [char; 4] = ['A', 'C', 'G', 'T'];
+
+    let mut best: Option<(usize, String)> = None;
+    for b1 in BASES {
+        for b2 in BASES {
+            for b3 in BASES {
+                let candidate = String::from_iter([b1, b2, b3]);
+                if translate_cds(&candidate, true, "*", translation_table)? != aa {
+                    continue;
+                }
+                let dist = candidate
+                    .chars()
+                    .zip(codon.to_uppercase().chars())
+                    .filter(|(a, b)| a != b)
+                    .count();
+                if best.as_ref().map(|(d, _)| dist < *d).unwrap_or(true) {
+                    best = Some((dist, candidate));
+                }
+            }
+        }
+    }
+
+    best.map(|(_, codon)| codon)
+        .ok_or_else(|| Error::NoCodonForAminoAcid(aa.to_string()))
+}
+
 #[cfg(test)]
 mod test {
     use std::{
         path::{Path, PathBuf},
         str::FromStr,
+        sync::{atomic::Ordering, Arc},
     };
 
     use anyhow::Error;
+    use biocommons_bioutils::assemblies::Assembly;
     use pretty_assertions::assert_eq;
     use regex::Regex;
     use test_log::test;
 
+    use cached::Cached;
+
     use crate::{
-        data::uta_sr::test_helpers::build_provider,
-        parser::{HgvsVariant, NoRef},
+        data::{
+            interface::TxSimilarityRecord, mane::ManeProvider, uta_sr::test_helpers::build_provider,
+        },
+        parser::{
+            HgvsVariant, NaEdit, NoRef, ProtLocEdit, ProteinConsequence, ProteinEdit,
+            UncertainLengthChange,
+        },
+        sequences::{aa1_to_aa3, translate_cds, TranslationTable},
+        validator::ValidationLevel,
     };
 
-    use super::{Config, Mapper};
+    use super::{Config, Mapper, MapperStats, AUTO_TX_AC};
+
+    #[test]
+    fn issue_131() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+
+        let var_c = HgvsVariant::from_str("NM_001253909.2:c.416_417insGTG")?;
+        let var_p_test = mapper.c_to_p(&var_c, None)?;
+
+        assert_eq!(format!("{}", &var_p_test), "NP_001240838.1:p.=");
+        insta::assert_yaml_snapshot!(&var_p_test);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync() {
+        fn is_sync<T: Sync>() {}
+        is_sync::<super::Mapper>();
+    }
+
+    #[test]
+    fn alignment_mapper_cache() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+        let var_c = HgvsVariant::from_str("NM_022464.4:c.3G>A")?;
+
+        let var_n = mapper.c_to_n(&var_c)?;
+        let cache = mapper
+            .alignment_mapper_cache
+            .as_ref()
+            .expect("cache enabled");
+        assert_eq!(cache.lock().expect("poisoned").cache_size(), 1);
+        assert_eq!(mapper.c_to_n(&var_c)?, var_n);
+        assert!(
+            cache
+                .lock()
+                .expect("poisoned")
+                .cache_hits()
+                .unwrap_or_default()
+                > 0
+        );
+
+        let mapper = mapper.with_cache_size(0);
+        assert!(mapper.alignment_mapper_cache.is_none());
+        assert_eq!(mapper.config().cache_size, 0);
+        assert_eq!(mapper.c_to_n(&var_c)?, var_n);
+
+        Ok(())
+    }
+
+    #[test]
+    fn g_to_r_and_r_to_g() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+        let var_n = HgvsVariant::from_str("NR_111984.1:n.44G>A")?;
+        let alt_ac = mapper
+            .provider
+            .get_tx_mapping_options("NR_111984.1")?
+            .into_iter()
+            .find(|opt| opt.alt_aln_method == "splign")
+            .map(|opt| opt.alt_ac)
+            .expect("no splign alignment");
+        let var_g = mapper.n_to_g(&var_n, &alt_ac, "splign")?;
+
+        let var_r = mapper.g_to_r(&var_g, "NR_111984.1", "splign")?;
+        assert_eq!(format!("{}", &var_r), "NR_111984.1:r.44g>a");
+        assert_eq!(mapper.g_to_t(&var_g, "NR_111984.1", "splign")?, var_r);
+
+        assert_eq!(mapper.r_to_g(&var_r, &alt_ac, "splign")?, var_g);
+        assert_eq!(mapper.t_to_g(&var_r, &alt_ac, "splign")?, var_g);
+        assert!(mapper.r_to_g(&var_n, &alt_ac, "splign").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn n_to_g_repeat() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+        let var_n = HgvsVariant::from_str("NR_111984.1:n.44_49CA[3]")?;
+        let alt_ac = mapper
+            .provider
+            .get_tx_mapping_options("NR_111984.1")?
+            .into_iter()
+            .find(|opt| opt.alt_aln_method == "splign")
+            .map(|opt| opt.alt_ac)
+            .expect("no splign alignment");
+
+        let var_g = mapper.n_to_g(&var_n, &alt_ac, "splign")?;
+        assert_eq!(var_g.loc_range().map(|r| r.len()), Some(6));
+        match var_g.na_edit() {
+            Some(NaEdit::Repeat { sequence, count }) => {
+                assert!(sequence == "CA" || sequence == "TG");
+                assert_eq!(count, &UncertainLengthChange::Known(3));
+            }
+            _ => panic!("expected repeat but got {var_g}"),
+        }
+        assert_eq!(mapper.g_to_n(&var_g, "NR_111984.1", "splign")?, var_n);
+
+        Ok(())
+    }
+
+    #[test]
+    fn c_to_r_and_r_to_c() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+        let var_c = HgvsVariant::from_str("NM_080877.2:c.1735A>T")?;
+        let var_n = mapper.c_to_n(&var_c)?;
+
+        let var_r = mapper.c_to_r(&var_c)?;
+        assert_eq!(
+            format!("{}", &var_r),
+            format!("{}", &var_n)
+                .replace(":n.", ":r.")
+                .replace("A>T", "a>u")
+        );
+        assert!(format!("{}", &var_r).ends_with("a>u"));
+        assert_eq!(mapper.r_to_c(&var_r)?, var_c);
+        assert_eq!(mapper.r_to_c(&mapper.c_to_r(&var_c)?)?, var_c);
+
+        assert!(mapper.c_to_r(&var_n).is_err());
+        assert!(mapper.r_to_c(&var_c).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn p_to_c_roundtrip() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+
+        for (hgvs_p, tx_ac) in [
+            ("NP_001628.1:p.Gly528Arg", "NM_001637.3"),
+            ("NP_543153.1:p.Lys579Ter", "NM_080877.2"),
+        ] {
+            let var_p = HgvsVariant::from_str(hgvs_p)?;
+            let var_c = mapper.p_to_c(&var_p, tx_ac)?;
+            let prot_ac = var_p.accession().value.clone();
+            let var_p2 = mapper.c_to_p(&var_c, Some(&prot_ac))?;
+            assert_eq!(format!("{}", &var_p2), hgvs_p);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn p_to_c_mixed_notation() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+
+        let var_c3 = mapper.p_to_c(
+            &HgvsVariant::from_str("NP_001628.1:p.Gly528Arg")?,
+            "NM_001637.3",
+        )?;
+        let var_c1 = mapper.p_to_c(
+            &HgvsVariant::from_str("NP_001628.1:p.G528R")?,
+            "NM_001637.3",
+        )?;
+        assert_eq!(var_c1, var_c3);
+        assert_eq!(var_c1.loc_range(), Some(1581..1584));
+
+        Ok(())
+    }
+
+    #[test]
+    fn p_to_c_fails() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+
+        let var_p = HgvsVariant::from_str("NP_001628.1:p.Gly528ArgfsTer5")?;
+        assert!(matches!(
+            mapper.p_to_c(&var_p, "NM_001637.3"),
+            Err(super::Error::CannotBackProjectFrameshift(_))
+        ));
+        // wrong reference amino acid
+        let var_p = HgvsVariant::from_str("NP_001628.1:p.Trp528Arg")?;
+        assert!(matches!(
+            mapper.p_to_c(&var_p, "NM_001637.3"),
+            Err(super::Error::ProteinReferenceMismatch(_, _))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn map_batch() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+        let variants = vec![
+            HgvsVariant::from_str("NM_003777.3:c.13552_*36del57")?,
+            HgvsVariant::from_str("NC_000007.13:g.36561662C>T")?,
+        ];
+
+        let results = mapper.c_to_g_batch(&variants, "NC_000007.13", "splign");
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            format!("{}", &NoRef(results[0].as_ref().expect("mapping failed"))),
+            "NC_000007.13:g.21940852_21940908del"
+        );
+        assert!(results[1].is_err());
+
+        Ok(())
+    }
 
     #[test]
-    fn issue_131() -> Result<(), Error> {
+    fn map_batch_allele() -> Result<(), Error> {
         let mapper = build_mapper()?;
+        let var_c1 = HgvsVariant::from_str("NM_022464.4:c.3G>A")?;
+        let var_c2 = HgvsVariant::from_str("NM_022464.4:c.5del")?;
+        let allele = HgvsVariant::from_str("NM_022464.4:c.[3G>A;5del]")?;
+
+        let results = mapper.c_to_n_batch(&[allele]);
+        assert_eq!(results.len(), 1);
+        let expected = HgvsVariant::Allele {
+            accession: var_c1.accession().clone(),
+            gene_symbol: None,
+            variants: vec![mapper.c_to_n(&var_c1)?, mapper.c_to_n(&var_c2)?],
+        };
+        assert_eq!(results[0].as_ref().expect("mapping failed"), &expected);
 
-        let var_c = HgvsVariant::from_str("NM_001253909.2:c.416_417insGTG")?;
-        let var_p_test = mapper.c_to_p(&var_c, None)?;
+        Ok(())
+    }
 
-        assert_eq!(format!("{}", &var_p_test), "NP_001240838.1:p.=");
-        insta::assert_yaml_snapshot!(&var_p_test);
+    #[test]
+    fn test_threads() -> Result<(), Error> {
+        let mapper = std::sync::Arc::new(build_mapper()?);
+        let var_c = HgvsVariant::from_str("NM_003777.3:c.13552_*36del57")?;
+
+        let handles = (0..2)
+            .map(|_| {
+                let mapper = mapper.clone();
+                let var_c = var_c.clone();
+                std::thread::spawn(move || {
+                    mapper
+                        .c_to_g(&var_c, "NC_000007.13", "splign")
+                        .map(|var_g| format!("{}", &NoRef(&var_g)))
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            let var_g = handle.join().expect("thread panicked")?;
+            assert_eq!(var_g, "NC_000007.13:g.21940852_21940908del");
+        }
 
         Ok(())
     }
 
     #[test]
-    fn test_sync() {
-        fn is_sync<T: Sync>() {}
-        is_sync::<super::Mapper>();
+    fn g_to_all_transcripts() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+        let var_g = HgvsVariant::from_str("NC_000007.13:g.36561662C>T")?;
+
+        let results = mapper
+            .g_to_all_transcripts(&var_g, "splign")?
+            .into_iter()
+            .map(|result| result.map(|var_t| format!("{}", &var_t)))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert!(
+            results.contains(&String::from("NM_001637.3:c.1582G>A")),
+            "{results:?}"
+        );
+
+        let results = mapper
+            .g_to_all_transcripts_filtered(&var_g, "splign", |tx_ac| {
+                tx_ac.starts_with("NM_001637.")
+            })?
+            .into_iter()
+            .map(|var_t| format!("{}", &var_t))
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec!["NM_001637.3:c.1582G>A"]);
+
+        let var_c = HgvsVariant::from_str("NM_001637.3:c.1582G>A")?;
+        assert!(mapper.g_to_all_transcripts(&var_c, "splign").is_err());
+
+        Ok(())
     }
 
     fn build_mapper() -> Result<Mapper, Error> {
@@ -1035,6 +2639,37 @@ mod test {
         Ok(Mapper::new(&config, provider))
     }
 
+    #[test]
+    fn config_builder() {
+        assert_eq!(Config::builder().build(), Config::default());
+        assert_eq!(
+            Config::builder()
+                .replace_reference(false)
+                .strict_validation(true)
+                .prevalidation_level(ValidationLevel::Intrinsic)
+                .add_gene_symbol(true)
+                .strict_bounds(false)
+                .renormalize_g(false)
+                .genome_seq_available(false)
+                .cache_size(0)
+                .fallback_to_similar(true)
+                .prefer_mane_select(true)
+                .build(),
+            Config {
+                replace_reference: false,
+                strict_validation: true,
+                prevalidation_level: ValidationLevel::Intrinsic,
+                add_gene_symbol: true,
+                strict_bounds: false,
+                renormalize_g: false,
+                genome_seq_available: false,
+                cache_size: 0,
+                fallback_to_similar: true,
+                prefer_mane_select: true,
+            }
+        );
+    }
+
     #[test]
     fn fail_for_invalid_variant_types() -> Result<(), Error> {
         let mapper = build_mapper()?;
@@ -1057,6 +2692,47 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn validate_and_normalize() -> Result<(), Error> {
+        // Validation failures are only logged unless validation is strict.
+        let config = Config {
+            strict_validation: true,
+            ..Config::default()
+        };
+        let mapper = Mapper::new(&config, build_provider()?);
+
+        for (input, expected) in [
+            ("NM_001166478.1:c.31del", "NM_001166478.1:c.35del"),
+            ("NM_001166478.1:c.35_36insT", "NM_001166478.1:c.35dup"),
+            ("NM_000088.3:c.589_600inv", "NM_000088.3:c.590_599inv"),
+        ] {
+            let var = HgvsVariant::from_str(input)?;
+            assert_eq!(
+                format!("{}", mapper.validate_and_normalize(&var)?),
+                expected
+            );
+        }
+
+        // The deleted sequence does not match the length of the interval.
+        let var = HgvsVariant::from_str("NM_000088.3:c.34_36delG")?;
+        assert!(mapper.validate_and_normalize(&var).is_err());
+
+        let var_g = HgvsVariant::from_str("NC_000007.13:g.36561662C>T")?;
+        assert_eq!(
+            format!(
+                "{}",
+                mapper.validate_normalize_and_map_g_to_c(&var_g, "NM_001637.3", "splign")?
+            ),
+            "NM_001637.3:c.1582G>A"
+        );
+        let var_c = HgvsVariant::from_str("NM_001637.3:c.1582G>A")?;
+        assert!(mapper
+            .validate_normalize_and_map_g_to_c(&var_c, "NM_001637.3", "splign")
+            .is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn fail_c_to_p_on_invalid_nm_accession() -> Result<(), Error> {
         let mapper = build_mapper()?;
@@ -1180,12 +2856,18 @@ mod test {
         };
 
         use anyhow::Error;
-
-        use crate::data::interface;
-        use crate::{
-            data::interface::TxIdentityInfo,
-            mapper::variant::{Config, Mapper},
+        use biocommons_bioutils::assemblies::Assembly;
+        use indexmap::IndexMap;
+
+        use crate::data::{
+            error::Error as DataError,
+            gff3,
+            interface::{
+                self, GeneInfoRecord, Provider as _, TxExonsRecord, TxForRegionRecord,
+                TxIdentityInfo, TxInfoRecord, TxMappingOptionsRecord, TxSimilarityRecord,
+            },
         };
+        use crate::mapper::variant::{Config, Mapper};
         use std::sync::atomic::AtomicUsize;
         static PROVIDER_COUNT: AtomicUsize = AtomicUsize::new(0);
 
@@ -1197,10 +2879,17 @@ mod test {
             pub cds_end_i: i32,
         }
 
+        /// Mock provider serving the transcripts of a TSV file.
+        ///
+        /// Queries that the TSV records cannot answer are passed to the optional `inner`
+        /// provider, and `get_similar_transcripts()` returns the records set with
+        /// `with_similar_transcripts()`.
         pub struct Provider {
             data_version: String,
             schema_version: String,
             records: Vec<ProviderRecord>,
+            inner: Option<gff3::Provider>,
+            similar_transcripts: Vec<TxSimilarityRecord>,
         }
 
         impl Provider {
@@ -1220,8 +2909,37 @@ mod test {
                     records,
                     data_version: dummy_version.clone(),
                     schema_version: dummy_version,
+                    inner: None,
+                    similar_transcripts: Vec::new(),
                 })
             }
+
+            /// Return a provider without TSV records that passes all queries to `inner`.
+            pub fn with_inner(inner: gff3::Provider) -> Self {
+                Self {
+                    data_version: inner.data_version().to_string(),
+                    schema_version: inner.schema_version().to_string(),
+                    records: Vec::new(),
+                    inner: Some(inner),
+                    similar_transcripts: Vec::new(),
+                }
+            }
+
+            /// Return the provider with the records returned by `get_similar_transcripts()`.
+            pub fn with_similar_transcripts(self, records: Vec<TxSimilarityRecord>) -> Self {
+                Self {
+                    similar_transcripts: records,
+                    ..self
+                }
+            }
+
+            fn inner(&self) -> &gff3::Provider {
+                self.inner.as_ref().expect("for test use only")
+            }
+
+            fn record(&self, tx_ac: &str) -> Option<&ProviderRecord> {
+                self.records.iter().find(|record| record.accession == tx_ac)
+            }
         }
 
         impl interface::Provider for Provider {
@@ -1233,26 +2951,25 @@ mod test {
                 &self.schema_version
             }
 
-            fn get_assembly_map(
-                &self,
-                _assembly: biocommons_bioutils::assemblies::Assembly,
-            ) -> indexmap::IndexMap<String, String> {
-                panic!("for test use only");
+            fn capabilities(&self) -> interface::ProviderCapabilities {
+                match &self.inner {
+                    Some(inner) => {
+                        inner.capabilities() | interface::ProviderCapabilities::HAS_SIMILAR_TX
+                    }
+                    None => interface::ProviderCapabilities::HAS_TX_SEQ,
+                }
             }
 
-            fn get_gene_info(
-                &self,
-                _hgnc: &str,
-            ) -> Result<crate::data::interface::GeneInfoRecord, crate::data::error::Error>
-            {
-                panic!("for test use only");
+            fn get_assembly_map(&self, assembly: Assembly) -> IndexMap<String, String> {
+                self.inner().get_assembly_map(assembly)
             }
 
-            fn get_pro_ac_for_tx_ac(
-                &self,
-                _tx_ac: &str,
-            ) -> Result<Option<String>, crate::data::error::Error> {
-                panic!("for test use only");
+            fn get_gene_info(&self, hgnc: &str) -> Result<GeneInfoRecord, DataError> {
+                self.inner().get_gene_info(hgnc)
+            }
+
+            fn get_pro_ac_for_tx_ac(&self, tx_ac: &str) -> Result<Option<String>, DataError> {
+                self.inner().get_pro_ac_for_tx_ac(tx_ac)
             }
 
             fn get_seq_part(
@@ -1260,109 +2977,109 @@ mod test {
                 tx_ac: &str,
                 begin: Option<usize>,
                 end: Option<usize>,
-            ) -> Result<String, crate::data::error::Error> {
-                for record in &self.records {
-                    if record.accession == tx_ac {
-                        let seq = &record.transcript_sequence;
-                        return match (begin, end) {
-                            (None, None) => Ok(seq.to_string()),
-                            (None, Some(end)) => Ok(seq[..end].to_string()),
-                            (Some(begin), None) => Ok(seq[begin..].to_string()),
-                            (Some(begin), Some(end)) => Ok(seq[begin..end].to_string()),
-                        };
-                    }
+            ) -> Result<String, DataError> {
+                if let Some(record) = self.record(tx_ac) {
+                    let seq = &record.transcript_sequence;
+                    return match (begin, end) {
+                        (None, None) => Ok(seq.to_string()),
+                        (None, Some(end)) => Ok(seq[..end].to_string()),
+                        (Some(begin), None) => Ok(seq[begin..].to_string()),
+                        (Some(begin), Some(end)) => Ok(seq[begin..end].to_string()),
+                    };
+                }
+                match &self.inner {
+                    Some(inner) => inner.get_seq_part(tx_ac, begin, end),
+                    None => Err(DataError::NoSequenceRecord(tx_ac.to_string())),
                 }
-                Err(crate::data::error::Error::NoSequenceRecord(
-                    tx_ac.to_string(),
-                ))
             }
 
-            fn get_acs_for_protein_seq(
-                &self,
-                _seq: &str,
-            ) -> Result<Vec<String>, crate::data::error::Error> {
-                panic!("for test use only");
+            fn get_acs_for_protein_seq(&self, seq: &str) -> Result<Vec<String>, DataError> {
+                self.inner().get_acs_for_protein_seq(seq)
             }
 
             fn get_similar_transcripts(
                 &self,
-                _tx_ac: &str,
-            ) -> Result<Vec<crate::data::interface::TxSimilarityRecord>, crate::data::error::Error>
-            {
-                panic!("for test use only");
+                tx_ac: &str,
+            ) -> Result<Vec<TxSimilarityRecord>, DataError> {
+                Ok(self
+                    .similar_transcripts
+                    .iter()
+                    .filter(|record| record.tx_ac1 == tx_ac)
+                    .cloned()
+                    .collect())
             }
 
             fn get_tx_exons(
                 &self,
-                _tx_ac: &str,
-                _alt_ac: &str,
-                _alt_aln_method: &str,
-            ) -> Result<Vec<crate::data::interface::TxExonsRecord>, crate::data::error::Error>
-            {
-                todo!()
+                tx_ac: &str,
+                alt_ac: &str,
+                alt_aln_method: &str,
+            ) -> Result<Vec<TxExonsRecord>, DataError> {
+                self.inner().get_tx_exons(tx_ac, alt_ac, alt_aln_method)
             }
 
-            fn get_tx_for_gene(
-                &self,
-                _gene: &str,
-            ) -> Result<Vec<crate::data::interface::TxInfoRecord>, crate::data::error::Error>
-            {
-                panic!("for test use only");
+            fn get_tx_for_gene(&self, gene: &str) -> Result<Vec<TxInfoRecord>, DataError> {
+                self.inner().get_tx_for_gene(gene)
             }
 
             fn get_tx_for_region(
                 &self,
-                _alt_ac: &str,
-                _alt_aln_method: &str,
-                _start_i: i32,
-                _end_i: i32,
-            ) -> Result<Vec<crate::data::interface::TxForRegionRecord>, crate::data::error::Error>
-            {
-                panic!("for test use only");
+                alt_ac: &str,
+                alt_aln_method: &str,
+                start_i: i32,
+                end_i: i32,
+            ) -> Result<Vec<TxForRegionRecord>, DataError> {
+                self.inner()
+                    .get_tx_for_region(alt_ac, alt_aln_method, start_i, end_i)
             }
 
-            fn get_tx_identity_info(
-                &self,
-                tx_ac: &str,
-            ) -> Result<TxIdentityInfo, crate::data::error::Error> {
-                for record in &self.records {
-                    if record.accession == tx_ac {
-                        return Ok(TxIdentityInfo {
-                            tx_ac: record.accession.clone(),
-                            alt_ac: record.accession.clone(),
-                            alt_aln_method: "splign".to_string(),
-                            cds_start_i: record.cds_start_i,
-                            cds_end_i: record.cds_end_i,
-                            lengths: Vec::new(),
-                            hgnc: "MOCK".to_string(),
-                            ..Default::default()
-                        });
-                    }
+            fn get_tx_identity_info(&self, tx_ac: &str) -> Result<TxIdentityInfo, DataError> {
+                if let Some(record) = self.record(tx_ac) {
+                    return Ok(TxIdentityInfo {
+                        tx_ac: record.accession.clone(),
+                        alt_ac: record.accession.clone(),
+                        alt_aln_method: "splign".to_string(),
+                        cds_start_i: record.cds_start_i,
+                        cds_end_i: record.cds_end_i,
+                        lengths: vec![record.transcript_sequence.len() as i32],
+                        hgnc: "MOCK".to_string(),
+                        ..Default::default()
+                    });
+                }
+                match &self.inner {
+                    Some(inner) => inner.get_tx_identity_info(tx_ac),
+                    None => Err(DataError::NoSequenceRecord(tx_ac.to_string())),
                 }
-                Err(crate::data::error::Error::NoSequenceRecord(
-                    tx_ac.to_string(),
-                ))
             }
 
             fn get_tx_info(
                 &self,
-                _tx_ac: &str,
-                _alt_ac: &str,
-                _alt_aln_method: &str,
-            ) -> Result<crate::data::interface::TxInfoRecord, crate::data::error::Error>
-            {
-                panic!("for test use only");
+                tx_ac: &str,
+                alt_ac: &str,
+                alt_aln_method: &str,
+            ) -> Result<TxInfoRecord, DataError> {
+                self.inner().get_tx_info(tx_ac, alt_ac, alt_aln_method)
             }
 
             fn get_tx_mapping_options(
                 &self,
-                _tx_ac: &str,
-            ) -> Result<
-                Vec<crate::data::interface::TxMappingOptionsRecord>,
-                crate::data::error::Error,
-            > {
-                panic!("for test use only");
+                tx_ac: &str,
+            ) -> Result<Vec<TxMappingOptionsRecord>, DataError> {
+                self.inner().get_tx_mapping_options(tx_ac)
+            }
+        }
+
+        #[test]
+        fn get_tx_full_seq() -> Result<(), Error> {
+            let provider = Provider::new(Path::new("tests/data/mapper/sanity_cp.tsv"))?;
+            for record in &provider.records {
+                let id_info = provider.get_tx_identity_info(&record.accession)?;
+                let seq = provider.get_tx_full_seq(&record.accession)?;
+                assert_eq!(seq, record.transcript_sequence);
+                assert_eq!(seq.len() as i32, id_info.lengths.iter().sum::<i32>());
             }
+
+            Ok(())
         }
 
         pub fn build_mapper(strict_bounds: bool) -> Result<Mapper, Error> {
@@ -1390,6 +3107,448 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn capabilities_sanity() -> Result<(), Error> {
+        let mapper = sanity_mock::build_mapper(false)?;
+
+        // The mock has no alignments, so mapping to the genome fails instead of panicking.
+        let var_c = HgvsVariant::from_str("NM_999999.1:c.1A>G")?;
+        assert!(matches!(
+            mapper.c_to_g(&var_c, "NC_000001.11", "splign"),
+            Err(crate::mapper::Error::DataError(
+                crate::data::error::Error::CapabilityNotSupported(_)
+            ))
+        ));
+        assert_eq!(
+            format!("{}", mapper.c_to_p(&var_c, Some("MOCK"))?),
+            "MOCK:p.Met1?"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn c_to_n_standalone_sanity() -> Result<(), Error> {
+        let mapper = sanity_mock::build_mapper(false)?;
+        // CDS of the mock transcript as given in `sanity_cp.tsv`.
+        let (cds_start_i, cds_end_i) = (9, 39);
+
+        for hgvsc in [
+            "NM_999999.1:c.-9del",
+            "NM_999999.1:c.-1del",
+            "NM_999999.1:c.1del",
+            "NM_999999.1:c.30del",
+            "NM_999999.1:c.*1del",
+            "NM_999999.1:c.*3del",
+        ] {
+            let var_c = HgvsVariant::from_str(hgvsc)?;
+            let var_n = mapper.c_to_n(&var_c)?;
+            let (
+                HgvsVariant::CdsVariant {
+                    loc_edit: c_loc_edit,
+                    ..
+                },
+                HgvsVariant::TxVariant {
+                    loc_edit: n_loc_edit,
+                    ..
+                },
+            ) = (&var_c, &var_n)
+            else {
+                panic!("unexpected variant types");
+            };
+            let (c_interval, n_interval) = (c_loc_edit.loc.inner(), n_loc_edit.loc.inner());
+
+            assert_eq!(
+                &c_interval.to_tx_interval(cds_start_i, cds_end_i),
+                n_interval,
+                "{hgvsc}"
+            );
+            assert_eq!(
+                n_interval.to_cds_interval(cds_start_i, cds_end_i).as_ref(),
+                Some(c_interval),
+                "{hgvsc}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn affects_stop_codon() -> Result<(), Error> {
+        let mapper = sanity_mock::build_mapper(false)?;
+
+        // The CDS of the mock transcript ends with the stop codon `c.28_30`.
+        for (hgvsc, expected) in [
+            ("NM_999999.1:c.27A>G", false),
+            ("NM_999999.1:c.28T>A", true),
+            ("NM_999999.1:c.30G>A", true),
+            ("NM_999999.1:c.*1G>A", false),
+        ] {
+            let var_c = HgvsVariant::from_str(hgvsc)?;
+            assert_eq!(mapper.affects_stop_codon(&var_c)?, expected, "{hgvsc}");
+        }
+        let var_c = HgvsVariant::from_str("NM_999999.1:c.1A>G")?;
+        assert!(var_c.affects_start_codon());
+        assert!(!mapper.affects_stop_codon(&var_c)?);
+
+        Ok(())
+    }
+
+    /// Build a mapper on the GFF3 file that describes `TX1.1` and `TX1.2` at `chrT:g.11_110`
+    /// with the CDS starting at `g.31` and `g.53`, respectively, and the non-coding `TX2.1`
+    /// at `chrT:g.121_150`.
+    fn build_gff3_mapper() -> Result<Mapper, Error> {
+        let provider = crate::data::gff3::Provider::new(crate::data::gff3::Config {
+            gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+        Ok(Mapper::new(&Config::default(), Arc::new(provider)))
+    }
+
+    #[test]
+    fn remap_accession() -> Result<(), Error> {
+        let mapper = build_gff3_mapper()?;
+
+        let var_c = HgvsVariant::from_str("TX1.1:c.30G>A")?;
+        assert_eq!(
+            format!(
+                "{}",
+                mapper.remap_accession(&var_c, "TX1.2", "chrT", "splign")?
+            ),
+            "TX1.2:c.8G>A"
+        );
+        let var_c = HgvsVariant::from_str("TX1.1:c.-5C>T")?;
+        assert_eq!(
+            format!(
+                "{}",
+                mapper.remap_accession(&var_c, "TX1.2", "chrT", "splign")?
+            ),
+            "TX1.2:c.-27C>T"
+        );
+        assert!(matches!(
+            mapper.remap_accession(&var_c, "TX2.1", "chrT", "splign"),
+            Err(crate::mapper::Error::AccessionRemapFailed(..))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn g_to_t_auto() -> Result<(), Error> {
+        let build_mapper = |prefer_mane_select| -> Result<Mapper, Error> {
+            let inner = crate::data::gff3::Provider::new(crate::data::gff3::Config {
+                gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+                fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+            })?;
+            let provider = ManeProvider::new(inner, "tests/data/data/mane/mane_summary.txt")?;
+            Ok(Mapper::new(
+                &Config::builder()
+                    .prefer_mane_select(prefer_mane_select)
+                    .build(),
+                Arc::new(provider),
+            ))
+        };
+        let var_g = HgvsVariant::from_str("chrT:g.34A>G")?;
+
+        // `TX1.1` is the MANE Select transcript of `GENE1`, `TX1.2` is reported first.
+        let mapper = build_mapper(true)?;
+        assert_eq!(
+            format!("{}", mapper.g_to_t(&var_g, AUTO_TX_AC, "splign")?),
+            "TX1.1:c.4A>G"
+        );
+        let mapper = build_mapper(false)?;
+        assert_eq!(
+            format!("{}", mapper.g_to_t(&var_g, AUTO_TX_AC, "splign")?),
+            "TX1.2:c.-19A>G"
+        );
+
+        let var_g = HgvsVariant::from_str("chrT:g.5A>G")?;
+        assert!(matches!(
+            mapper.g_to_t(&var_g, AUTO_TX_AC, "splign"),
+            Err(crate::mapper::Error::NoOverlappingTranscripts(_))
+        ));
+
+        Ok(())
+    }
+
+    // `MTX1.1` on `NC_012920.1` has the CDS `ATA TGA AAA GCT TGG CTG AGA` at `m.11_31`,
+    // encoding `MWKAWL*` in the vertebrate mitochondrial code.
+
+    #[test]
+    fn m_to_p() -> Result<(), Error> {
+        let provider = Arc::new(crate::data::gff3::Provider::new(
+            crate::data::gff3::Config {
+                gff3_path: String::from("tests/data/data/gff3/mitochondrial.gff3"),
+                fasta_path: String::from("tests/data/data/gff3/mitochondrial.fa"),
+            },
+        )?);
+        let mapper = Mapper::new(&Config::default(), provider);
+
+        for (s, expected) in [
+            ("NC_012920.1:m.15G>A", "PMTX1.1:p.Trp2Ter"),
+            ("NC_012920.1:m.17A>G", "PMTX1.1:p.Lys3Glu"),
+            ("NC_012920.1:m.29A>G", "PMTX1.1:p.Ter7GlyextTer?"),
+        ] {
+            let var_m = HgvsVariant::from_str(s)?;
+            let var_p = mapper.m_to_p(&var_m, "splign", None)?;
+            assert_eq!(format!("{var_p}"), expected, "{s}");
+            assert_eq!(mapper.m_to_p(&var_m, "splign", Some("PMTX1.1"))?, var_p);
+        }
+
+        let var_m = HgvsVariant::from_str("NC_012920.1:m.17A>G")?;
+        assert!(matches!(
+            mapper.m_to_p(&var_m, "splign", Some("PX9.1")),
+            Err(super::Error::NoOverlappingTranscripts(_))
+        ));
+        let var_m = HgvsVariant::from_str("NC_012920.1:m.5A>G")?;
+        assert!(matches!(
+            mapper.m_to_p(&var_m, "splign", None),
+            Err(super::Error::NoOverlappingTranscripts(_))
+        ));
+        let var_g = HgvsVariant::from_str("NC_012920.1:g.17A>G")?;
+        assert!(matches!(
+            mapper.m_to_p(&var_g, "splign", None),
+            Err(super::Error::NotMtVariant(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn c_to_p_auto_and_verified() -> Result<(), Error> {
+        let provider = Arc::new(crate::data::gff3::Provider::new(
+            crate::data::gff3::Config {
+                gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+                fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+            },
+        )?);
+        let mapper = Mapper::new(&Config::default(), provider);
+
+        let var_c = HgvsVariant::from_str("TX1.1:c.4A>G")?;
+        let var_p = mapper.c_to_p_auto(&var_c)?;
+        assert_eq!(format!("{var_p}"), "PX1.1:p.Lys2Glu");
+        assert_eq!(mapper.c_to_p_verified(&var_c, "PX1.1")?, var_p);
+
+        match mapper.c_to_p_verified(&var_c, "PX1.2") {
+            Err(super::Error::ProteinAccessionMismatch { expected, found }) => {
+                assert_eq!(expected, "PX1.1");
+                assert_eq!(found, "PX1.2");
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+        // `c_to_p()` uses the given accession as is.
+        assert_eq!(
+            format!("{}", mapper.c_to_p(&var_c, Some("PX1.2"))?),
+            "PX1.2:p.Lys2Glu"
+        );
+
+        let var_g = HgvsVariant::from_str("chrT:g.34A>G")?;
+        assert!(mapper.c_to_p_auto(&var_g).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_gene_symbols_batch() -> Result<(), Error> {
+        use crate::data::interface::Provider as _;
+
+        let provider = Arc::new(crate::data::gff3::Provider::new(
+            crate::data::gff3::Config {
+                gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+                fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+            },
+        )?);
+        let mapper = Mapper::new(&Config::default(), provider.clone());
+
+        let variants = [
+            "TX1.1(GENE1):c.4A>G",
+            "TX1.2(GENE1):c.-19A>G",
+            "TX1.1:c.[4A>G;5del]",
+            "TX2.1(GENE2):n.5del",
+            "chrT:g.34A>G",
+        ]
+        .iter()
+        .map(|s| HgvsVariant::from_str(s))
+        .collect::<Result<Vec<_>, _>>()?;
+        let stripped = variants
+            .iter()
+            .map(|var| var.clone().strip_gene_symbol())
+            .collect::<Vec<_>>();
+
+        let added = mapper.add_gene_symbols_batch(&stripped)?;
+        assert_eq!(added.len(), variants.len());
+        for var in &added[..4] {
+            let hgnc = provider.get_tx_identity_info(&var.accession().value)?.hgnc;
+            assert_eq!(
+                var.gene_symbol().as_ref().map(|s| s.value.as_str()),
+                Some(hgnc.as_str())
+            );
+        }
+        assert_eq!(&added[..2], &variants[..2]);
+        assert_eq!(format!("{}", added[2]), "TX1.1(GENE1):c.[4A>G;5del]");
+        assert_eq!(added[4], variants[4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn assembly_for_variant() -> Result<(), Error> {
+        let mapper = build_gff3_mapper()?;
+
+        for (variant, expected) in [
+            ("NC_000017.10:g.41197701G>C", Some(Assembly::Grch37)),
+            ("NC_000017.11:g.43045684G>C", Some(Assembly::Grch38)),
+            ("NM_007294.3:c.5586G>C", None),
+            ("chrT:g.34A>G", None),
+        ] {
+            let var = HgvsVariant::from_str(variant)?;
+            assert_eq!(mapper.assembly_for_variant(&var), expected, "{variant}");
+        }
+
+        Ok(())
+    }
+
+    /// Return a similarity record of the unknown transcript `TX1.0` to `tx_ac2`.
+    fn similar_to_tx1_0(tx_ac2: &str, hgnc_eq: bool, cds_eq: bool) -> TxSimilarityRecord {
+        TxSimilarityRecord {
+            tx_ac1: "TX1.0".to_string(),
+            tx_ac2: tx_ac2.to_string(),
+            hgnc_eq,
+            cds_eq,
+            es_fp_eq: false,
+            cds_es_fp_eq: cds_eq,
+            cds_exon_lengths_fp_eq: cds_eq,
+        }
+    }
+
+    #[test]
+    fn fallback_to_similar() -> Result<(), Error> {
+        let build_mapper = |fallback_to_similar| -> Result<Mapper, Error> {
+            let inner = crate::data::gff3::Provider::new(crate::data::gff3::Config {
+                gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+                fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+            })?;
+            let provider = sanity_mock::Provider::with_inner(inner).with_similar_transcripts(vec![
+                similar_to_tx1_0("TX2.1", false, true),
+                similar_to_tx1_0("TX1.2", true, false),
+                similar_to_tx1_0("TX1.1", true, true),
+            ]);
+            Ok(Mapper::new(
+                &Config::builder()
+                    .fallback_to_similar(fallback_to_similar)
+                    .build(),
+                Arc::new(provider),
+            ))
+        };
+        let var_g = HgvsVariant::from_str("chrT:g.34A>G")?;
+        let var_c = HgvsVariant::from_str("TX1.0:c.4A>G")?;
+
+        let mapper = build_mapper(true)?;
+        assert_eq!(
+            mapper.find_similar_transcript("TX1.0")?,
+            Some("TX1.1".to_string())
+        );
+        assert_eq!(mapper.find_similar_transcript("TX1.1")?, None);
+        assert_eq!(
+            format!("{}", mapper.g_to_c(&var_g, "TX1.0", "splign")?),
+            "TX1.1:c.4A>G"
+        );
+        assert_eq!(
+            format!("{}", mapper.g_to_t(&var_g, "TX1.0", "splign")?),
+            "TX1.1:c.4A>G"
+        );
+        assert_eq!(
+            format!("{}", mapper.c_to_g(&var_c, "chrT", "splign")?),
+            "chrT:g.34A>G"
+        );
+
+        let mapper = build_mapper(false)?;
+        assert!(mapper.g_to_c(&var_g, "TX1.0", "splign").is_err());
+        assert!(mapper.g_to_t(&var_g, "TX1.0", "splign").is_err());
+        assert!(mapper.c_to_g(&var_c, "chrT", "splign").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn mapper_stats() -> Result<(), Error> {
+        let stats = Arc::new(MapperStats::default());
+        let mapper = build_gff3_mapper()?.with_stats(stats.clone());
+
+        let var_g = HgvsVariant::from_str("chrT:g.34A>G")?;
+        let var_c = mapper.g_to_c(&var_g, "TX1.1", "splign")?;
+        assert_eq!(format!("{var_c}"), "TX1.1:c.4A>G");
+        mapper.c_to_g(&var_c, "chrT", "splign")?;
+        mapper.c_to_g(&var_c, "chrT", "splign")?;
+        assert!(mapper.g_to_c(&var_g, "TX9.1", "splign").is_err());
+        // Nested projections are only counted for the called method.
+        mapper.g_to_t(&var_g, "TX1.1", "splign")?;
+        mapper.g_to_all_transcripts(&var_g, "splign")?;
+        mapper.remap_accession(&var_c, "TX1.2", "chrT", "splign")?;
+
+        let stats = mapper.stats().expect("stats are collected");
+        assert_eq!(stats.g_to_c_ok.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.g_to_c_err.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.c_to_g_ok.load(Ordering::Relaxed), 2);
+        assert_eq!(stats.c_to_g_err.load(Ordering::Relaxed), 0);
+        assert_eq!(stats.g_to_t_ok.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.g_to_n_ok.load(Ordering::Relaxed), 0);
+        assert_eq!(stats.t_to_g_ok.load(Ordering::Relaxed), 0);
+
+        let provider = crate::data::gff3::Provider::new(crate::data::gff3::Config {
+            gff3_path: String::from("tests/data/data/gff3/mitochondrial.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/mitochondrial.fa"),
+        })?;
+        let stats = Arc::new(MapperStats::default());
+        let mapper = Mapper::new(&Config::default(), Arc::new(provider)).with_stats(stats.clone());
+        let var_m = HgvsVariant::from_str("NC_012920.1:m.17A>G")?;
+        mapper.m_to_p(&var_m, "splign", None)?;
+        assert!(mapper.m_to_p(&var_m, "splign", Some("PX9.1")).is_err());
+        assert_eq!(stats.m_to_p_ok.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.m_to_p_err.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.g_to_c_ok.load(Ordering::Relaxed), 0);
+        assert_eq!(stats.c_to_p_ok.load(Ordering::Relaxed), 0);
+
+        assert!(build_gff3_mapper()?.stats().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn coding_consequence() -> Result<(), Error> {
+        let mapper = sanity_mock::build_mapper(false)?;
+
+        for (hgvsc, expected) in [
+            ("NM_999999.1:c.6A>G", ProteinConsequence::Synonymous),
+            ("NM_999999.1:c.6A>T", ProteinConsequence::Missense),
+            ("NM_999996.1:c.8C>A", ProteinConsequence::Nonsense),
+            ("NM_999999.1:c.22_23insT", ProteinConsequence::Frameshift),
+            (
+                "NM_999999.1:c.6_7insGGG",
+                ProteinConsequence::InframeInsertion,
+            ),
+            (
+                "NM_999999.1:c.10_12dup",
+                ProteinConsequence::InframeInsertion,
+            ),
+            ("NM_999999.1:c.4_15del", ProteinConsequence::InframeDeletion),
+            (
+                "NM_999999.1:c.11_12delinsTCCCA",
+                ProteinConsequence::InframeIndel,
+            ),
+            ("NM_999999.1:c.1A>G", ProteinConsequence::StartLost),
+            ("NM_999998.1:c.30G>T", ProteinConsequence::StopLost),
+            ("NM_999999.1:c.25_30del", ProteinConsequence::StopLost),
+            ("NM_999999.1:c.12+1G>A", ProteinConsequence::SpliceSite),
+            ("NM_999999.1:c.-2A>G", ProteinConsequence::Uncertain),
+        ] {
+            let var_c = HgvsVariant::from_str(hgvsc)?;
+            assert_eq!(mapper.coding_consequence(&var_c)?, expected, "{hgvsc}");
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn hgvs_c_to_p_silent() -> Result<(), Error> {
         let hgvsc = "NM_999999.1:c.6A>G";
@@ -1625,6 +3784,33 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn hgvs_c_is_inframe_agrees_with_c_to_p() -> Result<(), Error> {
+        let mapper = sanity_mock::build_mapper(false)?;
+
+        for hgvsc in [
+            "NM_999999.1:c.6_7insGGG",
+            "NM_999999.1:c.22_23insT",
+            "NM_999999.1:c.10_12del",
+            "NM_999999.1:c.4_15del",
+            "NM_999999.1:c.5_7del",
+            "NM_999999.1:c.11_12del",
+            "NM_999997.1:c.7del",
+            "NM_999999.1:c.11_12delinsTCCCA",
+            "NM_999999.1:c.8delinsGG",
+            "NM_999999.1:c.10_12dup",
+            "NM_999999.1:c.16_24dup",
+            "NM_999999.1:c.12_13dup",
+        ] {
+            let var_c = HgvsVariant::from_str(hgvsc)?;
+            let var_p = mapper.c_to_p(&var_c, Some("MOCK"))?;
+            let is_fs = matches!(var_p.prot_edit(), Some(ProteinEdit::Fs { .. }));
+            assert_eq!(var_c.is_inframe(), !is_fs, "{hgvsc} -> {var_p}");
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn hgvs_c_to_p_intron() -> Result<(), Error> {
         let hgvsc = "NM_999999.1:c.12+1G>A";
@@ -1785,6 +3971,41 @@ mod test {
         Ok(())
     }
 
+    /// Real-data counterpart of `hgvs_c_to_p_deletion_into_three_prime_utr_no_frameshift`.
+    ///
+    /// NP_071909.1 (SIL1) has 461 amino acids, so the stop codon is `c.1384_1386`.  The
+    /// in-frame deletion removes the last sense codon, the stop codon, and three bases of
+    /// the 3' UTR.  Translation then continues with `c.*4` up to the next stop codon, which
+    /// gives the inserted residues of the expected `p.Xaa461_Ter462delins...`.
+    #[test]
+    fn hgvs_c_to_p_deletion_into_three_prime_utr_real() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+        let var_c = HgvsVariant::from_str("NM_022464.4:c.1381_*3del")?;
+        let var_p = mapper.c_to_p(&var_c, Some("NP_071909.1"))?;
+
+        let provider = mapper.provider();
+        let cds_start = provider.get_tx_identity_info("NM_022464.4")?.cds_start_i as usize;
+        let tx_seq = provider.get_seq_part("NM_022464.4", None, None)?;
+        let translate = |seq: &str| {
+            let seq = &seq[..seq.len() / 3 * 3];
+            translate_cds(seq, true, "*", TranslationTable::Standard)
+        };
+        let deleted = translate(&tx_seq[cds_start + 1380..cds_start + 1386])?;
+        assert_eq!(&deleted[1..], "*");
+        let read_through = translate(&tx_seq[cds_start + 1389..])?;
+        let (inserted, _) = read_through
+            .split_once('*')
+            .expect("3' UTR must contain an in-frame stop codon");
+        let expected = format!(
+            "NP_071909.1:p.{}461_Ter462delins{}",
+            aa1_to_aa3(&deleted[..1])?,
+            aa1_to_aa3(inserted)?
+        );
+        assert_eq!(format!("{}", &var_p), expected);
+
+        Ok(())
+    }
+
     mod gcp_tests {
         use std::path::Path;
 
@@ -1853,6 +4074,58 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn gp_real() -> Result<(), Error> {
+        let mapper = build_mapper()?;
+        let path = PathBuf::from("tests/data/mapper/real_cp.tsv");
+        let records = gcp_tests::load_records(&path)?;
+
+        for record in records {
+            let var_g = HgvsVariant::from_str(&record.hgvs_g)?;
+            let var_c = HgvsVariant::from_str(&record.hgvs_c)?;
+            let tx_ac = var_c.accession().value.clone();
+            let expected = record.hgvs_p.expect("problem with result in test");
+            let prot_ac = expected.split(':').next();
+
+            let var_p = mapper.g_to_p(&var_g, &tx_ac, "splign", prot_ac)?;
+            let result = format!("{}", &var_p);
+            let expected = if result != expected {
+                expected.replace('*', "Ter")
+            } else {
+                expected.clone()
+            };
+            assert_eq!(result, expected, "{}", &record.hgvs_g);
+
+            // Only substitutions of single amino acids and deletions can be projected back.
+            let back_projectable = match &var_p {
+                HgvsVariant::ProtVariant {
+                    loc_edit: ProtLocEdit::Ordinary { loc, edit },
+                    ..
+                } => match edit.inner() {
+                    ProteinEdit::Subst { alternative } => {
+                        loc.inner().start == loc.inner().end && !alternative.is_empty()
+                    }
+                    ProteinEdit::Del => true,
+                    _ => false,
+                },
+                _ => false,
+            };
+            if back_projectable {
+                let var_c = mapper.p_to_c(&var_p, &tx_ac)?;
+                let alt_ac = var_g.accession();
+                assert_eq!(
+                    mapper.p_to_g(&var_p, &tx_ac, alt_ac, "splign")?,
+                    mapper.c_to_g(&var_c, alt_ac, "splign")?,
+                    "{var_p}"
+                );
+            } else {
+                assert!(mapper.p_to_c(&var_p, &tx_ac).is_err(), "{var_p}");
+            }
+        }
+
+        Ok(())
+    }
+
     // The following tests correspond to those in `test_hgvs_variantmapper_gcp.py`.
 
     fn run_gxp_test(path: &str, noref: bool) -> Result<(), Error> {
@@ -1,10 +1,20 @@
 //! Variant normalization.
 
-use std::{cmp::Ordering, ops::Range, sync::Arc};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    ops::Range,
+    sync::{
+        atomic::{self, AtomicUsize},
+        Arc, Mutex,
+    },
+};
+
+use rayon::prelude::*;
 
 pub use crate::normalizer::error::Error;
 use crate::{
-    data::interface::Provider,
+    data::interface::{Provider, TxExonsRecord},
     mapper::variant,
     parser::{
         GenomeInterval, GenomeLocEdit, HgvsVariant, MtInterval, MtLocEdit, Mu, NaEdit, RnaInterval,
@@ -34,6 +44,8 @@ mod error {
         Validation(String),
         #[error("cannot normalize protein-level variant: {0}")]
         ProteinVariant(String),
+        #[error("cannot normalize variant on circular molecule: {0}")]
+        CircularVariant(String),
         #[error("cannot normalize intronic variant: {0}")]
         IntronicVariant(String),
         #[error("coordinates are out of bound in: {0}")]
@@ -48,6 +60,8 @@ mod error {
         UtrExonBoundary(String),
         #[error("variant span is outside of sequence bounds: {0}")]
         VariantSpanOutsideSequenceBounds(String),
+        #[error("variant has no position: {0}")]
+        NoPosition(String),
     }
 }
 
@@ -62,6 +76,10 @@ pub enum Direction {
 #[derive(Debug, Clone)]
 pub struct Config {
     pub alt_aln_method: String,
+    /// Whether transcript variants may be shuffled across exon-intron boundaries and the
+    /// boundaries between UTRs and CDS.  If `false`, the shuffling window is limited to the
+    /// exon and the UTR or CDS part of the exon that contains the variant.  Insertions
+    /// directly at the UTR/CDS boundary are kept in place.
     pub cross_boundaries: bool,
     pub shuffle_direction: Direction,
     pub replace_reference: bool,
@@ -70,6 +88,12 @@ pub struct Config {
     #[allow(dead_code)]
     pub validate: bool,
     pub window_size: usize,
+    /// Window size for genomic (`g.`, `m.`) variants, `window_size` if `None`.
+    pub window_size_g: Option<usize>,
+    /// Window size for CDS (`c.`) variants, `window_size` if `None`.
+    pub window_size_c: Option<usize>,
+    /// Window size for transcript (`n.`, `r.`) variants, `window_size` if `None`.
+    pub window_size_n: Option<usize>,
 }
 
 impl Default for Config {
@@ -81,16 +105,182 @@ impl Default for Config {
             replace_reference: true,
             validate: true,
             window_size: 20,
+            window_size_g: None,
+            window_size_c: None,
+            window_size_n: None,
         }
     }
 }
 
+impl Config {
+    /// Return a builder starting from the default configuration.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+/// Builder for `Config`, starting from `Config::default()`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// Set the direction in which variants are shuffled, `FiveToThree` by default.
+    pub fn shuffle_direction(mut self, direction: Direction) -> Self {
+        self.config.shuffle_direction = direction;
+        self
+    }
+
+    /// Set whether variants may be shuffled across exon-intron and UTR-CDS boundaries,
+    /// disabled by default.
+    pub fn cross_boundaries(mut self, cross_boundaries: bool) -> Self {
+        self.config.cross_boundaries = cross_boundaries;
+        self
+    }
+
+    /// Set whether the reference sequence of the normalized variant is filled in from the
+    /// provider, enabled by default.
+    pub fn replace_reference(mut self, replace_reference: bool) -> Self {
+        self.config.replace_reference = replace_reference;
+        self
+    }
+
+    /// Set the alignment method used for looking up transcript boundaries, `"splign"` by
+    /// default.
+    pub fn alt_aln_method(mut self, alt_aln_method: impl Into<String>) -> Self {
+        self.config.alt_aln_method = alt_aln_method.into();
+        self
+    }
+
+    /// Set the number of bases fetched around the variant per shuffling step, `20` by
+    /// default.
+    pub fn window_size(mut self, window_size: usize) -> Self {
+        self.config.window_size = window_size;
+        self
+    }
+
+    /// Set the window size for genomic (`g.`, `m.`) variants, `window_size` by default.
+    pub fn window_size_g(mut self, window_size: usize) -> Self {
+        self.config.window_size_g = Some(window_size);
+        self
+    }
+
+    /// Set the window size for CDS (`c.`) variants, `window_size` by default.
+    pub fn window_size_c(mut self, window_size: usize) -> Self {
+        self.config.window_size_c = Some(window_size);
+        self
+    }
+
+    /// Set the window size for transcript (`n.`, `r.`) variants, `window_size` by default.
+    pub fn window_size_n(mut self, window_size: usize) -> Self {
+        self.config.window_size_n = Some(window_size);
+        self
+    }
+
+    /// Set whether to validate the sequence length, enabled by default.
+    pub fn validate(mut self, validate: bool) -> Self {
+        self.config.validate = validate;
+        self
+    }
+
+    /// Return the configuration.
+    pub fn build(self) -> Config {
+        self.config
+    }
+}
+
+/// Number of variants after which `Normalizer::normalize_batch_with_progress()` reports
+/// progress.
+const PROGRESS_BATCH_SIZE: usize = 100;
+
+/// Sorted `(tx_start_i, tx_end_i)` pairs of the exons of a transcript.
+type ExonBoundaries = Arc<Vec<(i32, i32)>>;
+
+/// Key of the exon boundary cache: `(tx_ac, alt_ac, alt_aln_method)`.
+type ExonBoundaryKey = (String, String, String);
+
+/// Cache of the exon boundaries of transcripts, see `Normalizer::with_exon_cache()`.
+///
+/// For each alignment of a transcript, the `(tx_start_i, tx_end_i)` pairs of the exons are
+/// stored sorted by position, followed by a pseudo-exon from the transcript end to
+/// `i32::MAX`.  This allows finding the exon containing a position by binary search.
+#[derive(Debug, Default)]
+pub struct ExonBoundaryCache {
+    exons: Mutex<HashMap<ExonBoundaryKey, ExonBoundaries>>,
+}
+
+impl ExonBoundaryCache {
+    /// Return the exon boundaries of the alignment of `tx_ac` to `alt_ac` with
+    /// `alt_aln_method`, calling `f` on the first access.
+    fn get_or_try_insert_with<F>(
+        &self,
+        tx_ac: &str,
+        alt_ac: &str,
+        alt_aln_method: &str,
+        f: F,
+    ) -> Result<ExonBoundaries, Error>
+    where
+        F: FnOnce() -> Result<Vec<(i32, i32)>, Error>,
+    {
+        let key = (
+            tx_ac.to_string(),
+            alt_ac.to_string(),
+            alt_aln_method.to_string(),
+        );
+        if let Some(exons) = self.exons.lock().expect("poisoned lock").get(&key) {
+            return Ok(exons.clone());
+        }
+        // Do not hold the lock while querying the provider.
+        let exons = Arc::new(f()?);
+        self.exons
+            .lock()
+            .expect("poisoned lock")
+            .insert(key, exons.clone());
+        Ok(exons)
+    }
+
+    /// Return the number of cached transcript alignments.
+    pub fn len(&self) -> usize {
+        self.exons.lock().expect("poisoned lock").len()
+    }
+
+    /// Return whether no transcript alignment is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Build the exon boundaries as stored in `ExonBoundaryCache`.
+fn exon_boundaries(exon_info: &[TxExonsRecord]) -> Vec<(i32, i32)> {
+    let mut exon_starts = exon_info.iter().map(|r| r.tx_start_i).collect::<Vec<_>>();
+    exon_starts.sort();
+    let mut exon_ends = exon_info.iter().map(|r| r.tx_end_i).collect::<Vec<_>>();
+    exon_ends.sort();
+    exon_starts.push(
+        *exon_ends
+            .last()
+            .expect("should not happen; must have at least one exon"),
+    );
+    exon_ends.push(i32::MAX);
+    exon_starts.into_iter().zip(exon_ends).collect()
+}
+
+/// Return the index of the exon in `exons` containing `pos`, if any.
+fn find_exon(exons: &[(i32, i32)], pos: i32) -> Option<usize> {
+    let idx = exons.partition_point(|&(_, end)| end <= pos);
+    exons
+        .get(idx)
+        .and_then(|&(start, end)| (start <= pos && pos < end).then_some(idx))
+}
+
 /// Normalizes variants (5' and 3' shifting).
 pub struct Normalizer<'a> {
     pub provider: Arc<dyn Provider + Send + Sync>,
     pub validator: Arc<dyn Validator + Send + Sync>,
     pub config: Config,
     pub mapper: &'a variant::Mapper,
+    exon_cache: Option<ExonBoundaryCache>,
 }
 
 /// Helper type used in `Normalizer::check_and_guard()`.
@@ -112,11 +302,43 @@ impl<'a> Normalizer<'a> {
             provider,
             validator,
             config,
+            exon_cache: None,
         }
     }
 
+    /// Cache the exon boundaries of transcripts on first access when normalizing.
+    ///
+    /// This avoids querying and sorting the exons of a transcript for each variant, e.g., when
+    /// normalizing many variants on transcripts with many exons.
+    pub fn with_exon_cache(mut self) -> Self {
+        self.exon_cache = Some(ExonBoundaryCache::default());
+        self
+    }
+
+    /// Return the exon boundary cache, if enabled.
+    pub fn exon_cache(&self) -> Option<&ExonBoundaryCache> {
+        self.exon_cache.as_ref()
+    }
+
+    /// Return the window size for normalizing `var`, see `Config::window_size_g` etc.
+    pub fn effective_window_size(&self, var: &HgvsVariant) -> usize {
+        let window_size = match var {
+            HgvsVariant::GenomeVariant { .. } | HgvsVariant::MtVariant { .. } => {
+                self.config.window_size_g
+            }
+            HgvsVariant::CdsVariant { .. } => self.config.window_size_c,
+            HgvsVariant::TxVariant { .. } | HgvsVariant::RnaVariant { .. } => {
+                self.config.window_size_n
+            }
+            _ => None,
+        };
+        window_size.unwrap_or(self.config.window_size)
+    }
+
     pub fn normalize(&self, var: &HgvsVariant) -> Result<HgvsVariant, Error> {
         let is_genome = matches!(&var, HgvsVariant::GenomeVariant { .. });
+        // Select the window before CDS variants are translated to transcript variants.
+        let window_size = self.effective_window_size(var);
 
         // Run the pre-normalization checks (a) whether trying to normalize the variant is an
         // error, (b) whether the variant should be returned as is, and (c) whether the variant
@@ -134,12 +356,82 @@ impl<'a> Normalizer<'a> {
         // the alleles.
         let boundary = self.get_boundary(&var)?;
         let (start, end, reference, alternative) =
-            self.normalize_alleles(&var, boundary.clone())?;
+            self.normalize_alleles(&var, boundary.clone(), window_size)?;
 
         // Build a new variant from the given alleles and possibly project back to the CDS.
         self.build_result(var, start, end, reference, alternative, boundary, cds_to_tx)
     }
 
+    /// Return the 0-based, half-open range of the normalized variant without building it.
+    ///
+    /// The range equals `loc_range()` of the result of `normalize()`.  CDS variants are
+    /// normalized on the transcript, so their range is given in transcript (`n.`) positions,
+    /// i.e., it equals `loc_range()` of the normalized variant after `Mapper::c_to_n()`.
+    pub fn get_normalized_position(&self, var: &HgvsVariant) -> Result<Range<i32>, Error> {
+        let is_genome = matches!(&var, HgvsVariant::GenomeVariant { .. });
+        let window_size = self.effective_window_size(var);
+
+        let CheckAndGuardResult { var, as_is, .. } = self.check_and_guard(var, is_genome)?;
+        if as_is {
+            let var = if let HgvsVariant::CdsVariant { .. } = var {
+                self.mapper
+                    .c_to_n(&var)
+                    .map_err(|_e| Error::CToNMappingFailed(format!("{}", var)))?
+            } else {
+                var
+            };
+            return var
+                .loc_range()
+                .ok_or_else(|| Error::NoPosition(format!("{}", var)));
+        }
+
+        let boundary = self.get_boundary(&var)?;
+        let (start, end, reference, alternative) =
+            self.normalize_alleles(&var, boundary.clone(), window_size)?;
+        let (ref_start, ref_end, _edit) =
+            self.build_result_edit(&var, start, end, reference, alternative, &boundary)?;
+
+        Ok((ref_start - 1)..ref_end)
+    }
+
+    /// Normalize all `variants` in parallel.
+    ///
+    /// The results are returned in the order of `variants`.
+    pub fn normalize_batch(&self, variants: &[HgvsVariant]) -> Vec<Result<HgvsVariant, Error>> {
+        self.normalize_batch_with_progress(variants, |_| ())
+    }
+
+    /// Normalize all `variants` in parallel and report progress.
+    ///
+    /// `progress` is called with the number of normalized variants after each batch of 100
+    /// variants, possibly from several threads.  The results are returned in the order of
+    /// `variants`.
+    pub fn normalize_batch_with_progress<F>(
+        &self,
+        variants: &[HgvsVariant],
+        progress: F,
+    ) -> Vec<Result<HgvsVariant, Error>>
+    where
+        F: Fn(usize) + Sync,
+    {
+        let completed = AtomicUsize::new(0);
+        variants
+            .par_chunks(PROGRESS_BATCH_SIZE)
+            .map(|chunk| {
+                let results = chunk
+                    .iter()
+                    .map(|var| self.normalize(var))
+                    .collect::<Vec<_>>();
+                let count = completed.fetch_add(chunk.len(), atomic::Ordering::Relaxed);
+                progress(count + chunk.len());
+                results
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
     // # Args
     //
     // * `is_genome` -- allows for disabling length validation for genome (where contigs are likely
@@ -154,9 +446,11 @@ impl<'a> Normalizer<'a> {
             .validate(&var)
             .map_err(|e| Error::Validation(e.to_string()))?;
 
-        // Bail out if we cannot reliably normalize.
+        // Bail out if we cannot reliably normalize.  Repeats are not shifted as they are
+        // already given relative to the repeat unit.
         if var.mu_na_edit().map(|e| !e.is_certain()).unwrap_or(true)
             || var.mu_loc_range().map(|e| !e.is_certain()).unwrap_or(false)
+            || matches!(var.na_edit(), Some(NaEdit::Repeat { .. }))
         {
             return Ok(CheckAndGuardResult {
                 var,
@@ -169,9 +463,18 @@ impl<'a> Normalizer<'a> {
         if let HgvsVariant::ProtVariant { .. } = var {
             return Err(Error::ProteinVariant(format!("{}", var)));
         }
+        // Shuffling would have to wrap around the origin of circular molecules.
+        if let HgvsVariant::CircularVariant { .. } = var {
+            return Err(Error::CircularVariant(format!("{}", var)));
+        }
 
         // NB: once we support gene conversions, guard against this here as well.
 
+        // Guard against providers that cannot serve the reference sequence.
+        self.provider
+            .capabilities()
+            .require(variant::required_seq_capability(&var))?;
+
         let var = if self.config.replace_reference {
             self.mapper
                 .replace_reference(var.clone())
@@ -216,20 +519,16 @@ impl<'a> Normalizer<'a> {
             _ => Ok(()),
         }?;
 
-        fn valid_seq_len(provider: &dyn Provider, ac: &str, len: usize) -> Result<bool, Error> {
-            let res = provider.get_seq_part(ac, Some(len - 1), Some(len))?;
+        fn valid_seq_len(provider: &dyn Provider, ac: &str, len: i64) -> Result<bool, Error> {
+            let res = provider.get_seq_part_i64(ac, Some(len - 1), Some(len))?;
             Ok(!res.is_empty())
         }
 
         // Bail out when coordinates are out of bounds.
-        if let Some(var_loc_range) = var.loc_range() {
+        if let Some(var_loc_range) = var.loc_range_i64() {
             if var_loc_range.start < 0
                 || !is_genome
-                    && !valid_seq_len(
-                        self.provider.as_ref(),
-                        var.accession(),
-                        var_loc_range.end as usize,
-                    )?
+                    && !valid_seq_len(self.provider.as_ref(), var.accession(), var_loc_range.end)?
             {
                 return Err(Error::CoordinatesOutOfBounds(format!("{}", &var)));
             }
@@ -285,70 +584,54 @@ impl<'a> Normalizer<'a> {
             let cds_end = tx_info.cds_end_i;
 
             // Obtain exon info.
-            let exon_info = self.provider.as_ref().get_tx_exons(
-                var.accession(),
-                alt_ac,
-                &self.config.alt_aln_method,
-            )?;
-            let mut exon_starts = exon_info.iter().map(|r| r.tx_start_i).collect::<Vec<_>>();
-            exon_starts.sort();
-            let mut exon_ends = exon_info.iter().map(|r| r.tx_end_i).collect::<Vec<_>>();
-            exon_ends.sort();
-            exon_starts.push(
-                *exon_ends
-                    .last()
-                    .expect("should not happen; must have at least one exon"),
-            );
-            exon_ends.push(i32::MAX);
-
-            // Find the end pos of the exon where the var locates.
-            let _left = 0;
-            let _right = i32::MAX;
+            let load_exons = || -> Result<Vec<(i32, i32)>, Error> {
+                let exon_info = self.provider.as_ref().get_tx_exons(
+                    var.accession(),
+                    alt_ac,
+                    &self.config.alt_aln_method,
+                )?;
+                Ok(exon_boundaries(&exon_info))
+            };
+            let exons = match &self.exon_cache {
+                Some(cache) => cache.get_or_try_insert_with(
+                    var.accession(),
+                    alt_ac,
+                    &self.config.alt_aln_method,
+                    load_exons,
+                )?,
+                None => Arc::new(load_exons()?),
+            };
 
-            // NB: the following content is from the original Python code.
-            // TODO: #242: implement methods to find tx regions
+            // Find the exons containing the first and last base of the variant.
             let loc_range = var
                 .loc_range()
                 .expect("location must have a concrete position");
-            let i = exon_starts
-                .iter()
-                .enumerate()
-                .find(|(idx, _x)| {
-                    loc_range.start >= exon_starts[*idx] && loc_range.start < exon_ends[*idx]
-                })
-                .ok_or(Error::ExonNotFoundForStart(format!("{}", &var)))?
-                .0;
-            let j = exon_starts
-                .iter()
-                .enumerate()
-                .find(|(idx, _x)| {
-                    loc_range.end > exon_starts[*idx] && loc_range.end - 1 < exon_ends[*idx]
-                })
-                .ok_or(Error::ExonNotFoundForEnd(format!("{}", &var)))?
-                .0;
+            let i = find_exon(&exons, loc_range.start)
+                .ok_or(Error::ExonNotFoundForStart(format!("{}", &var)))?;
+            let j = find_exon(&exons, loc_range.end - 1)
+                .ok_or(Error::ExonNotFoundForEnd(format!("{}", &var)))?;
 
             if i != j {
                 return Err(Error::ExonIntronBoundary(format!("{}", &var)));
             }
 
-            let mut left = exon_starts[i];
-            let mut right = exon_ends[i];
+            let (mut left, mut right) = exons[i];
 
-            if let Some(cds_start) = cds_start {
-                if loc_range.end - 1 < cds_start {
-                    right = std::cmp::min(right, cds_start);
-                } else if loc_range.start >= cds_start {
-                    left = std::cmp::max(left, cds_start);
-                } else {
-                    return Err(Error::UtrExonBoundary(format!("{}", &var)));
-                }
-            }
+            // Insertions between the last UTR base and the first CDS base (or vice versa)
+            // are flanked by bases on both sides of the boundary.  They are kept on the side
+            // of the boundary opposite to the shuffling direction so they stay in place.
+            let is_ins_at = |boundary: i32| {
+                matches!(var.na_edit(), Some(NaEdit::Ins { .. }))
+                    && loc_range.start + 1 == boundary
+                    && loc_range.end - 1 == boundary
+            };
+            let shuffle_right = self.config.shuffle_direction == Direction::FiveToThree;
 
-            if let Some(cds_end) = cds_end {
-                if loc_range.start >= cds_end {
-                    left = std::cmp::max(left, cds_end);
-                } else if loc_range.end - 1 < cds_end {
-                    right = std::cmp::min(right, cds_end);
+            for boundary in [cds_start, cds_end].into_iter().flatten() {
+                if loc_range.end - 1 < boundary || (shuffle_right && is_ins_at(boundary)) {
+                    right = std::cmp::min(right, boundary);
+                } else if loc_range.start >= boundary || is_ins_at(boundary) {
+                    left = std::cmp::max(left, boundary);
                 } else {
                     return Err(Error::UtrExonBoundary(format!("{}", &var)));
                 }
@@ -366,9 +649,9 @@ impl<'a> Normalizer<'a> {
         &self,
         var: &HgvsVariant,
         boundary: Range<i32>,
+        win_size: usize,
     ) -> Result<(i32, i32, String, String), Error> {
         let (reference, alternative) = self.get_ref_alt(var, &boundary)?;
-        let win_size = self.config.window_size;
 
         if self.config.shuffle_direction == Direction::FiveToThree {
             self.normalize_alleles_5_to_3(
@@ -415,7 +698,8 @@ impl<'a> Normalizer<'a> {
             let ref_seq = self.fetch_bounded_seq(
                 var,
                 base - 1,
-                base + stop - 1 + win_size,
+                // Saturate for positions close to `i32::MAX`, the boundary clamps the end.
+                (base + stop - 1).saturating_add(win_size),
                 win_size,
                 &boundary,
             )?;
@@ -513,6 +797,23 @@ impl<'a> Normalizer<'a> {
         boundary: Range<i32>,
         cds_to_tx: bool,
     ) -> Result<HgvsVariant, Error> {
+        let (ref_start, ref_end, edit) =
+            self.build_result_edit(&var, start, end, reference, alternative, &boundary)?;
+        self.build_result_construct(var, ref_start, ref_end, edit, cds_to_tx)
+    }
+
+    /// Compute the 1-based, inclusive positions and the edit of the normalized variant.
+    ///
+    /// NB: The parameter start/end are 1-based!
+    fn build_result_edit(
+        &self,
+        var: &HgvsVariant,
+        start: i32,
+        end: i32,
+        reference: String,
+        alternative: String,
+        boundary: &Range<i32>,
+    ) -> Result<(i32, i32, NaEdit), Error> {
         let ref_len = reference.len() as i32;
         let alt_len = alternative.len() as i32;
 
@@ -525,11 +826,11 @@ impl<'a> Normalizer<'a> {
             }
             Ordering::Greater => self.build_result_len_gt(
                 ref_len,
-                &var,
+                var,
                 start,
                 alt_len,
                 end,
-                &boundary,
+                boundary,
                 &alternative,
                 &reference,
             )?,
@@ -537,7 +838,7 @@ impl<'a> Normalizer<'a> {
 
         // Ensure the start is not 0.
         let (ref_start, ref_end, edit, _reference, alternative) = if ref_start == 0 {
-            let reference = self.fetch_bounded_seq(&var, 0, 1, 0, &boundary)?;
+            let reference = self.fetch_bounded_seq(var, 0, 1, 0, boundary)?;
             let alternative = format!("{}{}", alternative, &reference);
 
             (
@@ -555,9 +856,9 @@ impl<'a> Normalizer<'a> {
         };
 
         // Ensure the end is not outside of reference sequence.
-        let tgt_len = self.get_tgt_len(&var)?;
+        let tgt_len = self.get_tgt_len(var)?;
         let (ref_start, ref_end, edit) = if ref_end == tgt_len.saturating_add(1) {
-            let reference = self.fetch_bounded_seq(&var, tgt_len - 1, tgt_len, 0, &boundary)?;
+            let reference = self.fetch_bounded_seq(var, tgt_len - 1, tgt_len, 0, boundary)?;
             let alternative = format!("{}{}", &reference, alternative);
             (
                 tgt_len,
@@ -571,8 +872,7 @@ impl<'a> Normalizer<'a> {
             (ref_start, ref_end, edit)
         };
 
-        let res = self.build_result_construct(var, ref_start, ref_end, edit, cds_to_tx)?;
-        Ok(res)
+        Ok((ref_start, ref_end, edit))
     }
 
     fn build_result_construct(
@@ -1038,16 +1338,37 @@ fn normalize_alleles_right(
     Ok((start.try_into()?, stop.try_into()?, reference, alternative))
 }
 
+impl HgvsVariant {
+    /// Return whether normalizing the variant with `config` leaves it unchanged.
+    ///
+    /// The variant is normalized using the provider and validator of `mapper` and compared
+    /// to the result with `HgvsVariant::equivalent()`, i.e., explicitly given reference
+    /// sequences are ignored.  Use `is_trivially_normalized()` first to avoid the
+    /// normalization for most variants.
+    pub fn is_normalized(&self, mapper: &variant::Mapper, config: &Config) -> Result<bool, Error> {
+        let normalizer = Normalizer::new(
+            mapper,
+            mapper.provider(),
+            mapper.validator(),
+            config.clone(),
+        );
+        Ok(normalizer.normalize(self)?.equivalent(self))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use test_log::test;
 
     use anyhow::Error;
-    use std::{str::FromStr, sync::Arc};
+    use std::{
+        str::FromStr,
+        sync::{Arc, Mutex},
+    };
 
     use pretty_assertions::assert_eq;
 
-    use super::{Config, Direction, Normalizer};
+    use super::{Config, Direction, ExonBoundaryCache, Normalizer};
     use crate::{
         data::uta_sr::test_helpers::build_provider,
         mapper::variant::Mapper,
@@ -1055,6 +1376,52 @@ mod test {
         validator::IntrinsicValidator,
     };
 
+    /// Return a provider for `chrR`, which has a 200 bp `(CA)n` repeat at `chrR:g.51_250`.
+    fn repeat_provider() -> Result<Arc<crate::data::gff3::Provider>, Error> {
+        Ok(Arc::new(crate::data::gff3::Provider::new(
+            crate::data::gff3::Config {
+                gff3_path: String::from("tests/data/normalizer/repeat.gff3"),
+                fasta_path: String::from("tests/data/normalizer/repeat.fa"),
+            },
+        )?))
+    }
+
+    #[test]
+    fn config_builder() {
+        let config = Config::builder()
+            .shuffle_direction(Direction::ThreeToFive)
+            .cross_boundaries(true)
+            .replace_reference(false)
+            .alt_aln_method("blat")
+            .window_size(50)
+            .window_size_g(500)
+            .window_size_c(30)
+            .window_size_n(10)
+            .validate(false)
+            .build();
+
+        assert_eq!(config.shuffle_direction, Direction::ThreeToFive);
+        assert!(config.cross_boundaries);
+        assert!(!config.replace_reference);
+        assert_eq!(config.alt_aln_method, "blat");
+        assert_eq!(config.window_size, 50);
+        assert_eq!(config.window_size_g, Some(500));
+        assert_eq!(config.window_size_c, Some(30));
+        assert_eq!(config.window_size_n, Some(10));
+        assert!(!config.validate);
+
+        let default = Config::builder().build();
+        assert_eq!(default.shuffle_direction, Direction::FiveToThree);
+        assert!(!default.cross_boundaries);
+        assert!(default.replace_reference);
+        assert_eq!(default.alt_aln_method, "splign");
+        assert_eq!(default.window_size, 20);
+        assert_eq!(default.window_size_g, None);
+        assert_eq!(default.window_size_c, None);
+        assert_eq!(default.window_size_n, None);
+        assert!(default.validate);
+    }
+
     #[test]
     fn test_sync() {
         fn is_sync<T: Sync>() {}
@@ -1072,45 +1439,334 @@ mod test {
                 mapper,
                 provider.clone(),
                 validator.clone(),
-                Config {
-                    shuffle_direction: Direction::FiveToThree,
-                    cross_boundaries: true,
-                    ..Default::default()
-                },
+                Config::builder()
+                    .shuffle_direction(Direction::FiveToThree)
+                    .cross_boundaries(true)
+                    .build(),
             ),
             Normalizer::new(
                 mapper,
                 provider.clone(),
                 validator.clone(),
-                Config {
-                    shuffle_direction: Direction::ThreeToFive,
-                    cross_boundaries: true,
-                    ..Default::default()
-                },
+                Config::builder()
+                    .shuffle_direction(Direction::ThreeToFive)
+                    .cross_boundaries(true)
+                    .build(),
             ),
             Normalizer::new(
                 mapper,
                 provider.clone(),
                 validator.clone(),
-                Config {
-                    shuffle_direction: Direction::FiveToThree,
-                    cross_boundaries: false,
-                    ..Default::default()
-                },
+                Config::builder()
+                    .shuffle_direction(Direction::FiveToThree)
+                    .cross_boundaries(false)
+                    .build(),
             ),
             Normalizer::new(
                 mapper,
                 provider.clone(),
                 validator,
-                Config {
-                    shuffle_direction: Direction::ThreeToFive,
-                    cross_boundaries: false,
-                    ..Default::default()
-                },
+                Config::builder()
+                    .shuffle_direction(Direction::ThreeToFive)
+                    .cross_boundaries(false)
+                    .build(),
             ),
         ))
     }
 
+    #[test]
+    fn normalize_batch() -> Result<(), Error> {
+        let mapper = Mapper::new(&Default::default(), build_provider()?);
+        let (norm, _norm5, _normc, _norm5c) = normalizers(&mapper)?;
+
+        let inputs = [
+            "NM_000088.3:c.589_600inv",
+            "NM_001166478.1:c.31del",
+            "NM_001166478.1:c.35_36insT",
+            "NM_001166478.1:c.36_37insTC",
+            "NM_001166478.1:c.35_36dup",
+            "NM_001166478.1:c.2_7delinsTTTAGA",
+            "NM_001166478.1:c.30_31insT",
+            "NM_001166478.1:c.59delG",
+            "NM_001166478.1:c.36_37insTCTCTC",
+            "NM_000051.3:c.14_15insT",
+            "NM_000051.3:c.-5_-4insA",
+            "NM_000051.3:c.-4_-3insAC",
+            "NC_000006.11:g.49917122_49917123insA",
+            "NM_001001656.1:c.935T>C",
+            "NM_001001656.1:c.945dup",
+            "NM_001001656.1:c.946G>C",
+            "NM_001001656.1:c.935_946del",
+        ];
+        let variants = inputs
+            .iter()
+            .cycle()
+            .take(250)
+            .map(|s| HgvsVariant::from_str(s))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let to_strings = |results: Vec<Result<HgvsVariant, super::Error>>| {
+            results
+                .into_iter()
+                .map(|res| res.map(|var| format!("{var}")).map_err(|e| e.to_string()))
+                .collect::<Vec<_>>()
+        };
+        let sequential = to_strings(variants.iter().map(|var| norm.normalize(var)).collect());
+        assert_eq!(to_strings(norm.normalize_batch(&variants)), sequential);
+
+        let reported = Mutex::new(Vec::new());
+        let results = norm
+            .normalize_batch_with_progress(&variants, |count| reported.lock().unwrap().push(count));
+        assert_eq!(to_strings(results), sequential);
+        // Batches may complete in any order, so only the number of reports, their
+        // increase, and the final count are fixed.
+        let mut reported = reported.into_inner().unwrap();
+        reported.sort();
+        assert_eq!(reported.len(), 3);
+        assert!(reported.windows(2).all(|w| w[0] < w[1]), "{reported:?}");
+        assert_eq!(reported.last(), Some(&250));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_normalized_position() -> Result<(), Error> {
+        let provider = repeat_provider()?;
+        let mapper = Mapper::new(&Default::default(), provider.clone());
+        let (norm, norm5, _normc, _norm5c) = normalizers(&mapper)?;
+
+        let inputs = [
+            "chrR:g.61_62del",
+            "chrR:g.61_62dup",
+            "chrR:g.60_61insCA",
+            "chrR:g.60_61insGG",
+            "chrR:g.61_62delinsGT",
+            "chrR:g.61C>T",
+            "chrR:g.61C=",
+            "chrR:g.5_6del",
+        ];
+        for norm in [&norm, &norm5] {
+            for input in inputs {
+                let var = HgvsVariant::from_str(input)?;
+                let res = norm.normalize(&var)?;
+
+                assert_eq!(
+                    Some(norm.get_normalized_position(&var)?),
+                    res.loc_range(),
+                    "{input} ~ {res}"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn is_normalized() -> Result<(), Error> {
+        let provider = repeat_provider()?;
+        let mapper = Mapper::new(&Default::default(), provider);
+        let config = Config::default();
+        let config5 = Config::builder()
+            .shuffle_direction(Direction::ThreeToFive)
+            .build();
+
+        let var = HgvsVariant::from_str("chrR:g.61C>T")?;
+        assert!(var.is_trivially_normalized());
+        assert!(var.is_normalized(&mapper, &config)?);
+        assert!(var.is_normalized(&mapper, &config5)?);
+
+        let var = HgvsVariant::from_str("chrR:g.249_250dup")?;
+        assert!(!var.is_trivially_normalized());
+        assert!(var.is_normalized(&mapper, &config)?);
+        assert!(!var.is_normalized(&mapper, &config5)?);
+
+        let var = HgvsVariant::from_str("chrR:g.60_61insCA")?;
+        assert!(!var.is_trivially_normalized());
+        assert!(!var.is_normalized(&mapper, &config)?);
+        assert!(!var.is_normalized(&mapper, &config5)?);
+        assert!(mapper
+            .normalizer()?
+            .normalize(&var)?
+            .is_normalized(&mapper, &config)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_utr_cds_boundary() -> Result<(), Error> {
+        // `TX1.1` has `A` at `c.-2`, `c.-1`, `c.1`, `c.65`, `c.66`, `c.*1`, and `c.*2`.
+        let provider = Arc::new(crate::data::gff3::Provider::new(
+            crate::data::gff3::Config {
+                gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+                fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+            },
+        )?);
+        let mapper = Mapper::new(&Default::default(), provider);
+        let (norm, norm5, normc, norm5c) = normalizers(&mapper)?;
+
+        // (input, 3' result, 5' result) without crossing boundaries.
+        for (input, exp_3, exp_5) in [
+            ("TX1.1:c.-2_-1insA", "TX1.1:c.-1dupA", "TX1.1:c.-2dupA"),
+            ("TX1.1:c.-1_1insA", "TX1.1:c.-1dupA", "TX1.1:c.1dupA"),
+            ("TX1.1:c.1_2insA", "TX1.1:c.1dupA", "TX1.1:c.1dupA"),
+            ("TX1.1:c.65_66insA", "TX1.1:c.66dupA", "TX1.1:c.65dupA"),
+            ("TX1.1:c.66_*1insA", "TX1.1:c.66dupA", "TX1.1:c.*1dupA"),
+            ("TX1.1:c.*1_*2insA", "TX1.1:c.*2dupA", "TX1.1:c.*1dupA"),
+        ] {
+            let var = HgvsVariant::from_str(input)?;
+            assert_eq!(format!("{}", normc.normalize(&var)?), exp_3, "{input}");
+            assert_eq!(format!("{}", norm5c.normalize(&var)?), exp_5, "{input}");
+        }
+
+        // Crossing boundaries shuffles the same variants into the CDS/UTR.
+        let var = HgvsVariant::from_str("TX1.1:c.-1_1insA")?;
+        assert_eq!(format!("{}", norm.normalize(&var)?), "TX1.1:c.1dupA");
+        assert_eq!(format!("{}", norm5.normalize(&var)?), "TX1.1:c.-2dupA");
+
+        // Variants affecting bases on both sides of the boundary cannot be normalized.
+        let var = HgvsVariant::from_str("TX1.1:c.-1_1dup")?;
+        assert!(matches!(
+            normc.normalize(&var),
+            Err(super::Error::UtrExonBoundary(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn window_size_per_type() -> Result<(), Error> {
+        let provider = repeat_provider()?;
+        let mapper = Mapper::new(&Default::default(), provider.clone());
+        let normalizer = |config: Config| {
+            Normalizer::new(
+                &mapper,
+                provider.clone(),
+                Arc::new(IntrinsicValidator::new(true)),
+                config,
+            )
+        };
+        let default = normalizer(Config::default());
+        let wide = normalizer(Config::builder().window_size_g(500).build());
+        let wide_5 = normalizer(
+            Config::builder()
+                .shuffle_direction(Direction::ThreeToFive)
+                .window_size_g(500)
+                .build(),
+        );
+
+        let var_g = HgvsVariant::from_str("chrR:g.61_90del")?;
+        let var_c = HgvsVariant::from_str("NM_000001.1:c.10del")?;
+        let var_n = HgvsVariant::from_str("NR_000001.1:n.10del")?;
+        assert_eq!(default.effective_window_size(&var_g), 20);
+        assert_eq!(wide.effective_window_size(&var_g), 500);
+        assert_eq!(wide.effective_window_size(&var_c), 20);
+        assert_eq!(wide.effective_window_size(&var_n), 20);
+        let narrow_tx = normalizer(Config::builder().window_size_c(5).window_size_n(10).build());
+        assert_eq!(narrow_tx.effective_window_size(&var_g), 20);
+        assert_eq!(narrow_tx.effective_window_size(&var_c), 5);
+        assert_eq!(narrow_tx.effective_window_size(&var_n), 10);
+
+        // The deletion is shuffled across the whole repeat within a single window.
+        assert_eq!(
+            format!("{}", NoRef(&wide.normalize(&var_g)?)),
+            "chrR:g.221_250del"
+        );
+        assert_eq!(
+            format!("{}", NoRef(&wide_5.normalize(&var_g)?)),
+            "chrR:g.51_80del"
+        );
+        // The default window is extended step by step and yields the same result.
+        assert_eq!(
+            format!("{}", NoRef(&default.normalize(&var_g)?)),
+            "chrR:g.221_250del"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_exon() {
+        let exons = [(0, 100), (100, 250), (250, 300), (300, i32::MAX)];
+        assert_eq!(super::find_exon(&exons, 0), Some(0));
+        assert_eq!(super::find_exon(&exons, 99), Some(0));
+        assert_eq!(super::find_exon(&exons, 100), Some(1));
+        assert_eq!(super::find_exon(&exons, 299), Some(2));
+        assert_eq!(super::find_exon(&exons, 300), Some(3));
+        assert_eq!(super::find_exon(&exons, -1), None);
+        assert_eq!(super::find_exon(&[(10, 20), (30, 40)], 25), None);
+    }
+
+    #[test]
+    fn with_exon_cache() -> Result<(), Error> {
+        let mapper = Mapper::new(&Default::default(), build_provider()?);
+        let (_norm, _norm5, normc, _norm5c) = normalizers(&mapper)?;
+        let cached = Normalizer::new(
+            &mapper,
+            mapper.provider(),
+            normc.validator.clone(),
+            normc.config.clone(),
+        )
+        .with_exon_cache();
+        assert!(normc.exon_cache().is_none());
+        assert!(cached.exon_cache().is_some_and(|cache| cache.is_empty()));
+
+        for s in [
+            "NM_001166478.1:c.35_36insT",
+            "NM_001166478.1:c.59delG",
+            "NM_000051.3:c.-4_-3insAC",
+            "NM_001166478.1:c.31del",
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            assert_eq!(
+                format!("{}", cached.normalize(&var)?),
+                format!("{}", normc.normalize(&var)?),
+                "{s}"
+            );
+        }
+        assert_eq!(cached.exon_cache().map(|cache| cache.len()), Some(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn exon_cache_per_alignment() -> Result<(), Error> {
+        let cache = ExonBoundaryCache::default();
+        let exons = |exons: Vec<(i32, i32)>| move || Ok(exons);
+
+        let splign = cache.get_or_try_insert_with(
+            "NM_01234.5",
+            "NC_000001.11",
+            "splign",
+            exons(vec![(0, 100)]),
+        )?;
+        let blat = cache.get_or_try_insert_with(
+            "NM_01234.5",
+            "NC_000001.11",
+            "blat",
+            exons(vec![(0, 90)]),
+        )?;
+        let other_alt_ac = cache.get_or_try_insert_with(
+            "NM_01234.5",
+            "NC_000001.10",
+            "splign",
+            exons(vec![(0, 80)]),
+        )?;
+        assert_eq!(*splign, vec![(0, 100)]);
+        assert_eq!(*blat, vec![(0, 90)]);
+        assert_eq!(*other_alt_ac, vec![(0, 80)]);
+        assert_eq!(cache.len(), 3);
+
+        let cached = cache.get_or_try_insert_with(
+            "NM_01234.5",
+            "NC_000001.11",
+            "splign",
+            exons(vec![(0, 10)]),
+        )?;
+        assert_eq!(*cached, vec![(0, 100)]);
+
+        Ok(())
+    }
+
     #[test]
     fn normalize_cds_3_prime_shuffling() -> Result<(), Error> {
         let mapper = Mapper::new(&Default::default(), build_provider()?);
@@ -0,0 +1,228 @@
+//! Builders for `HgvsVariant` values.
+//!
+//! Each builder is started with the accession, e.g.,
+//! `CdsVariantBuilder::new("NM_000088.3").loc_edit(loc_edit).build()`.
+
+use crate::parser::{
+    Accession, CdsLocEdit, Error, GeneSymbol, GenomeLocEdit, HgvsVariant, MtLocEdit, ProtLocEdit,
+    RnaLocEdit, TxLocEdit,
+};
+
+/// Define a builder for the `HgvsVariant` variant `$variant` with location and edit
+/// type `$loc_edit`.
+macro_rules! variant_builder {
+    ($(#[$meta:meta])* $name:ident, $variant:ident, $loc_edit:ty) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub struct $name {
+            accession: Accession,
+            gene_symbol: Option<GeneSymbol>,
+            loc_edit: Option<$loc_edit>,
+        }
+
+        impl $name {
+            /// Start building a variant on `accession`.
+            pub fn new(accession: impl Into<String>) -> Self {
+                Self {
+                    accession: Accession::from(accession.into()),
+                    gene_symbol: None,
+                    loc_edit: None,
+                }
+            }
+
+            /// Set the optional gene symbol.
+            pub fn gene_symbol(mut self, gene_symbol: impl Into<String>) -> Self {
+                self.gene_symbol = Some(GeneSymbol::from(gene_symbol.into()));
+                self
+            }
+
+            /// Set the location and edit, required.
+            pub fn loc_edit(mut self, loc_edit: $loc_edit) -> Self {
+                self.loc_edit = Some(loc_edit);
+                self
+            }
+
+            /// Return the variant, fails if the location and edit are missing.
+            pub fn build(self) -> Result<HgvsVariant, Error> {
+                Ok(HgvsVariant::$variant {
+                    loc_edit: self.loc_edit.ok_or_else(|| {
+                        Error::IncompleteVariant(format!("{}: loc_edit", self.accession.value))
+                    })?,
+                    accession: self.accession,
+                    gene_symbol: self.gene_symbol,
+                })
+            }
+        }
+    };
+}
+
+variant_builder!(
+    /// Builder for `HgvsVariant::GenomeVariant`, i.e., genome (`g.`) variants.
+    GenomeVariantBuilder,
+    GenomeVariant,
+    GenomeLocEdit
+);
+variant_builder!(
+    /// Builder for `HgvsVariant::CdsVariant`, i.e., coding (`c.`) variants.
+    CdsVariantBuilder,
+    CdsVariant,
+    CdsLocEdit
+);
+variant_builder!(
+    /// Builder for `HgvsVariant::TxVariant`, i.e., transcript (`n.`) variants.
+    TxVariantBuilder,
+    TxVariant,
+    TxLocEdit
+);
+variant_builder!(
+    /// Builder for `HgvsVariant::RnaVariant`, i.e., RNA (`r.`) variants.
+    RnaVariantBuilder,
+    RnaVariant,
+    RnaLocEdit
+);
+variant_builder!(
+    /// Builder for `HgvsVariant::ProtVariant`, i.e., protein (`p.`) variants.
+    ProtVariantBuilder,
+    ProtVariant,
+    ProtLocEdit
+);
+variant_builder!(
+    /// Builder for `HgvsVariant::MtVariant`, i.e., mitochondrial (`m.`) variants.
+    MtVariantBuilder,
+    MtVariant,
+    MtLocEdit
+);
+
+impl HgvsVariant {
+    /// Construct a genome (`g.`) variant without gene symbol.
+    pub fn genome(accession: impl Into<String>, loc_edit: GenomeLocEdit) -> Self {
+        HgvsVariant::GenomeVariant {
+            accession: Accession::from(accession.into()),
+            gene_symbol: None,
+            loc_edit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::parser::{
+        CdsFrom, CdsInterval, CdsLocEdit, CdsPos, Error, GenomeInterval, GenomeLocEdit,
+        HgvsVariant, Mu, NaEdit, ProtInterval, ProtLocEdit, ProtPos, ProteinEdit,
+    };
+
+    use super::{CdsVariantBuilder, GenomeVariantBuilder, ProtVariantBuilder};
+
+    fn snv() -> Mu<NaEdit> {
+        Mu::Certain(NaEdit::RefAlt {
+            reference: "A".to_string(),
+            alternative: "T".to_string(),
+        })
+    }
+
+    #[test]
+    fn build_genome_variant() -> Result<(), Error> {
+        let loc_edit = GenomeLocEdit {
+            loc: Mu::Certain(GenomeInterval {
+                start: Some(100),
+                end: Some(100),
+            }),
+            edit: snv(),
+        };
+        let expected = HgvsVariant::GenomeVariant {
+            accession: crate::parser::Accession::new("NC_000001.11"),
+            gene_symbol: None,
+            loc_edit: loc_edit.clone(),
+        };
+
+        assert_eq!(
+            GenomeVariantBuilder::new("NC_000001.11")
+                .loc_edit(loc_edit.clone())
+                .build()?,
+            expected
+        );
+        assert_eq!(HgvsVariant::genome("NC_000001.11", loc_edit), expected);
+        assert_eq!(HgvsVariant::from_str("NC_000001.11:g.100A>T")?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn build_cds_variant() -> Result<(), Error> {
+        let pos = CdsPos {
+            base: 22,
+            offset: Some(1),
+            cds_from: CdsFrom::Start,
+        };
+        let variant = CdsVariantBuilder::new("NM_01234.5")
+            .gene_symbol("TEST")
+            .loc_edit(CdsLocEdit {
+                loc: Mu::Certain(CdsInterval {
+                    start: pos.clone(),
+                    end: pos,
+                }),
+                edit: snv(),
+            })
+            .build()?;
+
+        assert_eq!(
+            variant,
+            HgvsVariant::from_str("NM_01234.5(TEST):c.22+1A>T")?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn build_prot_variant() -> Result<(), Error> {
+        let pos = ProtPos {
+            aa: "Leu".to_string(),
+            number: 10,
+        };
+        let variant = ProtVariantBuilder::new("NP_01234.5")
+            .loc_edit(ProtLocEdit::Ordinary {
+                loc: Mu::Certain(ProtInterval {
+                    start: pos.clone(),
+                    end: pos,
+                }),
+                edit: Mu::Certain(ProteinEdit::Subst {
+                    alternative: "Pro".to_string(),
+                }),
+            })
+            .build()?;
+
+        assert_eq!(variant, HgvsVariant::from_str("NP_01234.5:p.Leu10Pro")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn build_missing_loc_edit() {
+        assert!(matches!(
+            CdsVariantBuilder::new("NM_01234.5")
+                .gene_symbol("TEST")
+                .build(),
+            Err(Error::IncompleteVariant(_))
+        ));
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
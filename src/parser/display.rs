@@ -6,7 +6,10 @@
 
 use std::fmt::Display;
 
-use crate::{parser::ds::*, sequences::aa_to_aa3};
+use crate::{
+    parser::ds::*,
+    sequences::{aa_to_aa1, aa_to_aa3},
+};
 
 /// Newtype that allows to suppress printing of reference bases.
 pub struct NoRef<'a, T>(pub &'a T)
@@ -92,6 +95,7 @@ impl Display for NaEdit {
             NaEdit::Dup { reference } => write!(f, "dup{reference}"),
             NaEdit::InvRef { reference } => write!(f, "inv{reference}"),
             NaEdit::InvNum { count } => write!(f, "inv{count}"),
+            NaEdit::Repeat { sequence, count } => write!(f, "{sequence}[{count}]"),
         }
     }
 }
@@ -129,6 +133,7 @@ impl Display for NoRef<'_, NaEdit> {
             NoRef(NaEdit::Ins { alternative }) => write!(f, "ins{alternative}"),
             NoRef(NaEdit::Dup { .. }) => write!(f, "dup"),
             NoRef(NaEdit::InvRef { .. }) | NoRef(NaEdit::InvNum { .. }) => write!(f, "inv"),
+            NoRef(NaEdit::Repeat { sequence, count }) => write!(f, "{sequence}[{count}]"),
         }
     }
 }
@@ -139,6 +144,7 @@ impl Display for UncertainLengthChange {
             UncertainLengthChange::None => write!(f, ""),
             UncertainLengthChange::Unknown => write!(f, "?"),
             UncertainLengthChange::Known(count) => write!(f, "{count}"),
+            UncertainLengthChange::Range(min, max) => write!(f, "{min}_{max}"),
         }
     }
 }
@@ -149,69 +155,119 @@ impl Display for Accession {
     }
 }
 
+/// Notation to use for the stop codon (terminator) in protein edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TerminatorNotation {
+    /// Three-letter code `Ter` as recommended by HGVS.
+    #[default]
+    Ter,
+    /// One-letter code `*`.
+    Star,
+    /// Legacy code `X`.
+    X,
+}
+
+impl TerminatorNotation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TerminatorNotation::Ter => "Ter",
+            TerminatorNotation::Star => "*",
+            TerminatorNotation::X => "X",
+        }
+    }
+}
+
+/// Configuration for the display of protein edits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProteinDisplayConfig {
+    /// Notation to use for the stop codon in frameshifts.
+    pub terminator_notation: TerminatorNotation,
+    /// Whether to use 1-letter instead of 3-letter amino acid codes.
+    pub use_single_letter_aa: bool,
+}
+
+/// Format the amino acids `seq` with 1- or 3-letter codes as configured in `config`.
+fn fmt_aa(seq: &str, config: &ProteinDisplayConfig) -> String {
+    if config.use_single_letter_aa {
+        aa_to_aa1(seq).expect("aa_to_aa1 conversion failed")
+    } else {
+        aa_to_aa3(seq).expect("aa_to_aa3 conversion failed")
+    }
+}
+
+/// Configuration for the display of variants, see `HgvsVariant::display_with()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayConfig {
+    /// Whether to write the reference bases of nucleic acid edits, see `NoRef`.
+    pub show_reference: bool,
+    /// Whether to write the gene symbol after the accession.
+    pub show_gene_symbol: bool,
+    /// Whether to use 1-letter instead of 3-letter amino acid codes.
+    pub use_single_letter_aa: bool,
+    /// Notation to use for the stop codon in frameshifts.
+    pub terminator: TerminatorNotation,
+}
+
+impl Default for DisplayConfig {
+    /// The configuration used by the `Display` implementation of `HgvsVariant`.
+    fn default() -> Self {
+        Self {
+            show_reference: true,
+            show_gene_symbol: true,
+            use_single_letter_aa: false,
+            terminator: TerminatorNotation::Ter,
+        }
+    }
+}
+
+impl DisplayConfig {
+    fn protein_display_config(&self) -> ProteinDisplayConfig {
+        ProteinDisplayConfig {
+            terminator_notation: self.terminator,
+            use_single_letter_aa: self.use_single_letter_aa,
+        }
+    }
+}
+
+impl ProteinEdit {
+    /// Return a value displaying the edit as configured in `config`.
+    pub fn display_with_config<'a>(
+        &'a self,
+        config: &'a ProteinDisplayConfig,
+    ) -> impl Display + 'a {
+        ProteinEditDisplay { edit: self, config }
+    }
+}
+
+/// Helper for `ProteinEdit::display_with_config()`.
+struct ProteinEditDisplay<'a> {
+    edit: &'a ProteinEdit,
+    config: &'a ProteinDisplayConfig,
+}
+
 impl Display for ProteinEdit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
+        self.display_with_config(&ProteinDisplayConfig::default())
+            .fmt(f)
+    }
+}
+
+impl Display for ProteinEditDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.edit {
             ProteinEdit::Fs {
                 alternative,
-                terminal,
+                terminal: _,
                 length,
-            } => match (alternative, terminal, length) {
-                (None, None, UncertainLengthChange::None) => write!(f, "fsTer"),
-                (None, None, UncertainLengthChange::Unknown) => write!(f, "fsTer?"),
-                (None, None, UncertainLengthChange::Known(count)) => write!(f, "fsTer{count}"),
-                (Some(alt), None, UncertainLengthChange::None) => write!(f, "{alt}fsTer"),
-                (Some(alt), None, UncertainLengthChange::Unknown) => write!(f, "{alt}fsTer?"),
-                (Some(alt), None, UncertainLengthChange::Known(count)) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
-                    write!(f, "{alt}fsTer{count}")
-                }
-                (None, Some(ter), UncertainLengthChange::None) => {
-                    let mut ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
-                    if ter.is_empty() {
-                        ter = "Ter".to_string();
-                    }
-                    write!(f, "fs{ter}")
-                }
-                (None, Some(ter), UncertainLengthChange::Unknown) => {
-                    let mut ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
-                    if ter.is_empty() {
-                        ter = "Ter".to_string();
-                    }
-                    write!(f, "fs{ter}?")
-                }
-                (None, Some(ter), UncertainLengthChange::Known(count)) => {
-                    let mut ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
-                    if ter.is_empty() {
-                        ter = "Ter".to_string();
-                    }
-                    write!(f, "fs{ter}{count}")
-                }
-                (Some(alt), Some(ter), UncertainLengthChange::None) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
-                    let mut ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
-                    if ter.is_empty() {
-                        ter = "Ter".to_string();
-                    }
-                    write!(f, "{alt}fs{ter}")
-                }
-                (Some(alt), Some(ter), UncertainLengthChange::Unknown) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
-                    let mut ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
-                    if ter.is_empty() {
-                        ter = "Ter".to_string();
-                    }
-                    write!(f, "{alt}fs{ter}?")
-                }
-                (Some(alt), Some(ter), UncertainLengthChange::Known(count)) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
-                    let mut ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
-                    if ter.is_empty() {
-                        ter = "Ter".to_string();
-                    }
-                    write!(f, "{alt}fs{ter}{count}")
+            } => {
+                if let Some(alt) = alternative {
+                    let alt = fmt_aa(alt, self.config);
+                    write!(f, "{alt}")?;
                 }
-            },
+                // The terminal is always the stop codon, only the notation is configurable.
+                let ter = self.config.terminator_notation.as_str();
+                write!(f, "fs{ter}{length}")
+            }
             ProteinEdit::Ext {
                 aa_ext,
                 ext_aa,
@@ -219,43 +275,59 @@ impl Display for ProteinEdit {
             } => match (aa_ext, ext_aa, change) {
                 (None, None, UncertainLengthChange::None) => write!(f, "ext"),
                 (None, None, UncertainLengthChange::Unknown) => write!(f, "ext?"),
-                (None, None, UncertainLengthChange::Known(count)) => write!(f, "ext{count}"),
+                (
+                    None,
+                    None,
+                    count @ (UncertainLengthChange::Known(_) | UncertainLengthChange::Range(..)),
+                ) => write!(f, "ext{count}"),
                 (Some(alt), None, UncertainLengthChange::None) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
+                    let alt = fmt_aa(alt, self.config);
                     write!(f, "{alt}ext")
                 }
                 (Some(alt), None, UncertainLengthChange::Unknown) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
+                    let alt = fmt_aa(alt, self.config);
                     write!(f, "{alt}ext?")
                 }
-                (Some(alt), None, UncertainLengthChange::Known(count)) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
+                (
+                    Some(alt),
+                    None,
+                    count @ (UncertainLengthChange::Known(_) | UncertainLengthChange::Range(..)),
+                ) => {
+                    let alt = fmt_aa(alt, self.config);
                     write!(f, "{alt}ext{count}")
                 }
                 (None, Some(ter), UncertainLengthChange::None) => write!(f, "ext{ter}"),
                 (None, Some(ter), UncertainLengthChange::Unknown) => write!(f, "ext{ter}?"),
-                (None, Some(ter), UncertainLengthChange::Known(count)) => {
-                    let ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
+                (
+                    None,
+                    Some(ter),
+                    count @ (UncertainLengthChange::Known(_) | UncertainLengthChange::Range(..)),
+                ) => {
+                    let ter = fmt_aa(ter, self.config);
                     write!(f, "ext{ter}{count}")
                 }
                 (Some(alt), Some(ter), UncertainLengthChange::None) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
-                    let ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
+                    let alt = fmt_aa(alt, self.config);
+                    let ter = fmt_aa(ter, self.config);
                     write!(f, "{alt}ext{ter}")
                 }
                 (Some(alt), Some(ter), UncertainLengthChange::Unknown) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
-                    let ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
+                    let alt = fmt_aa(alt, self.config);
+                    let ter = fmt_aa(ter, self.config);
                     write!(f, "{alt}ext{ter}?")
                 }
-                (Some(alt), Some(ter), UncertainLengthChange::Known(count)) => {
-                    let alt = aa_to_aa3(alt).expect("aa_to_aa3 conversion failed");
-                    let ter = aa_to_aa3(ter).expect("aa_to_aa3 conversion failed");
+                (
+                    Some(alt),
+                    Some(ter),
+                    count @ (UncertainLengthChange::Known(_) | UncertainLengthChange::Range(..)),
+                ) => {
+                    let alt = fmt_aa(alt, self.config);
+                    let ter = fmt_aa(ter, self.config);
                     write!(f, "{alt}ext{ter}{count}")
                 }
             },
             ProteinEdit::Subst { alternative } => {
-                let alternative = aa_to_aa3(alternative).expect("aa_to_aa3 conversion failed");
+                let alternative = fmt_aa(alternative, self.config);
                 if alternative.is_empty() {
                     write!(f, "=")
                 } else {
@@ -263,11 +335,11 @@ impl Display for ProteinEdit {
                 }
             }
             ProteinEdit::DelIns { alternative } => {
-                let alternative = aa_to_aa3(alternative).expect("aa_to_aa3 conversion failed");
+                let alternative = fmt_aa(alternative, self.config);
                 write!(f, "delins{alternative}")
             }
             ProteinEdit::Ins { alternative } => {
-                let alternative = aa_to_aa3(alternative).expect("aa_to_aa3 conversion failed");
+                let alternative = fmt_aa(alternative, self.config);
                 write!(f, "ins{alternative}")
             }
             ProteinEdit::Del => write!(f, "del"),
@@ -284,6 +356,21 @@ impl Display for ProtPos {
     }
 }
 
+impl ProtInterval {
+    /// Write the interval with amino acid codes as configured in `config`.
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        config: &ProteinDisplayConfig,
+    ) -> std::fmt::Result {
+        write!(f, "{}{}", fmt_aa(&self.start.aa, config), self.start.number)?;
+        if self.start != self.end {
+            write!(f, "_{}{}", fmt_aa(&self.end.aa, config), self.end.number)?;
+        }
+        Ok(())
+    }
+}
+
 impl Display for ProtInterval {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.start)?;
@@ -309,6 +396,37 @@ impl Display for ProtLocEdit {
     }
 }
 
+/// Helper for displaying a `ProtLocEdit` as configured, see `HgvsVariant::display_with()`.
+struct ProtLocEditDisplay<'a> {
+    loc_edit: &'a ProtLocEdit,
+    config: ProteinDisplayConfig,
+}
+
+impl Display for ProtLocEditDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.loc_edit {
+            ProtLocEdit::Ordinary { loc, edit } => {
+                match loc {
+                    Mu::Certain(loc) => loc.fmt_with(f, &self.config)?,
+                    Mu::Uncertain(loc) => {
+                        write!(f, "(")?;
+                        loc.fmt_with(f, &self.config)?;
+                        write!(f, ")")?;
+                    }
+                }
+                match edit {
+                    Mu::Certain(edit) => write!(f, "{}", edit.display_with_config(&self.config)),
+                    Mu::Uncertain(edit) => {
+                        write!(f, "({})", edit.display_with_config(&self.config))
+                    }
+                }
+            }
+            ProtLocEdit::InitiationUncertain => write!(f, "{}1?", fmt_aa("Met", &self.config)),
+            _ => write!(f, "{}", self.loc_edit),
+        }
+    }
+}
+
 impl Display for NoRef<'_, ProtLocEdit> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.inner().fmt(f)
@@ -486,148 +604,111 @@ impl Display for MtInterval {
     }
 }
 
-impl Display for HgvsVariant {
+impl HgvsVariant {
+    /// Return a value displaying the variant as configured in `config`.
+    ///
+    /// The `Display` implementation uses `DisplayConfig::default()`.
+    pub fn display_with<'a>(&'a self, config: &'a DisplayConfig) -> impl Display + 'a {
+        HgvsVariantDisplay {
+            variant: self,
+            config,
+        }
+    }
+}
+
+/// Helper for `HgvsVariant::display_with()`.
+struct HgvsVariantDisplay<'a> {
+    variant: &'a HgvsVariant,
+    config: &'a DisplayConfig,
+}
+
+impl Display for HgvsVariantDisplay<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            HgvsVariant::CdsVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            } => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":c.{loc_edit}")
-            }
-            HgvsVariant::GenomeVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            } => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":g.{loc_edit}")
-            }
-            HgvsVariant::MtVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            } => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":m.{loc_edit}")
-            }
-            HgvsVariant::TxVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            } => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":n.{loc_edit}")
-            }
-            HgvsVariant::ProtVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            } => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":p.{loc_edit}")
-            }
-            HgvsVariant::RnaVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            } => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":r.{loc_edit}")
-            }
+        write!(f, "{}", self.variant.accession())?;
+        if let (true, Some(gene_symbol)) =
+            (self.config.show_gene_symbol, self.variant.gene_symbol())
+        {
+            write!(f, "({gene_symbol})")?;
+        }
+        let (prefix, loc_edits) = loc_edit_parts(self.variant, self.config);
+        if let HgvsVariant::Allele { .. } = self.variant {
+            write!(f, ":{prefix}.[{loc_edits}]")
+        } else {
+            write!(f, ":{prefix}.{loc_edits}")
         }
     }
 }
 
+impl Display for HgvsVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_with(&DisplayConfig::default()))
+    }
+}
+
 impl Display for NoRef<'_, HgvsVariant> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            NoRef(HgvsVariant::CdsVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            }) => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":c.{}", NoRef(loc_edit))
-            }
-            NoRef(HgvsVariant::GenomeVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            }) => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":g.{}", NoRef(loc_edit))
-            }
-            NoRef(HgvsVariant::MtVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            }) => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":m.{}", NoRef(loc_edit))
-            }
-            NoRef(HgvsVariant::TxVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            }) => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":n.{}", NoRef(loc_edit))
-            }
-            NoRef(HgvsVariant::ProtVariant {
-                accession,
-                gene_symbol,
-                loc_edit,
-            }) => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":p.{}", NoRef(loc_edit))
-            }
-            NoRef(HgvsVariant::RnaVariant {
-                accession,
-                gene_symbol,
+        let config = DisplayConfig {
+            show_reference: false,
+            ..Default::default()
+        };
+        let display = self.inner().display_with(&config);
+        write!(f, "{display}")
+    }
+}
+
+/// Return the type prefix (e.g., `"c"`) and the location/edit of `var`; the location/edits
+/// of the variants of alleles are `;`-separated and nested alleles are flattened.
+fn loc_edit_parts(var: &HgvsVariant, config: &DisplayConfig) -> (&'static str, String) {
+    fn fmt_loc_edit<'a, T>(loc_edit: &'a T, config: &DisplayConfig) -> String
+    where
+        T: Display,
+        NoRef<'a, T>: Display,
+    {
+        if config.show_reference {
+            format!("{loc_edit}")
+        } else {
+            format!("{}", NoRef(loc_edit))
+        }
+    }
+
+    match var {
+        HgvsVariant::CdsVariant { loc_edit, .. } => {
+            ("c", fmt_loc_edit::<CdsLocEdit>(loc_edit, config))
+        }
+        HgvsVariant::GenomeVariant { loc_edit, .. } => {
+            ("g", fmt_loc_edit::<GenomeLocEdit>(loc_edit, config))
+        }
+        HgvsVariant::MtVariant { loc_edit, .. } => {
+            ("m", fmt_loc_edit::<MtLocEdit>(loc_edit, config))
+        }
+        HgvsVariant::CircularVariant { loc_edit, .. } => {
+            ("o", fmt_loc_edit::<GenomeLocEdit>(loc_edit, config))
+        }
+        HgvsVariant::TxVariant { loc_edit, .. } => {
+            ("n", fmt_loc_edit::<TxLocEdit>(loc_edit, config))
+        }
+        HgvsVariant::ProtVariant { loc_edit, .. } => {
+            let display = ProtLocEditDisplay {
                 loc_edit,
-            }) => {
-                write!(f, "{accession}")?;
-                if let Some(gene_symbol) = gene_symbol {
-                    write!(f, "({gene_symbol})")?;
-                }
-                write!(f, ":r.{}", NoRef(loc_edit))
-            }
+                config: config.protein_display_config(),
+            };
+            ("p", format!("{display}"))
+        }
+        HgvsVariant::RnaVariant { loc_edit, .. } => {
+            ("r", fmt_loc_edit::<RnaLocEdit>(loc_edit, config))
+        }
+        HgvsVariant::Allele { variants, .. } => {
+            let parts = variants
+                .iter()
+                .map(|v| loc_edit_parts(v, config))
+                .collect::<Vec<_>>();
+            let prefix = parts.first().map(|(prefix, _)| *prefix).unwrap_or_default();
+            let loc_edits = parts
+                .into_iter()
+                .map(|(_, loc_edit)| loc_edit)
+                .collect::<Vec<_>>()
+                .join(";");
+            (prefix, loc_edits)
         }
     }
 }
@@ -644,9 +725,10 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use crate::parser::{
-        Accession, CdsFrom, CdsInterval, CdsLocEdit, CdsPos, GeneSymbol, GenomeInterval,
-        GenomeLocEdit, HgvsVariant, MtInterval, MtLocEdit, Mu, NaEdit, ProtInterval, ProtLocEdit,
-        ProtPos, ProteinEdit, RnaInterval, RnaLocEdit, RnaPos, TxInterval, TxLocEdit, TxPos,
+        Accession, CdsFrom, CdsInterval, CdsLocEdit, CdsPos, DisplayConfig, GeneSymbol,
+        GenomeInterval, GenomeLocEdit, HgvsVariant, MtInterval, MtLocEdit, Mu, NaEdit, NoRef,
+        Parseable, ProtInterval, ProtLocEdit, ProtPos, ProteinDisplayConfig, ProteinEdit,
+        RnaInterval, RnaLocEdit, RnaPos, TerminatorNotation, TxInterval, TxLocEdit, TxPos,
         UncertainLengthChange,
     };
 
@@ -841,6 +923,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn na_edit_repeat() {
+        assert_eq!(
+            format!(
+                "{}",
+                NaEdit::Repeat {
+                    sequence: "CAG".to_string(),
+                    count: UncertainLengthChange::Known(40),
+                }
+            ),
+            "CAG[40]".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                NoRef(&NaEdit::Repeat {
+                    sequence: "CAG".to_string(),
+                    count: UncertainLengthChange::Range(36, 39),
+                })
+            ),
+            "CAG[36_39]".to_string()
+        );
+    }
+
     #[test]
     fn uncertain_length_change() {
         assert_eq!(format!("{}", UncertainLengthChange::None), "".to_string(),);
@@ -852,17 +958,16 @@ mod test {
             format!("{}", UncertainLengthChange::Known(42)),
             "42".to_string(),
         );
+        assert_eq!(
+            format!("{}", UncertainLengthChange::Range(36, 39)),
+            "36_39".to_string(),
+        );
     }
 
     #[test]
     fn accession() {
         assert_eq!(
-            format!(
-                "{}",
-                Accession {
-                    value: "TTN".to_string()
-                }
-            ),
+            format!("{}", Accession::from("TTN".to_string())),
             "TTN".to_string()
         )
     }
@@ -1006,6 +1111,42 @@ mod test {
         );
     }
 
+    #[test]
+    fn protein_edit_fs_terminator_notation() {
+        for (notation, terminal, expected) in [
+            (TerminatorNotation::Ter, "Ter", "LeufsTer12"),
+            (TerminatorNotation::Star, "*", "Leufs*12"),
+            (TerminatorNotation::X, "X", "LeufsX12"),
+        ] {
+            let config = ProteinDisplayConfig {
+                terminator_notation: notation,
+                ..Default::default()
+            };
+            let edit = ProteinEdit::Fs {
+                alternative: Some("Leu".to_string()),
+                terminal: Some(terminal.to_string()),
+                length: UncertainLengthChange::Known(12),
+            };
+
+            let displayed = format!("{}", edit.display_with_config(&config));
+            assert_eq!(displayed, expected);
+            // The parser keeps the notation of the terminal, so this round-trips.
+            assert_eq!(ProteinEdit::parse(&displayed), Ok(("", edit)));
+        }
+
+        // Default follows the HGVS recommendation, regardless of the input notation.
+        let edit = ProteinEdit::Fs {
+            alternative: None,
+            terminal: Some("X".to_string()),
+            length: UncertainLengthChange::None,
+        };
+        assert_eq!(
+            ProteinDisplayConfig::default().terminator_notation,
+            TerminatorNotation::Ter
+        );
+        assert_eq!(format!("{edit}"), "fsTer");
+    }
+
     #[test]
     fn protein_edit_ext() {
         assert_eq!(
@@ -1790,9 +1931,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::CdsVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "TTN".to_string()
                     }),
@@ -1823,9 +1962,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::CdsVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: None,
                     loc_edit: CdsLocEdit {
                         loc: Mu::Certain(CdsInterval {
@@ -1857,9 +1994,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::GenomeVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "TTN".to_string()
                     }),
@@ -1882,9 +2017,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::GenomeVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: None,
                     loc_edit: GenomeLocEdit {
                         loc: Mu::Certain(GenomeInterval {
@@ -1908,9 +2041,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::MtVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "TTN".to_string()
                     }),
@@ -1933,9 +2064,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::MtVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: None,
                     loc_edit: MtLocEdit {
                         loc: Mu::Certain(MtInterval {
@@ -1953,15 +2082,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn hgvs_variant_circular() -> Result<(), Error> {
+        let var = HgvsVariant::CircularVariant {
+            accession: Accession::from("NC_001422.1".to_string()),
+            gene_symbol: None,
+            loc_edit: GenomeLocEdit {
+                loc: Mu::Certain(GenomeInterval {
+                    start: Some(1),
+                    end: Some(1),
+                }),
+                edit: Mu::Certain(NaEdit::RefAlt {
+                    reference: "A".to_string(),
+                    alternative: "T".to_string(),
+                }),
+            },
+        };
+        assert_eq!(format!("{var}"), "NC_001422.1:o.1A>T");
+        assert_eq!(HgvsVariant::from_str("NC_001422.1:o.1A>T")?, var);
+        assert_eq!(
+            format!("{}", HgvsVariant::from_str("NC_001422.1:o.[1A>T;10del]")?),
+            "NC_001422.1:o.[1A>T;10del]"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn hgvs_variant_tx() {
         assert_eq!(
             format!(
                 "{}",
                 HgvsVariant::TxVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "TTN".to_string()
                     }),
@@ -1990,9 +2143,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::TxVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: None,
                     loc_edit: TxLocEdit {
                         loc: Mu::Certain(TxInterval {
@@ -2022,9 +2173,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::RnaVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "TTN".to_string()
                     }),
@@ -2053,9 +2202,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::RnaVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: None,
                     loc_edit: RnaLocEdit {
                         loc: Mu::Certain(RnaInterval {
@@ -2085,9 +2232,7 @@ mod test {
             format!(
                 "{}",
                 HgvsVariant::ProtVariant {
-                    accession: Accession {
-                        value: "NA12345.1".to_string()
-                    },
+                    accession: Accession::from("NA12345.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "TTN".to_string()
                     }),
@@ -2098,6 +2243,81 @@ mod test {
         );
     }
 
+    #[test]
+    fn hgvs_variant_display_with() -> Result<(), Error> {
+        let var_c = HgvsVariant::from_str("NM_000088.3(COL1A1):c.[1A>T;4_5delCT]")?;
+        let var_fs = HgvsVariant::from_str("NP_000079.2(COL1A1):p.Leu2ArgfsTer12")?;
+        let var_ter = HgvsVariant::from_str("NP_000079.2(COL1A1):p.Gly2_Leu3delinsTer")?;
+        let var_met = HgvsVariant::from_str("NP_000079.2(COL1A1):p.Met1?")?;
+
+        for show_reference in [true, false] {
+            for show_gene_symbol in [true, false] {
+                for use_single_letter_aa in [true, false] {
+                    for (terminator, ter) in [
+                        (TerminatorNotation::Ter, "Ter"),
+                        (TerminatorNotation::Star, "*"),
+                        (TerminatorNotation::X, "X"),
+                    ] {
+                        let config = DisplayConfig {
+                            show_reference,
+                            show_gene_symbol,
+                            use_single_letter_aa,
+                            terminator,
+                        };
+                        let gene = if show_gene_symbol { "(COL1A1)" } else { "" };
+                        let ct = if show_reference { "CT" } else { "" };
+                        let aa = |aa1: &'static str, aa3: &'static str| {
+                            if use_single_letter_aa {
+                                aa1
+                            } else {
+                                aa3
+                            }
+                        };
+
+                        assert_eq!(
+                            format!("{}", var_c.display_with(&config)),
+                            format!("NM_000088.3{gene}:c.[1A>T;4_5del{ct}]"),
+                        );
+                        assert_eq!(
+                            format!("{}", var_fs.display_with(&config)),
+                            format!(
+                                "NP_000079.2{gene}:p.{}2{}fs{ter}12",
+                                aa("L", "Leu"),
+                                aa("R", "Arg")
+                            ),
+                        );
+                        assert_eq!(
+                            format!("{}", var_ter.display_with(&config)),
+                            format!(
+                                "NP_000079.2{gene}:p.{}2_{}3delins{}",
+                                aa("G", "Gly"),
+                                aa("L", "Leu"),
+                                aa("*", "Ter")
+                            ),
+                        );
+                        assert_eq!(
+                            format!("{}", var_met.display_with(&config)),
+                            format!("NP_000079.2{gene}:p.{}1?", aa("M", "Met")),
+                        );
+                    }
+                }
+            }
+        }
+
+        for var in [&var_c, &var_fs, &var_ter, &var_met] {
+            assert_eq!(
+                format!("{}", var.display_with(&DisplayConfig::default())),
+                format!("{var}")
+            );
+        }
+        assert_eq!(
+            format!("{}", NoRef(&var_c)),
+            "NM_000088.3(COL1A1):c.[1A>T;4_5del]"
+        );
+
+        Ok(())
+    }
+
     // This test uses the "gauntlet" file from the hgvs package for round-tripping.
     #[test]
     fn roundtrip_hgvs_gauntlet() -> Result<(), Error> {
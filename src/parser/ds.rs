@@ -1,12 +1,53 @@
 //! Data structures for representing HGVS variant descriptions.
 
-use std::ops::{Deref, Range};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::{Deref, Range},
+};
 
 use crate::parser::error::Error;
+use crate::sequences::{
+    aa_to_aa1, amino_acid_group, reading_frame_offset, revcomp, MITOCHONDRIAL_ACCESSION_PREFIXES,
+};
 use log::warn;
 
+/// Return the number of positions in `[start, end]` for coordinates without a position `0`.
+fn span_length(start: i32, end: i32) -> i32 {
+    end - start + 1 - i32::from(start < 0 && end > 0)
+}
+
+/// Return the 0-based, half-open range of `[start, end]` for coordinates without a
+/// position `0`, i.e., position `-1` is directly followed by position `1`.
+fn range_0based(start: i32, end: i32) -> Range<i32> {
+    let to_0based = |pos: i32| if pos > 0 { pos - 1 } else { pos };
+    to_0based(start)..(to_0based(end) + 1)
+}
+
+/// Default window for `HgvsVariant::is_splicing()` covering the canonical splice donor
+/// (`+1`, `+2`) and acceptor (`-2`, `-1`) sites.
+pub const SPLICE_SITE_WINDOW: i32 = 2;
+
+/// Return whether the interval given by its bases and offsets reaches into the first
+/// `window` intronic bases next to an exon boundary.
+fn near_splice_site(start: (i32, i32), end: (i32, i32), window: i32) -> bool {
+    let ((start_base, start_offset), (end_base, end_offset)) = (start, end);
+    let near = |offset: i32| offset != 0 && offset.abs() <= window;
+    if near(start_offset) || near(end_offset) {
+        return true;
+    }
+    // Intervals further inside the intron cover the sites if they reach into the exon or
+    // span it from one intron into the next.
+    let same_base = start_base == end_base;
+    (end_offset > 0 && !(same_base && start_offset > 0))
+        || (start_offset < 0 && !(same_base && end_offset < 0))
+        || (start_offset > 0 && end_offset < 0 && end_base > start_base + 1)
+}
+
 /// Expression of "maybe uncertain".
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub enum Mu<T> {
     /// Certain variant of `T`.
     Certain(T),
@@ -53,7 +94,9 @@ impl<T> Mu<T> {
 }
 
 /// Representation of gene symbol, e.g., `TTN` or `Ttn`.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct GeneSymbol {
     pub value: String,
 }
@@ -79,7 +122,9 @@ impl Deref for GeneSymbol {
 }
 
 /// Edit of nucleic acids.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub enum NaEdit {
     /// A substitution where both reference and alternative allele are nucleic acid strings
     /// (or empty).
@@ -101,9 +146,50 @@ pub enum NaEdit {
     InvRef { reference: String },
     /// Inversion of a stretch given by its length.
     InvNum { count: i32 },
+    /// Repeated sequence with the (potentially uncertain) number of repeat units, e.g.,
+    /// `CAG[40]` or `CAG[36_39]`.  The repeat unit may be empty, e.g., `[12]`.
+    Repeat {
+        sequence: String,
+        count: UncertainLengthChange,
+    },
 }
 
 impl NaEdit {
+    /// Return the edit with all sequences reverse-complemented, e.g., for the other strand.
+    pub fn reverse_complement(&self) -> NaEdit {
+        match self {
+            NaEdit::RefAlt {
+                reference,
+                alternative,
+            } => NaEdit::RefAlt {
+                reference: revcomp(reference),
+                alternative: revcomp(alternative),
+            },
+            NaEdit::NumAlt { count, alternative } => NaEdit::NumAlt {
+                count: *count,
+                alternative: revcomp(alternative),
+            },
+            NaEdit::DelRef { reference } => NaEdit::DelRef {
+                reference: revcomp(reference),
+            },
+            NaEdit::Ins { alternative } => NaEdit::Ins {
+                alternative: revcomp(alternative),
+            },
+            NaEdit::Dup { reference } => NaEdit::Dup {
+                reference: revcomp(reference),
+            },
+            NaEdit::InvRef { reference } => NaEdit::InvRef {
+                reference: revcomp(reference),
+            },
+            NaEdit::DelNum { count } => NaEdit::DelNum { count: *count },
+            NaEdit::InvNum { count } => NaEdit::InvNum { count: *count },
+            NaEdit::Repeat { sequence, count } => NaEdit::Repeat {
+                sequence: revcomp(sequence),
+                count: count.clone(),
+            },
+        }
+    }
+
     /// Returns whether the edit has a count (and not reference sequence).
     pub fn is_na_edit_num(&self) -> bool {
         match self {
@@ -111,11 +197,42 @@ impl NaEdit {
             | NaEdit::DelRef { .. }
             | NaEdit::Ins { .. }
             | NaEdit::Dup { .. }
-            | NaEdit::InvRef { .. } => false,
+            | NaEdit::InvRef { .. }
+            | NaEdit::Repeat { .. } => false,
             NaEdit::NumAlt { .. } | NaEdit::DelNum { .. } | NaEdit::InvNum { .. } => true,
         }
     }
 
+    /// Return the net change in length (alternative minus reference length) in bases.
+    ///
+    /// Returns `None` if this cannot be derived from the edit alone, i.e., for edits that give
+    /// the reference as a count, for repeats, and for deletions, duplications, and
+    /// deletion-insertions without reference sequence.
+    pub fn length_change(&self) -> Option<i32> {
+        match self {
+            NaEdit::RefAlt {
+                reference,
+                alternative,
+            } => {
+                if reference.is_empty() && !alternative.is_empty() {
+                    None
+                } else {
+                    Some(alternative.len() as i32 - reference.len() as i32)
+                }
+            }
+            NaEdit::DelRef { reference } => {
+                (!reference.is_empty()).then(|| -(reference.len() as i32))
+            }
+            NaEdit::Ins { alternative } => Some(alternative.len() as i32),
+            NaEdit::Dup { reference } => (!reference.is_empty()).then_some(reference.len() as i32),
+            NaEdit::InvRef { .. } => Some(0),
+            NaEdit::NumAlt { .. }
+            | NaEdit::DelNum { .. }
+            | NaEdit::InvNum { .. }
+            | NaEdit::Repeat { .. } => None,
+        }
+    }
+
     /// Returns whether the edit is an insertion.
     pub fn is_ins(&self) -> bool {
         matches!(self, NaEdit::Ins { .. })
@@ -126,6 +243,77 @@ impl NaEdit {
         matches!(self, NaEdit::Dup { .. })
     }
 
+    /// Returns whether the edit is a single nucleotide variant, i.e., a substitution of one
+    /// base by a different one.
+    pub fn is_snv(&self) -> bool {
+        matches!(
+            self,
+            NaEdit::RefAlt { reference, alternative }
+                if reference.len() == 1 && alternative.len() == 1 && reference != alternative
+        )
+    }
+
+    /// Returns whether the edit is a multi nucleotide variant, i.e., a deletion-insertion that
+    /// replaces more than one base by a different sequence of the same length.
+    pub fn is_mnv(&self) -> bool {
+        matches!(
+            self,
+            NaEdit::RefAlt { reference, alternative }
+                if reference.len() > 1
+                    && reference.len() == alternative.len()
+                    && reference != alternative
+        )
+    }
+
+    /// Returns whether the edit changes the length of the sequence.
+    ///
+    /// This is the case if `length_change()` is non-zero and for all deletions, insertions, and
+    /// duplications, also if their sequence is not given.  Repeats and deletion-insertions
+    /// without reference sequence are not considered as their length change is unknown.
+    pub fn is_indel(&self) -> bool {
+        match self {
+            NaEdit::NumAlt { count, alternative } => *count != alternative.len() as i32,
+            NaEdit::DelRef { .. } | NaEdit::DelNum { .. } | NaEdit::Dup { .. } => true,
+            NaEdit::Ins { alternative } => !alternative.is_empty(),
+            NaEdit::RefAlt { .. }
+            | NaEdit::InvRef { .. }
+            | NaEdit::InvNum { .. }
+            | NaEdit::Repeat { .. } => self.length_change().is_some_and(|change| change != 0),
+        }
+    }
+
+    /// Return the number of reference bases affected by the edit.
+    ///
+    /// Insertions affect no reference bases, duplications affect the duplicated bases.
+    /// Returns `None` if the reference is not given as a sequence, i.e., for edits that give
+    /// the reference as a count, for repeats, and if the reference sequence is omitted.
+    pub fn affected_base_count(&self) -> Option<usize> {
+        match self {
+            NaEdit::RefAlt { reference, .. }
+            | NaEdit::DelRef { reference }
+            | NaEdit::Dup { reference }
+            | NaEdit::InvRef { reference } => (!reference.is_empty()).then_some(reference.len()),
+            NaEdit::Ins { .. } => Some(0),
+            NaEdit::NumAlt { .. }
+            | NaEdit::DelNum { .. }
+            | NaEdit::InvNum { .. }
+            | NaEdit::Repeat { .. } => None,
+        }
+    }
+
+    /// Returns whether the edit is a deletion (or deletion-insertion) that removes at least
+    /// `threshold` bases.
+    ///
+    /// Deletions without reference sequence are never considered large.
+    pub fn is_large_deletion(&self, threshold: usize) -> bool {
+        let deleted = match self {
+            NaEdit::DelNum { count } => Some(*count),
+            NaEdit::NumAlt { count, alternative } => Some(*count - alternative.len() as i32),
+            _ => self.length_change().map(|change| -change),
+        };
+        deleted.is_some_and(|deleted| deleted > 0 && deleted as usize >= threshold)
+    }
+
     /// Ensures that the reference is a count and no reference bases.
     pub fn with_num(&self) -> Self {
         match self {
@@ -146,7 +334,46 @@ impl NaEdit {
             | NaEdit::DelNum { .. }
             | NaEdit::InvNum { .. }
             | NaEdit::Ins { .. }
-            | NaEdit::Dup { .. } => self.clone(),
+            | NaEdit::Dup { .. }
+            | NaEdit::Repeat { .. } => self.clone(),
+        }
+    }
+
+    /// Return the edit without reference bases or counts.
+    ///
+    /// Identity substitutions become `=`, other substitutions and deletion-insertions become
+    /// `delins` with the alternative only.
+    pub fn to_minimal(self) -> Self {
+        match self {
+            NaEdit::RefAlt {
+                reference,
+                alternative,
+            } => NaEdit::RefAlt {
+                alternative: if reference == alternative {
+                    String::new()
+                } else {
+                    alternative
+                },
+                reference: String::new(),
+            },
+            NaEdit::NumAlt { count, alternative } => NaEdit::RefAlt {
+                reference: String::new(),
+                alternative: if count == 0 {
+                    String::new()
+                } else {
+                    alternative
+                },
+            },
+            NaEdit::DelRef { .. } | NaEdit::DelNum { .. } => NaEdit::DelRef {
+                reference: String::new(),
+            },
+            NaEdit::Dup { .. } => NaEdit::Dup {
+                reference: String::new(),
+            },
+            NaEdit::InvRef { .. } | NaEdit::InvNum { .. } => NaEdit::InvRef {
+                reference: String::new(),
+            },
+            NaEdit::Ins { .. } | NaEdit::Repeat { .. } => self,
         }
     }
 
@@ -194,21 +421,141 @@ impl NaEdit {
             NaEdit::Dup { .. } => NaEdit::Dup { reference },
             NaEdit::InvRef { .. } => NaEdit::InvRef { reference },
             NaEdit::InvNum { .. } => NaEdit::InvRef { reference },
+            NaEdit::Repeat { sequence, count } => {
+                warn!("Calling with_reference() on NaEdit::Repeat");
+                NaEdit::Repeat { sequence, count }
+            }
+        }
+    }
+
+    /// Return an updated `NaEdit` that has the reference sequence cleared.
+    ///
+    /// Edits without a reference sequence are returned unchanged.
+    pub fn without_reference(self) -> Self {
+        match self {
+            NaEdit::RefAlt { alternative, .. } => NaEdit::RefAlt {
+                reference: String::new(),
+                alternative,
+            },
+            NaEdit::DelRef { .. } => NaEdit::DelRef {
+                reference: String::new(),
+            },
+            NaEdit::Dup { .. } => NaEdit::Dup {
+                reference: String::new(),
+            },
+            NaEdit::InvRef { .. } => NaEdit::InvRef {
+                reference: String::new(),
+            },
+            NaEdit::NumAlt { .. }
+            | NaEdit::DelNum { .. }
+            | NaEdit::Ins { .. }
+            | NaEdit::InvNum { .. }
+            | NaEdit::Repeat { .. } => self,
         }
     }
 }
 
 /// Uncertain change through extension.
-#[derive(Clone, Debug, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub enum UncertainLengthChange {
     #[default]
     None,
     Unknown,
     Known(i32),
+    /// Uncertain range given by minimum and maximum, e.g., `36_39`.
+    Range(i32, i32),
+}
+
+/// Type of molecule that an accession refers to, derived from its prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MoleculeType {
+    /// RefSeq mRNA, e.g., `NM_000088.3` or `XM_011524341.2`.
+    Mrna,
+    /// RefSeq non-coding RNA, e.g., `NR_027676.1`.
+    NcRna,
+    /// RefSeq protein, e.g., `NP_000079.2`.
+    Protein,
+    /// RefSeq genomic sequence, e.g., `NC_000017.10`.
+    Genomic,
+    /// RefSeqGene record, e.g., `NG_007400.1`.
+    RefSeqGene,
+    /// Mitochondrial genome, e.g., `NC_012920.1`.
+    Mitochondrial,
+    /// Any Ensembl accession, e.g., `ENST00000225964.10`.
+    Ensembl,
+    /// Locus Reference Genomic, e.g., `LRG_1t1`.
+    Lrg,
+    /// Unknown prefix.
+    Unknown,
+}
+
+/// Context of a variant that was parsed from a string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseContext {
+    /// The string that the variant was parsed from.
+    pub source: String,
+}
+
+/// Variant together with the context it was parsed in, see
+/// `HgvsVariant::parse_with_context()`.
+///
+/// The context is ignored when comparing and hashing, so two `ParsedVariant`s are equal
+/// if their variants are.  The variant is available through `Deref`.
+#[derive(Clone, Debug)]
+pub struct ParsedVariant {
+    pub variant: HgvsVariant,
+    pub context: ParseContext,
+}
+
+impl ParsedVariant {
+    /// Return the string that the variant was parsed from.
+    pub fn source_string(&self) -> &str {
+        &self.context.source
+    }
+
+    /// Return the variant without the parse context.
+    pub fn without_context(self) -> HgvsVariant {
+        self.variant
+    }
+}
+
+impl Deref for ParsedVariant {
+    type Target = HgvsVariant;
+
+    fn deref(&self) -> &Self::Target {
+        &self.variant
+    }
+}
+
+impl PartialEq for ParsedVariant {
+    fn eq(&self, other: &Self) -> bool {
+        self.variant == other.variant
+    }
+}
+
+impl Eq for ParsedVariant {}
+
+impl Hash for ParsedVariant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.variant.hash(state);
+    }
 }
 
 /// Representation of accession, e.g., `NM_01234.5`.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct Accession {
     pub value: String,
 }
@@ -231,10 +578,86 @@ impl Accession {
     pub fn from(value: String) -> Self {
         Self { value }
     }
+
+    /// Return the version of the accession, e.g., `5` for `NM_01234.5`.
+    pub fn version(&self) -> Option<u32> {
+        self.value.split_once('.')?.1.parse().ok()
+    }
+
+    /// Return the accession without the version, e.g., `NM_01234` for `NM_01234.5`.
+    pub fn without_version(&self) -> &str {
+        self.value
+            .split_once('.')
+            .map(|(name, _)| name)
+            .unwrap_or(&self.value)
+    }
+
+    /// Return the base accession shared by all versions, e.g., `NM_01234` for `NM_01234.5`.
+    ///
+    /// This is the same as `without_version()`.
+    pub fn base(&self) -> &str {
+        self.without_version()
+    }
+
+    /// Return whether the accession has a version, e.g., `NM_01234.5`.
+    pub fn is_versioned(&self) -> bool {
+        self.version().is_some()
+    }
+
+    /// Return the type of molecule based on the accession prefix.
+    pub fn molecule_type(&self) -> MoleculeType {
+        if MITOCHONDRIAL_ACCESSION_PREFIXES
+            .iter()
+            .any(|prefix| self.value.starts_with(prefix))
+        {
+            return MoleculeType::Mitochondrial;
+        } else if self.is_lrg() {
+            return MoleculeType::Lrg;
+        } else if self.is_ensembl() {
+            return MoleculeType::Ensembl;
+        }
+        match self.value.split_once('_').map(|(prefix, _)| prefix) {
+            Some("NM" | "XM") => MoleculeType::Mrna,
+            Some("NR" | "XR") => MoleculeType::NcRna,
+            Some("NP" | "XP" | "YP" | "WP") => MoleculeType::Protein,
+            Some("NC" | "NT" | "NW" | "NZ" | "AC") => MoleculeType::Genomic,
+            Some("NG") => MoleculeType::RefSeqGene,
+            _ => MoleculeType::Unknown,
+        }
+    }
+
+    /// Return whether this is a RefSeq accession, e.g., `NM_01234.5`.
+    pub fn is_refseq(&self) -> bool {
+        !matches!(
+            self.molecule_type(),
+            MoleculeType::Ensembl | MoleculeType::Lrg | MoleculeType::Unknown
+        )
+    }
+
+    /// Return whether this is an Ensembl accession, e.g., `ENST00000225964.10`.
+    pub fn is_ensembl(&self) -> bool {
+        self.value.starts_with("ENS")
+    }
+
+    /// Return whether this is a Locus Reference Genomic (LRG) accession, e.g., `LRG_1t1`.
+    pub fn is_lrg(&self) -> bool {
+        self.lrg_id().is_some()
+    }
+
+    /// Return the numeric identifier of an LRG accession, e.g., `1` for `LRG_1t1`.
+    pub fn lrg_id(&self) -> Option<u32> {
+        let rest = self.value.strip_prefix("LRG_")?;
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        rest[..end].parse().ok()
+    }
 }
 
 /// Protein edit with interval end edit.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub enum ProteinEdit {
     Fs {
         alternative: Option<String>,
@@ -268,8 +691,111 @@ pub enum ProteinEdit {
     Ident,
 }
 
+/// Return whether `aa` denotes a stop codon, as 1- or 3-letter amino acid.
+fn is_stop_aa(aa: &str) -> bool {
+    matches!(aa, "Ter" | "*" | "X")
+}
+
+impl ProteinEdit {
+    /// Returns whether the edit is a frameshift.
+    pub fn is_frameshift(&self) -> bool {
+        matches!(self, ProteinEdit::Fs { .. })
+    }
+
+    /// Returns whether the edit is a substitution by a stop codon.
+    pub fn is_nonsense(&self) -> bool {
+        matches!(self, ProteinEdit::Subst { alternative } if is_stop_aa(alternative))
+    }
+
+    /// Returns whether the edit is a substitution by an amino acid.
+    ///
+    /// Substitutions by the reference amino acid cannot be recognized from the edit alone,
+    /// see `ProtLocEdit::is_missense()`.
+    pub fn is_missense(&self) -> bool {
+        matches!(
+            self,
+            ProteinEdit::Subst { alternative }
+                if !is_stop_aa(alternative) && alternative != "?" && alternative != "="
+        )
+    }
+
+    /// Returns whether the edit is `=`.
+    ///
+    /// Substitutions by the reference amino acid cannot be recognized from the edit alone,
+    /// see `ProtLocEdit::is_synonymous()`.
+    pub fn is_synonymous(&self) -> bool {
+        matches!(self, ProteinEdit::Ident)
+    }
+
+    /// Returns whether the edit is a deletion, insertion, duplication, or deletion-insertion.
+    pub fn is_inframe_indel(&self) -> bool {
+        matches!(
+            self,
+            ProteinEdit::Del
+                | ProteinEdit::Ins { .. }
+                | ProteinEdit::Dup
+                | ProteinEdit::DelIns { .. }
+        )
+    }
+
+    /// Returns whether the edit is an extension.
+    pub fn is_extension(&self) -> bool {
+        matches!(self, ProteinEdit::Ext { .. })
+    }
+
+    /// Return the net change in length in amino acids.
+    ///
+    /// Returns `None` if this cannot be derived from the edit alone, i.e., for frameshifts,
+    /// nonsense substitutions, extensions of unknown length, and for deletions, duplications,
+    /// and deletion-insertions whose length is given by the location.
+    pub fn length_change(&self) -> Option<i32> {
+        match self {
+            ProteinEdit::Subst { alternative } => {
+                (!matches!(alternative.as_str(), "*" | "Ter" | "X" | "?")).then_some(0)
+            }
+            ProteinEdit::Ins { alternative } => aa_length(alternative),
+            ProteinEdit::Ext {
+                change: UncertainLengthChange::Known(change),
+                ..
+            } => Some(change.abs()),
+            ProteinEdit::Ident => Some(0),
+            ProteinEdit::Fs { .. }
+            | ProteinEdit::Ext { .. }
+            | ProteinEdit::DelIns { .. }
+            | ProteinEdit::Del
+            | ProteinEdit::Dup => None,
+        }
+    }
+}
+
+/// Return the number of amino acids in the 1- or 3-letter sequence `seq`.
+fn aa_length(seq: &str) -> Option<i32> {
+    aa_to_aa1(seq).ok().map(|seq| seq.len() as i32)
+}
+
+/// The type of a `HgvsVariant` without its payload, see `HgvsVariant::kind()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HgvsVariantKind {
+    /// `c.` variant.
+    Cds,
+    /// `g.` variant.
+    Genome,
+    /// `m.` variant.
+    Mt,
+    /// `o.` variant.
+    Circular,
+    /// `n.` variant.
+    Tx,
+    /// `r.` variant.
+    Rna,
+    /// `p.` variant.
+    Prot,
+    /// Allele of variants in cis.
+    Allele,
+}
+
 /// A HGVS variant specification.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum HgvsVariant {
     /// Variant specification with `c.` location.
     CdsVariant {
@@ -289,6 +815,12 @@ pub enum HgvsVariant {
         gene_symbol: Option<GeneSymbol>,
         loc_edit: MtLocEdit,
     },
+    /// Variant specification with `o.` location, i.e., on a circular molecule.
+    CircularVariant {
+        accession: Accession,
+        gene_symbol: Option<GeneSymbol>,
+        loc_edit: GenomeLocEdit,
+    },
     /// Variant specification with `n.` location.
     TxVariant {
         accession: Accession,
@@ -307,6 +839,14 @@ pub enum HgvsVariant {
         gene_symbol: Option<GeneSymbol>,
         loc_edit: RnaLocEdit,
     },
+    /// Allele with several variants in cis, e.g., `c.[1A>T;10G>C]`.
+    ///
+    /// The entries of `variants` carry the same `accession` and `gene_symbol` as the allele.
+    Allele {
+        accession: Accession,
+        gene_symbol: Option<GeneSymbol>,
+        variants: Vec<HgvsVariant>,
+    },
 }
 
 impl HgvsVariant {
@@ -316,9 +856,11 @@ impl HgvsVariant {
             HgvsVariant::CdsVariant { loc_edit, .. } => loc_edit.edit.inner().is_na_edit_num(),
             HgvsVariant::GenomeVariant { loc_edit, .. } => loc_edit.edit.inner().is_na_edit_num(),
             HgvsVariant::MtVariant { loc_edit, .. } => loc_edit.edit.inner().is_na_edit_num(),
+            HgvsVariant::CircularVariant { loc_edit, .. } => loc_edit.edit.inner().is_na_edit_num(),
             HgvsVariant::TxVariant { loc_edit, .. } => loc_edit.edit.inner().is_na_edit_num(),
             HgvsVariant::RnaVariant { loc_edit, .. } => loc_edit.edit.inner().is_na_edit_num(),
             HgvsVariant::ProtVariant { .. } => false,
+            HgvsVariant::Allele { variants, .. } => variants.iter().any(|v| v.is_na_edit_num()),
         }
     }
 
@@ -352,6 +894,15 @@ impl HgvsVariant {
                 gene_symbol,
                 loc_edit: loc_edit.with_num(),
             },
+            HgvsVariant::CircularVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            } => HgvsVariant::CircularVariant {
+                accession,
+                gene_symbol,
+                loc_edit: loc_edit.with_num(),
+            },
             HgvsVariant::TxVariant {
                 accession,
                 gene_symbol,
@@ -379,11 +930,23 @@ impl HgvsVariant {
                 gene_symbol,
                 loc_edit: loc_edit.with_num(),
             },
+            HgvsVariant::Allele {
+                accession,
+                gene_symbol,
+                variants,
+            } => HgvsVariant::Allele {
+                accession,
+                gene_symbol,
+                variants: variants.into_iter().map(|v| v.with_na_ref_num()).collect(),
+            },
         }
     }
 
-    /// Replace reference sequence.
-    pub fn with_reference(self, value: String) -> Self {
+    /// Return the variant with all reference bases and counts removed from its `NaEdit`s.
+    ///
+    /// Accession, gene symbol, and location are kept.  Protein variants are returned
+    /// unchanged.  See `NaEdit::to_minimal()`.
+    pub fn to_minimal(self) -> Self {
         match self {
             HgvsVariant::CdsVariant {
                 accession,
@@ -392,7 +955,7 @@ impl HgvsVariant {
             } => HgvsVariant::CdsVariant {
                 accession,
                 gene_symbol,
-                loc_edit: loc_edit.with_reference(value),
+                loc_edit: loc_edit.with_minimal_edit(),
             },
             HgvsVariant::GenomeVariant {
                 accession,
@@ -401,7 +964,7 @@ impl HgvsVariant {
             } => HgvsVariant::GenomeVariant {
                 accession,
                 gene_symbol,
-                loc_edit: loc_edit.with_reference(value),
+                loc_edit: loc_edit.with_minimal_edit(),
             },
             HgvsVariant::MtVariant {
                 accession,
@@ -410,7 +973,16 @@ impl HgvsVariant {
             } => HgvsVariant::MtVariant {
                 accession,
                 gene_symbol,
-                loc_edit: loc_edit.with_reference(value),
+                loc_edit: loc_edit.with_minimal_edit(),
+            },
+            HgvsVariant::CircularVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            } => HgvsVariant::CircularVariant {
+                accession,
+                gene_symbol,
+                loc_edit: loc_edit.with_minimal_edit(),
             },
             HgvsVariant::TxVariant {
                 accession,
@@ -419,20 +991,17 @@ impl HgvsVariant {
             } => HgvsVariant::TxVariant {
                 accession,
                 gene_symbol,
-                loc_edit: loc_edit.with_reference(value),
+                loc_edit: loc_edit.with_minimal_edit(),
             },
             HgvsVariant::ProtVariant {
                 accession,
                 gene_symbol,
                 loc_edit,
-            } => {
-                warn!("Calling with_reference on ProtVariant");
-                HgvsVariant::ProtVariant {
-                    accession,
-                    gene_symbol,
-                    loc_edit,
-                }
-            }
+            } => HgvsVariant::ProtVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            },
             HgvsVariant::RnaVariant {
                 accession,
                 gene_symbol,
@@ -440,49 +1009,371 @@ impl HgvsVariant {
             } => HgvsVariant::RnaVariant {
                 accession,
                 gene_symbol,
-                loc_edit: loc_edit.with_reference(value),
+                loc_edit: loc_edit.with_minimal_edit(),
+            },
+            HgvsVariant::Allele {
+                accession,
+                gene_symbol,
+                variants,
+            } => HgvsVariant::Allele {
+                accession,
+                gene_symbol,
+                variants: variants.into_iter().map(|v| v.to_minimal()).collect(),
             },
         }
     }
 
-    /// Return the gene symbol.
-    pub fn gene_symbol(&self) -> &Option<GeneSymbol> {
-        match self {
-            HgvsVariant::CdsVariant { gene_symbol, .. } => gene_symbol,
-            HgvsVariant::GenomeVariant { gene_symbol, .. } => gene_symbol,
-            HgvsVariant::MtVariant { gene_symbol, .. } => gene_symbol,
-            HgvsVariant::TxVariant { gene_symbol, .. } => gene_symbol,
-            HgvsVariant::ProtVariant { gene_symbol, .. } => gene_symbol,
-            HgvsVariant::RnaVariant { gene_symbol, .. } => gene_symbol,
+    /// Return whether the two variants are equal after converting both to minimal form.
+    ///
+    /// This ignores differences in the explicitly given reference, e.g., between `c.5dup` and
+    /// `c.5dupT`.
+    pub fn equivalent(&self, other: &HgvsVariant) -> bool {
+        self.clone().to_minimal() == other.clone().to_minimal()
+    }
+
+    /// Return whether the two variants are equal when ignoring the gene symbols, e.g., for
+    /// `NM_000088.3(COL1A1):c.1A>T` and `NM_000088.3:c.1A>T`.
+    pub fn same_variant(&self, other: &HgvsVariant) -> bool {
+        self.clone().strip_gene_symbol() == other.clone().strip_gene_symbol()
+    }
+
+    /// Return whether the two variants have the same accession and location, regardless of
+    /// the edit.
+    ///
+    /// The variants must be of the same type, e.g., both `c.` variants.  Protein variants
+    /// without location such as `p.?` only have the same position as the same description.
+    /// Alleles have the same position if their variants have pairwise.
+    pub fn same_position(&self, other: &HgvsVariant) -> bool {
+        if self.accession() != other.accession() {
+            return false;
+        }
+        match (self, other) {
+            (
+                HgvsVariant::CdsVariant { loc_edit: a, .. },
+                HgvsVariant::CdsVariant { loc_edit: b, .. },
+            ) => a.loc == b.loc,
+            (
+                HgvsVariant::GenomeVariant { loc_edit: a, .. },
+                HgvsVariant::GenomeVariant { loc_edit: b, .. },
+            )
+            | (
+                HgvsVariant::CircularVariant { loc_edit: a, .. },
+                HgvsVariant::CircularVariant { loc_edit: b, .. },
+            ) => a.loc == b.loc,
+            (
+                HgvsVariant::MtVariant { loc_edit: a, .. },
+                HgvsVariant::MtVariant { loc_edit: b, .. },
+            ) => a.loc == b.loc,
+            (
+                HgvsVariant::TxVariant { loc_edit: a, .. },
+                HgvsVariant::TxVariant { loc_edit: b, .. },
+            ) => a.loc == b.loc,
+            (
+                HgvsVariant::RnaVariant { loc_edit: a, .. },
+                HgvsVariant::RnaVariant { loc_edit: b, .. },
+            ) => a.loc == b.loc,
+            (
+                HgvsVariant::ProtVariant { loc_edit: a, .. },
+                HgvsVariant::ProtVariant { loc_edit: b, .. },
+            ) => match (a, b) {
+                (ProtLocEdit::Ordinary { loc: a, .. }, ProtLocEdit::Ordinary { loc: b, .. }) => {
+                    a == b
+                }
+                _ => a == b,
+            },
+            (HgvsVariant::Allele { variants: a, .. }, HgvsVariant::Allele { variants: b, .. }) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.same_position(b))
+            }
+            _ => false,
         }
     }
 
-    /// Return the accession.
-    pub fn accession(&self) -> &Accession {
-        match self {
-            HgvsVariant::CdsVariant { accession, .. } => accession,
-            HgvsVariant::GenomeVariant { accession, .. } => accession,
-            HgvsVariant::MtVariant { accession, .. } => accession,
-            HgvsVariant::TxVariant { accession, .. } => accession,
-            HgvsVariant::ProtVariant { accession, .. } => accession,
-            HgvsVariant::RnaVariant { accession, .. } => accession,
+    /// Return whether the two variants have the same edit, regardless of accession and
+    /// location, e.g., for `NM_000088.3:c.1A>T` and `NC_000017.11:g.50201632A>T`.
+    ///
+    /// Nucleic acid edits are compared irrespective of the variant type.  Protein variants
+    /// without location such as `p.?` only have the same edit as the same description.
+    /// Alleles have the same edit if their variants have pairwise.
+    pub fn same_edit(&self, other: &HgvsVariant) -> bool {
+        match (self, other) {
+            (
+                HgvsVariant::ProtVariant { loc_edit: a, .. },
+                HgvsVariant::ProtVariant { loc_edit: b, .. },
+            ) => match (a, b) {
+                (ProtLocEdit::Ordinary { edit: a, .. }, ProtLocEdit::Ordinary { edit: b, .. }) => {
+                    a == b
+                }
+                _ => a == b,
+            },
+            (HgvsVariant::Allele { variants: a, .. }, HgvsVariant::Allele { variants: b, .. }) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.same_edit(b))
+            }
+            _ => match (self.mu_na_edit(), other.mu_na_edit()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
         }
     }
 
-    /// Return the 0-based range of the location, possibly wrapped into `Mu`
-    pub fn mu_loc_range(&self) -> Option<Mu<Range<i32>>> {
+    /// Replace reference sequence.
+    pub fn with_reference(self, value: String) -> Self {
         match self {
-            HgvsVariant::CdsVariant { loc_edit, .. } => loc_edit
-                .loc
-                .inner()
-                .clone()
-                .try_into()
-                .ok()
-                .map(|l| Mu::from(l, loc_edit.loc.is_certain())),
-            HgvsVariant::GenomeVariant { loc_edit, .. } => loc_edit
-                .loc
-                .inner()
-                .clone()
+            HgvsVariant::CdsVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            } => HgvsVariant::CdsVariant {
+                accession,
+                gene_symbol,
+                loc_edit: loc_edit.with_reference(value),
+            },
+            HgvsVariant::GenomeVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            } => HgvsVariant::GenomeVariant {
+                accession,
+                gene_symbol,
+                loc_edit: loc_edit.with_reference(value),
+            },
+            HgvsVariant::MtVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            } => HgvsVariant::MtVariant {
+                accession,
+                gene_symbol,
+                loc_edit: loc_edit.with_reference(value),
+            },
+            HgvsVariant::CircularVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            } => HgvsVariant::CircularVariant {
+                accession,
+                gene_symbol,
+                loc_edit: loc_edit.with_reference(value),
+            },
+            HgvsVariant::TxVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            } => HgvsVariant::TxVariant {
+                accession,
+                gene_symbol,
+                loc_edit: loc_edit.with_reference(value),
+            },
+            HgvsVariant::ProtVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            } => {
+                warn!("Calling with_reference on ProtVariant");
+                HgvsVariant::ProtVariant {
+                    accession,
+                    gene_symbol,
+                    loc_edit,
+                }
+            }
+            HgvsVariant::RnaVariant {
+                accession,
+                gene_symbol,
+                loc_edit,
+            } => HgvsVariant::RnaVariant {
+                accession,
+                gene_symbol,
+                loc_edit: loc_edit.with_reference(value),
+            },
+            HgvsVariant::Allele {
+                accession,
+                gene_symbol,
+                variants,
+            } => {
+                warn!("Calling with_reference on Allele");
+                HgvsVariant::Allele {
+                    accession,
+                    gene_symbol,
+                    variants,
+                }
+            }
+        }
+    }
+
+    /// Replace the `NaEdit` of the variant, keeping whether the edit is certain.
+    ///
+    /// Protein variants and alleles are returned unchanged.
+    pub fn with_na_edit(mut self, edit: NaEdit) -> Self {
+        if self.set_na_edit(edit).is_err() {
+            warn!("Calling with_na_edit on variant without NaEdit");
+        }
+        self
+    }
+
+    /// Replace the `NaEdit` of the variant in place, keeping whether the edit is certain.
+    ///
+    /// Returns `Error::VariantTypeError` for protein variants and alleles.
+    pub fn set_na_edit(&mut self, edit: NaEdit) -> Result<(), Error> {
+        match self.mu_na_edit_mut() {
+            Some(mu_edit) => {
+                *mu_edit = Mu::from(edit, mu_edit.is_certain());
+                Ok(())
+            }
+            None => Err(Error::VariantTypeError(format!("{}", self))),
+        }
+    }
+
+    /// Return the gene symbol.
+    pub fn gene_symbol(&self) -> &Option<GeneSymbol> {
+        match self {
+            HgvsVariant::CdsVariant { gene_symbol, .. } => gene_symbol,
+            HgvsVariant::GenomeVariant { gene_symbol, .. } => gene_symbol,
+            HgvsVariant::MtVariant { gene_symbol, .. } => gene_symbol,
+            HgvsVariant::CircularVariant { gene_symbol, .. } => gene_symbol,
+            HgvsVariant::TxVariant { gene_symbol, .. } => gene_symbol,
+            HgvsVariant::ProtVariant { gene_symbol, .. } => gene_symbol,
+            HgvsVariant::RnaVariant { gene_symbol, .. } => gene_symbol,
+            HgvsVariant::Allele { gene_symbol, .. } => gene_symbol,
+        }
+    }
+
+    /// Return the variant without gene symbol.
+    ///
+    /// The gene symbols of the variants of an allele are removed as well.
+    pub fn strip_gene_symbol(mut self) -> Self {
+        self.set_gene_symbol(None);
+        self
+    }
+
+    /// Return the variant with the gene symbol set to `value`.
+    ///
+    /// The gene symbols of the variants of an allele are set as well.
+    pub fn with_gene_symbol(mut self, value: impl Into<String>) -> Self {
+        self.set_gene_symbol(Some(GeneSymbol::from(value.into())));
+        self
+    }
+
+    fn set_gene_symbol(&mut self, value: Option<GeneSymbol>) {
+        match self {
+            HgvsVariant::CdsVariant { gene_symbol, .. }
+            | HgvsVariant::GenomeVariant { gene_symbol, .. }
+            | HgvsVariant::MtVariant { gene_symbol, .. }
+            | HgvsVariant::CircularVariant { gene_symbol, .. }
+            | HgvsVariant::TxVariant { gene_symbol, .. }
+            | HgvsVariant::ProtVariant { gene_symbol, .. }
+            | HgvsVariant::RnaVariant { gene_symbol, .. } => *gene_symbol = value,
+            HgvsVariant::Allele {
+                gene_symbol,
+                variants,
+                ..
+            } => {
+                for var in variants.iter_mut() {
+                    var.set_gene_symbol(value.clone());
+                }
+                *gene_symbol = value;
+            }
+        }
+    }
+
+    /// Return the type of the variant.
+    pub fn kind(&self) -> HgvsVariantKind {
+        match self {
+            HgvsVariant::CdsVariant { .. } => HgvsVariantKind::Cds,
+            HgvsVariant::GenomeVariant { .. } => HgvsVariantKind::Genome,
+            HgvsVariant::MtVariant { .. } => HgvsVariantKind::Mt,
+            HgvsVariant::CircularVariant { .. } => HgvsVariantKind::Circular,
+            HgvsVariant::TxVariant { .. } => HgvsVariantKind::Tx,
+            HgvsVariant::RnaVariant { .. } => HgvsVariantKind::Rna,
+            HgvsVariant::ProtVariant { .. } => HgvsVariantKind::Prot,
+            HgvsVariant::Allele { .. } => HgvsVariantKind::Allele,
+        }
+    }
+
+    /// Return whether the variant is a `c.` variant.
+    pub fn is_cds(&self) -> bool {
+        self.kind() == HgvsVariantKind::Cds
+    }
+
+    /// Return whether the variant is a `g.` variant.
+    pub fn is_genome(&self) -> bool {
+        self.kind() == HgvsVariantKind::Genome
+    }
+
+    /// Return whether the variant is a `m.` variant.
+    pub fn is_mt(&self) -> bool {
+        self.kind() == HgvsVariantKind::Mt
+    }
+
+    /// Return whether the variant is a `n.` variant.
+    pub fn is_tx(&self) -> bool {
+        self.kind() == HgvsVariantKind::Tx
+    }
+
+    /// Return whether the variant is a `r.` variant.
+    pub fn is_rna(&self) -> bool {
+        self.kind() == HgvsVariantKind::Rna
+    }
+
+    /// Return whether the variant is a `p.` variant.
+    pub fn is_prot(&self) -> bool {
+        self.kind() == HgvsVariantKind::Prot
+    }
+
+    /// Return whether the variant is on nucleic acid level, i.e., not a `p.` variant.
+    ///
+    /// Alleles are judged by their first variant.
+    pub fn is_nucleic_acid(&self) -> bool {
+        match self {
+            HgvsVariant::ProtVariant { .. } => false,
+            HgvsVariant::Allele { variants, .. } => {
+                variants.first().is_some_and(|var| var.is_nucleic_acid())
+            }
+            _ => true,
+        }
+    }
+
+    /// Return whether the variant is a `c.`, `n.`, or `r.` variant.
+    ///
+    /// Alleles are judged by their first variant.
+    pub fn is_transcript(&self) -> bool {
+        match self {
+            HgvsVariant::CdsVariant { .. }
+            | HgvsVariant::TxVariant { .. }
+            | HgvsVariant::RnaVariant { .. } => true,
+            HgvsVariant::Allele { variants, .. } => {
+                variants.first().is_some_and(|var| var.is_transcript())
+            }
+            _ => false,
+        }
+    }
+
+    /// Return the accession.
+    pub fn accession(&self) -> &Accession {
+        match self {
+            HgvsVariant::CdsVariant { accession, .. } => accession,
+            HgvsVariant::GenomeVariant { accession, .. } => accession,
+            HgvsVariant::MtVariant { accession, .. } => accession,
+            HgvsVariant::CircularVariant { accession, .. } => accession,
+            HgvsVariant::TxVariant { accession, .. } => accession,
+            HgvsVariant::ProtVariant { accession, .. } => accession,
+            HgvsVariant::RnaVariant { accession, .. } => accession,
+            HgvsVariant::Allele { accession, .. } => accession,
+        }
+    }
+
+    /// Return the 0-based range of the location, possibly wrapped into `Mu`
+    pub fn mu_loc_range(&self) -> Option<Mu<Range<i32>>> {
+        match self {
+            HgvsVariant::CdsVariant { loc_edit, .. } => loc_edit
+                .loc
+                .inner()
+                .clone()
+                .try_into()
+                .ok()
+                .map(|l| Mu::from(l, loc_edit.loc.is_certain())),
+            HgvsVariant::GenomeVariant { loc_edit, .. } => loc_edit
+                .loc
+                .inner()
+                .clone()
                 .try_into()
                 .ok()
                 .map(|l| Mu::from(l, loc_edit.loc.is_certain())),
@@ -493,6 +1384,13 @@ impl HgvsVariant {
                 .try_into()
                 .ok()
                 .map(|l| Mu::from(l, loc_edit.loc.is_certain())),
+            HgvsVariant::CircularVariant { loc_edit, .. } => loc_edit
+                .loc
+                .inner()
+                .clone()
+                .try_into()
+                .ok()
+                .map(|l| Mu::from(l, loc_edit.loc.is_certain())),
             HgvsVariant::TxVariant { loc_edit, .. } => {
                 Some(From::from(loc_edit.loc.inner().clone()))
                     .map(|l| Mu::from(l, loc_edit.loc.is_certain()))
@@ -513,12 +1411,22 @@ impl HgvsVariant {
         self.mu_loc_range().map(|l| l.inner().clone())
     }
 
+    /// Return the 0-based range of the location with `i64` positions.
+    ///
+    /// Use this for computing with positions that may be close to `i32::MAX`, e.g., on
+    /// chromosomes of large plant genomes, without risking an overflow.
+    pub fn loc_range_i64(&self) -> Option<Range<i64>> {
+        self.loc_range()
+            .map(|range| i64::from(range.start)..i64::from(range.end))
+    }
+
     /// Return the `NaEdit` wrapped in `Mu`, if any.
     pub fn mu_na_edit(&self) -> Option<&Mu<NaEdit>> {
         match self {
             HgvsVariant::CdsVariant { loc_edit, .. } => Some(&loc_edit.edit),
             HgvsVariant::GenomeVariant { loc_edit, .. } => Some(&loc_edit.edit),
             HgvsVariant::MtVariant { loc_edit, .. } => Some(&loc_edit.edit),
+            HgvsVariant::CircularVariant { loc_edit, .. } => Some(&loc_edit.edit),
             HgvsVariant::TxVariant { loc_edit, .. } => Some(&loc_edit.edit),
             HgvsVariant::RnaVariant { loc_edit, .. } => Some(&loc_edit.edit),
             _ => None,
@@ -530,6 +1438,57 @@ impl HgvsVariant {
         self.mu_na_edit().map(|e| e.inner())
     }
 
+    /// Return the `NaEdit` wrapped in `Mu` for modification, if any.
+    pub fn mu_na_edit_mut(&mut self) -> Option<&mut Mu<NaEdit>> {
+        match self {
+            HgvsVariant::CdsVariant { loc_edit, .. } => Some(&mut loc_edit.edit),
+            HgvsVariant::GenomeVariant { loc_edit, .. } => Some(&mut loc_edit.edit),
+            HgvsVariant::MtVariant { loc_edit, .. } => Some(&mut loc_edit.edit),
+            HgvsVariant::CircularVariant { loc_edit, .. } => Some(&mut loc_edit.edit),
+            HgvsVariant::TxVariant { loc_edit, .. } => Some(&mut loc_edit.edit),
+            HgvsVariant::RnaVariant { loc_edit, .. } => Some(&mut loc_edit.edit),
+            _ => None,
+        }
+    }
+
+    /// Return the `NaEdit` for modification, if any.
+    pub fn na_edit_mut(&mut self) -> Option<&mut NaEdit> {
+        self.mu_na_edit_mut().map(|e| e.inner_mut())
+    }
+
+    /// Return the 1-based start and end base positions of a nucleic acid variant's location
+    /// for modification.
+    ///
+    /// Offsets of intronic positions and the CDS anchor of `c.` positions are left as they
+    /// are.  Returns `None` for protein variants, alleles, and locations with an unknown
+    /// start or end.
+    pub fn loc_edit_range_mut(&mut self) -> Option<(&mut i32, &mut i32)> {
+        match self {
+            HgvsVariant::CdsVariant { loc_edit, .. } => {
+                let loc = loc_edit.loc.inner_mut();
+                Some((&mut loc.start.base, &mut loc.end.base))
+            }
+            HgvsVariant::GenomeVariant { loc_edit, .. }
+            | HgvsVariant::CircularVariant { loc_edit, .. } => {
+                let loc = loc_edit.loc.inner_mut();
+                loc.start.as_mut().zip(loc.end.as_mut())
+            }
+            HgvsVariant::MtVariant { loc_edit, .. } => {
+                let loc = loc_edit.loc.inner_mut();
+                loc.start.as_mut().zip(loc.end.as_mut())
+            }
+            HgvsVariant::TxVariant { loc_edit, .. } => {
+                let loc = loc_edit.loc.inner_mut();
+                Some((&mut loc.start.base, &mut loc.end.base))
+            }
+            HgvsVariant::RnaVariant { loc_edit, .. } => {
+                let loc = loc_edit.loc.inner_mut();
+                Some((&mut loc.start.base, &mut loc.end.base))
+            }
+            HgvsVariant::ProtVariant { .. } | HgvsVariant::Allele { .. } => None,
+        }
+    }
+
     /// Return the `ProtLocEdit` if any.
     pub fn mu_prot_edit(&self) -> Option<&Mu<ProteinEdit>> {
         match self {
@@ -546,6 +1505,133 @@ impl HgvsVariant {
         self.mu_prot_edit().map(|e| e.inner())
     }
 
+    /// Return the net change in length of the variant.
+    ///
+    /// This is given in bases for nucleic acid variants and in amino acids for protein
+    /// variants.  If the edit does not specify the reference, its length is taken from the
+    /// location (unless intronic).  Returns `None` if the change is unknown.
+    pub fn length_change(&self) -> Option<i32> {
+        let loc_length = || {
+            (!self.spans_intron())
+                .then(|| self.loc_range())
+                .flatten()
+                .map(|range| range.end - range.start)
+        };
+        if let Some(edit) = self.na_edit() {
+            edit.length_change().or_else(|| {
+                let length = loc_length()?;
+                match edit {
+                    NaEdit::RefAlt { alternative, .. } | NaEdit::NumAlt { alternative, .. } => {
+                        Some(alternative.len() as i32 - length)
+                    }
+                    NaEdit::DelRef { .. } | NaEdit::DelNum { .. } => Some(-length),
+                    NaEdit::Dup { .. } => Some(length),
+                    NaEdit::InvNum { .. } => Some(0),
+                    NaEdit::Repeat {
+                        sequence,
+                        count: UncertainLengthChange::Known(count),
+                    } if !sequence.is_empty() => Some(sequence.len() as i32 * count - length),
+                    _ => None,
+                }
+            })
+        } else if let Some(edit) = self.prot_edit() {
+            edit.length_change().or_else(|| {
+                let length = loc_length()?;
+                match edit {
+                    ProteinEdit::Del => Some(-length),
+                    ProteinEdit::Dup => Some(length),
+                    ProteinEdit::DelIns { alternative } => Some(aa_length(alternative)? - length),
+                    _ => None,
+                }
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Return whether the net change in length of a nucleic acid variant is a multiple of
+    /// three.
+    ///
+    /// Returns `false` for protein variants and if the change is unknown.
+    pub fn is_inframe(&self) -> bool {
+        self.na_edit()
+            .and(self.length_change())
+            .map(|n| n % 3 == 0)
+            .unwrap_or(false)
+    }
+
+    /// Return whether a coding or transcript variant is within `window` intronic bases of an
+    /// exon boundary.
+    ///
+    /// Use `SPLICE_SITE_WINDOW` to check the canonical splice donor and acceptor sites
+    /// (`+1`/`+2` and `-2`/`-1`).  Larger windows catch variants that may still affect
+    /// splicing, e.g., the extended donor site up to `+8` and the acceptor region up to
+    /// `-10`.  Deletions and insertions further inside the intron count if they reach into
+    /// the window or the exon.
+    pub fn is_splicing(&self, window: i32) -> bool {
+        let (start, end) = match self {
+            HgvsVariant::CdsVariant { loc_edit, .. } => {
+                let loc = loc_edit.loc.inner();
+                (
+                    (loc.start.base, loc.start.offset.unwrap_or(0)),
+                    (loc.end.base, loc.end.offset.unwrap_or(0)),
+                )
+            }
+            HgvsVariant::TxVariant { loc_edit, .. } => {
+                let loc = loc_edit.loc.inner();
+                (
+                    (loc.start.base, loc.start.offset.unwrap_or(0)),
+                    (loc.end.base, loc.end.offset.unwrap_or(0)),
+                )
+            }
+            _ => return false,
+        };
+        near_splice_site(start, end, window)
+    }
+
+    /// Return whether a coding variant overlaps the CDS positions `start` to `end`, counted
+    /// from the CDS start.
+    fn overlaps_cds_range(&self, start: i32, end: i32) -> bool {
+        let pos = |base| CdsPos {
+            base,
+            offset: None,
+            cds_from: CdsFrom::Start,
+        };
+        match self {
+            HgvsVariant::CdsVariant { loc_edit, .. } => {
+                loc_edit.loc.inner().overlaps(&CdsInterval {
+                    start: pos(start),
+                    end: pos(end),
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Return whether a coding variant overlaps the start codon, i.e., `c.1_3`.
+    ///
+    /// Intronic positions next to the start codon are not part of it.
+    pub fn affects_start_codon(&self) -> bool {
+        self.overlaps_cds_range(1, 3)
+    }
+
+    /// Return whether a coding variant overlaps the stop codon, given the CDS position of its
+    /// last base, i.e., the length of the CDS including the stop codon.
+    pub fn affects_stop_codon(&self, cds_end_codon_pos: i32) -> bool {
+        self.overlaps_cds_range(cds_end_codon_pos - 2, cds_end_codon_pos)
+    }
+
+    /// Classify the consequence of a protein variant, see `ProtLocEdit::consequence()`.
+    ///
+    /// All other variants yield `ProteinConsequence::Uncertain`, use
+    /// `mapper::variant::Mapper::coding_consequence()` for coding variants.
+    pub fn protein_consequence(&self) -> ProteinConsequence {
+        match self {
+            HgvsVariant::ProtVariant { loc_edit, .. } => loc_edit.consequence(),
+            _ => ProteinConsequence::Uncertain,
+        }
+    }
+
     /// Return whether start or end position is intronic (offset != 0).
     pub fn spans_intron(&self) -> bool {
         match self {
@@ -564,10 +1650,32 @@ impl HgvsVariant {
             _ => false,
         }
     }
+
+    /// Returns whether the variant is known to be normalized without looking at the sequence.
+    ///
+    /// This is the case for identity edits, single nucleotide substitutions, and variants
+    /// with uncertain location or edit, as normalization leaves these unchanged.  A return
+    /// value of `false` does not imply that the variant needs normalization, use
+    /// `is_normalized()` for an exact answer.
+    pub fn is_trivially_normalized(&self) -> bool {
+        let (mu_na_edit, mu_loc_range) = match (self.mu_na_edit(), self.mu_loc_range()) {
+            (Some(mu_na_edit), Some(mu_loc_range)) => (mu_na_edit, mu_loc_range),
+            _ => return false,
+        };
+        if !mu_na_edit.is_certain() || !mu_loc_range.is_certain() {
+            return true;
+        }
+
+        let na_edit = mu_na_edit.inner();
+        matches!(na_edit, NaEdit::RefAlt { reference, alternative } if reference == alternative)
+            || na_edit.is_snv()
+    }
 }
 
 /// Coding sequence location with edit.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct CdsLocEdit {
     /// Location on the CDS.
     pub loc: Mu<CdsInterval>,
@@ -597,10 +1705,25 @@ impl CdsLocEdit {
             },
         }
     }
+
+    /// Return the LocEdit with the `NaEdit` in minimal form, see `NaEdit::to_minimal()`.
+    fn with_minimal_edit(self) -> Self {
+        CdsLocEdit {
+            loc: self.loc,
+            edit: match self.edit {
+                Mu::Certain(edit) => Mu::Certain(edit.to_minimal()),
+                Mu::Uncertain(edit) => Mu::Uncertain(edit.to_minimal()),
+            },
+        }
+    }
 }
 
 /// CDS position interval.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// Intervals are ordered lexicographically by `(start, end)`.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct CdsInterval {
     /// Start position
     pub start: CdsPos,
@@ -608,6 +1731,107 @@ pub struct CdsInterval {
     pub end: CdsPos,
 }
 
+impl CdsInterval {
+    /// Return the 0-based, half-open range of the interval relative to the CDS start.
+    ///
+    /// `c.1` maps to `0..1` and the 5' UTR to negative values, e.g., `c.-1` maps to `-1..0`.
+    /// Returns `None` for intervals with intronic positions and for intervals touching the
+    /// 3' UTR (`c.*1` etc.) as their position relative to the CDS start depends on the CDS
+    /// length.
+    pub fn to_0based_half_open_tx(&self) -> Option<Range<i32>> {
+        (self.start.offset.unwrap_or(0) == 0
+            && self.end.offset.unwrap_or(0) == 0
+            && self.start.cds_from == CdsFrom::Start
+            && self.end.cds_from == CdsFrom::Start)
+            .then(|| range_0based(self.start.base, self.end.base))
+    }
+
+    /// Return the 0-based position within its codon of the start base.
+    ///
+    /// Returns `None` if the interval does not start in the CDS itself, i.e., in an intron or
+    /// in the UTRs.
+    pub fn codon_position(&self) -> Option<u8> {
+        let start = &self.start;
+        if start.cds_from == CdsFrom::Start && start.base > 0 && start.offset.unwrap_or(0) == 0 {
+            Some(reading_frame_offset(start.base))
+        } else {
+            None
+        }
+    }
+
+    /// Return whether the length of the interval is a multiple of three.
+    ///
+    /// Intervals with intronic positions or spanning the CDS end are not considered in-frame.
+    pub fn is_inframe(&self) -> bool {
+        self.length().is_some_and(|len| len > 0 && len % 3 == 0)
+    }
+
+    /// Return the number of bases in the interval.
+    ///
+    /// Returns `None` for intervals with intronic positions or spanning the CDS end.
+    pub fn length(&self) -> Option<i32> {
+        let (start, end) = (&self.start, &self.end);
+        (start.offset.unwrap_or(0) == 0
+            && end.offset.unwrap_or(0) == 0
+            && start.cds_from == end.cds_from)
+            .then(|| span_length(start.base, end.base))
+    }
+
+    /// Return whether the two intervals share a position.
+    pub fn overlaps(&self, other: &CdsInterval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Return whether `pos` lies within the interval.
+    pub fn contains(&self, pos: &CdsPos) -> bool {
+        &self.start <= pos && pos <= &self.end
+    }
+
+    /// Return the smallest interval covering both intervals.
+    pub fn union(&self, other: &CdsInterval) -> CdsInterval {
+        CdsInterval {
+            start: self.start.clone().min(other.start.clone()),
+            end: self.end.clone().max(other.end.clone()),
+        }
+    }
+
+    /// Convert to a transcript (`n.`) interval, see `CdsPos::to_tx_pos()`.
+    pub fn to_tx_interval(&self, cds_start_i: i32, cds_end_i: i32) -> TxInterval {
+        TxInterval {
+            start: self.start.to_tx_pos(cds_start_i, cds_end_i),
+            end: self.end.to_tx_pos(cds_start_i, cds_end_i),
+        }
+    }
+
+    /// Return the positions shared by both intervals, `None` if they do not overlap.
+    pub fn intersection(&self, other: &CdsInterval) -> Option<CdsInterval> {
+        self.overlaps(other).then(|| CdsInterval {
+            start: self.start.clone().max(other.start.clone()),
+            end: self.end.clone().min(other.end.clone()),
+        })
+    }
+
+    /// Return whether both start and end lie in the 5' UTR.
+    pub fn is_entirely_utr_5(&self) -> bool {
+        self.start.in_utr_5() && self.end.in_utr_5()
+    }
+
+    /// Return whether both start and end lie in the 3' UTR.
+    pub fn is_entirely_utr_3(&self) -> bool {
+        self.start.in_utr_3() && self.end.in_utr_3()
+    }
+
+    /// Return whether both start and end lie in the CDS.
+    pub fn is_entirely_cds(&self) -> bool {
+        self.start.in_cds() && self.end.in_cds()
+    }
+
+    /// Return whether start and end lie in different regions, e.g., `c.-3_3`.
+    pub fn spans_utr_cds_boundary(&self) -> bool {
+        !(self.is_entirely_utr_5() || self.is_entirely_utr_3() || self.is_entirely_cds())
+    }
+}
+
 impl TryFrom<CdsInterval> for Range<i32> {
     type Error = Error;
 
@@ -631,14 +1855,19 @@ impl TryFrom<CdsInterval> for Range<i32> {
 
 /// Specifies whether the CDS position is relative to the CDS start or
 /// CDS end.
-#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub enum CdsFrom {
     Start,
     End,
 }
 
 /// CDS position.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// Positions are ordered by `cds_from` (positions relative to the CDS start come first),
+/// then by `base`, then by `offset` where a missing offset is treated as `0`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct CdsPos {
     /// Base position.
     pub base: i32,
@@ -648,8 +1877,72 @@ pub struct CdsPos {
     pub cds_from: CdsFrom,
 }
 
+impl PartialOrd for CdsPos {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CdsPos {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cds_from
+            .cmp(&other.cds_from)
+            .then(self.base.cmp(&other.base))
+            .then(cmp_offsets(self.offset, other.offset))
+    }
+}
+
+impl CdsPos {
+    /// Return whether the position lies in the 5' UTR, e.g., `c.-3` or `c.-3+1`.
+    pub fn in_utr_5(&self) -> bool {
+        self.cds_from == CdsFrom::Start && self.base < 0
+    }
+
+    /// Return whether the position lies in the 3' UTR, e.g., `c.*3`.
+    pub fn in_utr_3(&self) -> bool {
+        self.cds_from == CdsFrom::End
+    }
+
+    /// Return whether the position lies in the CDS, e.g., `c.3` or `c.3+1`.
+    ///
+    /// As for the UTRs, intronic positions are assigned by their base.
+    pub fn in_cds(&self) -> bool {
+        self.cds_from == CdsFrom::Start && self.base > 0
+    }
+
+    /// Convert to a transcript (`n.`) position.
+    ///
+    /// `cds_start_i` and `cds_end_i` are the 0-based, half-open CDS boundaries on the
+    /// transcript, as in `TxIdentityInfo`.  Positions outside of the transcript are not
+    /// checked.
+    pub fn to_tx_pos(&self, cds_start_i: i32, cds_end_i: i32) -> TxPos {
+        let n = match self.cds_from {
+            // correct for lack of c.0 coordinate
+            CdsFrom::Start if self.base < 0 => self.base + cds_start_i + 1,
+            CdsFrom::Start => self.base + cds_start_i,
+            CdsFrom::End => self.base + cds_end_i,
+        };
+        TxPos {
+            // correct for lack of n.0 coordinate
+            base: if n <= 0 { n - 1 } else { n },
+            offset: self.offset,
+        }
+    }
+}
+
+/// Compare optional offsets of positions, treating `None` as `0`.
+///
+/// To be consistent with `PartialEq`, `None` sorts before `Some(0)`.
+fn cmp_offsets(lhs: Option<i32>, rhs: Option<i32>) -> Ordering {
+    lhs.unwrap_or(0)
+        .cmp(&rhs.unwrap_or(0))
+        .then(lhs.is_some().cmp(&rhs.is_some()))
+}
+
 /// Genome sequence location with edit.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct GenomeLocEdit {
     /// Location on the genome.
     pub loc: Mu<GenomeInterval>,
@@ -679,10 +1972,23 @@ impl GenomeLocEdit {
             },
         }
     }
+
+    /// Return the LocEdit with the `NaEdit` in minimal form, see `NaEdit::to_minimal()`.
+    fn with_minimal_edit(self) -> Self {
+        GenomeLocEdit {
+            loc: self.loc,
+            edit: match self.edit {
+                Mu::Certain(edit) => Mu::Certain(edit.to_minimal()),
+                Mu::Uncertain(edit) => Mu::Uncertain(edit.to_minimal()),
+            },
+        }
+    }
 }
 
 /// Genome position interval.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// Intervals are ordered by `(start, end)` where unknown (`None`) positions sort last.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct GenomeInterval {
     /// Start position
     pub start: Option<i32>,
@@ -690,6 +1996,99 @@ pub struct GenomeInterval {
     pub end: Option<i32>,
 }
 
+impl GenomeInterval {
+    /// Return the number of bases in the interval, `None` if a bound is unknown.
+    pub fn length(&self) -> Option<i32> {
+        Some(self.end? - self.start? + 1)
+    }
+
+    /// Return the interval as 0-based, half-open `(start, end)` as used, e.g., in BED files.
+    ///
+    /// Returns `None` if a bound is unknown.
+    pub fn to_0based_half_open(&self) -> Option<(i32, i32)> {
+        Some((self.start? - 1, self.end?))
+    }
+
+    /// Construct from 0-based, half-open coordinates, see `to_0based_half_open()`.
+    pub fn from_0based_half_open(start: i32, end: i32) -> Self {
+        Self {
+            start: Some(start + 1),
+            end: Some(end),
+        }
+    }
+
+    /// Return whether the two intervals share a position, `None` if a bound is unknown.
+    pub fn overlaps(&self, other: &GenomeInterval) -> Option<bool> {
+        Some(self.start? <= other.end? && other.start? <= self.end?)
+    }
+
+    /// Return whether `pos` lies within the interval, `None` if a bound is unknown.
+    pub fn contains(&self, pos: i32) -> Option<bool> {
+        Some(self.start? <= pos && pos <= self.end?)
+    }
+
+    /// Return whether the two intervals share a position, see `intersection()`.
+    ///
+    /// Unknown (`None`) bounds are treated as unbounded.
+    pub fn overlaps_unbounded(&self, other: &GenomeInterval) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Return whether `pos` lies within the interval.
+    ///
+    /// Unknown (`None`) bounds are treated as unbounded.
+    pub fn contains_unbounded(&self, pos: i32) -> bool {
+        self.start.map_or(true, |start| start <= pos) && self.end.map_or(true, |end| pos <= end)
+    }
+
+    /// Return the smallest interval covering both intervals.
+    ///
+    /// Unknown (`None`) bounds are treated as unbounded and thus kept.
+    pub fn union(&self, other: &GenomeInterval) -> GenomeInterval {
+        GenomeInterval {
+            start: self.start.zip(other.start).map(|(a, b)| a.min(b)),
+            end: self.end.zip(other.end).map(|(a, b)| a.max(b)),
+        }
+    }
+
+    /// Return the positions shared by both intervals, `None` if they do not overlap.
+    ///
+    /// Unknown (`None`) bounds are treated as unbounded.
+    pub fn intersection(&self, other: &GenomeInterval) -> Option<GenomeInterval> {
+        let start = self.start.max(other.start);
+        let end = match (self.end, other.end) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        match (start, end) {
+            (Some(start), Some(end)) if start > end => None,
+            _ => Some(GenomeInterval { start, end }),
+        }
+    }
+}
+
+impl PartialOrd for GenomeInterval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GenomeInterval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_unknown_last(self.start, other.start).then(cmp_unknown_last(self.end, other.end))
+    }
+}
+
+/// Compare optional positions, sorting unknown (`None`) positions last.
+fn cmp_unknown_last(lhs: Option<i32>, rhs: Option<i32>) -> Ordering {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => lhs.cmp(&rhs),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
 impl TryInto<Range<i32>> for GenomeInterval {
     type Error = Error;
 
@@ -705,7 +2104,9 @@ impl TryInto<Range<i32>> for GenomeInterval {
 }
 
 /// Mitochondrial sequence location with edit.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct MtLocEdit {
     /// Location on the mitochondrium.
     pub loc: Mu<MtInterval>,
@@ -735,9 +2136,22 @@ impl MtLocEdit {
             },
         }
     }
+
+    /// Return the LocEdit with the `NaEdit` in minimal form, see `NaEdit::to_minimal()`.
+    fn with_minimal_edit(self) -> Self {
+        MtLocEdit {
+            loc: self.loc,
+            edit: match self.edit {
+                Mu::Certain(edit) => Mu::Certain(edit.to_minimal()),
+                Mu::Uncertain(edit) => Mu::Uncertain(edit.to_minimal()),
+            },
+        }
+    }
 }
 /// Mitochondrial position interval.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// Intervals are ordered by `(start, end)` where unknown (`None`) positions sort last.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct MtInterval {
     /// Start position
     pub start: Option<i32>,
@@ -745,6 +2159,35 @@ pub struct MtInterval {
     pub end: Option<i32>,
 }
 
+impl MtInterval {
+    /// Return the interval as 0-based, half-open `(start, end)` as used, e.g., in BED files.
+    ///
+    /// Returns `None` if a bound is unknown.
+    pub fn to_0based_half_open(&self) -> Option<(i32, i32)> {
+        Some((self.start? - 1, self.end?))
+    }
+
+    /// Construct from 0-based, half-open coordinates, see `to_0based_half_open()`.
+    pub fn from_0based_half_open(start: i32, end: i32) -> Self {
+        Self {
+            start: Some(start + 1),
+            end: Some(end),
+        }
+    }
+}
+
+impl PartialOrd for MtInterval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MtInterval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_unknown_last(self.start, other.start).then(cmp_unknown_last(self.end, other.end))
+    }
+}
+
 impl TryInto<Range<i32>> for MtInterval {
     type Error = Error;
 
@@ -760,7 +2203,9 @@ impl TryInto<Range<i32>> for MtInterval {
 }
 
 /// Transcript sequence location with edit.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct TxLocEdit {
     /// Loction on a transcript.
     pub loc: Mu<TxInterval>,
@@ -790,10 +2235,25 @@ impl TxLocEdit {
             },
         }
     }
+
+    /// Return the LocEdit with the `NaEdit` in minimal form, see `NaEdit::to_minimal()`.
+    fn with_minimal_edit(self) -> Self {
+        TxLocEdit {
+            loc: self.loc,
+            edit: match self.edit {
+                Mu::Certain(edit) => Mu::Certain(edit.to_minimal()),
+                Mu::Uncertain(edit) => Mu::Uncertain(edit.to_minimal()),
+            },
+        }
+    }
 }
 
 /// Transcript position interval.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// Intervals are ordered lexicographically by `(start, end)`.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct TxInterval {
     /// Start position
     pub start: TxPos,
@@ -801,6 +2261,58 @@ pub struct TxInterval {
     pub end: TxPos,
 }
 
+impl TxInterval {
+    /// Return the number of bases in the interval, `None` for intervals with intronic
+    /// positions.
+    pub fn length(&self) -> Option<i32> {
+        (self.start.offset.unwrap_or(0) == 0 && self.end.offset.unwrap_or(0) == 0)
+            .then(|| span_length(self.start.base, self.end.base))
+    }
+
+    /// Return whether the two intervals share a position.
+    pub fn overlaps(&self, other: &TxInterval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Return whether `pos` lies within the interval.
+    pub fn contains(&self, pos: &TxPos) -> bool {
+        &self.start <= pos && pos <= &self.end
+    }
+
+    /// Return the smallest interval covering both intervals.
+    pub fn union(&self, other: &TxInterval) -> TxInterval {
+        TxInterval {
+            start: self.start.clone().min(other.start.clone()),
+            end: self.end.clone().max(other.end.clone()),
+        }
+    }
+
+    /// Return the positions shared by both intervals, `None` if they do not overlap.
+    pub fn intersection(&self, other: &TxInterval) -> Option<TxInterval> {
+        self.overlaps(other).then(|| TxInterval {
+            start: self.start.clone().max(other.start.clone()),
+            end: self.end.clone().min(other.end.clone()),
+        })
+    }
+
+    /// Convert to a CDS (`c.`) interval, see `TxPos::to_cds_pos()`.
+    pub fn to_cds_interval(&self, cds_start_i: i32, cds_end_i: i32) -> Option<CdsInterval> {
+        Some(CdsInterval {
+            start: self.start.to_cds_pos(cds_start_i, cds_end_i)?,
+            end: self.end.to_cds_pos(cds_start_i, cds_end_i)?,
+        })
+    }
+
+    /// Return the 0-based, half-open range of the interval on the transcript.
+    ///
+    /// Positions before the transcript (`n.-1` etc.) map to negative values.  Returns `None`
+    /// for intervals with intronic positions.
+    pub fn to_0based_half_open_tx(&self) -> Option<Range<i32>> {
+        (self.start.offset.unwrap_or(0) == 0 && self.end.offset.unwrap_or(0) == 0)
+            .then(|| range_0based(self.start.base, self.end.base))
+    }
+}
+
 impl From<TxInterval> for Range<i32> {
     /// The transcript interval will be converted from 1-based inclusive coordinates
     /// `[start, end]` to 0-based, half-open Rust range `[start - 1, end)`.
@@ -817,7 +2329,10 @@ impl From<TxInterval> for Range<i32> {
 }
 
 /// Transcript position.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// Positions are ordered by `base`, then by `offset` where a missing offset is treated as
+/// `0`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct TxPos {
     /// Base position.
     pub base: i32,
@@ -825,8 +2340,58 @@ pub struct TxPos {
     pub offset: Option<i32>,
 }
 
+impl PartialOrd for TxPos {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TxPos {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.base
+            .cmp(&other.base)
+            .then(cmp_offsets(self.offset, other.offset))
+    }
+}
+
+impl TxPos {
+    /// Return whether the position lies in an exon, i.e., has no offset.
+    pub fn in_exon(&self) -> bool {
+        self.offset.unwrap_or(0) == 0
+    }
+
+    /// Return whether the position lies in an intron, e.g., `n.10+1`.
+    pub fn in_intron(&self) -> bool {
+        !self.in_exon()
+    }
+
+    /// Convert to a CDS (`c.`) position, see `CdsPos::to_tx_pos()` for the arguments.
+    ///
+    /// Returns `None` for the invalid position `n.0`.
+    pub fn to_cds_pos(&self, cds_start_i: i32, cds_end_i: i32) -> Option<CdsPos> {
+        let (base, cds_from) = if self.base == 0 {
+            return None;
+        } else if self.base <= cds_start_i {
+            // correct for lack of c.0 coordinate
+            let base = self.base - cds_start_i - i32::from(self.base > 0);
+            (base, CdsFrom::Start)
+        } else if self.base <= cds_end_i {
+            (self.base - cds_start_i, CdsFrom::Start)
+        } else {
+            (self.base - cds_end_i, CdsFrom::End)
+        };
+        Some(CdsPos {
+            base,
+            offset: self.offset,
+            cds_from,
+        })
+    }
+}
+
 /// RNA sequence location with edit.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct RnaLocEdit {
     /// Location on a transcript.
     pub loc: Mu<RnaInterval>,
@@ -856,9 +2421,24 @@ impl RnaLocEdit {
             },
         }
     }
+
+    /// Return the LocEdit with the `NaEdit` in minimal form, see `NaEdit::to_minimal()`.
+    fn with_minimal_edit(self) -> Self {
+        RnaLocEdit {
+            loc: self.loc,
+            edit: match self.edit {
+                Mu::Certain(edit) => Mu::Certain(edit.to_minimal()),
+                Mu::Uncertain(edit) => Mu::Uncertain(edit.to_minimal()),
+            },
+        }
+    }
 }
 /// RNA position interval.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// Intervals are ordered lexicographically by `(start, end)`.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct RnaInterval {
     /// Start position
     pub start: RnaPos,
@@ -866,6 +2446,25 @@ pub struct RnaInterval {
     pub end: RnaPos,
 }
 
+impl RnaInterval {
+    /// Return the number of bases in the interval, `None` for intervals with intronic
+    /// positions.
+    pub fn length(&self) -> Option<i32> {
+        (self.start.offset.unwrap_or(0) == 0 && self.end.offset.unwrap_or(0) == 0)
+            .then(|| span_length(self.start.base, self.end.base))
+    }
+
+    /// Return whether the two intervals share a position.
+    pub fn overlaps(&self, other: &RnaInterval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Return whether `pos` lies within the interval.
+    pub fn contains(&self, pos: &RnaPos) -> bool {
+        &self.start <= pos && pos <= &self.end
+    }
+}
+
 impl From<RnaInterval> for Range<i32> {
     /// The RNA interval will be converted from 1-based inclusive coordinates
     /// `[start, end]` to 0-based, half-open Rust range `[start - 1, end)`.
@@ -876,43 +2475,240 @@ impl From<RnaInterval> for Range<i32> {
         if val.start.base > 0 {
             (val.start.base - 1)..val.end.base
         } else {
-            val.start.base..(val.end.base + 1)
+            val.start.base..(val.end.base + 1)
+        }
+    }
+}
+
+/// RNA position.
+///
+/// Positions are ordered by `base`, then by `offset` where a missing offset is treated as
+/// `0`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct RnaPos {
+    /// Base position.
+    pub base: i32,
+    /// Optional offset.
+    pub offset: Option<i32>,
+}
+
+impl PartialOrd for RnaPos {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RnaPos {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.base
+            .cmp(&other.base)
+            .then(cmp_offsets(self.offset, other.offset))
+    }
+}
+
+/// Consequence of a variant on the protein.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ProteinConsequence {
+    /// No change of the protein, e.g., `p.=` or `p.Lys2=`.
+    Synonymous,
+    /// Substitution by a different amino acid, e.g., `p.Lys2Asn`.
+    Missense,
+    /// Substitution by a stop codon, e.g., `p.Ser3Ter`.
+    Nonsense,
+    /// Frameshift, e.g., `p.Ala3ArgfsTer6`.
+    Frameshift,
+    /// In-frame insertion or duplication, e.g., `p.Lys2_Ala3insGly`.
+    InframeInsertion,
+    /// In-frame deletion, e.g., `p.Lys4del`.
+    InframeDeletion,
+    /// In-frame deletion-insertion, e.g., `p.Lys4delinsIlePro`.
+    InframeIndel,
+    /// Change of the initiation codon, e.g., `p.Met1?`.
+    StartLost,
+    /// Change of the stop codon, e.g., `p.Ter10TyrextTer3`.
+    StopLost,
+    /// Insertion or deletion-insertion introducing a stop codon, e.g., `p.Lys4_Ala5insTer`.
+    StopGained,
+    /// N-terminal extension, e.g., `p.Met1ext-5`.
+    Extension,
+    /// Change of a splice site, only derived from the coding variant.
+    SpliceSite,
+    /// The consequence is unknown, e.g., `p.?` or `p.0`.
+    Uncertain,
+}
+
+/// Protein sequence location with edit or special.
+///
+/// The specials sort before ordinary location edits in the order `NoProtein`,
+/// `NoProteinUncertain`, `Unknown`, `InitiationUncertain`, `NoChange`, and
+/// `NoChangeUncertain`.  Ordinary location edits are ordered by their interval.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ProtLocEdit {
+    Ordinary {
+        loc: Mu<ProtInterval>,
+        edit: Mu<ProteinEdit>,
+    },
+    /// `=`
+    NoChange,
+    /// `(=)`
+    NoChangeUncertain,
+    /// `0`
+    NoProtein,
+    /// `0?`
+    NoProteinUncertain,
+    /// `?`
+    Unknown,
+    /// `Met1?`
+    InitiationUncertain,
+}
+
+impl PartialOrd for ProtLocEdit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ProtLocEdit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn rank(loc_edit: &ProtLocEdit) -> u8 {
+            match loc_edit {
+                ProtLocEdit::NoProtein => 0,
+                ProtLocEdit::NoProteinUncertain => 1,
+                ProtLocEdit::Unknown => 2,
+                ProtLocEdit::InitiationUncertain => 3,
+                ProtLocEdit::NoChange => 4,
+                ProtLocEdit::NoChangeUncertain => 5,
+                ProtLocEdit::Ordinary { .. } => 6,
+            }
+        }
+
+        match (self, other) {
+            (
+                ProtLocEdit::Ordinary { loc, edit },
+                ProtLocEdit::Ordinary {
+                    loc: other_loc,
+                    edit: other_edit,
+                },
+            ) => loc
+                .inner()
+                .cmp(other_loc.inner())
+                .then_with(|| loc.cmp(other_loc))
+                .then_with(|| edit.cmp(other_edit)),
+            _ => rank(self).cmp(&rank(other)),
+        }
+    }
+}
+
+impl ProtLocEdit {
+    /// Return whether the edit substitutes the reference amino acid by itself.
+    fn is_subst_by_reference(&self) -> bool {
+        match self {
+            ProtLocEdit::Ordinary { loc, edit } => match edit.inner() {
+                ProteinEdit::Subst { alternative } => {
+                    let reference = &loc.inner().start.aa;
+                    loc.inner().start == loc.inner().end
+                        && aa_to_aa1(reference).ok() == aa_to_aa1(alternative).ok()
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns whether the variant does not change the protein, e.g., `p.=` or `p.Lys2Lys`.
+    pub fn is_synonymous(&self) -> bool {
+        match self {
+            ProtLocEdit::Ordinary { edit, .. } => {
+                edit.inner().is_synonymous() || self.is_subst_by_reference()
+            }
+            ProtLocEdit::NoChange | ProtLocEdit::NoChangeUncertain => true,
+            _ => false,
+        }
+    }
+
+    /// Returns whether the variant substitutes an amino acid by a different one.
+    pub fn is_missense(&self) -> bool {
+        match self {
+            ProtLocEdit::Ordinary { edit, .. } => {
+                edit.inner().is_missense() && !self.is_subst_by_reference()
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether the variant substitutes an amino acid by a different one of the same
+    /// biochemical class, e.g., `p.Val600Ile`.
+    ///
+    /// See `amino_acid_group()` for the classes.
+    pub fn is_conservative(&self) -> bool {
+        match self {
+            ProtLocEdit::Ordinary { loc, edit } if self.is_missense() => match edit.inner() {
+                ProteinEdit::Subst { alternative } => {
+                    let reference = amino_acid_group(&loc.inner().start.aa);
+                    reference.is_some() && reference == amino_acid_group(alternative)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Classify the consequence of the variant on the protein.
+    ///
+    /// Changes of `Met1` count as start loss and changes of the stop codon as stop loss,
+    /// regardless of the edit.
+    pub fn consequence(&self) -> ProteinConsequence {
+        let (loc, edit) = match self {
+            ProtLocEdit::Ordinary { loc, edit } => (loc.inner(), edit.inner()),
+            ProtLocEdit::NoChange | ProtLocEdit::NoChangeUncertain => {
+                return ProteinConsequence::Synonymous
+            }
+            ProtLocEdit::InitiationUncertain => return ProteinConsequence::StartLost,
+            ProtLocEdit::NoProtein | ProtLocEdit::NoProteinUncertain | ProtLocEdit::Unknown => {
+                return ProteinConsequence::Uncertain
+            }
+        };
+        let has_stop = |seq: &str| aa_to_aa1(seq).is_ok_and(|seq| seq.contains('*'));
+
+        if self.is_synonymous() {
+            ProteinConsequence::Synonymous
+        } else if edit.is_frameshift() {
+            ProteinConsequence::Frameshift
+        } else if is_stop_aa(&loc.start.aa) || is_stop_aa(&loc.end.aa) {
+            ProteinConsequence::StopLost
+        } else if edit.is_extension() {
+            ProteinConsequence::Extension
+        } else if loc.start.number == 1 {
+            ProteinConsequence::StartLost
+        } else {
+            match edit {
+                ProteinEdit::Subst { alternative } if alternative == "?" => {
+                    ProteinConsequence::Uncertain
+                }
+                ProteinEdit::Subst { .. } if edit.is_nonsense() => ProteinConsequence::Nonsense,
+                ProteinEdit::Subst { .. } => ProteinConsequence::Missense,
+                ProteinEdit::Ins { alternative } | ProteinEdit::DelIns { alternative }
+                    if has_stop(alternative) =>
+                {
+                    ProteinConsequence::StopGained
+                }
+                ProteinEdit::Ins { .. } | ProteinEdit::Dup => ProteinConsequence::InframeInsertion,
+                ProteinEdit::Del => ProteinConsequence::InframeDeletion,
+                ProteinEdit::DelIns { .. } => ProteinConsequence::InframeIndel,
+                ProteinEdit::Fs { .. } | ProteinEdit::Ext { .. } | ProteinEdit::Ident => {
+                    unreachable!("handled above")
+                }
+            }
         }
     }
 }
 
-/// RNA position.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
-pub struct RnaPos {
-    /// Base position.
-    pub base: i32,
-    /// Optional offset.
-    pub offset: Option<i32>,
-}
-
-/// Protein sequence location with edit or special.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
-pub enum ProtLocEdit {
-    Ordinary {
-        loc: Mu<ProtInterval>,
-        edit: Mu<ProteinEdit>,
-    },
-    /// `=`
-    NoChange,
-    /// `(=)`
-    NoChangeUncertain,
-    /// `0`
-    NoProtein,
-    /// `0?`
-    NoProteinUncertain,
-    /// `?`
-    Unknown,
-    /// `Met1?`
-    InitiationUncertain,
-}
-
 /// Protein position interval.
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// Intervals are ordered lexicographically by `(start, end)`.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub struct ProtInterval {
     /// Start position
     pub start: ProtPos,
@@ -920,6 +2716,23 @@ pub struct ProtInterval {
     pub end: ProtPos,
 }
 
+impl ProtInterval {
+    /// Return the number of amino acids in the interval.
+    pub fn length(&self) -> i32 {
+        self.end.number - self.start.number + 1
+    }
+
+    /// Return whether the two intervals share an amino acid position.
+    pub fn overlaps(&self, other: &ProtInterval) -> bool {
+        self.start.number <= other.end.number && other.start.number <= self.end.number
+    }
+
+    /// Return whether `pos` lies within the interval.
+    pub fn contains(&self, pos: &ProtPos) -> bool {
+        self.start.number <= pos.number && pos.number <= self.end.number
+    }
+}
+
 impl From<ProtInterval> for Range<i32> {
     fn from(val: ProtInterval) -> Self {
         if val.start.number > 0 {
@@ -931,7 +2744,9 @@ impl From<ProtInterval> for Range<i32> {
 }
 
 /// Protein position.
-#[derive(Clone, Debug, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+///
+/// Positions are ordered by `number`, then alphabetically by `aa`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
 pub struct ProtPos {
     /// Amino acid value.
     pub aa: String,
@@ -939,12 +2754,686 @@ pub struct ProtPos {
     pub number: i32,
 }
 
+impl PartialOrd for ProtPos {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ProtPos {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.number
+            .cmp(&other.number)
+            .then_with(|| self.aa.cmp(&other.aa))
+    }
+}
+
+impl ProtPos {
+    /// Return the signed distance in amino acids from this position to `other`.
+    pub fn distance(&self, other: &ProtPos) -> i32 {
+        other.number - self.number
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
     use pretty_assertions::assert_eq;
 
-    use super::{TxInterval, TxPos};
-    use crate::parser::Mu;
+    use super::{
+        Accession, CdsFrom, CdsInterval, CdsPos, GenomeInterval, MoleculeType, MtInterval, NaEdit,
+        ProtInterval, ProtPos, ProteinConsequence, RnaPos, TxInterval, TxPos,
+    };
+    use crate::parser::{HgvsVariant, HgvsVariantKind, Mu, SPLICE_SITE_WINDOW};
+
+    #[test]
+    fn accession_version() {
+        assert_eq!(Accession::new("NM_01234.5").version(), Some(5));
+        assert_eq!(Accession::new("NM_01234.5").without_version(), "NM_01234");
+        assert_eq!(
+            Accession::new("ENST00000225964.10").base(),
+            "ENST00000225964"
+        );
+        assert!(Accession::new("NM_01234.5").is_versioned());
+        assert_eq!(Accession::new("ENST00000225964.10").version(), Some(10));
+        assert_eq!(Accession::new("NM_01234").version(), None);
+        assert_eq!(Accession::new("NM_01234").without_version(), "NM_01234");
+        assert!(!Accession::new("NM_01234").is_versioned());
+        assert!(!Accession::new("NM_01234.x").is_versioned());
+        assert_eq!(Accession::new("LRG_1t1").version(), None);
+    }
+
+    #[test]
+    fn accession_molecule_type() {
+        for (ac, molecule_type, refseq, ensembl) in [
+            ("NM_000088.3", MoleculeType::Mrna, true, false),
+            ("XM_011524341.2", MoleculeType::Mrna, true, false),
+            ("NR_027676.1", MoleculeType::NcRna, true, false),
+            ("XR_001737578.1", MoleculeType::NcRna, true, false),
+            ("NP_000079.2", MoleculeType::Protein, true, false),
+            ("XP_011522643.1", MoleculeType::Protein, true, false),
+            ("NC_000017.10", MoleculeType::Genomic, true, false),
+            ("NT_167249.1", MoleculeType::Genomic, true, false),
+            ("NW_003315950.2", MoleculeType::Genomic, true, false),
+            ("AC_01234.5", MoleculeType::Genomic, true, false),
+            ("NG_007400.1", MoleculeType::RefSeqGene, true, false),
+            ("NC_012920.1", MoleculeType::Mitochondrial, true, false),
+            ("NC_001807.4", MoleculeType::Mitochondrial, true, false),
+            ("ENST00000225964.10", MoleculeType::Ensembl, false, true),
+            ("ENSP00000225964.5", MoleculeType::Ensembl, false, true),
+            ("LRG_1t1", MoleculeType::Lrg, false, false),
+            ("MD5_01234abc", MoleculeType::Unknown, false, false),
+            ("chr1", MoleculeType::Unknown, false, false),
+        ] {
+            let accession = Accession::new(ac);
+            assert_eq!(accession.molecule_type(), molecule_type, "{ac}");
+            assert_eq!(accession.is_refseq(), refseq, "{ac}");
+            assert_eq!(accession.is_ensembl(), ensembl, "{ac}");
+        }
+    }
+
+    #[test]
+    fn accession_lrg() {
+        assert!(!Accession::new("NM_01234.5").is_lrg());
+        assert_eq!(Accession::new("NM_01234.5").lrg_id(), None);
+        assert!(Accession::new("LRG_1").is_lrg());
+        assert_eq!(Accession::new("LRG_1").lrg_id(), Some(1));
+        assert_eq!(Accession::new("LRG_199t1").lrg_id(), Some(199));
+        assert_eq!(Accession::new("LRG_199p1").lrg_id(), Some(199));
+        assert_eq!(Accession::new("LRG_").lrg_id(), None);
+    }
+
+    #[test]
+    fn cds_interval_codon_position() -> Result<(), anyhow::Error> {
+        for (interval, position) in [
+            ("1", Some(0)),
+            ("2", Some(1)),
+            ("3", Some(2)),
+            ("4", Some(0)),
+            ("5_7", Some(1)),
+            ("4+1", None),
+            ("-1", None),
+            ("*1", None),
+        ] {
+            assert_eq!(
+                CdsInterval::from_str(interval)?.codon_position(),
+                position,
+                "{interval}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn cds_interval_is_inframe() -> Result<(), anyhow::Error> {
+        for (interval, inframe) in [
+            ("1_3", true),
+            ("4_9", true),
+            ("1_4", false),
+            ("5", false),
+            ("-3_3", true),
+            ("*1_*3", true),
+            ("4+1_6", false),
+            ("10_*2", false),
+        ] {
+            assert_eq!(
+                CdsInterval::from_str(interval)?.is_inframe(),
+                inframe,
+                "{interval}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn interval_length_overlaps_contains() -> Result<(), anyhow::Error> {
+        for (interval, length) in [
+            ("1_3", Some(3)),
+            ("5", Some(1)),
+            ("-3_3", Some(6)),
+            ("-3_-1", Some(3)),
+            ("*1_*3", Some(3)),
+            ("4+1_6", None),
+            ("10_*2", None),
+        ] {
+            assert_eq!(
+                CdsInterval::from_str(interval)?.length(),
+                length,
+                "{interval}"
+            );
+        }
+        for (interval, length) in [("1_3", Some(3)), ("-2_2", Some(4)), ("4-1_6", None)] {
+            assert_eq!(
+                TxInterval::from_str(interval)?.length(),
+                length,
+                "{interval}"
+            );
+        }
+        for (interval, length) in [("1_3", Some(3)), ("5", Some(1)), ("?_5", None)] {
+            assert_eq!(
+                GenomeInterval::from_str(interval)?.length(),
+                length,
+                "{interval}"
+            );
+        }
+
+        let cds = CdsInterval::from_str("4_6")?;
+        for (other, overlaps) in [("1_3", false), ("1_4", true), ("6_9", true), ("7_9", false)] {
+            assert_eq!(
+                cds.overlaps(&CdsInterval::from_str(other)?),
+                overlaps,
+                "{other}"
+            );
+        }
+        for (pos, contains) in [("3", false), ("4", true), ("6", true), ("6+1", false)] {
+            assert_eq!(
+                cds.contains(&CdsInterval::from_str(pos)?.start),
+                contains,
+                "{pos}"
+            );
+        }
+
+        let tx = TxInterval::from_str("4_6")?;
+        assert!(tx.overlaps(&TxInterval::from_str("6_9")?));
+        assert!(!tx.overlaps(&TxInterval::from_str("7_9")?));
+        assert!(tx.contains(&TxInterval::from_str("4")?.start));
+        assert!(!tx.contains(&TxInterval::from_str("4-1")?.start));
+
+        let g = GenomeInterval::from_str("4_6")?;
+        assert_eq!(g.overlaps(&GenomeInterval::from_str("6_9")?), Some(true));
+        assert_eq!(g.overlaps(&GenomeInterval::from_str("7_9")?), Some(false));
+        assert_eq!(g.overlaps(&GenomeInterval::from_str("?_9")?), None);
+        assert_eq!(g.contains(4), Some(true));
+        assert_eq!(g.contains(7), Some(false));
+        assert_eq!(GenomeInterval::from_str("?_6")?.contains(4), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn genome_interval_union_intersection() -> Result<(), anyhow::Error> {
+        let g = |s: &str| GenomeInterval::from_str(s);
+
+        // Adjacent intervals share an endpoint.
+        assert_eq!(g("4_6")?.union(&g("6_9")?), g("4_9")?);
+        assert_eq!(g("4_6")?.intersection(&g("6_9")?), Some(g("6")?));
+        // Disjoint intervals.
+        assert_eq!(g("4_6")?.union(&g("8_9")?), g("4_9")?);
+        assert_eq!(g("4_6")?.intersection(&g("7_9")?), None);
+        assert!(!g("4_6")?.overlaps_unbounded(&g("7_9")?));
+        // Subset.
+        assert_eq!(g("1_10")?.union(&g("4_6")?), g("1_10")?);
+        assert_eq!(g("1_10")?.intersection(&g("4_6")?), Some(g("4_6")?));
+        // Unknown bounds are unbounded.
+        assert_eq!(g("?_6")?.union(&g("4_9")?), g("?_9")?);
+        assert_eq!(g("?_6")?.intersection(&g("4_9")?), Some(g("4_6")?));
+        assert_eq!(g("?_6")?.intersection(&g("2_?")?), Some(g("2_6")?));
+        assert_eq!(g("?_6")?.intersection(&g("7_?")?), None);
+        assert!(g("?_6")?.overlaps_unbounded(&g("4_9")?));
+        assert!(!g("?_6")?.overlaps_unbounded(&g("7_?")?));
+        assert!(g("4_?")?.contains_unbounded(1_000_000));
+        assert!(!g("4_?")?.contains_unbounded(3));
+        assert!(g("?_6")?.contains_unbounded(-100));
+
+        Ok(())
+    }
+
+    #[test]
+    fn cds_tx_interval_union_intersection() -> Result<(), anyhow::Error> {
+        let c = |s: &str| CdsInterval::from_str(s);
+
+        assert_eq!(c("4_6")?.union(&c("6_9")?), c("4_9")?);
+        assert_eq!(c("4_6")?.intersection(&c("6_9")?), Some(c("6_6")?));
+        assert_eq!(c("4_6")?.intersection(&c("7_9")?), None);
+        assert_eq!(c("-5_10")?.intersection(&c("4_6")?), Some(c("4_6")?));
+        assert_eq!(c("-5_10")?.union(&c("4_*6")?), c("-5_*6")?);
+        // Intronic positions are ordered by their offsets.
+        assert_eq!(
+            c("6+1_7-1")?.intersection(&c("6_6+5")?),
+            Some(c("6+1_6+5")?)
+        );
+        assert_eq!(c("6+10_7-1")?.intersection(&c("6_6+5")?), None);
+        assert!(c("6_7")?.contains(&c("6+3")?.start));
+
+        let t = |s: &str| TxInterval::from_str(s);
+        assert_eq!(t("4_6")?.union(&t("6_9")?), t("4_9")?);
+        assert_eq!(t("4_6")?.intersection(&t("6_9")?), Some(t("6_6")?));
+        assert_eq!(t("4_6")?.intersection(&t("7_9")?), None);
+        assert_eq!(t("1_10")?.intersection(&t("4-2_6")?), Some(t("4-2_6")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn prot_interval_and_pos() {
+        let pos = |number| ProtPos {
+            aa: "Leu".to_string(),
+            number,
+        };
+        let interval = |start, end| ProtInterval {
+            start: pos(start),
+            end: pos(end),
+        };
+
+        assert_eq!(pos(10).distance(&pos(15)), 5);
+        assert_eq!(pos(15).distance(&pos(10)), -5);
+        assert_eq!(pos(10).distance(&pos(10)), 0);
+
+        assert_eq!(interval(10, 10).length(), 1);
+        assert_eq!(interval(10, 15).length(), 6);
+
+        assert!(interval(10, 15).overlaps(&interval(15, 20)));
+        assert!(interval(10, 15).overlaps(&interval(5, 10)));
+        assert!(!interval(10, 15).overlaps(&interval(16, 20)));
+        assert!(!interval(10, 15).overlaps(&interval(5, 9)));
+
+        assert!(interval(10, 15).contains(&pos(10)));
+        assert!(interval(10, 15).contains(&pos(15)));
+        assert!(!interval(10, 15).contains(&pos(9)));
+        assert!(!interval(10, 15).contains(&pos(16)));
+    }
+
+    #[test]
+    fn variant_is_splicing() -> Result<(), anyhow::Error> {
+        for (variant, window, splicing) in [
+            ("NM_01234.5:c.100+1G>A", SPLICE_SITE_WINDOW, true),
+            ("NM_01234.5:c.100+2T>A", SPLICE_SITE_WINDOW, true),
+            ("NM_01234.5:c.101-1G>A", SPLICE_SITE_WINDOW, true),
+            ("NM_01234.5:c.101-2A>G", SPLICE_SITE_WINDOW, true),
+            ("NM_01234.5:c.100+3A>G", SPLICE_SITE_WINDOW, false),
+            ("NM_01234.5:c.100+10G>A", SPLICE_SITE_WINDOW, false),
+            ("NM_01234.5:c.100+8G>A", 8, true),
+            ("NM_01234.5:c.101-10A>G", 10, true),
+            ("NM_01234.5:c.100G>A", SPLICE_SITE_WINDOW, false),
+            ("NM_01234.5:c.100_100+5del", SPLICE_SITE_WINDOW, true),
+            ("NM_01234.5:c.101-5_101del", SPLICE_SITE_WINDOW, true),
+            ("NM_01234.5:c.100+2_100+3insA", SPLICE_SITE_WINDOW, true),
+            ("NM_01234.5:c.100+5_100+10del", SPLICE_SITE_WINDOW, false),
+            ("NM_01234.5:c.101-10_101-5del", SPLICE_SITE_WINDOW, false),
+            ("NM_01234.5:c.100+5_101-5del", SPLICE_SITE_WINDOW, false),
+            ("NM_01234.5:c.100+5_200-5del", SPLICE_SITE_WINDOW, true),
+            ("NM_01234.5:c.101-10_150+10del", SPLICE_SITE_WINDOW, true),
+            ("NR_01234.5:n.100+1G>A", SPLICE_SITE_WINDOW, true),
+            ("NC_000001.10:g.100G>A", SPLICE_SITE_WINDOW, false),
+        ] {
+            assert_eq!(
+                HgvsVariant::from_str(variant)?.is_splicing(window),
+                splicing,
+                "{variant}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn variant_affects_start_stop_codon() -> Result<(), anyhow::Error> {
+        for (variant, start, stop) in [
+            ("NM_999999.1:c.1A>G", true, false),
+            ("NM_999999.1:c.3G>A", true, false),
+            ("NM_999999.1:c.4A>G", false, false),
+            ("NM_999999.1:c.-1A>G", false, false),
+            ("NM_999999.1:c.-2_1del", true, false),
+            ("NM_999999.1:c.3+1G>A", false, false),
+            ("NM_999999.1:c.27A>G", false, false),
+            ("NM_999999.1:c.28T>A", false, true),
+            ("NM_999999.1:c.30G>A", false, true),
+            ("NM_999999.1:c.25_29del", false, true),
+            ("NM_999999.1:c.*1A>G", false, false),
+            ("NM_999999.1:n.1A>G", false, false),
+        ] {
+            let variant = HgvsVariant::from_str(variant)?;
+            assert_eq!(variant.affects_start_codon(), start, "{variant}");
+            assert_eq!(variant.affects_stop_codon(30), stop, "{variant}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn na_edit_length_change() -> Result<(), anyhow::Error> {
+        for (loc_edit, change) in [
+            ("10A>G", Some(0)),
+            ("10=", Some(0)),
+            ("10_11delinsGT", None),
+            ("10_11delACinsG", Some(-1)),
+            ("10_11delAC", Some(-2)),
+            ("10_11del", None),
+            ("10_11insGGG", Some(3)),
+            ("10_11dupAC", Some(2)),
+            ("10_11dup", None),
+            ("10_12invACG", Some(0)),
+            ("10_12del3", None),
+            ("10_12inv3", None),
+            ("10_12CAG[12]", None),
+        ] {
+            let variant = HgvsVariant::from_str(&format!("NM_01234.5:c.{loc_edit}"))?;
+            let edit = variant.na_edit().expect("is a nucleic acid variant");
+            assert_eq!(edit.length_change(), change, "{loc_edit}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn na_edit_classification() -> Result<(), anyhow::Error> {
+        // (edit, SNV, MNV, indel, affected bases, large deletion for threshold 2)
+        for (loc_edit, snv, mnv, indel, affected, large_del) in [
+            ("10A>G", true, false, false, Some(1), false),
+            ("10A=", false, false, false, Some(1), false),
+            ("10=", false, false, false, None, false),
+            ("10_11delACinsGT", false, true, false, Some(2), false),
+            ("10_11delACinsAC", false, false, false, Some(2), false),
+            ("10_11delinsGT", false, false, false, None, false),
+            ("10_11delACinsG", false, false, true, Some(2), false),
+            ("10_12delACGinsG", false, false, true, Some(3), true),
+            ("10_11delACinsGGG", false, false, true, Some(2), false),
+            ("10_11del2insG", false, false, true, None, false),
+            ("10_11del2insGT", false, false, false, None, false),
+            ("10delA", false, false, true, Some(1), false),
+            ("10_11delAC", false, false, true, Some(2), true),
+            ("10_11del", false, false, true, None, false),
+            ("10del1", false, false, true, None, false),
+            ("10_11del2", false, false, true, None, true),
+            ("10_11insG", false, false, true, Some(0), false),
+            ("10_11insGGG", false, false, true, Some(0), false),
+            ("10dupA", false, false, true, Some(1), false),
+            ("10_11dupAC", false, false, true, Some(2), false),
+            ("10_11dup", false, false, true, None, false),
+            ("10_12invACG", false, false, false, Some(3), false),
+            ("10_12inv", false, false, false, None, false),
+            ("10_12inv3", false, false, false, None, false),
+            ("10_12CAG[12]", false, false, false, None, false),
+        ] {
+            let variant = HgvsVariant::from_str(&format!("NM_01234.5:c.{loc_edit}"))?;
+            let edit = variant.na_edit().expect("is a nucleic acid variant");
+            assert_eq!(edit.is_snv(), snv, "{loc_edit}");
+            assert_eq!(edit.is_mnv(), mnv, "{loc_edit}");
+            assert_eq!(edit.is_indel(), indel, "{loc_edit}");
+            assert_eq!(edit.affected_base_count(), affected, "{loc_edit}");
+            assert_eq!(edit.is_large_deletion(2), large_del, "{loc_edit}");
+            if let Some(change) = edit.length_change() {
+                assert_eq!(edit.is_indel(), change != 0, "{loc_edit}");
+            }
+        }
+
+        let edit = NaEdit::DelRef {
+            reference: String::from("ACG"),
+        };
+        assert!(edit.is_large_deletion(3));
+        assert!(!edit.is_large_deletion(4));
+        assert!(NaEdit::DelNum { count: 1 }.is_large_deletion(0));
+        assert!(!NaEdit::DelNum { count: 0 }.is_large_deletion(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn protein_edit_predicates() -> Result<(), anyhow::Error> {
+        // (variant, frameshift, nonsense, missense, synonymous, inframe indel, extension)
+        for (variant, fs, nonsense, missense, synonymous, indel, ext) in [
+            ("p.Lys2GlufsTer5", true, false, false, false, false, false),
+            ("p.Lys2fs", true, false, false, false, false, false),
+            ("p.Lys2Ter", false, true, false, false, false, false),
+            ("p.Lys2*", false, true, false, false, false, false),
+            ("p.K2X", false, true, false, false, false, false),
+            ("p.Lys2Glu", false, false, true, false, false, false),
+            ("p.K2E", false, false, true, false, false, false),
+            ("p.Lys2=", false, false, false, true, false, false),
+            ("p.Lys2Lys", false, false, false, true, false, false),
+            ("p.K2K", false, false, false, true, false, false),
+            ("p.Lys2del", false, false, false, false, true, false),
+            ("p.Lys2_Leu3insGlu", false, false, false, false, true, false),
+            ("p.Lys2dup", false, false, false, false, true, false),
+            (
+                "p.Lys2delinsGluVal",
+                false,
+                false,
+                false,
+                false,
+                true,
+                false,
+            ),
+            (
+                "p.Ter110GlnextTer17",
+                false,
+                false,
+                false,
+                false,
+                false,
+                true,
+            ),
+            ("p.Met1ext-5", false, false, false, false, false, true),
+        ] {
+            let var_p = HgvsVariant::from_str(&format!("NP_01234.5:{variant}"))?;
+            let (loc_edit, edit) = match &var_p {
+                HgvsVariant::ProtVariant { loc_edit, .. } => {
+                    (loc_edit, var_p.prot_edit().expect("has protein edit"))
+                }
+                _ => panic!("not a protein variant: {variant}"),
+            };
+            assert_eq!(edit.is_frameshift(), fs, "{variant}");
+            assert_eq!(edit.is_nonsense(), nonsense, "{variant}");
+            assert_eq!(loc_edit.is_missense(), missense, "{variant}");
+            assert_eq!(loc_edit.is_synonymous(), synonymous, "{variant}");
+            assert_eq!(edit.is_inframe_indel(), indel, "{variant}");
+            assert_eq!(edit.is_extension(), ext, "{variant}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn prot_loc_edit_is_conservative() -> Result<(), anyhow::Error> {
+        for (variant, conservative) in [
+            ("p.Val600Ile", true),
+            ("p.V600I", true),
+            ("p.Asp10Glu", true),
+            ("p.Lys10Asp", false),
+            ("p.Gly10Ala", false),
+            ("p.Val600Val", false),
+            ("p.Trp10Ter", false),
+            ("p.Val600_Leu601delinsIleIle", false),
+            ("p.=", false),
+        ] {
+            match HgvsVariant::from_str(&format!("NP_01234.5:{variant}"))? {
+                HgvsVariant::ProtVariant { loc_edit, .. } => {
+                    assert_eq!(loc_edit.is_conservative(), conservative, "{variant}")
+                }
+                _ => panic!("not a protein variant: {variant}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn cds_pos_region() -> Result<(), anyhow::Error> {
+        for (variant, utr_5, cds, utr_3) in [
+            ("NM_01234.5:c.-3A>T", true, false, false),
+            ("NM_01234.5:c.-3+1A>T", true, false, false),
+            ("NM_01234.5:c.-3-1A>T", true, false, false),
+            ("NM_01234.5:c.3A>T", false, true, false),
+            ("NM_01234.5:c.3+1A>T", false, true, false),
+            ("NM_01234.5:c.3-1A>T", false, true, false),
+            ("NM_01234.5:c.*3A>T", false, false, true),
+            ("NM_01234.5:c.*3+1A>T", false, false, true),
+            ("NM_01234.5:c.*3-1A>T", false, false, true),
+        ] {
+            match HgvsVariant::from_str(variant)? {
+                HgvsVariant::CdsVariant { loc_edit, .. } => {
+                    let pos = &loc_edit.loc.inner().start;
+                    assert_eq!(pos.in_utr_5(), utr_5, "{variant}");
+                    assert_eq!(pos.in_cds(), cds, "{variant}");
+                    assert_eq!(pos.in_utr_3(), utr_3, "{variant}");
+                }
+                _ => panic!("not a coding variant: {variant}"),
+            }
+        }
+
+        for (interval, utr_5, cds, utr_3) in [
+            ("-5_-3", true, false, false),
+            ("-3_3", false, false, false),
+            ("3_10", false, true, false),
+            ("10_*3", false, false, false),
+            ("*3_*5", false, false, true),
+        ] {
+            let interval = CdsInterval::from_str(interval)?;
+            assert_eq!(interval.is_entirely_utr_5(), utr_5, "{interval:?}");
+            assert_eq!(interval.is_entirely_cds(), cds, "{interval:?}");
+            assert_eq!(interval.is_entirely_utr_3(), utr_3, "{interval:?}");
+            assert_eq!(
+                interval.spans_utr_cds_boundary(),
+                !(utr_5 || cds || utr_3),
+                "{interval:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn tx_pos_exon_intron() {
+        for (pos, exon) in [
+            (
+                TxPos {
+                    base: 10,
+                    offset: None,
+                },
+                true,
+            ),
+            (
+                TxPos {
+                    base: 10,
+                    offset: Some(0),
+                },
+                true,
+            ),
+            (
+                TxPos {
+                    base: 10,
+                    offset: Some(1),
+                },
+                false,
+            ),
+            (
+                TxPos {
+                    base: -10,
+                    offset: Some(-3),
+                },
+                false,
+            ),
+        ] {
+            assert_eq!(pos.in_exon(), exon, "{pos:?}");
+            assert_eq!(pos.in_intron(), !exon, "{pos:?}");
+        }
+    }
+
+    #[test]
+    fn protein_consequence() -> Result<(), anyhow::Error> {
+        for (variant, consequence) in [
+            ("NP_01234.5:p.=", ProteinConsequence::Synonymous),
+            ("NP_01234.5:p.Lys2=", ProteinConsequence::Synonymous),
+            ("NP_01234.5:p.Lys2Lys", ProteinConsequence::Synonymous),
+            ("NP_01234.5:p.Lys2Asn", ProteinConsequence::Missense),
+            ("NP_01234.5:p.Ser3Ter", ProteinConsequence::Nonsense),
+            ("NP_01234.5:p.Ser3*", ProteinConsequence::Nonsense),
+            ("NP_01234.5:p.Ala3ArgfsTer6", ProteinConsequence::Frameshift),
+            (
+                "NP_01234.5:p.Lys2_Ala3insGly",
+                ProteinConsequence::InframeInsertion,
+            ),
+            (
+                "NP_01234.5:p.Phe6_Arg7dup",
+                ProteinConsequence::InframeInsertion,
+            ),
+            (
+                "NP_01234.5:p.Lys2_Ala5del",
+                ProteinConsequence::InframeDeletion,
+            ),
+            (
+                "NP_01234.5:p.Lys4delinsIlePro",
+                ProteinConsequence::InframeIndel,
+            ),
+            ("NP_01234.5:p.Met1?", ProteinConsequence::StartLost),
+            ("NP_01234.5:p.Met1Val", ProteinConsequence::StartLost),
+            ("NP_01234.5:p.Ter10TyrextTer3", ProteinConsequence::StopLost),
+            (
+                "NP_01234.5:p.Lys9_Ter10delinsGly",
+                ProteinConsequence::StopLost,
+            ),
+            (
+                "NP_01234.5:p.Lys4_Ala5insTer",
+                ProteinConsequence::StopGained,
+            ),
+            (
+                "NP_01234.5:p.Lys4delinsIleTer",
+                ProteinConsequence::StopGained,
+            ),
+            ("NP_01234.5:p.Met1ext-5", ProteinConsequence::Extension),
+            ("NP_01234.5:p.?", ProteinConsequence::Uncertain),
+            ("NP_01234.5:p.0?", ProteinConsequence::Uncertain),
+            ("NM_01234.5:c.6A>T", ProteinConsequence::Uncertain),
+        ] {
+            assert_eq!(
+                HgvsVariant::from_str(variant)?.protein_consequence(),
+                consequence,
+                "{variant}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn variant_loc_range_i64() -> Result<(), anyhow::Error> {
+        let var = HgvsVariant::from_str("NC_000001.11:g.2200000_2200010del")?;
+        assert_eq!(var.loc_range_i64(), Some(2_199_999..2_200_010));
+
+        // The largest positions that can be represented.
+        let var = HgvsVariant::from_str("NC_000001.11:g.2147483640_2147483647del")?;
+        assert_eq!(
+            var.loc_range_i64(),
+            Some(2_147_483_639..i64::from(i32::MAX))
+        );
+        assert_eq!(var.length_change(), Some(-8));
+
+        // Larger positions are rejected by the parser rather than wrapped around.
+        assert!(HgvsVariant::from_str("NC_000001.11:g.2200000000A>T").is_err());
+        assert_eq!(
+            HgvsVariant::from_str("NP_000079.2:p.?")?.loc_range_i64(),
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn variant_length_change() -> Result<(), anyhow::Error> {
+        for (variant, change, inframe) in [
+            ("NM_01234.5:c.10_12del", Some(-3), true),
+            ("NM_01234.5:c.10_11delinsGGGGG", Some(3), true),
+            ("NM_01234.5:c.10del", Some(-1), false),
+            ("NM_01234.5:c.10_11dup", Some(2), false),
+            ("NM_01234.5:c.10_11inv", Some(0), true),
+            ("NM_01234.5:c.10_15CAG[4]", Some(6), true),
+            ("NM_01234.5:c.10+1_10+3del", None, false),
+            ("NC_000001.10:g.100_101insA", Some(1), false),
+            ("NP_01234.5:p.Lys2_Leu3del", Some(-2), false),
+            ("NP_01234.5:p.Lys2delinsGluVal", Some(1), false),
+            ("NP_01234.5:p.Lys2_Leu3insGlu", Some(1), false),
+            ("NP_01234.5:p.Lys2Glu", Some(0), false),
+        ] {
+            let variant = HgvsVariant::from_str(variant)?;
+            assert_eq!(variant.length_change(), change, "{variant}");
+            assert_eq!(variant.is_inframe(), inframe, "{variant}");
+        }
+
+        Ok(())
+    }
 
     #[test]
     fn mu_construct() {
@@ -1017,6 +3506,111 @@ mod test {
         );
     }
 
+    #[test]
+    fn cds_pos_ord() {
+        let pos = |base, offset, cds_from| CdsPos {
+            base,
+            offset,
+            cds_from,
+        };
+        let mut values = vec![
+            pos(1, None, CdsFrom::End),
+            pos(2, Some(1), CdsFrom::Start),
+            pos(2, None, CdsFrom::Start),
+            pos(2, Some(-1), CdsFrom::Start),
+            pos(-5, None, CdsFrom::Start),
+            pos(2, Some(0), CdsFrom::Start),
+        ];
+        values.sort();
+
+        assert_eq!(
+            values,
+            vec![
+                pos(-5, None, CdsFrom::Start),
+                pos(2, Some(-1), CdsFrom::Start),
+                pos(2, None, CdsFrom::Start),
+                pos(2, Some(0), CdsFrom::Start),
+                pos(2, Some(1), CdsFrom::Start),
+                pos(1, None, CdsFrom::End),
+            ]
+        );
+    }
+
+    #[test]
+    fn tx_rna_pos_ord() {
+        let tx = |base, offset| TxPos { base, offset };
+        assert!(tx(1, None) < tx(2, None));
+        assert!(tx(2, Some(-3)) < tx(2, None));
+        assert!(tx(2, None) < tx(2, Some(1)));
+        assert!(tx(2, Some(10)) < tx(3, Some(-10)));
+
+        let rna = |base, offset| RnaPos { base, offset };
+        assert!(rna(1, None) < rna(2, None));
+        assert!(rna(2, Some(-3)) < rna(2, None));
+        assert!(rna(2, Some(10)) < rna(3, Some(-10)));
+    }
+
+    #[test]
+    fn genome_interval_ord() {
+        let itv = |start, end| GenomeInterval { start, end };
+        let mut values = vec![
+            itv(None, None),
+            itv(Some(10), None),
+            itv(None, Some(5)),
+            itv(Some(10), Some(20)),
+            itv(Some(5), Some(5)),
+        ];
+        values.sort();
+
+        assert_eq!(
+            values,
+            vec![
+                itv(Some(5), Some(5)),
+                itv(Some(10), Some(20)),
+                itv(Some(10), None),
+                itv(None, Some(5)),
+                itv(None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn sort_cds_variants() -> Result<(), anyhow::Error> {
+        let intervals = |values: &[&str]| -> Result<Vec<CdsInterval>, anyhow::Error> {
+            values
+                .iter()
+                .map(|s| match HgvsVariant::from_str(s)? {
+                    HgvsVariant::CdsVariant { loc_edit, .. } => Ok(loc_edit.loc.inner().clone()),
+                    _ => anyhow::bail!("not a c. variant: {}", s),
+                })
+                .collect()
+        };
+
+        let mut values = intervals(&[
+            "NM_000001.1:c.*1A>G",
+            "NM_000001.1:c.2A>G",
+            "NM_000001.1:c.2_3del",
+            "NM_000001.1:c.-10A>G",
+            "NM_000001.1:c.2+1G>A",
+            "NM_000001.1:c.2-1G>A",
+        ])?;
+        values.sort();
+
+        assert_eq!(
+            values,
+            intervals(&[
+                "NM_000001.1:c.-10A>G",
+                "NM_000001.1:c.2-1G>A",
+                "NM_000001.1:c.2A>G",
+                "NM_000001.1:c.2_3del",
+                "NM_000001.1:c.2+1G>A",
+                "NM_000001.1:c.*1A>G",
+            ])?
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn mu_from_tx_interval() {
         let test_itv = TxInterval {
@@ -1044,6 +3638,334 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn interval_0based_half_open() -> Result<(), anyhow::Error> {
+        for (start, end) in [(0, 1), (99, 102), (1000, 1000)] {
+            let g_interval = GenomeInterval::from_0based_half_open(start, end);
+            assert_eq!(g_interval.start, Some(start + 1));
+            assert_eq!(g_interval.end, Some(end));
+            assert_eq!(g_interval.to_0based_half_open(), Some((start, end)));
+
+            let m_interval = MtInterval::from_0based_half_open(start, end);
+            assert_eq!(m_interval.to_0based_half_open(), Some((start, end)));
+        }
+        let g_interval = GenomeInterval {
+            start: None,
+            end: Some(10),
+        };
+        assert_eq!(g_interval.to_0based_half_open(), None);
+
+        // Agreement with `loc_range()`.
+        for s in [
+            "NC_000001.11:g.100A>T",
+            "NC_000001.11:g.100_102del",
+            "NC_000001.11:g.100_101insT",
+            "NC_012920.1:m.100_102del",
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            let range = var.loc_range().map(|range| (range.start, range.end));
+            let half_open = match &var {
+                HgvsVariant::GenomeVariant { loc_edit, .. } => {
+                    loc_edit.loc.inner().to_0based_half_open()
+                }
+                HgvsVariant::MtVariant { loc_edit, .. } => {
+                    loc_edit.loc.inner().to_0based_half_open()
+                }
+                _ => unreachable!(),
+            };
+            assert_eq!(half_open, range, "{s}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn interval_0based_half_open_tx() -> Result<(), anyhow::Error> {
+        for (s, expected) in [
+            ("NM_01234.5:c.1A>T", Some(0..1)),
+            ("NM_01234.5:c.10_12del", Some(9..12)),
+            ("NM_01234.5:c.-1A>T", Some(-1..0)),
+            ("NM_01234.5:c.-5_-3del", Some(-5..-2)),
+            ("NM_01234.5:c.-2_2del", Some(-2..2)),
+            ("NM_01234.5:c.*1_*3del", None),
+            ("NM_01234.5:c.10_*3del", None),
+            ("NM_01234.5:c.10+1_10+2del", None),
+            ("NM_01234.5:c.-1_*1del", None),
+            ("NR_01234.5:n.1A>T", Some(0..1)),
+            ("NR_01234.5:n.10_12del", Some(9..12)),
+            ("NR_01234.5:n.-2_2del", Some(-2..2)),
+            ("NR_01234.5:n.10+1del", None),
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            let range = match &var {
+                HgvsVariant::CdsVariant { loc_edit, .. } => {
+                    loc_edit.loc.inner().to_0based_half_open_tx()
+                }
+                HgvsVariant::TxVariant { loc_edit, .. } => {
+                    loc_edit.loc.inner().to_0based_half_open_tx()
+                }
+                _ => unreachable!(),
+            };
+            assert_eq!(range, expected, "{s}");
+            // Agreement with `loc_range()` for intervals on one side of position `0`.
+            if range
+                .as_ref()
+                .is_some_and(|range| range.start >= 0 || range.end <= 0)
+            {
+                assert_eq!(range, var.loc_range(), "{s}");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn is_trivially_normalized() -> Result<(), anyhow::Error> {
+        for (s, expected) in [
+            ("NC_000001.11:g.100A>T", true),
+            ("NC_000001.11:g.100=", true),
+            ("NC_000001.11:g.100A=", true),
+            ("NM_001166478.1:c.31T>C", true),
+            ("NM_001166478.1:c.(31_35)del", true),
+            ("NM_001166478.1:c.31_35(del)", true),
+            ("NM_001166478.1:c.35_36insT", false),
+            ("NM_001166478.1:c.31del", false),
+            ("NM_001166478.1:c.31_32delinsGT", false),
+            ("NP_001159950.1:p.Leu10Arg", false),
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            assert_eq!(var.is_trivially_normalized(), expected, "{s}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn strip_and_add_gene_symbol() -> Result<(), anyhow::Error> {
+        use std::io::BufRead;
+
+        use super::GeneSymbol;
+
+        let reader = std::io::BufReader::new(std::fs::File::open("tests/data/parser/gauntlet")?);
+        let mut variants = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if !line.starts_with('#') && !line.is_empty() {
+                variants.push(HgvsVariant::from_str(line)?);
+            }
+        }
+        variants.push(HgvsVariant::from_str(
+            "NM_000088.3(COL1A1):c.[1A>T;10_12del]",
+        )?);
+
+        for var in variants {
+            let stripped = var.clone().strip_gene_symbol();
+            assert_eq!(stripped.gene_symbol(), &None, "{var}");
+
+            let added = stripped.with_gene_symbol("GENE");
+            assert_eq!(added.gene_symbol(), &Some(GeneSymbol::new("GENE")), "{var}");
+            if let Some(gene_symbol) = var.gene_symbol() {
+                assert_eq!(added.with_gene_symbol(gene_symbol.value.clone()), var);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn na_edit_with_reference() -> Result<(), anyhow::Error> {
+        fn reference(edit: &NaEdit) -> &str {
+            match edit {
+                NaEdit::RefAlt { reference, .. }
+                | NaEdit::DelRef { reference }
+                | NaEdit::Dup { reference }
+                | NaEdit::InvRef { reference } => reference,
+                _ => panic!("edit has no reference"),
+            }
+        }
+
+        for (input, expected) in [
+            ("NM_01234.5:c.22C>T", "NM_01234.5:c.22A>T"),
+            ("NM_01234.5:c.22_23delCT", "NM_01234.5:c.22_23delA"),
+            ("NM_01234.5:c.22_23dupCT", "NM_01234.5:c.22_23dupA"),
+            ("NM_01234.5:c.22_23invCT", "NM_01234.5:c.22_23invA"),
+            ("NM_01234.5:c.22_23(delCT)", "NM_01234.5:c.22_23(delA)"),
+        ] {
+            let variant = HgvsVariant::from_str(input)?;
+
+            let edit = variant.na_edit().unwrap().clone().without_reference();
+            assert_eq!(reference(&edit), "");
+            let edit = edit.with_reference("A".to_string());
+            assert_eq!(reference(&edit), "A");
+
+            let variant = variant.with_na_edit(edit);
+            assert_eq!(format!("{variant}"), expected);
+        }
+
+        let ins = NaEdit::Ins {
+            alternative: "T".to_string(),
+        };
+        assert_eq!(ins.clone().without_reference(), ins);
+
+        let variant = HgvsVariant::from_str("NP_01234.5:p.Leu2Ter")?;
+        assert_eq!(variant.clone().with_na_edit(ins), variant);
+
+        Ok(())
+    }
+
+    #[test]
+    fn hgvs_variant_kind() -> Result<(), anyhow::Error> {
+        for (hgvs, kind) in [
+            ("NM_01234.5:c.22+1A>T", HgvsVariantKind::Cds),
+            ("NC_000001.11:g.123A>T", HgvsVariantKind::Genome),
+            ("NC_012920.1:m.123A>T", HgvsVariantKind::Mt),
+            ("NC_001416.1:o.123A>T", HgvsVariantKind::Circular),
+            ("NR_01234.5:n.22A>T", HgvsVariantKind::Tx),
+            ("NM_01234.5:r.22a>u", HgvsVariantKind::Rna),
+            ("NP_01234.5:p.Leu3Pro", HgvsVariantKind::Prot),
+            ("NM_01234.5:c.[1A>T;4del]", HgvsVariantKind::Allele),
+        ] {
+            let var = HgvsVariant::from_str(hgvs)?;
+            assert_eq!(var.kind(), kind, "{hgvs}");
+            assert_eq!(
+                (
+                    var.is_cds(),
+                    var.is_genome(),
+                    var.is_mt(),
+                    var.is_tx(),
+                    var.is_rna(),
+                    var.is_prot()
+                ),
+                (
+                    matches!(var, HgvsVariant::CdsVariant { .. }),
+                    matches!(var, HgvsVariant::GenomeVariant { .. }),
+                    matches!(var, HgvsVariant::MtVariant { .. }),
+                    matches!(var, HgvsVariant::TxVariant { .. }),
+                    matches!(var, HgvsVariant::RnaVariant { .. }),
+                    matches!(var, HgvsVariant::ProtVariant { .. }),
+                ),
+                "{hgvs}"
+            );
+
+            // The predicates replace matches with catch-all arms such as these.
+            let is_nucleic_acid = !matches!(var, HgvsVariant::ProtVariant { .. });
+            let is_transcript = matches!(
+                var,
+                HgvsVariant::CdsVariant { .. }
+                    | HgvsVariant::TxVariant { .. }
+                    | HgvsVariant::RnaVariant { .. }
+                    | HgvsVariant::Allele { .. }
+            );
+            assert_eq!(var.is_nucleic_acid(), is_nucleic_acid, "{hgvs}");
+            assert_eq!(var.is_transcript(), is_transcript, "{hgvs}");
+        }
+
+        let allele = HgvsVariant::from_str("NP_01234.5:p.[Leu3Pro;Leu5Pro]")?;
+        assert!(!allele.is_nucleic_acid());
+        assert!(!allele.is_transcript());
+
+        Ok(())
+    }
+
+    #[test]
+    fn same_variant_position_edit() -> Result<(), anyhow::Error> {
+        let var = |s: &str| HgvsVariant::from_str(s).unwrap();
+
+        // Differ in gene symbol only.
+        let a = var("NM_000088.3(COL1A1):c.1A>T");
+        let b = var("NM_000088.3:c.1A>T");
+        assert_ne!(a, b);
+        assert!(a.same_variant(&b));
+        assert!(a.same_position(&b));
+        assert!(a.same_edit(&b));
+
+        // Differ in edit only.
+        let b = var("NM_000088.3(COL1A1):c.1A>G");
+        assert!(!a.same_variant(&b));
+        assert!(a.same_position(&b));
+        assert!(!a.same_edit(&b));
+
+        // Differ in position only.
+        let b = var("NM_000088.3(COL1A1):c.2A>T");
+        assert!(!a.same_variant(&b));
+        assert!(!a.same_position(&b));
+        assert!(a.same_edit(&b));
+
+        // Differ in accession only.
+        let b = var("NM_000088.4(COL1A1):c.1A>T");
+        assert!(!a.same_variant(&b));
+        assert!(!a.same_position(&b));
+        assert!(a.same_edit(&b));
+
+        // Differ in variant type only.
+        let b = var("NM_000088.3(COL1A1):n.1A>T");
+        assert!(!a.same_variant(&b));
+        assert!(!a.same_position(&b));
+        assert!(a.same_edit(&b));
+
+        // Protein variants.
+        let a = var("NP_000079.2(COL1A1):p.Gly2Arg");
+        assert!(a.same_variant(&var("NP_000079.2:p.Gly2Arg")));
+        assert!(a.same_position(&var("NP_000079.2:p.Gly2Ter")));
+        assert!(!a.same_edit(&var("NP_000079.2:p.Gly2Ter")));
+        assert!(a.same_edit(&var("NP_000079.2:p.Gly3Arg")));
+        assert!(!a.same_position(&var("NP_000079.2:p.Gly3Arg")));
+        assert!(var("NP_000079.2:p.?").same_position(&var("NP_000079.2:p.?")));
+        assert!(!var("NP_000079.2:p.?").same_edit(&var("NP_000079.2:p.0")));
+        assert!(!a.same_edit(&var("NM_000088.3:c.1A>T")));
+
+        // Alleles.
+        let a = var("NM_000088.3(COL1A1):c.[1A>T;10del]");
+        assert!(a.same_variant(&var("NM_000088.3:c.[1A>T;10del]")));
+        assert!(a.same_position(&var("NM_000088.3:c.[1A>G;10dup]")));
+        assert!(a.same_edit(&var("NM_000088.3:c.[2A>T;11del]")));
+        assert!(!a.same_position(&var("NM_000088.3:c.[1A>T]")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn na_edit_mut() -> Result<(), anyhow::Error> {
+        let mut variant = HgvsVariant::from_str("NC_000001.11:g.100C>T")?;
+        if let Some(NaEdit::RefAlt { alternative, .. }) = variant.na_edit_mut() {
+            *alternative = "G".to_string();
+        }
+        assert_eq!(format!("{variant}"), "NC_000001.11:g.100C>G");
+
+        let (start, end) = variant.loc_edit_range_mut().unwrap();
+        *start = 200;
+        *end = 201;
+        variant.set_na_edit(NaEdit::DelRef {
+            reference: "CA".to_string(),
+        })?;
+        assert_eq!(format!("{variant}"), "NC_000001.11:g.200_201delCA");
+
+        let mut variant = HgvsVariant::from_str("NM_01234.5:c.-10+2_*3(del)")?;
+        let (start, end) = variant.loc_edit_range_mut().unwrap();
+        *start -= 1;
+        *end += 1;
+        variant.set_na_edit(NaEdit::Dup {
+            reference: String::new(),
+        })?;
+        assert_eq!(format!("{variant}"), "NM_01234.5:c.-11+2_*4(dup)");
+
+        let mut variant = HgvsVariant::from_str("NC_000001.11:g.?_100del")?;
+        assert!(variant.loc_edit_range_mut().is_none());
+
+        let mut variant = HgvsVariant::from_str("NP_01234.5:p.Leu2Ter")?;
+        assert!(variant.na_edit_mut().is_none());
+        assert!(variant.loc_edit_range_mut().is_none());
+        assert!(matches!(
+            variant.set_na_edit(NaEdit::DelRef {
+                reference: String::new(),
+            }),
+            Err(crate::parser::Error::VariantTypeError(_))
+        ));
+
+        Ok(())
+    }
 }
 
 // <LICENSE>
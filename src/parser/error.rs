@@ -1,5 +1,6 @@
 //! Error type definition.
 
+use nom::error::{VerboseError, VerboseErrorKind};
 use thiserror::Error;
 
 /// Error type for parsing of HGVS expressions.
@@ -17,6 +18,13 @@ pub enum Error {
     /// Invalid HGVS expression.
     #[error("{0} is not a valid HGVS expression interval")]
     InvalidHgvsVariant(String),
+    /// Parsing failed at the given byte offset of the input.
+    #[error("{}", fmt_error_at(.input, *.offset, .expected))]
+    ErrorAt {
+        input: String,
+        offset: usize,
+        expected: Vec<String>,
+    },
 
     /// Ill-defined conversion.
     #[error("conversion of interval with different offsets (CDS start/end) is ill-defined: {0}")]
@@ -25,6 +33,16 @@ pub enum Error {
     #[error("cannot convert interval with None position into range: {0}")]
     CannotNonePositionIntoRange(String),
 
+    /// A required field was not set when building a variant.
+    #[error("cannot build variant, missing {0}")]
+    IncompleteVariant(String),
+    /// The operation is not supported for this kind of variant.
+    #[error("operation not supported for variant: {0}")]
+    VariantTypeError(String),
+    /// Reading or writing a file of HGVS expressions failed.
+    #[error("I/O error: {0}")]
+    Io(String),
+
     #[error("ref or alt must be non-empty in: {0}")]
     RefOrAltMustBeNonEmpty(String),
     #[error("number of deleted bases must be positive in: {0}")]
@@ -34,3 +52,71 @@ pub enum Error {
     #[error("number of inverted bases must be positive in: {0}")]
     NumInvBasesNotPositive(String),
 }
+
+impl Error {
+    /// Construct `Error::ErrorAt` from the nom error obtained when parsing `input`.
+    ///
+    /// The offset is taken from the innermost error and the expected tokens are those of all
+    /// errors reported at that position.
+    pub fn from_nom(input: &str, err: nom::Err<VerboseError<&str>>) -> Self {
+        let errors = match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.errors,
+            nom::Err::Incomplete(_) => Vec::new(),
+        };
+        let rest = errors.first().map(|(rest, _)| *rest).unwrap_or_default();
+        let at_rest = errors
+            .iter()
+            .filter(|(r, _)| r.len() == rest.len())
+            .map(|(_, kind)| kind);
+
+        // Prefer the human-readable contexts over the nom error kinds.
+        let contexts = at_rest
+            .clone()
+            .filter_map(|kind| match kind {
+                VerboseErrorKind::Context(ctx) => Some(ctx.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let tokens = if contexts.is_empty() {
+            at_rest
+                .filter_map(|kind| match kind {
+                    VerboseErrorKind::Char(c) => Some(format!("'{c}'")),
+                    VerboseErrorKind::Nom(nom::error::ErrorKind::Alt) => None,
+                    VerboseErrorKind::Nom(kind) => Some(kind.description().to_string()),
+                    VerboseErrorKind::Context(_) => None,
+                })
+                .collect()
+        } else {
+            contexts
+        };
+        let mut expected = Vec::new();
+        for token in tokens {
+            if !expected.contains(&token) {
+                expected.push(token);
+            }
+        }
+
+        Error::ErrorAt {
+            input: input.to_string(),
+            offset: input.len() - rest.len(),
+            expected,
+        }
+    }
+}
+
+/// Format parse error with a caret pointing to the failure position.
+fn fmt_error_at(input: &str, offset: usize, expected: &[String]) -> String {
+    let expected = if expected.is_empty() {
+        String::from("valid HGVS")
+    } else {
+        expected.join(", ")
+    };
+    let column = input
+        .get(..offset)
+        .map(|prefix| prefix.chars().count())
+        .unwrap_or(offset);
+    format!(
+        "invalid HGVS at offset {offset}, expected {expected}\n  |\n  | {input}\n  | {}^",
+        " ".repeat(column)
+    )
+}
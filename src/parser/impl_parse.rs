@@ -4,22 +4,53 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::char,
-    character::complete::{alphanumeric1, digit1, satisfy},
-    combinator::{all_consuming, map, opt, recognize},
-    sequence::{pair, tuple},
-    AsChar, IResult,
+    character::complete::{alphanumeric1, digit1, one_of, satisfy},
+    combinator::{all_consuming, map, not, opt, recognize},
+    error::{context, ErrorKind, ParseError, VerboseError},
+    multi::separated_list1,
+    sequence::{delimited, pair, terminated, tuple},
+    AsChar,
 };
 
 use crate::parser::ds::*;
 use crate::parser::parse_funcs::*;
 
+/// Parser function as passed to `alt_furthest()`.
+type ParserFn<O> = fn(&str) -> IResult<&str, O>;
+
+/// Try `parsers` in turn like `alt()` but on failure return the error of the parser that
+/// got furthest into `input` rather than the error of the last one.
+///
+/// This makes the position reported in parse errors point to the actual problem.  Errors of
+/// parsers that failed at the same position are merged so all expected tokens are reported.
+fn alt_furthest<'a, O>(input: &'a str, parsers: &[ParserFn<O>]) -> IResult<&'a str, O> {
+    let remaining = |e: &VerboseError<&str>| e.errors.first().map(|(rest, _)| rest.len());
+    let mut furthest: Option<VerboseError<&str>> = None;
+    for parser in parsers {
+        match parser(input) {
+            Err(nom::Err::Error(mut e)) => match &mut furthest {
+                Some(f) if remaining(&e) == remaining(f) => f.errors.append(&mut e.errors),
+                Some(f) if remaining(&e) > remaining(f) => (),
+                _ => furthest = Some(e),
+            },
+            result => return result,
+        }
+    }
+    let err = furthest.unwrap_or_else(|| VerboseError::from_error_kind(input, ErrorKind::Alt));
+    Err(nom::Err::Error(VerboseError::append(
+        input,
+        ErrorKind::Alt,
+        err,
+    )))
+}
+
 impl HgvsVariant {
     fn parse_cds_variant(input: &str) -> IResult<&str, Self> {
         map(
             tuple((
                 Accession::parse,
                 opt(tuple((tag("("), GeneSymbol::parse, tag(")")))),
-                tag(":c."),
+                context(":c.", tag(":c.")),
                 CdsLocEdit::parse,
             )),
             |(accession, opt_gs, _, pos_edit)| HgvsVariant::CdsVariant {
@@ -35,7 +66,7 @@ impl HgvsVariant {
             tuple((
                 Accession::parse,
                 opt(tuple((tag("("), GeneSymbol::parse, tag(")")))),
-                tag(":g."),
+                context(":g.", tag(":g.")),
                 GenomeLocEdit::parse,
             )),
             |(accession, opt_gs, _, pos_edit)| HgvsVariant::GenomeVariant {
@@ -51,7 +82,7 @@ impl HgvsVariant {
             tuple((
                 Accession::parse,
                 opt(tuple((tag("("), GeneSymbol::parse, tag(")")))),
-                tag(":m."),
+                context(":m.", tag(":m.")),
                 MtLocEdit::parse,
             )),
             |(accession, opt_gs, _, pos_edit)| HgvsVariant::MtVariant {
@@ -62,12 +93,28 @@ impl HgvsVariant {
         )(input)
     }
 
+    fn parse_circular_variant(input: &str) -> IResult<&str, Self> {
+        map(
+            tuple((
+                Accession::parse,
+                opt(tuple((tag("("), GeneSymbol::parse, tag(")")))),
+                context(":o.", tag(":o.")),
+                GenomeLocEdit::parse,
+            )),
+            |(accession, opt_gs, _, pos_edit)| HgvsVariant::CircularVariant {
+                accession,
+                gene_symbol: opt_gs.map(|(_, gene_symbol, _)| gene_symbol),
+                loc_edit: pos_edit,
+            },
+        )(input)
+    }
+
     fn parse_tx_variant(input: &str) -> IResult<&str, Self> {
         map(
             tuple((
                 Accession::parse,
                 opt(tuple((tag("("), GeneSymbol::parse, tag(")")))),
-                tag(":n."),
+                context(":n.", tag(":n.")),
                 TxLocEdit::parse,
             )),
             |(accession, opt_gs, _, pos_edit)| HgvsVariant::TxVariant {
@@ -83,7 +130,7 @@ impl HgvsVariant {
             tuple((
                 Accession::parse,
                 opt(tuple((tag("("), GeneSymbol::parse, tag(")")))),
-                tag(":p."),
+                context(":p.", tag(":p.")),
                 ProtLocEdit::parse,
             )),
             |(accession, opt_gs, _, pos_edit)| HgvsVariant::ProtVariant {
@@ -99,7 +146,7 @@ impl HgvsVariant {
             tuple((
                 Accession::parse,
                 opt(tuple((tag("("), GeneSymbol::parse, tag(")")))),
-                tag(":r."),
+                context(":r.", tag(":r.")),
                 RnaLocEdit::parse,
             )),
             |(accession, opt_gs, _, pos_edit)| HgvsVariant::RnaVariant {
@@ -109,19 +156,131 @@ impl HgvsVariant {
             },
         )(input)
     }
+
+    /// Parse an allele `<accession>(<gene>)<prefix>[<loc_edit>;...]` and build the contained
+    /// variants with `make_variant`.
+    fn parse_allele<'a, T: Parseable>(
+        input: &'a str,
+        prefix: &'static str,
+        make_variant: fn(Accession, Option<GeneSymbol>, T) -> HgvsVariant,
+    ) -> IResult<&'a str, Self> {
+        map(
+            tuple((
+                Accession::parse,
+                opt(tuple((tag("("), GeneSymbol::parse, tag(")")))),
+                context(prefix, tag(prefix)),
+                delimited(char('['), separated_list1(char(';'), T::parse), char(']')),
+            )),
+            |(accession, opt_gs, _, loc_edits)| {
+                let gene_symbol = opt_gs.map(|(_, gene_symbol, _)| gene_symbol);
+                HgvsVariant::Allele {
+                    variants: loc_edits
+                        .into_iter()
+                        .map(|loc_edit| {
+                            make_variant(accession.clone(), gene_symbol.clone(), loc_edit)
+                        })
+                        .collect(),
+                    accession,
+                    gene_symbol,
+                }
+            },
+        )(input)
+    }
+
+    fn parse_allele_variant(input: &str) -> IResult<&str, Self> {
+        let parsers: [ParserFn<Self>; 7] = [
+            |input| {
+                Self::parse_allele::<CdsLocEdit>(
+                    input,
+                    ":c.",
+                    |accession, gene_symbol, loc_edit| HgvsVariant::CdsVariant {
+                        accession,
+                        gene_symbol,
+                        loc_edit,
+                    },
+                )
+            },
+            |input| {
+                Self::parse_allele::<GenomeLocEdit>(
+                    input,
+                    ":g.",
+                    |accession, gene_symbol, loc_edit| HgvsVariant::GenomeVariant {
+                        accession,
+                        gene_symbol,
+                        loc_edit,
+                    },
+                )
+            },
+            |input| {
+                Self::parse_allele::<MtLocEdit>(input, ":m.", |accession, gene_symbol, loc_edit| {
+                    HgvsVariant::MtVariant {
+                        accession,
+                        gene_symbol,
+                        loc_edit,
+                    }
+                })
+            },
+            |input| {
+                Self::parse_allele::<GenomeLocEdit>(
+                    input,
+                    ":o.",
+                    |accession, gene_symbol, loc_edit| HgvsVariant::CircularVariant {
+                        accession,
+                        gene_symbol,
+                        loc_edit,
+                    },
+                )
+            },
+            |input| {
+                Self::parse_allele::<TxLocEdit>(input, ":n.", |accession, gene_symbol, loc_edit| {
+                    HgvsVariant::TxVariant {
+                        accession,
+                        gene_symbol,
+                        loc_edit,
+                    }
+                })
+            },
+            |input| {
+                Self::parse_allele::<ProtLocEdit>(
+                    input,
+                    ":p.",
+                    |accession, gene_symbol, loc_edit| HgvsVariant::ProtVariant {
+                        accession,
+                        gene_symbol,
+                        loc_edit,
+                    },
+                )
+            },
+            |input| {
+                Self::parse_allele::<RnaLocEdit>(
+                    input,
+                    ":r.",
+                    |accession, gene_symbol, loc_edit| HgvsVariant::RnaVariant {
+                        accession,
+                        gene_symbol,
+                        loc_edit,
+                    },
+                )
+            },
+        ];
+        alt_furthest(input, &parsers)
+    }
 }
 
 impl Parseable for HgvsVariant {
     /// Parse a `HgvsVariant` from the given `str`.
     fn parse(input: &str) -> IResult<&str, Self> {
-        all_consuming(alt((
+        let parsers: [ParserFn<Self>; 8] = [
             Self::parse_cds_variant,
             Self::parse_genome_variant,
             Self::parse_mt_variant,
+            Self::parse_circular_variant,
             Self::parse_tx_variant,
             Self::parse_prot_variant,
             Self::parse_rna_variant,
-        )))(input)
+            Self::parse_allele_variant,
+        ];
+        all_consuming(move |input| alt_furthest(input, &parsers))(input)
     }
 }
 
@@ -151,7 +310,8 @@ impl Parseable for ProteinEdit {
 
 impl Parseable for NaEdit {
     fn parse(input: &str) -> IResult<&str, Self> {
-        alt((
+        let parsers: [ParserFn<Self>; 11] = [
+            na_edit::repeat,
             na_edit::ident,
             na_edit::subst,
             na_edit::delins_ref_alt,
@@ -162,12 +322,29 @@ impl Parseable for NaEdit {
             na_edit::dup,
             na_edit::inv_num,
             na_edit::inv_ref,
-        ))(input)
+        ];
+        alt_furthest(input, &parsers)
     }
 }
 
-impl Parseable for Accession {
-    fn parse(input: &str) -> IResult<&str, Self> {
+impl Accession {
+    /// Parse Locus Reference Genomic (LRG) accessions, e.g., `LRG_1`, `LRG_1t1`, or `LRG_1p1`.
+    fn parse_lrg(input: &str) -> IResult<&str, Self> {
+        let parser_accession = terminated(
+            recognize(tuple((
+                tag("LRG_"),
+                digit1,
+                opt(pair(one_of("tp"), digit1)),
+            ))),
+            not(satisfy(|c| c.is_alphanumeric() || c == '_' || c == '.')),
+        );
+
+        let mut parser = map(parser_accession, |value: &str| Self::new(value));
+
+        parser(input)
+    }
+
+    fn parse_ordinary(input: &str) -> IResult<&str, Self> {
         let parser_accession = recognize(tuple((
             satisfy(|c| c.is_alpha()),
             alphanum::narrowed_alphanumeric1,
@@ -175,14 +352,18 @@ impl Parseable for Accession {
             opt(pair(char('.'), digit1)),
         )));
 
-        let mut parser = map(parser_accession, |value| Self {
-            value: value.to_string(),
-        });
+        let mut parser = map(parser_accession, Self::new);
 
         parser(input)
     }
 }
 
+impl Parseable for Accession {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        alt((Self::parse_lrg, Self::parse_ordinary))(input)
+    }
+}
+
 impl GeneSymbol {
     pub fn parse(input: &str) -> IResult<&str, Self> {
         let mut gene_symbol_parser = map(alphanumeric1, |symbol: &str| Self {
@@ -227,7 +408,7 @@ where
 
 impl Parseable for CdsInterval {
     fn parse(input: &str) -> IResult<&str, Self> {
-        cds_pos::loc(input)
+        context("CDS position", cds_pos::loc)(input)
     }
 }
 
@@ -242,7 +423,7 @@ impl Parseable for CdsLocEdit {
 
 impl Parseable for GenomeInterval {
     fn parse(input: &str) -> IResult<&str, Self> {
-        genome_pos::loc(input)
+        context("genome position", genome_pos::loc)(input)
     }
 }
 
@@ -257,7 +438,7 @@ impl Parseable for GenomeLocEdit {
 
 impl Parseable for MtInterval {
     fn parse(input: &str) -> IResult<&str, Self> {
-        mt_pos::loc(input)
+        context("mitochondrial position", mt_pos::loc)(input)
     }
 }
 
@@ -272,7 +453,7 @@ impl Parseable for MtLocEdit {
 
 impl Parseable for TxInterval {
     fn parse(input: &str) -> IResult<&str, Self> {
-        tx_pos::loc(input)
+        context("transcript position", tx_pos::loc)(input)
     }
 }
 
@@ -287,7 +468,7 @@ impl Parseable for TxLocEdit {
 
 impl Parseable for RnaInterval {
     fn parse(input: &str) -> IResult<&str, Self> {
-        rna_pos::loc(input)
+        context("RNA position", rna_pos::loc)(input)
     }
 }
 
@@ -302,7 +483,7 @@ impl Parseable for RnaLocEdit {
 
 impl Parseable for ProtInterval {
     fn parse(input: &str) -> IResult<&str, Self> {
-        prot_pos::loc(input)
+        context("protein position", prot_pos::loc)(input)
     }
 }
 
@@ -365,9 +546,7 @@ mod test {
             Ok((
                 "",
                 HgvsVariant::CdsVariant {
-                    accession: Accession {
-                        value: "NR_01234.1".to_string()
-                    },
+                    accession: Accession::from("NR_01234.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "XYZ".to_string()
                     }),
@@ -397,9 +576,7 @@ mod test {
             Ok((
                 "",
                 HgvsVariant::GenomeVariant {
-                    accession: Accession {
-                        value: "NR_01234.1".to_string()
-                    },
+                    accession: Accession::from("NR_01234.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "XYZ".to_string()
                     }),
@@ -421,9 +598,7 @@ mod test {
             Ok((
                 "",
                 HgvsVariant::MtVariant {
-                    accession: Accession {
-                        value: "NR_01234.1".to_string()
-                    },
+                    accession: Accession::from("NR_01234.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "XYZ".to_string()
                     }),
@@ -440,14 +615,32 @@ mod test {
                 }
             ))
         );
+        assert_eq!(
+            HgvsVariant::parse("NC_001422.1:o.1A>T"),
+            Ok((
+                "",
+                HgvsVariant::CircularVariant {
+                    accession: Accession::from("NC_001422.1".to_string()),
+                    gene_symbol: None,
+                    loc_edit: GenomeLocEdit {
+                        loc: Mu::Certain(GenomeInterval {
+                            start: Some(1),
+                            end: Some(1),
+                        }),
+                        edit: Mu::Certain(NaEdit::RefAlt {
+                            reference: "A".to_string(),
+                            alternative: "T".to_string()
+                        })
+                    }
+                }
+            ))
+        );
         assert_eq!(
             HgvsVariant::parse("NR_01234.1(XYZ):n.123_123C>T"),
             Ok((
                 "",
                 HgvsVariant::TxVariant {
-                    accession: Accession {
-                        value: "NR_01234.1".to_string()
-                    },
+                    accession: Accession::from("NR_01234.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "XYZ".to_string()
                     }),
@@ -475,9 +668,7 @@ mod test {
             Ok((
                 "",
                 HgvsVariant::RnaVariant {
-                    accession: Accession {
-                        value: "NR_01234.1".to_string()
-                    },
+                    accession: Accession::from("NR_01234.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "XYZ".to_string()
                     }),
@@ -505,9 +696,7 @@ mod test {
             Ok((
                 "",
                 HgvsVariant::ProtVariant {
-                    accession: Accession {
-                        value: "NR_01234.1".to_string()
-                    },
+                    accession: Accession::from("NR_01234.1".to_string()),
                     gene_symbol: Some(GeneSymbol {
                         value: "XYZ".to_string()
                     }),
@@ -827,12 +1016,21 @@ mod test {
     fn accession_parse() {
         assert_eq!(
             Accession::parse("NM_01234"),
-            Ok((
-                "",
-                Accession {
-                    value: "NM_01234".to_owned()
-                }
-            )),
+            Ok(("", Accession::from("NM_01234".to_owned()))),
+        );
+    }
+
+    #[test]
+    fn accession_parse_lrg() {
+        for value in ["LRG_1", "LRG_1t1", "LRG_199p1", "LRG_13t12"] {
+            assert_eq!(
+                Accession::parse(&format!("{value}:c.1A>T")),
+                Ok((":c.1A>T", Accession::from(value.to_owned()))),
+            );
+        }
+        assert_eq!(
+            Accession::parse("LRG_1x1:c.1A>T"),
+            Ok((":c.1A>T", Accession::from("LRG_1x1".to_owned()))),
         );
     }
 
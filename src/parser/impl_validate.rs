@@ -2,12 +2,13 @@
 
 use std::ops::Range;
 
+use crate::static_data::MITOCHONDRIAL_GENOME_LENGTH;
 use crate::validator::Error;
 use crate::validator::Validateable;
 
 use super::{
-    CdsInterval, CdsLocEdit, GenomeInterval, GenomeLocEdit, HgvsVariant, MtLocEdit, NaEdit,
-    ProtLocEdit, RnaLocEdit, TxLocEdit,
+    CdsInterval, CdsLocEdit, GenomeInterval, GenomeLocEdit, HgvsVariant, MtInterval, MtLocEdit,
+    NaEdit, ProtLocEdit, RnaLocEdit, TxInterval, TxLocEdit, UncertainLengthChange,
 };
 
 impl Validateable for NaEdit {
@@ -40,7 +41,13 @@ impl Validateable for NaEdit {
                     Ok(())
                 }
             }
-            NaEdit::Ins { alternative: _ } => Ok(()),
+            NaEdit::Ins { alternative } => {
+                if alternative.is_empty() {
+                    Err(Error::EmptyInsertion(format!("{:?}", self)))
+                } else {
+                    Ok(())
+                }
+            }
             NaEdit::Dup { reference: _ } => Ok(()),
             NaEdit::InvRef { reference: _ } => Ok(()),
             NaEdit::InvNum { count } => {
@@ -50,10 +57,51 @@ impl Validateable for NaEdit {
                     Ok(())
                 }
             }
+            NaEdit::Repeat { count, .. } => match count {
+                UncertainLengthChange::Known(count) if *count < 1 => {
+                    Err(Error::NumRepeatUnitsNotPositive(format!("{:?}", self)))
+                }
+                UncertainLengthChange::Range(min, max) if *min < 1 || min > max => {
+                    Err(Error::NumRepeatUnitsNotPositive(format!("{:?}", self)))
+                }
+                _ => Ok(()),
+            },
         }
     }
 }
 
+/// Check that the length of the interval `range` is a multiple of the repeat unit length.
+///
+/// Single-position repeats (e.g., `c.52CAG[40]`) only give the first repeat unit position
+/// and are not checked.
+fn validate_repeat_span<T: std::fmt::Debug>(
+    edit: &NaEdit,
+    range: &Range<i32>,
+    loc_edit: &T,
+) -> Result<(), Error> {
+    if let NaEdit::Repeat { sequence, .. } = edit {
+        if !sequence.is_empty() && range.len() > 1 && range.len() % sequence.len() != 0 {
+            return Err(Error::RepeatUnitLengthMismatch(format!("{:?}", loc_edit)));
+        }
+    }
+    Ok(())
+}
+
+/// Check that the length of the duplicated reference sequence, if given, equals the length of
+/// the interval `range`.
+fn validate_dup_span<T: std::fmt::Debug>(
+    edit: &NaEdit,
+    range: &Range<i32>,
+    loc_edit: &T,
+) -> Result<(), Error> {
+    if let NaEdit::Dup { reference } = edit {
+        if !reference.is_empty() && range.len() != reference.len() {
+            return Err(Error::ImpliedLengthMismatch(format!("{:?}", loc_edit)));
+        }
+    }
+    Ok(())
+}
+
 impl Validateable for HgvsVariant {
     fn validate(&self) -> Result<(), Error> {
         // NB: we only need to validate `self.loc_edit`.  The cases that the Python library
@@ -62,9 +110,11 @@ impl Validateable for HgvsVariant {
             HgvsVariant::CdsVariant { loc_edit, .. } => loc_edit.validate(),
             HgvsVariant::GenomeVariant { loc_edit, .. } => loc_edit.validate(),
             HgvsVariant::MtVariant { loc_edit, .. } => loc_edit.validate(),
+            HgvsVariant::CircularVariant { loc_edit, .. } => loc_edit.validate(),
             HgvsVariant::TxVariant { loc_edit, .. } => loc_edit.validate(),
             HgvsVariant::ProtVariant { loc_edit, .. } => loc_edit.validate(),
             HgvsVariant::RnaVariant { loc_edit, .. } => loc_edit.validate(),
+            HgvsVariant::Allele { variants, .. } => variants.iter().try_for_each(|v| v.validate()),
         }
     }
 }
@@ -89,12 +139,16 @@ impl Validateable for CdsLocEdit {
             NaEdit::RefAlt { .. }
             | NaEdit::DelRef { .. }
             | NaEdit::Dup { .. }
-            | NaEdit::Ins { .. }
             | NaEdit::InvRef { .. } => {
                 // We cannot make assumptions about reference length as we can have positon
                 // offsets.
                 Ok(())
             }
+            edit @ NaEdit::Ins { .. } => edit.validate(),
+            edit @ NaEdit::Repeat { .. } => {
+                edit.validate()?;
+                validate_repeat_span(edit, &range, self)
+            }
             NaEdit::DelNum { count } | NaEdit::NumAlt { count, .. } | NaEdit::InvNum { count } => {
                 if range.len() as i32 != *count {
                     Err(Error::ImpliedLengthMismatch(format!("{:?}", self)))
@@ -107,15 +161,29 @@ impl Validateable for CdsLocEdit {
 }
 
 impl Validateable for CdsInterval {
+    /// Check that the start position is not after the end position.
+    ///
+    /// Positions are compared using their ordering, i.e., positions relative to the CDS start
+    /// precede those relative to the CDS end and intronic offsets are taken into account.
     fn validate(&self) -> Result<(), Error> {
-        Ok(()) // TODO
+        if self.start > self.end {
+            Err(Error::InvalidInterval(format!("{:?}", self)))
+        } else {
+            Ok(())
+        }
     }
 }
 
 impl Validateable for GenomeLocEdit {
     fn validate(&self) -> Result<(), Error> {
-        self.loc.inner().validate()?;
-        self.edit.inner().validate()
+        let loc = self.loc.inner();
+        loc.validate()?;
+        self.edit.inner().validate()?;
+        if let (Some(start), Some(end)) = (loc.start, loc.end) {
+            validate_repeat_span(self.edit.inner(), &((start - 1)..end), self)?;
+            validate_dup_span(self.edit.inner(), &((start - 1)..end), self)?;
+        }
+        Ok(())
     }
 }
 
@@ -143,13 +211,49 @@ impl Validateable for GenomeInterval {
 
 impl Validateable for MtLocEdit {
     fn validate(&self) -> Result<(), Error> {
-        Ok(()) // TODO
+        self.loc.inner().validate()?;
+        self.edit.inner().validate()
+    }
+}
+
+impl Validateable for MtInterval {
+    /// Check that the positions are within the human mitochondrial genome.
+    ///
+    /// Unknown positions (e.g., `m.?`) are not checked.
+    fn validate(&self) -> Result<(), Error> {
+        for position in [self.start, self.end].into_iter().flatten() {
+            if !(1..=MITOCHONDRIAL_GENOME_LENGTH).contains(&position) {
+                return Err(Error::PositionOutOfRange {
+                    position,
+                    min: 1,
+                    max: MITOCHONDRIAL_GENOME_LENGTH,
+                });
+            }
+        }
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if start > end {
+                return Err(Error::StartMustBeLessThanEnd(format!("{:?}", self)));
+            }
+        }
+
+        Ok(())
     }
 }
 
 impl Validateable for TxLocEdit {
     fn validate(&self) -> Result<(), Error> {
-        Ok(()) // TODO
+        self.loc.inner().validate()
+    }
+}
+
+impl Validateable for TxInterval {
+    /// Check that the start position is not after the end position, see `CdsInterval`.
+    fn validate(&self) -> Result<(), Error> {
+        if self.start > self.end {
+            Err(Error::InvalidInterval(format!("{:?}", self)))
+        } else {
+            Ok(())
+        }
     }
 }
 
@@ -167,8 +271,10 @@ impl Validateable for ProtLocEdit {
 
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
     use crate::{
-        parser::GenomeInterval,
+        parser::{GenomeInterval, HgvsVariant, MtInterval},
         validator::{Error, Validateable},
     };
 
@@ -200,6 +306,136 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn validate_mt_position_range() -> Result<(), anyhow::Error> {
+        let var = HgvsVariant::from_str("NC_012920.1:m.1A>T")?;
+        assert!(var.validate().is_ok());
+        let var = HgvsVariant::from_str("NC_012920.1:m.16569A>T")?;
+        assert!(var.validate().is_ok());
+        let m_interval = MtInterval {
+            start: None,
+            end: None,
+        };
+        assert!(m_interval.validate().is_ok());
+
+        let var = HgvsVariant::from_str("NC_012920.1:m.16570A>T")?;
+        assert!(matches!(
+            var.validate(),
+            Err(Error::PositionOutOfRange {
+                position: 16570,
+                min: 1,
+                max: 16569
+            })
+        ));
+        let var = HgvsVariant::from_str("NC_012920.1:m.200_100del")?;
+        assert!(var.validate().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_allele() -> Result<(), anyhow::Error> {
+        let var = HgvsVariant::from_str("NC_000001.10:g.[100A>T;200del]")?;
+        assert!(var.validate().is_ok());
+
+        let var = HgvsVariant::from_str("NC_000001.10:g.[100A>T;20_10del]")?;
+        assert!(var.validate().is_err());
+
+        Ok(())
+    }
+
+    #[rstest::rstest]
+    #[case("NM_01234.5:c.10_20del", true)]
+    #[case("NM_01234.5:c.10del", true)]
+    #[case("NM_01234.5:c.-5_*5del", true)]
+    #[case("NM_01234.5:c.10+1_10+5del", true)]
+    #[case("NM_01234.5:c.10+5_11-5del", true)]
+    #[case("NM_01234.5:c.20_10del", false)]
+    #[case("NM_01234.5:c.*5_10del", false)]
+    #[case("NM_01234.5:c.10+5_10+1del", false)]
+    #[case("NM_01234.5:c.11-5_10+5del", false)]
+    #[case("NR_01234.5:n.10_20del", true)]
+    #[case("NR_01234.5:n.10-1_10del", true)]
+    #[case("NR_01234.5:n.20_10del", false)]
+    #[case("NR_01234.5:n.10+1_10-1del", false)]
+    fn validate_interval_order(
+        #[case] hgvs: &str,
+        #[case] valid: bool,
+    ) -> Result<(), anyhow::Error> {
+        let var = HgvsVariant::from_str(hgvs)?;
+        assert_eq!(var.validate().is_ok(), valid, "{hgvs}");
+        if !valid {
+            assert!(matches!(var.validate(), Err(Error::InvalidInterval(_))));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_interval_order_strict() -> Result<(), anyhow::Error> {
+        use crate::validator::{IntrinsicValidator, Validator};
+
+        let var = HgvsVariant::from_str("NM_01234.5:c.20_10del")?;
+        assert!(matches!(
+            IntrinsicValidator::new(true).validate(&var),
+            Err(Error::InvalidInterval(_))
+        ));
+        assert!(IntrinsicValidator::new(false).validate(&var).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_repeat() -> Result<(), anyhow::Error> {
+        let var = HgvsVariant::from_str("NC_000004.12:g.3074877_3074937CAG[23]")?;
+        assert!(var.validate().is_err());
+
+        let var = HgvsVariant::from_str("NC_000004.12:g.3074877_3074936CAG[23]")?;
+        assert!(var.validate().is_ok());
+        let var = HgvsVariant::from_str("NM_002111.8:c.52CAG[36_39]")?;
+        assert!(var.validate().is_ok());
+
+        let var = HgvsVariant::from_str("NM_002111.8:c.52_54CAG[0]")?;
+        assert!(var.validate().is_err());
+        let var = HgvsVariant::from_str("NM_002111.8:c.52_54CAG[39_36]")?;
+        assert!(var.validate().is_err());
+        let var = HgvsVariant::from_str("NM_002111.8:c.52_56CAG[40]")?;
+        assert!(var.validate().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_insertion() -> Result<(), anyhow::Error> {
+        use crate::parser::NaEdit;
+
+        let var = HgvsVariant::from_str("NM_01234.5:c.10_11insA")?;
+        assert!(var.validate().is_ok());
+
+        let var = var.with_na_edit(NaEdit::Ins {
+            alternative: String::new(),
+        });
+        assert!(matches!(var.validate(), Err(Error::EmptyInsertion(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_dup() -> Result<(), anyhow::Error> {
+        let var = HgvsVariant::from_str("NC_000001.10:g.100_101dupAT")?;
+        assert!(var.validate().is_ok());
+        let var = HgvsVariant::from_str("NC_000001.10:g.100_101dup")?;
+        assert!(var.validate().is_ok());
+
+        let var = HgvsVariant::from_str("NC_000001.10:g.100_102dupAT")?;
+        assert!(matches!(
+            var.validate(),
+            Err(Error::ImpliedLengthMismatch(_))
+        ));
+
+        Ok(())
+    }
 }
 
 // <LICENSE>
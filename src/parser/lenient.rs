@@ -0,0 +1,194 @@
+//! Lenient parsing of HGVS expressions with common deviations from the standard.
+
+use std::str::FromStr;
+
+use log::debug;
+
+use crate::parser::{impl_parse::Parseable, Accession, Error, HgvsVariant};
+
+/// Prefixes of RefSeq accessions that get version `1` if given without version.
+const REFSEQ_PREFIXES: &[&str] = &["NM_", "NR_", "NC_", "NP_", "XM_", "XR_", "XP_", "NG_"];
+
+/// Characters that can occur in the location of a nucleic acid variant.
+const LOC_CHARS: &str = "0123456789+-*_?()";
+
+/// Return whether `s` is a non-empty sequence of nucleotides.
+fn is_na_seq(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| "ACGTUNacgtun".contains(c))
+}
+
+impl HgvsVariant {
+    /// Parse `s` after rewriting common deviations from the HGVS standard.
+    ///
+    /// Submissions to databases such as ClinVar frequently contain such deviations.  The
+    /// following rewrites are performed:
+    ///
+    /// - `/` instead of `>` in substitutions, e.g., `c.123A/T` becomes `c.123A>T`
+    /// - reference context before the substituted base, e.g., `c.123AT>C` becomes `c.123T>C`
+    /// - RefSeq accessions without version get version `1`, e.g., `NM_000059:c.123A>T` becomes
+    ///   `NM_000059.1:c.123A>T`
+    /// - deletion of a single base given as a range into the intron, e.g.,
+    ///   `c.123_123+1delA` becomes `c.123+1del`
+    ///
+    /// If the rewritten expression cannot be parsed, the error for `s` is returned.
+    pub fn parse_lenient(s: &str) -> Result<HgvsVariant, Error> {
+        let rewritten = rewrite(s.trim());
+        HgvsVariant::from_str(&rewritten).or_else(|_| HgvsVariant::from_str(s))
+    }
+}
+
+/// Rewrite the HGVS expression `s` as described for `HgvsVariant::parse_lenient()`.
+fn rewrite(s: &str) -> String {
+    let Some((accession, variant)) = s.split_once(':') else {
+        return s.to_string();
+    };
+    let accession = rewrite_accession(accession);
+    let variant = match variant.split_once('.') {
+        Some((prefix @ ("c" | "g" | "m" | "n" | "o" | "r"), loc_edit)) => {
+            format!("{prefix}.{}", rewrite_na_loc_edit(loc_edit))
+        }
+        _ => variant.to_string(),
+    };
+    format!("{accession}:{variant}")
+}
+
+/// Add version `1` to RefSeq accessions without version; `accession` may include the gene
+/// symbol in parentheses.
+///
+/// Other accessions, e.g., `LRG_199t1`, and accessions that the strict parser does not
+/// accept are left unchanged.
+fn rewrite_accession(accession: &str) -> String {
+    let (ac, gene_symbol) = accession.split_at(accession.find('(').unwrap_or(accession.len()));
+    let Ok(("", parsed)) = Accession::parse(ac) else {
+        return accession.to_string();
+    };
+    if parsed.version().is_none()
+        && REFSEQ_PREFIXES.iter().any(|prefix| ac.starts_with(prefix))
+        && ac.ends_with(|c: char| c.is_ascii_digit())
+    {
+        debug!("adding version 1 to accession {ac}");
+        format!("{ac}.1{gene_symbol}")
+    } else {
+        accession.to_string()
+    }
+}
+
+/// Rewrite the location and edit of a nucleic acid variant.
+fn rewrite_na_loc_edit(loc_edit: &str) -> String {
+    let split = loc_edit
+        .find(|c: char| !LOC_CHARS.contains(c))
+        .unwrap_or(loc_edit.len());
+    let (mut loc, mut edit) = (loc_edit[..split].to_string(), loc_edit[split..].to_string());
+
+    if let Some((reference, alternative)) = edit.split_once('/') {
+        if is_na_seq(reference) && is_na_seq(alternative) {
+            debug!("replacing '/' by '>' in substitution {edit}");
+            edit = format!("{reference}>{alternative}");
+        }
+    }
+
+    if let Some((reference, alternative)) = edit.split_once('>') {
+        if !loc.contains('_')
+            && reference.len() > 1
+            && alternative.len() == 1
+            && is_na_seq(reference)
+            && is_na_seq(alternative)
+        {
+            debug!("stripping reference context from substitution {edit}");
+            edit = format!("{}>{alternative}", &reference[reference.len() - 1..]);
+        }
+    }
+
+    if let Some((start, end)) = loc.split_once('_') {
+        let is_single_base_del = edit
+            .strip_prefix("del")
+            .is_some_and(|reference| reference.len() == 1 && is_na_seq(reference));
+        let intronic = if end.strip_prefix(start).is_some_and(|o| o.starts_with('+')) {
+            Some(end)
+        } else if start.strip_prefix(end).is_some_and(|o| o.starts_with('-')) {
+            Some(start)
+        } else {
+            None
+        };
+        if let (true, Some(intronic)) = (is_single_base_del, intronic) {
+            debug!("rewriting single base deletion {loc}{edit} to {intronic}del");
+            (loc, edit) = (intronic.to_string(), "del".to_string());
+        }
+    }
+
+    format!("{loc}{edit}")
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::parser::{Error, HgvsVariant};
+
+    #[test]
+    fn parse_lenient() -> Result<(), Error> {
+        for (input, expected) in [
+            ("NM_000059.3:c.123A/G", "NM_000059.3:c.123A>G"),
+            ("NM_000059.3(BRCA2):c.123A/G", "NM_000059.3(BRCA2):c.123A>G"),
+            ("NM_000059.3:c.-12C/T", "NM_000059.3:c.-12C>T"),
+            ("NM_000059.3:c.*45G/A", "NM_000059.3:c.*45G>A"),
+            ("NM_000059.3:c.123+5G/A", "NM_000059.3:c.123+5G>A"),
+            ("NM_000059.3:n.123A/G", "NM_000059.3:n.123A>G"),
+            ("NC_000013.11:g.32316461A/G", "NC_000013.11:g.32316461A>G"),
+            ("ENST00000380152:c.123A/G", "ENST00000380152:c.123A>G"),
+            ("NM_000059.3:c.123AT>C", "NM_000059.3:c.123T>C"),
+            ("NM_000059.3:c.123GAT>C", "NM_000059.3:c.123T>C"),
+            ("NM_000059.3:c.123-2AG>C", "NM_000059.3:c.123-2G>C"),
+            ("NM_000059:c.123A>G", "NM_000059.1:c.123A>G"),
+            ("NM_000059(BRCA2):c.123A>G", "NM_000059.1(BRCA2):c.123A>G"),
+            ("NC_000013:g.32316461A/G", "NC_000013.1:g.32316461A>G"),
+            ("NP_000050:p.Arg12Trp", "NP_000050.1:p.Arg12Trp"),
+            ("LRG_199t1:c.123A/G", "LRG_199t1:c.123A>G"),
+            ("LRG_199:g.123A>G", "LRG_199:g.123A>G"),
+            ("NM_000059.3:c.123_123+1delA", "NM_000059.3:c.123+1del"),
+            ("NM_000059.3:c.124-1_124delG", "NM_000059.3:c.124-1del"),
+            ("NM_000059:c.123_123+1delA", "NM_000059.1:c.123+1del"),
+            (
+                "NM_000059.3:c.123_124delinsAG",
+                "NM_000059.3:c.123_124delinsAG",
+            ),
+            (" NM_000059.3:c.123del ", "NM_000059.3:c.123del"),
+        ] {
+            let var = HgvsVariant::parse_lenient(input)?;
+            assert_eq!(format!("{var}"), expected, "{input}");
+            assert_eq!(var, HgvsVariant::from_str(expected)?, "{input}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_lenient_error() {
+        let input = "NM_000059.3:c.123A/";
+        let Err(Error::ErrorAt {
+            input: err_input, ..
+        }) = HgvsVariant::parse_lenient(input)
+        else {
+            panic!("expected parse error");
+        };
+        assert_eq!(err_input, input);
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
@@ -4,36 +4,63 @@
 //! The data structures also provide the `Display` trait for conversion to
 //! strings etc.
 
+mod builder;
 mod display;
 mod ds;
 mod error;
 mod impl_parse;
 mod impl_validate;
+mod lenient;
 mod parse_funcs;
+mod sort;
 
-use std::str::FromStr;
+use std::{
+    borrow::Borrow,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
 
+pub use crate::parser::builder::*;
 pub use crate::parser::display::*;
 pub use crate::parser::ds::*;
 pub use crate::parser::error::*;
 use crate::parser::impl_parse::*;
+pub(crate) use crate::parser::parse_funcs::{na::NA_IUPAC, protein::AA1};
+pub use crate::parser::sort::*;
 
 impl FromStr for HgvsVariant {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self::parse(s)
-            .map_err(|_e| Error::InvalidHgvsVariant(s.to_string()))
+            .map_err(|e| Error::from_nom(s, e))
             .map(|(_rest, variant)| variant)
     }
 }
 
+impl HgvsVariant {
+    /// Parse `s` like `from_str()` and keep `s` as the source string of the result.
+    ///
+    /// Use this when the variant must be reported in terms of the original input, e.g.,
+    /// in error messages when processing many variants.
+    pub fn parse_with_context(s: &str) -> Result<ParsedVariant, Error> {
+        Ok(ParsedVariant {
+            variant: Self::from_str(s)?,
+            context: ParseContext {
+                source: s.to_string(),
+            },
+        })
+    }
+}
+
 impl FromStr for GenomeInterval {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self::parse(s)
-            .map_err(|_e| Error::InvalidGenomeInterval(s.to_string()))
+            .map_err(|e| Error::from_nom(s, e))
             .map(|(_rest, g_interval)| g_interval)
     }
 }
@@ -43,7 +70,7 @@ impl FromStr for TxInterval {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self::parse(s)
-            .map_err(|_e| Error::InvalidTxInterval(s.to_string()))
+            .map_err(|e| Error::from_nom(s, e))
             .map(|(_rest, g_interval)| g_interval)
     }
 }
@@ -53,14 +80,101 @@ impl FromStr for CdsInterval {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self::parse(s)
-            .map_err(|_e| Error::InvalidCdsInterval(s.to_string()))
+            .map_err(|e| Error::from_nom(s, e))
             .map(|(_rest, g_interval)| g_interval)
     }
 }
 
+impl HgvsVariant {
+    /// Serialize the variant to JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Serialize the variant to indented JSON.
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserialize a variant from JSON as written by `to_json()`.
+    pub fn from_json(s: &str) -> Result<HgvsVariant, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Serialize the variant to MessagePack, with field names.
+    #[cfg(feature = "msgpack")]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
+
+    /// Deserialize a variant from MessagePack as written by `to_msgpack()`.
+    #[cfg(feature = "msgpack")]
+    pub fn from_msgpack(bytes: &[u8]) -> Result<HgvsVariant, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+}
+
+/// Parse the HGVS expressions from a file with one expression per line.
+///
+/// See `parse_hgvs_reader()`.  If the file cannot be opened, a single error with line
+/// number `0` is returned.
+pub fn parse_hgvs_file<P: AsRef<Path>>(
+    path: P,
+) -> impl Iterator<Item = Result<(usize, HgvsVariant), (usize, Error)>> {
+    let (reader, open_error) = match File::open(path) {
+        Ok(file) => (Some(BufReader::new(file)), None),
+        Err(e) => (None, Some(Err((0, Error::Io(e.to_string()))))),
+    };
+    open_error
+        .into_iter()
+        .chain(reader.into_iter().flat_map(parse_hgvs_reader))
+}
+
+/// Lazily parse the HGVS expressions from `reader` with one expression per line.
+///
+/// Blank lines and lines starting with `#` are skipped.  Each result carries the 1-based
+/// line number.
+pub fn parse_hgvs_reader<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = Result<(usize, HgvsVariant), (usize, Error)>> {
+    reader.lines().enumerate().filter_map(|(i, line)| {
+        let line_no = i + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => return Some(Err((line_no, Error::Io(e.to_string())))),
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            None
+        } else {
+            Some(
+                HgvsVariant::from_str(line)
+                    .map(|variant| (line_no, variant))
+                    .map_err(|e| (line_no, e)),
+            )
+        }
+    })
+}
+
+/// Write `variants` to the file at `path` with one HGVS expression per line.
+pub fn write_hgvs_file<P, I>(path: P, variants: I) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+    I: IntoIterator,
+    I::Item: Borrow<HgvsVariant>,
+{
+    let io_error = |e: std::io::Error| Error::Io(e.to_string());
+    let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+    for variant in variants {
+        writeln!(writer, "{}", variant.borrow()).map_err(io_error)?;
+    }
+    writer.flush().map_err(io_error)
+}
+
 #[cfg(test)]
 mod test {
     use anyhow::Error;
+    use rstest::rstest;
     use std::{
         fs::File,
         io::{BufRead, BufReader},
@@ -78,9 +192,7 @@ mod test {
         assert_eq!(
             HgvsVariant::from_str("NM_01234.5:c.22+1A>T")?,
             HgvsVariant::CdsVariant {
-                accession: Accession {
-                    value: "NM_01234.5".to_string()
-                },
+                accession: Accession::from("NM_01234.5".to_string()),
                 gene_symbol: None,
                 loc_edit: CdsLocEdit {
                     loc: Mu::Certain(CdsInterval {
@@ -106,6 +218,176 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    #[case("NM_000088.3:c.[1A>T;10G>C]")]
+    #[case("NM_000088.3(COL1A1):c.[1A>T;10_12del]")]
+    #[case("NC_000017.10:g.[48279242A>T;48279250G>C]")]
+    fn from_str_allele(#[case] hgvs: &str) -> Result<(), Error> {
+        let var = HgvsVariant::from_str(hgvs)?;
+        assert_eq!(format!("{var}"), hgvs);
+
+        if let HgvsVariant::Allele {
+            accession,
+            gene_symbol,
+            variants,
+        } = &var
+        {
+            assert_eq!(variants.len(), 2);
+            for v in variants {
+                assert_eq!(v.accession(), accession);
+                assert_eq!(v.gene_symbol(), gene_symbol);
+            }
+        } else {
+            panic!("expected allele but got {var:?}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_str_allele_invalid() {
+        assert!(HgvsVariant::from_str("NM_000088.3:c.[]").is_err());
+        assert!(HgvsVariant::from_str("NM_000088.3:c.[1A>T;]").is_err());
+        assert!(HgvsVariant::from_str("NM_000088.3:c.[1A>T;10G>C").is_err());
+    }
+
+    #[rstest]
+    #[case("NM_002111.8(HTT):c.52CAG[40]", "CAG")]
+    #[case("NM_002111.8:c.52_117CAG[36_39]", "CAG")]
+    #[case("NM_000332.3(ATXN1):c.589CAG[?]", "CAG")]
+    #[case("NM_004993.6(ATXN3):c.916_918CAG[72]", "CAG")]
+    #[case("NC_000019.10:g.45770205CAG[120]", "CAG")]
+    #[case("NR_111984.1:n.44[12]", "")]
+    #[case("NM_002024.5(FMR1):c.1-1_1[18]", "")]
+    #[case("NM_002111.8:r.52cag[40]", "cag")]
+    fn from_str_repeat(#[case] hgvs: &str, #[case] unit: &str) -> Result<(), Error> {
+        let var = HgvsVariant::from_str(hgvs)?;
+        assert_eq!(format!("{var}"), hgvs);
+
+        match var.na_edit() {
+            Some(NaEdit::Repeat { sequence, .. }) => assert_eq!(sequence, unit),
+            _ => panic!("expected repeat but got {var:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("NM_001234:c.abc", 12, "CDS position")]
+    #[case("NM_001234.1:c.12A>", 18, "nucleotide")]
+    #[case("NC_000001.10:x.100A>T", 12, ":c.")]
+    fn from_str_error_at(
+        #[case] hgvs: &str,
+        #[case] offset: usize,
+        #[case] expected: &str,
+    ) -> Result<(), Error> {
+        match HgvsVariant::from_str(hgvs) {
+            Err(super::Error::ErrorAt {
+                offset: actual_offset,
+                expected: actual_expected,
+                ..
+            }) => {
+                assert_eq!(actual_offset, offset);
+                assert!(
+                    actual_expected.iter().any(|e| e == expected),
+                    "{actual_expected:?}"
+                );
+            }
+            result => panic!("expected ErrorAt but got {result:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_str_error_at_display() -> Result<(), Error> {
+        let err = HgvsVariant::from_str("NM_001234:c.abc").unwrap_err();
+        assert_eq!(
+            format!("{err}"),
+            "invalid HGVS at offset 12, expected CDS position\n  |\n  | NM_001234:c.abc\n  |             ^"
+        );
+
+        assert!(matches!(
+            GenomeInterval::from_str("x"),
+            Err(super::Error::ErrorAt { offset: 0, .. })
+        ));
+        assert!(matches!(
+            CdsInterval::from_str("x"),
+            Err(super::Error::ErrorAt { offset: 0, .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn hash() -> Result<(), Error> {
+        use std::{
+            collections::HashSet,
+            hash::{DefaultHasher, Hash, Hasher},
+        };
+
+        let hash = |var: &HgvsVariant| {
+            let mut hasher = DefaultHasher::new();
+            var.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let hgvs = [
+            "NM_01234.5(GENE):c.22+1A>T",
+            "NC_000001.10:g.100_102del",
+            "NP_001005484.1:p.Leu2Ser",
+            "NM_022464.4:c.[3G>A;5del]",
+        ];
+        for hgvs in hgvs {
+            assert_eq!(
+                hash(&HgvsVariant::from_str(hgvs)?),
+                hash(&HgvsVariant::from_str(hgvs)?)
+            );
+        }
+
+        let set = hgvs
+            .iter()
+            .chain(hgvs.iter())
+            .map(|hgvs| HgvsVariant::from_str(hgvs))
+            .collect::<Result<HashSet<_>, _>>()?;
+        assert_eq!(set.len(), hgvs.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_with_context() -> Result<(), Error> {
+        use std::{
+            collections::hash_map::DefaultHasher,
+            hash::{Hash, Hasher},
+        };
+
+        fn hash(value: &impl Hash) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let hgvs = "NM_01234.5(GENE):c.22+1A>T";
+        let parsed = HgvsVariant::parse_with_context(hgvs)?;
+        assert_eq!(parsed.source_string(), hgvs);
+        let cloned = parsed.clone();
+        assert_eq!(cloned.source_string(), hgvs);
+        assert_eq!(cloned, parsed);
+
+        // The context does not affect equality and hashing.
+        let var = HgvsVariant::from_str(hgvs)?;
+        assert_eq!(*parsed, var);
+        assert_eq!(hash(&parsed), hash(&var));
+        assert_eq!(parsed.without_context(), var);
+
+        let allele = HgvsVariant::parse_with_context("NM_022464.4:c.[3G>A;5del]")?;
+        assert_eq!(allele.source_string(), "NM_022464.4:c.[3G>A;5del]");
+        assert!(HgvsVariant::parse_with_context("x").is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn not_ok() -> Result<(), Error> {
         assert!(HgvsVariant::from_str("x").is_err());
@@ -130,6 +412,169 @@ mod test {
         Ok(())
     }
 
+    /// Return the variants of the "gauntlet" file plus `m.`, `o.`, and allele variants.
+    fn serde_variants() -> Result<Vec<HgvsVariant>, Error> {
+        let reader = BufReader::new(File::open("tests/data/parser/gauntlet")?);
+        let mut result = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if !line.starts_with('#') && !line.is_empty() {
+                result.push(HgvsVariant::from_str(line)?);
+            }
+        }
+        for s in [
+            "NC_012920.1:m.8993T>G",
+            "NC_001422.1:o.1A>T",
+            "NM_000088.3(COL1A1):c.[1A>T;10_12del]",
+        ] {
+            result.push(HgvsVariant::from_str(s)?);
+        }
+        Ok(result)
+    }
+
+    #[test]
+    fn json_roundtrip() -> Result<(), Error> {
+        for var in serde_variants()? {
+            assert_eq!(HgvsVariant::from_json(&var.to_json()?)?, var);
+            assert_eq!(HgvsVariant::from_json(&var.to_json_pretty()?)?, var);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_roundtrip() -> Result<(), Error> {
+        for var in serde_variants()? {
+            assert_eq!(HgvsVariant::from_msgpack(&var.to_msgpack()?)?, var);
+        }
+
+        Ok(())
+    }
+
+    // Parse and display LRG variants from the "gauntlet" file.
+    #[test]
+    fn hgvs_gauntlet_lrg_roundtrip() -> Result<(), Error> {
+        let reader = BufReader::new(File::open("tests/data/parser/gauntlet")?);
+
+        let mut count = 0;
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.starts_with("LRG_") {
+                let var = HgvsVariant::from_str(line)?;
+                assert!(var.accession().is_lrg(), "line = {line}");
+                assert_eq!(format!("{var}"), line);
+                count += 1;
+            }
+        }
+        assert!(count > 0);
+
+        Ok(())
+    }
+
+    // Convert variants from the "gauntlet" file to minimal form and round-trip them.
+    #[test]
+    fn hgvs_gauntlet_minimal_roundtrip() -> Result<(), Error> {
+        let reader = BufReader::new(File::open("tests/data/parser/gauntlet")?);
+
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if !line.starts_with('#') && !line.is_empty() {
+                let var = HgvsVariant::from_str(line)?;
+                let minimal = var.clone().to_minimal();
+                assert_eq!(minimal.accession(), var.accession(), "line = {line}");
+                assert_eq!(minimal.gene_symbol(), var.gene_symbol(), "line = {line}");
+                assert_eq!(minimal.loc_range(), var.loc_range(), "line = {line}");
+                assert_eq!(
+                    HgvsVariant::from_str(&format!("{minimal}"))?,
+                    minimal,
+                    "line = {line}"
+                );
+                assert!(var.equivalent(&minimal), "line = {line}");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("AC_01234.5:g.5dup", "AC_01234.5:g.5dupT", true)]
+    #[case("AC_01234.5:g.7_8dup", "AC_01234.5:g.7_8dupTG", true)]
+    #[case("AC_01234.5:c.76_78del", "AC_01234.5:c.76_78delACT", true)]
+    #[case("AC_01234.5:c.76_78del3", "AC_01234.5:c.76_78delACT", true)]
+    #[case("AC_01234.5:c.77_79dup", "AC_01234.5:c.77_79dupCTG", true)]
+    #[case("AC_01234.5:c.76_78del", "AC_01234.5:c.76_79delACTG", false)]
+    #[case("AC_01234.5:c.76_78del", "AC_01234.5:c.76_78dup", false)]
+    #[case("AC_01234.5:c.76_78del", "AC_01234.6:c.76_78del", false)]
+    fn equivalent(
+        #[case] lhs: &str,
+        #[case] rhs: &str,
+        #[case] expected: bool,
+    ) -> Result<(), Error> {
+        let lhs = HgvsVariant::from_str(lhs)?;
+        let rhs = HgvsVariant::from_str(rhs)?;
+        assert_eq!(lhs.equivalent(&rhs), expected);
+        assert_eq!(rhs.equivalent(&lhs), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_hgvs_file_gauntlet() -> Result<(), Error> {
+        let results = super::parse_hgvs_file("tests/data/parser/gauntlet").collect::<Vec<_>>();
+
+        assert!(!results.is_empty());
+        for result in &results {
+            assert!(result.is_ok(), "result = {:?}", &result);
+        }
+        // The first expression follows the header comments.
+        let (line_no, variant) = results[0].as_ref().expect("checked above");
+        assert_eq!(*line_no, 11);
+        assert_eq!(format!("{variant}"), "AC_01234.5:c.1A>T");
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_hgvs_file_missing() {
+        let results = super::parse_hgvs_file("tests/data/parser/missing").collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Err((0, super::Error::Io(_)))));
+    }
+
+    #[test]
+    fn parse_hgvs_reader_errors() {
+        let input = "# header\n\nNM_01234.5:c.1A>T\nNM_01234.5:c.1A>>T\n";
+        let results = super::parse_hgvs_reader(input.as_bytes()).collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], Ok((3, _))));
+        assert!(matches!(results[1], Err((4, super::Error::ErrorAt { .. }))));
+    }
+
+    #[test]
+    fn write_hgvs_file_roundtrip() -> Result<(), Error> {
+        let variants = super::parse_hgvs_file("tests/data/parser/gauntlet")
+            .map(|result| result.map(|(_, variant)| variant))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|(_, e)| e)?;
+        let path = std::env::temp_dir().join(format!("hgvs-rs-{}.txt", std::process::id()));
+
+        super::write_hgvs_file(&path, &variants)?;
+        let written = super::parse_hgvs_file(&path)
+            .map(|result| result.map(|(_, variant)| variant))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|(_, e)| e);
+        std::fs::remove_file(&path)?;
+        assert_eq!(written?, variants);
+
+        Ok(())
+    }
+
     // This test uses the "reject" file from the hgvs package.
     #[test]
     fn hgvs_reject() -> Result<(), Error> {
@@ -1,13 +1,21 @@
 //! Implementation of parser functions.
 
+use nom::error::VerboseError;
+
+/// Result type of the parser functions.
+///
+/// `VerboseError` is used so that the position of a failure and the expected input can be
+/// reported to the user.
+pub type IResult<I, O> = nom::IResult<I, O, VerboseError<I>>;
+
 /// Code for parsing alpha/numeric strings.
 pub mod alphanum {
     use nom::character::complete::alphanumeric1;
 
+    use super::IResult;
+
     // cf. https://stackoverflow.com/a/73437782/84349
-    pub fn narrowed_alphanumeric1(
-        input: &str,
-    ) -> Result<(&str, &str), nom::Err<nom::error::Error<&str>>> {
+    pub fn narrowed_alphanumeric1(input: &str) -> IResult<&str, &str> {
         alphanumeric1(input)
     }
 }
@@ -17,15 +25,18 @@ pub mod alphanum {
 pub mod protein {
     use nom::{
         bytes::complete::take,
+        error::{ParseError, VerboseError},
         multi::{many0, many1},
     };
 
+    use super::IResult;
+
     pub static AA1: &str = "ACDEFGHIKLMNPQRSTVWYBZXU";
 
-    pub fn aa1(input: &str) -> Result<(&str, &str), nom::Err<nom::error::Error<&str>>> {
+    pub fn aa1(input: &str) -> IResult<&str, &str> {
         let (rest, c) = take(1usize)(input)?;
         if !AA1.contains(c) {
-            Err(nom::Err::Error(nom::error::Error::new(
+            Err(nom::Err::Error(VerboseError::from_error_kind(
                 input,
                 nom::error::ErrorKind::Fail,
             )))
@@ -34,20 +45,20 @@ pub mod protein {
         }
     }
 
-    pub fn aa10(input: &str) -> Result<(&str, Vec<&str>), nom::Err<nom::error::Error<&str>>> {
+    pub fn aa10(input: &str) -> IResult<&str, Vec<&str>> {
         many0(aa1)(input)
     }
 
-    pub fn aa11(input: &str) -> Result<(&str, Vec<&str>), nom::Err<nom::error::Error<&str>>> {
+    pub fn aa11(input: &str) -> IResult<&str, Vec<&str>> {
         many1(aa1)(input)
     }
 
     pub static AAT1: &str = "ACDEFGHIKLMNPQRSTVWYBZXU*";
 
-    pub fn aat1(input: &str) -> Result<(&str, &str), nom::Err<nom::error::Error<&str>>> {
+    pub fn aat1(input: &str) -> IResult<&str, &str> {
         let (rest, c) = take(1usize)(input)?;
         if !AAT1.contains(c) {
-            Err(nom::Err::Error(nom::error::Error::new(
+            Err(nom::Err::Error(VerboseError::from_error_kind(
                 input,
                 nom::error::ErrorKind::Fail,
             )))
@@ -56,11 +67,11 @@ pub mod protein {
         }
     }
 
-    pub fn aat10(input: &str) -> Result<(&str, Vec<&str>), nom::Err<nom::error::Error<&str>>> {
+    pub fn aat10(input: &str) -> IResult<&str, Vec<&str>> {
         many0(aat1)(input)
     }
 
-    pub fn aat11(input: &str) -> Result<(&str, Vec<&str>), nom::Err<nom::error::Error<&str>>> {
+    pub fn aat11(input: &str) -> IResult<&str, Vec<&str>> {
         many1(aat1)(input)
     }
 
@@ -69,10 +80,10 @@ pub mod protein {
         "Gln", "Arg", "Ser", "Thr", "Val", "Trp", "Tyr", "Asx", "Glx", "Xaa", "Sec",
     ];
 
-    pub fn aa3(input: &str) -> Result<(&str, &str), nom::Err<nom::error::Error<&str>>> {
+    pub fn aa3(input: &str) -> IResult<&str, &str> {
         let (rest, triplet) = take(3usize)(input)?;
         if !AA3.contains(&triplet) {
-            Err(nom::Err::Error(nom::error::Error::new(
+            Err(nom::Err::Error(VerboseError::from_error_kind(
                 input,
                 nom::error::ErrorKind::Fail,
             )))
@@ -81,11 +92,11 @@ pub mod protein {
         }
     }
 
-    pub fn aa30(input: &str) -> Result<(&str, Vec<&str>), nom::Err<nom::error::Error<&str>>> {
+    pub fn aa30(input: &str) -> IResult<&str, Vec<&str>> {
         many0(aa3)(input)
     }
 
-    pub fn aa31(input: &str) -> Result<(&str, Vec<&str>), nom::Err<nom::error::Error<&str>>> {
+    pub fn aa31(input: &str) -> IResult<&str, Vec<&str>> {
         many1(aa3)(input)
     }
 
@@ -94,10 +105,10 @@ pub mod protein {
         "Gln", "Arg", "Ser", "Thr", "Val", "Trp", "Tyr", "Asx", "Glx", "Xaa", "Sec", "Ter",
     ];
 
-    pub fn aat3(input: &str) -> Result<(&str, &str), nom::Err<nom::error::Error<&str>>> {
+    pub fn aat3(input: &str) -> IResult<&str, &str> {
         let (rest, triplet) = take(3usize)(input)?;
         if !AAT3.contains(&triplet) {
-            Err(nom::Err::Error(nom::error::Error::new(
+            Err(nom::Err::Error(VerboseError::from_error_kind(
                 input,
                 nom::error::ErrorKind::Fail,
             )))
@@ -106,11 +117,11 @@ pub mod protein {
         }
     }
 
-    pub fn aat30(input: &str) -> Result<(&str, Vec<&str>), nom::Err<nom::error::Error<&str>>> {
+    pub fn aat30(input: &str) -> IResult<&str, Vec<&str>> {
         many0(aat3)(input)
     }
 
-    pub fn aat31(input: &str) -> Result<(&str, Vec<&str>), nom::Err<nom::error::Error<&str>>> {
+    pub fn aat31(input: &str) -> IResult<&str, Vec<&str>> {
         many1(aat3)(input)
     }
 }
@@ -121,8 +132,10 @@ pub mod protein_edit {
     use nom::character::complete::{digit0, digit1};
     use nom::combinator::opt;
 
+    use nom::bytes::complete::tag;
     use nom::sequence::pair;
-    use nom::{bytes::complete::tag, IResult};
+
+    use super::IResult;
 
     use super::protein::*;
     use crate::parser::ds::{ProteinEdit, UncertainLengthChange};
@@ -297,32 +310,38 @@ pub mod na {
     use nom::{
         bytes::complete::{take_while, take_while1},
         character::complete::one_of,
+        error::context,
     };
 
+    use super::IResult;
+
     pub static NA_IUPAC: &str = "ACGTURYMKWSBDHVNacgturymkwsbdhvn";
 
-    pub fn na(input: &str) -> Result<(&str, char), nom::Err<nom::error::Error<&str>>> {
-        one_of(NA_IUPAC)(input)
+    pub fn na(input: &str) -> IResult<&str, char> {
+        context("nucleotide", one_of(NA_IUPAC))(input)
     }
 
-    pub fn na0(input: &str) -> Result<(&str, &str), nom::Err<nom::error::Error<&str>>> {
+    pub fn na0(input: &str) -> IResult<&str, &str> {
         take_while(|c: char| NA_IUPAC.contains(c))(input)
     }
 
-    pub fn na1(input: &str) -> Result<(&str, &str), nom::Err<nom::error::Error<&str>>> {
+    pub fn na1(input: &str) -> IResult<&str, &str> {
         take_while1(|c: char| NA_IUPAC.contains(c))(input)
     }
 }
 
 /// Functions for parsing nucleic acid edits.
 pub mod na_edit {
+    use nom::branch::alt;
     use nom::bytes::complete::tag;
     use nom::character::complete::{char as nom_char, digit1};
-    use nom::combinator::map;
-    use nom::sequence::tuple;
-    use nom::{multi::many0, sequence::pair, IResult};
+    use nom::combinator::{map, map_res};
+    use nom::sequence::{delimited, separated_pair, tuple};
+    use nom::{multi::many0, sequence::pair};
+
+    use super::IResult;
 
-    use crate::parser::NaEdit;
+    use crate::parser::{ds::UncertainLengthChange, NaEdit};
 
     use super::na::{na, na0, na1};
 
@@ -429,6 +448,42 @@ pub mod na_edit {
             },
         ))
     }
+
+    /// Parse repeat count in brackets, e.g., `[40]`, `[?]`, or `[36_39]`.
+    ///
+    /// Counts that do not fit into an `i32` are rejected.
+    fn repeat_count(input: &str) -> IResult<&str, UncertainLengthChange> {
+        delimited(
+            nom_char('['),
+            alt((
+                map_res(
+                    separated_pair(digit1, nom_char('_'), digit1),
+                    |(min, max): (&str, &str)| {
+                        Ok::<_, std::num::ParseIntError>(UncertainLengthChange::Range(
+                            min.parse()?,
+                            max.parse()?,
+                        ))
+                    },
+                ),
+                map_res(digit1, |count: &str| {
+                    count.parse().map(UncertainLengthChange::Known)
+                }),
+                map(nom_char('?'), |_| UncertainLengthChange::Unknown),
+            )),
+            nom_char(']'),
+        )(input)
+    }
+
+    pub fn repeat(input: &str) -> IResult<&str, NaEdit> {
+        let (rest, (sequence, count)) = pair(na0, repeat_count)(input)?;
+        Ok((
+            rest,
+            NaEdit::Repeat {
+                sequence: sequence.to_string(),
+                count,
+            },
+        ))
+    }
 }
 
 /// Parsing of CDS position and interval.
@@ -439,11 +494,12 @@ pub mod cds_pos {
         character::complete::digit1,
         combinator::{map, opt, recognize},
         sequence::{pair, tuple},
-        IResult,
     };
 
     use crate::parser::{CdsFrom, CdsInterval, CdsPos};
 
+    use super::IResult;
+
     fn pos_from_start(input: &str) -> IResult<&str, CdsPos> {
         let (rest, base) = recognize(pair(opt(alt((tag("+"), tag("-")))), digit1))(input)?;
         let (rest, offset) = opt(recognize(pair(alt((tag("+"), tag("-"))), digit1)))(rest)?;
@@ -506,22 +562,23 @@ pub mod cds_pos {
 /// Parsing of Genome position and interval.
 pub mod genome_pos {
     use nom::{
-        branch::alt, bytes::complete::tag, character::complete::digit1, combinator::map,
-        sequence::tuple, IResult,
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::digit1,
+        combinator::{map, map_res},
+        sequence::tuple,
     };
 
     use crate::parser::GenomeInterval;
 
+    use super::IResult;
+
+    /// Parse a position or `?`; positions beyond `i32::MAX` are rejected.
     pub fn pos(input: &str) -> IResult<&str, Option<i32>> {
-        map(alt((digit1, tag("?"))), |value| {
-            if value == "?" {
-                None
-            } else {
-                Some(str::parse::<i32>(value).expect(
-                    "should not happen; previous parsing should guarantee string with digits",
-                ))
-            }
-        })(input)
+        alt((
+            map_res(digit1, |value: &str| value.parse::<i32>().map(Some)),
+            map(tag("?"), |_| None),
+        ))(input)
     }
 
     pub fn int(input: &str) -> IResult<&str, GenomeInterval> {
@@ -544,22 +601,23 @@ pub mod genome_pos {
 /// Parsing of mt position and interval.
 pub mod mt_pos {
     use nom::{
-        branch::alt, bytes::complete::tag, character::complete::digit1, combinator::map,
-        sequence::tuple, IResult,
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::digit1,
+        combinator::{map, map_res},
+        sequence::tuple,
     };
 
     use crate::parser::MtInterval;
 
+    use super::IResult;
+
+    /// Parse a position or `?`; positions beyond `i32::MAX` are rejected.
     pub fn pos(input: &str) -> IResult<&str, Option<i32>> {
-        map(alt((digit1, tag("?"))), |value| {
-            if value == "?" {
-                None
-            } else {
-                Some(str::parse::<i32>(value).expect(
-                    "should not happen; previous parsing should guarantee string with digits",
-                ))
-            }
-        })(input)
+        alt((
+            map_res(digit1, |value: &str| value.parse::<i32>().map(Some)),
+            map(tag("?"), |_| None),
+        ))(input)
     }
 
     pub fn int(input: &str) -> IResult<&str, MtInterval> {
@@ -588,11 +646,12 @@ pub mod tx_pos {
         character::complete::digit1,
         combinator::{map, opt, recognize},
         sequence::{pair, tuple},
-        IResult,
     };
 
     use crate::parser::{TxInterval, TxPos};
 
+    use super::IResult;
+
     pub fn pos(input: &str) -> IResult<&str, TxPos> {
         let (rest, base) = recognize(pair(opt(alt((tag("+"), tag("-")))), digit1))(input)?;
         let (rest, offset) = opt(recognize(pair(alt((tag("+"), tag("-"))), digit1)))(rest)?;
@@ -635,11 +694,12 @@ pub mod rna_pos {
         character::complete::digit1,
         combinator::{map, opt, recognize},
         sequence::{pair, tuple},
-        IResult,
     };
 
     use crate::parser::{RnaInterval, RnaPos};
 
+    use super::IResult;
+
     pub fn pos(input: &str) -> IResult<&str, RnaPos> {
         let (rest, base) = recognize(pair(opt(alt((tag("+"), tag("-")))), digit1))(input)?;
         let (rest, offset) = opt(recognize(pair(alt((tag("+"), tag("-"))), digit1)))(rest)?;
@@ -682,11 +742,12 @@ pub mod prot_pos {
         character::complete::digit1,
         combinator::map,
         sequence::{pair, tuple},
-        IResult,
     };
 
     use crate::parser::{ProtInterval, ProtPos};
 
+    use super::IResult;
+
     use super::protein::{aat1, aat3};
 
     pub fn pos(input: &str) -> IResult<&str, ProtPos> {
@@ -727,6 +788,7 @@ mod test {
     };
 
     use super::*;
+    use nom::error::{ParseError, VerboseErrorKind};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -734,7 +796,7 @@ mod test {
         assert_eq!(protein::aa1("ACD"), Ok(("CD", "A")));
         assert_eq!(
             protein::aa1("*"),
-            Err(nom::Err::Error(nom::error::Error::new(
+            Err(nom::Err::Error(VerboseError::from_error_kind(
                 "*",
                 nom::error::ErrorKind::Fail,
             )))
@@ -751,10 +813,12 @@ mod test {
     fn protein_aa11() {
         assert_eq!(
             protein::aa11(""),
-            Err(nom::Err::Error(nom::error::Error::new(
-                "",
-                nom::error::ErrorKind::Eof,
-            )))
+            Err(nom::Err::Error(VerboseError {
+                errors: vec![
+                    ("", VerboseErrorKind::Nom(nom::error::ErrorKind::Eof)),
+                    ("", VerboseErrorKind::Nom(nom::error::ErrorKind::Many1)),
+                ]
+            }))
         );
         assert_eq!(protein::aa11("ACD"), Ok(("", vec!["A", "C", "D"])));
     }
@@ -764,7 +828,7 @@ mod test {
         assert_eq!(protein::aat1("*ACD"), Ok(("ACD", "*")));
         assert_eq!(
             protein::aat1("="),
-            Err(nom::Err::Error(nom::error::Error::new(
+            Err(nom::Err::Error(VerboseError::from_error_kind(
                 "=",
                 nom::error::ErrorKind::Fail,
             )))
@@ -781,10 +845,12 @@ mod test {
     fn protein_aat11() {
         assert_eq!(
             protein::aat11(""),
-            Err(nom::Err::Error(nom::error::Error::new(
-                "",
-                nom::error::ErrorKind::Eof,
-            )))
+            Err(nom::Err::Error(VerboseError {
+                errors: vec![
+                    ("", VerboseErrorKind::Nom(nom::error::ErrorKind::Eof)),
+                    ("", VerboseErrorKind::Nom(nom::error::ErrorKind::Many1)),
+                ]
+            }))
         );
         assert_eq!(protein::aat11("ACD*"), Ok(("", vec!["A", "C", "D", "*"])));
     }
@@ -794,7 +860,7 @@ mod test {
         assert_eq!(protein::aa3("LeuMet"), Ok(("Met", "Leu")));
         assert_eq!(
             protein::aa3("Ter"),
-            Err(nom::Err::Error(nom::error::Error::new(
+            Err(nom::Err::Error(VerboseError::from_error_kind(
                 "Ter",
                 nom::error::ErrorKind::Fail,
             )))
@@ -814,10 +880,12 @@ mod test {
     fn protein_aa31() {
         assert_eq!(
             protein::aa31(""),
-            Err(nom::Err::Error(nom::error::Error::new(
-                "",
-                nom::error::ErrorKind::Eof,
-            )))
+            Err(nom::Err::Error(VerboseError {
+                errors: vec![
+                    ("", VerboseErrorKind::Nom(nom::error::ErrorKind::Eof)),
+                    ("", VerboseErrorKind::Nom(nom::error::ErrorKind::Many1)),
+                ]
+            }))
         );
         assert_eq!(
             protein::aa30("MetValTrp"),
@@ -830,7 +898,7 @@ mod test {
         assert_eq!(protein::aat3("TerLeuMet"), Ok(("LeuMet", "Ter")));
         assert_eq!(
             protein::aat3("==="),
-            Err(nom::Err::Error(nom::error::Error::new(
+            Err(nom::Err::Error(VerboseError::from_error_kind(
                 "===",
                 nom::error::ErrorKind::Fail,
             )))
@@ -850,10 +918,12 @@ mod test {
     fn protein_aat31() {
         assert_eq!(
             protein::aat31(""),
-            Err(nom::Err::Error(nom::error::Error::new(
-                "",
-                nom::error::ErrorKind::Eof,
-            )))
+            Err(nom::Err::Error(VerboseError {
+                errors: vec![
+                    ("", VerboseErrorKind::Nom(nom::error::ErrorKind::Eof)),
+                    ("", VerboseErrorKind::Nom(nom::error::ErrorKind::Many1)),
+                ]
+            }))
         );
         assert_eq!(
             protein::aat31("TerLeuMet"),
@@ -1639,6 +1709,54 @@ mod test {
         );
     }
 
+    #[test]
+    fn naedit_repeat() {
+        assert_eq!(
+            na_edit::repeat("CAG[40]"),
+            Ok((
+                "",
+                NaEdit::Repeat {
+                    sequence: "CAG".to_owned(),
+                    count: UncertainLengthChange::Known(40),
+                }
+            ))
+        );
+        assert_eq!(
+            na_edit::repeat("CTG[?]"),
+            Ok((
+                "",
+                NaEdit::Repeat {
+                    sequence: "CTG".to_owned(),
+                    count: UncertainLengthChange::Unknown,
+                }
+            ))
+        );
+        assert_eq!(
+            na_edit::repeat("CAG[36_39]"),
+            Ok((
+                "",
+                NaEdit::Repeat {
+                    sequence: "CAG".to_owned(),
+                    count: UncertainLengthChange::Range(36, 39),
+                }
+            ))
+        );
+        assert_eq!(
+            na_edit::repeat("[12]"),
+            Ok((
+                "",
+                NaEdit::Repeat {
+                    sequence: "".to_owned(),
+                    count: UncertainLengthChange::Known(12),
+                }
+            ))
+        );
+        assert!(na_edit::repeat("CAG[]").is_err());
+        assert!(na_edit::repeat("CAG").is_err());
+        assert!(na_edit::repeat("CAG[99999999999]").is_err());
+        assert!(na_edit::repeat("CAG[36_99999999999]").is_err());
+    }
+
     #[test]
     fn mtpos_loc() {
         assert_eq!(
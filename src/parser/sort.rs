@@ -0,0 +1,376 @@
+//! Stable ordering of variants, e.g., for presenting them in reports.
+
+use std::cmp::Ordering;
+
+use crate::parser::{HgvsVariant, NaEdit, ProtLocEdit, ProteinEdit};
+
+/// Key for sorting variants by type, chromosome, accession, position, and edit type.
+///
+/// Obtain with `HgvsVariant::sort_key()`.  Variants are ordered by their type (`g.`, `m.`,
+/// `o.`, `c.`, `n.`, `r.`, `p.`), then by chromosome for `g.` variants, by accession, by
+/// start and end position, and finally by the type of edit (substitution, deletion,
+/// insertion, duplication, deletion-insertion, inversion, ...).  Unknown positions sort
+/// last.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VariantSortKey<'a> {
+    /// Priority of the variant type.
+    pub variant_type: u8,
+    /// Chromosome number of `g.` variants, `u32::MAX` if not on a known chromosome.
+    pub chrom: u32,
+    /// Accession of the reference sequence.
+    pub accession: &'a str,
+    /// 0-based start position.
+    pub start: i64,
+    /// 0-based end position.
+    pub end: i64,
+    /// Priority of the edit type.
+    pub edit_type: u8,
+}
+
+/// Return the chromosome number for a RefSeq chromosome accession such as `NC_000001.11` or
+/// a chromosome name such as `chr1`; `X`, `Y`, and `M` are numbered `23` to `25`.
+fn chrom_number(accession: &str) -> Option<u32> {
+    let name = match accession.strip_prefix("NC_") {
+        Some(rest) => rest.split_once('.').map(|(name, _)| name).unwrap_or(rest),
+        None => accession.strip_prefix("chr").unwrap_or(accession),
+    };
+    match name {
+        "X" => Some(23),
+        "Y" => Some(24),
+        "M" | "MT" => Some(25),
+        _ => name.parse().ok().filter(|&num| (1..=25).contains(&num)),
+    }
+}
+
+/// Return the priority of a nucleic acid edit type.
+fn na_edit_priority(edit: &NaEdit) -> u8 {
+    match edit {
+        NaEdit::RefAlt {
+            reference,
+            alternative,
+        } if reference.len() <= 1 && alternative.len() == 1 => 0,
+        NaEdit::NumAlt { count, alternative } if *count == 1 && alternative.len() == 1 => 0,
+        NaEdit::RefAlt { alternative, .. } if alternative.is_empty() => 1,
+        NaEdit::DelRef { .. } | NaEdit::DelNum { .. } => 1,
+        NaEdit::Ins { .. } => 2,
+        NaEdit::Dup { .. } => 3,
+        NaEdit::RefAlt { .. } | NaEdit::NumAlt { .. } => 4,
+        NaEdit::InvRef { .. } | NaEdit::InvNum { .. } => 5,
+        NaEdit::Repeat { .. } => 6,
+    }
+}
+
+/// Return the priority of a protein edit type.
+fn prot_edit_priority(edit: &ProteinEdit) -> u8 {
+    match edit {
+        ProteinEdit::Subst { .. } => 0,
+        ProteinEdit::Del => 1,
+        ProteinEdit::Ins { .. } => 2,
+        ProteinEdit::Dup => 3,
+        ProteinEdit::DelIns { .. } => 4,
+        ProteinEdit::Fs { .. } => 5,
+        ProteinEdit::Ext { .. } => 6,
+        ProteinEdit::Ident => 7,
+    }
+}
+
+/// Return the priority of the variant type, alleles come last.
+fn variant_type_priority(var: &HgvsVariant) -> u8 {
+    match var {
+        HgvsVariant::GenomeVariant { .. } => 0,
+        HgvsVariant::MtVariant { .. } => 1,
+        HgvsVariant::CircularVariant { .. } => 2,
+        HgvsVariant::CdsVariant { .. } => 3,
+        HgvsVariant::TxVariant { .. } => 4,
+        HgvsVariant::RnaVariant { .. } => 5,
+        HgvsVariant::ProtVariant { .. } => 6,
+        HgvsVariant::Allele { .. } => 7,
+    }
+}
+
+impl HgvsVariant {
+    /// Return the key for sorting variants, see `VariantSortKey`.
+    ///
+    /// Alleles are sorted by their first variant.
+    pub fn sort_key(&self) -> VariantSortKey<'_> {
+        let variant_type = match self {
+            HgvsVariant::Allele { variants, .. } => {
+                return match variants.first() {
+                    Some(first) => VariantSortKey {
+                        accession: self.accession(),
+                        ..first.sort_key()
+                    },
+                    None => VariantSortKey {
+                        variant_type: u8::MAX,
+                        chrom: u32::MAX,
+                        accession: self.accession(),
+                        start: i64::MAX,
+                        end: i64::MAX,
+                        edit_type: u8::MAX,
+                    },
+                };
+            }
+            _ => variant_type_priority(self),
+        };
+        let chrom = match self {
+            HgvsVariant::GenomeVariant { accession, .. } => chrom_number(accession),
+            _ => None,
+        };
+        let range = self.loc_range();
+        let edit_type = match (self.na_edit(), self) {
+            (Some(edit), _) => na_edit_priority(edit),
+            (
+                None,
+                HgvsVariant::ProtVariant {
+                    loc_edit: ProtLocEdit::Ordinary { edit, .. },
+                    ..
+                },
+            ) => prot_edit_priority(edit.inner()),
+            (None, _) => u8::MAX,
+        };
+
+        VariantSortKey {
+            variant_type,
+            chrom: chrom.unwrap_or(u32::MAX),
+            accession: self.accession(),
+            start: range.as_ref().map_or(i64::MAX, |r| i64::from(r.start)),
+            end: range.as_ref().map_or(i64::MAX, |r| i64::from(r.end)),
+            edit_type,
+        }
+    }
+}
+
+impl PartialOrd for HgvsVariant {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Variants are grouped by type in the same order as for `sort_key()` but with alleles
+/// last.  Protein variants are then ordered by their `ProtLocEdit` and all other variants
+/// by their 0-based position (unknown positions last).  Ties are broken by the accession
+/// and then by the remaining fields.
+///
+/// In contrast to `sort_key()`, variants on different accessions are not grouped.
+impl Ord for HgvsVariant {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let position = |var: &HgvsVariant| {
+            let range = var.loc_range();
+            (range.is_none(), range.map(|range| (range.start, range.end)))
+        };
+
+        variant_type_priority(self)
+            .cmp(&variant_type_priority(other))
+            .then_with(|| match (self, other) {
+                (
+                    HgvsVariant::ProtVariant { loc_edit, .. },
+                    HgvsVariant::ProtVariant {
+                        loc_edit: other_loc_edit,
+                        ..
+                    },
+                ) => loc_edit.cmp(other_loc_edit),
+                (
+                    HgvsVariant::Allele { variants, .. },
+                    HgvsVariant::Allele {
+                        variants: other_variants,
+                        ..
+                    },
+                ) => variants.cmp(other_variants),
+                _ => position(self).cmp(&position(other)),
+            })
+            .then_with(|| self.accession().cmp(other.accession()))
+            .then_with(|| self.gene_symbol().cmp(other.gene_symbol()))
+            .then_with(|| cmp_na_loc_edits(self, other))
+    }
+}
+
+/// Compare the location edits of two nucleic acid variants of the same type.
+///
+/// Returns `Ordering::Equal` for other variants.
+fn cmp_na_loc_edits(lhs: &HgvsVariant, rhs: &HgvsVariant) -> Ordering {
+    match (lhs, rhs) {
+        (
+            HgvsVariant::GenomeVariant { loc_edit: lhs, .. },
+            HgvsVariant::GenomeVariant { loc_edit: rhs, .. },
+        )
+        | (
+            HgvsVariant::CircularVariant { loc_edit: lhs, .. },
+            HgvsVariant::CircularVariant { loc_edit: rhs, .. },
+        ) => lhs.cmp(rhs),
+        (
+            HgvsVariant::MtVariant { loc_edit: lhs, .. },
+            HgvsVariant::MtVariant { loc_edit: rhs, .. },
+        ) => lhs.cmp(rhs),
+        (
+            HgvsVariant::CdsVariant { loc_edit: lhs, .. },
+            HgvsVariant::CdsVariant { loc_edit: rhs, .. },
+        ) => lhs.cmp(rhs),
+        (
+            HgvsVariant::TxVariant { loc_edit: lhs, .. },
+            HgvsVariant::TxVariant { loc_edit: rhs, .. },
+        ) => lhs.cmp(rhs),
+        (
+            HgvsVariant::RnaVariant { loc_edit: lhs, .. },
+            HgvsVariant::RnaVariant { loc_edit: rhs, .. },
+        ) => lhs.cmp(rhs),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Sort `vars` by `HgvsVariant::sort_key()`; the order of variants with equal keys is kept.
+pub fn sort_variants(vars: &mut [HgvsVariant]) {
+    vars.sort_by(|lhs, rhs| lhs.sort_key().cmp(&rhs.sort_key()));
+}
+
+/// Return whether `vars` is sorted by `HgvsVariant::sort_key()`.
+pub fn is_sorted(vars: &[HgvsVariant]) -> bool {
+    vars.windows(2)
+        .all(|pair| pair[0].sort_key() <= pair[1].sort_key())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cmp::Ordering, str::FromStr};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{chrom_number, is_sorted, sort_variants};
+    use crate::parser::{Error, HgvsVariant};
+
+    #[test]
+    fn chrom_number_from_accession() {
+        assert_eq!(chrom_number("NC_000001.11"), Some(1));
+        assert_eq!(chrom_number("NC_000023.11"), Some(23));
+        assert_eq!(chrom_number("NC_000010"), Some(10));
+        assert_eq!(chrom_number("chr2"), Some(2));
+        assert_eq!(chrom_number("X"), Some(23));
+        assert_eq!(chrom_number("NC_012920.1"), None);
+        assert_eq!(chrom_number("NM_000088.3"), None);
+    }
+
+    #[test]
+    fn sort_mixed_variants() -> Result<(), Error> {
+        let expected = [
+            "NC_000002.12:g.100A>G",
+            "NC_000002.12:g.100del",
+            "NC_000002.12:g.100_101del",
+            "NC_000010.11:g.5C>T",
+            "NC_000023.11:g.1A>T",
+            "NC_012920.1:m.8993T>G",
+            "NM_000088.3:c.[1A>T;10_12del]",
+            "NM_000088.3:c.10A>T",
+            "NM_000088.3:c.10_11insA",
+            "NM_000088.3:c.589_600inv",
+            "NM_001166478.1:c.-5del",
+            "NR_000001.1:n.5G>C",
+            "NP_000079.2:p.Gly12Ala",
+            "NP_000079.2:p.Gly12del",
+            "NP_000079.2:p.Val400Ter",
+            "NP_000079.2:p.?",
+        ];
+        let mut vars = [
+            expected[8],
+            expected[12],
+            expected[3],
+            expected[15],
+            expected[0],
+            expected[9],
+            expected[5],
+            expected[13],
+            expected[2],
+            expected[11],
+            expected[4],
+            expected[10],
+            expected[7],
+            expected[1],
+            expected[14],
+            expected[6],
+        ]
+        .into_iter()
+        .map(HgvsVariant::from_str)
+        .collect::<Result<Vec<_>, _>>()?;
+        assert!(!is_sorted(&vars));
+
+        sort_variants(&mut vars);
+
+        assert!(is_sorted(&vars));
+        assert_eq!(
+            vars.iter().map(|var| format!("{var}")).collect::<Vec<_>>(),
+            expected
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ord_protein_variants() -> Result<(), Error> {
+        let expected = [
+            "NP_000079.2:p.0",
+            "NP_000079.2:p.0?",
+            "NP_000079.2:p.?",
+            "NP_000079.2:p.Met1?",
+            "NP_000079.2:p.=",
+            "NP_000079.2:p.(=)",
+            "NP_000078.1:p.Gly12Ala",
+            "NP_000079.2:p.Gly12Ala",
+            "NP_000079.2:p.Gly12_Ala14del",
+            "NP_000079.2:p.Val400Ter",
+        ];
+        let mut vars = [
+            expected[8],
+            expected[4],
+            expected[1],
+            expected[9],
+            expected[7],
+            expected[0],
+            expected[5],
+            expected[2],
+            expected[6],
+            expected[3],
+        ]
+        .into_iter()
+        .map(HgvsVariant::from_str)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        vars.sort_unstable();
+
+        assert_eq!(
+            vars.iter().map(|var| format!("{var}")).collect::<Vec<_>>(),
+            expected
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ord_consistent_with_eq() -> Result<(), Error> {
+        let lhs = HgvsVariant::from_str("NM_000088.3:c.10A>T")?;
+        let rhs = HgvsVariant::from_str("NM_000088.3:c.10A>G")?;
+        assert_ne!(lhs.cmp(&rhs), Ordering::Equal);
+        assert_eq!(lhs.cmp(&lhs.clone()), Ordering::Equal);
+        // Same position on a different accession sorts after.
+        let other_ac = HgvsVariant::from_str("NM_000089.1:c.10A>T")?;
+        assert_eq!(lhs.cmp(&other_ac), Ordering::Less);
+        // Protein variants sort after all nucleic acid variants.
+        let prot = HgvsVariant::from_str("NP_000079.2:p.Gly12Ala")?;
+        assert_eq!(lhs.cmp(&prot), Ordering::Less);
+
+        Ok(())
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
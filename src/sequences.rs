@@ -4,7 +4,11 @@
 
 use ahash::AHashMap;
 use md5::{Digest, Md5};
-use std::sync::LazyLock;
+use std::{collections::HashMap, sync::LazyLock};
+
+use crate::parser::{AA1, NA_IUPAC};
+
+pub use crate::static_data::MITOCHONDRIAL_GENOME_LENGTH;
 
 pub use crate::sequences::error::Error;
 
@@ -79,11 +83,90 @@ pub fn trim_common_suffixes(reference: &str, alternative: &str) -> (usize, Strin
     )
 }
 
-/// Reverse complementing shortcut.
+/// Reverse complementing shortcut, see `reverse_complement()`.
 pub fn revcomp(seq: &str) -> String {
-    std::str::from_utf8(&bio::alphabets::dna::revcomp(seq.as_bytes()))
-        .expect("invalid utf-8 encoding")
-        .to_string()
+    reverse_complement(seq)
+}
+
+/// Return the reverse complement of a DNA sequence.
+///
+/// The full IUPAC alphabet is supported and the case of the bases is kept, other characters
+/// are left unchanged.
+///
+/// # Example
+///
+/// ```
+/// use hgvs::sequences::reverse_complement;
+///
+/// assert_eq!(reverse_complement("AACGTN"), "NACGTT");
+/// assert_eq!(reverse_complement("RYKMbdhv"), "bdhvKMRY");
+/// ```
+pub fn reverse_complement(seq: &str) -> String {
+    String::from_utf8(reverse_complement_bytes(seq.as_bytes())).expect("invalid utf-8 encoding")
+}
+
+/// Return the reverse complement of a DNA sequence given as bytes.
+///
+/// This avoids the UTF-8 round trip of `reverse_complement()`.
+pub fn reverse_complement_bytes(seq: &[u8]) -> Vec<u8> {
+    seq.iter().rev().map(|&b| complement_byte(b)).collect()
+}
+
+/// Return the complement of a DNA sequence without reversing it.
+///
+/// As for `reverse_complement()`, the full IUPAC alphabet is supported.
+///
+/// # Example
+///
+/// ```
+/// use hgvs::sequences::complement;
+///
+/// assert_eq!(complement("AACGTN"), "TTGCAN");
+/// assert_eq!(complement("RYKMbdhv"), "YRMKvhdb");
+/// ```
+pub fn complement(seq: &str) -> String {
+    String::from_utf8(seq.bytes().map(complement_byte).collect()).expect("invalid utf-8 encoding")
+}
+
+/// Return the complement of a single IUPAC DNA base, keeping its case.
+///
+/// `S`, `W`, and `N` are their own complements, non-IUPAC bytes are returned unchanged.
+pub fn complement_byte(b: u8) -> u8 {
+    bio::alphabets::dna::complement(b)
+}
+
+/// Return the fraction of `G` and `C` bases (case-insensitive) in `seq`.
+///
+/// Ambiguous bases such as `N` count towards the length only.  Returns `f64::NAN` for an
+/// empty sequence.
+pub fn gc_content(seq: &str) -> f64 {
+    if seq.is_empty() {
+        return f64::NAN;
+    }
+    let gc = seq
+        .bytes()
+        .filter(|c| matches!(c.to_ascii_uppercase(), b'G' | b'C'))
+        .count();
+    gc as f64 / seq.len() as f64
+}
+
+/// Return whether `seq` consists of IUPAC DNA nucleotide codes only (case-insensitive).
+///
+/// In contrast to the HGVS parser, `U` is not accepted.
+pub fn is_valid_dna(seq: &str) -> bool {
+    seq.chars()
+        .all(|c| NA_IUPAC.contains(c) && !c.eq_ignore_ascii_case(&'U'))
+}
+
+/// Return whether `seq` consists of IUPAC RNA nucleotide codes only (case-insensitive).
+pub fn is_valid_rna(seq: &str) -> bool {
+    seq.chars()
+        .all(|c| "ACGURYMKWSBDHVN".contains(c.to_ascii_uppercase()))
+}
+
+/// Return whether `seq` consists of 1-letter amino acid codes and `*` (stop) only.
+pub fn is_valid_protein(seq: &str) -> bool {
+    seq.chars().all(|c| c == '*' || AA1.contains(c))
 }
 
 /// Allow selection of translation table.
@@ -107,6 +190,34 @@ pub enum TranslationTable {
     VertebrateMitochondrial,
 }
 
+/// Prefixes of the accessions of the human mitochondrial genome, i.e., the rCRS used by
+/// GRCh37.p13 and GRCh38 and the Yoruba sequence used by UCSC hg19.
+pub(crate) const MITOCHONDRIAL_ACCESSION_PREFIXES: &[&str] = &["NC_012920.", "NC_001807."];
+
+impl TranslationTable {
+    /// Return the translation table for a transcript aligned to `alt_ac`.
+    ///
+    /// Transcripts on the mitochondrial genome use the vertebrate mitochondrial code, all
+    /// others use the standard code, extended by selenocysteine if `is_selenoprotein`.
+    ///
+    /// # Args
+    ///
+    /// * `alt_ac` -- Accession of the contig the transcript is aligned to.
+    /// * `is_selenoprotein` -- Whether the transcript encodes a selenoprotein.
+    pub fn for_alt_ac(alt_ac: &str, is_selenoprotein: bool) -> Self {
+        if MITOCHONDRIAL_ACCESSION_PREFIXES
+            .iter()
+            .any(|prefix| alt_ac.starts_with(prefix))
+        {
+            TranslationTable::VertebrateMitochondrial
+        } else if is_selenoprotein {
+            TranslationTable::Selenocysteine
+        } else {
+            TranslationTable::Standard
+        }
+    }
+}
+
 /// Coerces string of 1- or 3-letter amino acids to 1-letter representation.
 ///
 /// Fails if the sequence is not of valid 3/1-letter amino acids.
@@ -205,6 +316,44 @@ pub fn aa3_to_aa1(seq: &str) -> Result<String, Error> {
     Ok(result)
 }
 
+/// Biochemical class of an amino acid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AminoAcidGroup {
+    /// Ala, Val, Ile, Leu, Met, Pro.
+    NonpolarAliphatic,
+    /// Phe, Trp, Tyr.
+    Aromatic,
+    /// Ser, Thr, Cys, Asn, Gln.
+    PolarUncharged,
+    /// Lys, Arg, His.
+    PositivelyCharged,
+    /// Asp, Glu.
+    NegativelyCharged,
+}
+
+/// Return the biochemical class of an amino acid given as 1- or 3-letter code.
+///
+/// Returns `None` for stop codons, ambiguity codes and amino acids without a class, i.e.,
+/// Gly, Sec and Pyl.
+///
+/// # Args
+///
+/// * `aa` -- A single amino acid, e.g., `"V"` or `"Val"`.
+pub fn amino_acid_group(aa: &str) -> Option<AminoAcidGroup> {
+    let aa1 = match aa.len() {
+        1 | 3 => aa_to_aa1(aa).ok()?,
+        _ => return None,
+    };
+    match aa1.as_str() {
+        "A" | "V" | "I" | "L" | "M" | "P" => Some(AminoAcidGroup::NonpolarAliphatic),
+        "F" | "W" | "Y" => Some(AminoAcidGroup::Aromatic),
+        "S" | "T" | "C" | "N" | "Q" => Some(AminoAcidGroup::PolarUncharged),
+        "K" | "R" | "H" => Some(AminoAcidGroup::PositivelyCharged),
+        "D" | "E" => Some(AminoAcidGroup::NegativelyCharged),
+        _ => None,
+    }
+}
+
 /// Indicates whether a string looks like a 3-letter AA string.
 ///
 /// # Args
@@ -410,6 +559,185 @@ pub fn translate_cds(
     Ok(result)
 }
 
+/// Translates a coding sequence into 1-letter amino acids, stopping at the first stop codon.
+///
+/// The stop codon itself is not part of the result.
+///
+/// # Args
+///
+/// * `cds` -- A DNA or RNA coding sequence, its length must be a multiple of three.
+/// * `table` -- Indicates which codon to amino acid translation table to use.
+///
+/// # Returns
+///
+/// The corresponding single letter amino acid sequence as ASCII bytes.
+pub fn translate(cds: &[u8], table: TranslationTable) -> Result<Vec<u8>, Error> {
+    if cds.len() % 3 != 0 {
+        return Err(Error::UntranslatableDnaLenth(cds.len()));
+    }
+
+    let mut translator = CodonTranslator::new(table);
+    let mut result = Vec::with_capacity(cds.len() / 3);
+    for codon in cds.chunks_exact(3) {
+        match translator.translate(codon)? {
+            b'*' => break,
+            aa => result.push(aa),
+        }
+    }
+    Ok(result)
+}
+
+/// Iterator over the complete codons of a sequence, see `reading_frame()`.
+pub struct ReadingFrameIterator<'a> {
+    chunks: std::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for ReadingFrameIterator<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next()
+    }
+}
+
+/// Return an iterator over the codons of `cds` in the reading frame starting at `start`.
+///
+/// Trailing bases that do not form a complete codon are skipped.
+///
+/// # Args
+///
+/// * `cds` -- A DNA or RNA sequence.
+/// * `start` -- The 0-based position of the first base of the first codon.
+pub fn reading_frame(cds: &[u8], start: usize) -> ReadingFrameIterator<'_> {
+    ReadingFrameIterator {
+        chunks: cds[start.min(cds.len())..].chunks_exact(3),
+    }
+}
+
+/// Return the 0-based position of the first base of the first stop codon in `seq`.
+///
+/// Only the codons in the reading frame starting at the first base are considered.  Codons
+/// that cannot be translated are skipped.
+///
+/// # Args
+///
+/// * `seq` -- A DNA or RNA sequence.
+/// * `table` -- Indicates which codon to amino acid translation table to use.
+pub fn find_stop_codon(seq: &[u8], table: TranslationTable) -> Option<usize> {
+    let mut translator = CodonTranslator::new(table);
+    reading_frame(seq, 0)
+        .position(|codon| matches!(translator.translate(codon), Ok(b'*')))
+        .map(|idx| idx * 3)
+}
+
+/// Return whether `cds` has a stop codon before the annotated stop codon.
+///
+/// # Args
+///
+/// * `cds` -- A DNA or RNA coding sequence.
+/// * `expected_cds_length` -- The annotated length of the CDS, including the stop codon.
+/// * `table` -- Indicates which codon to amino acid translation table to use.
+pub fn has_premature_stop(cds: &[u8], expected_cds_length: usize, table: TranslationTable) -> bool {
+    find_stop_codon(cds, table).is_some_and(|pos| pos + 3 < expected_cds_length)
+}
+
+/// Return the standard genetic code.
+///
+/// The result maps each of the 64 upper case DNA codons to its 1-letter amino acid with
+/// `b'*'` for stop codons.
+pub fn codon_table() -> HashMap<[u8; 3], u8> {
+    DNA_TO_AA1_LUT_VEC
+        .iter()
+        .map(|(dna, aa1)| (dna.as_bytes(), aa1.as_bytes()[0]))
+        .filter(|(dna, _)| dna.iter().all(|c| b"ACGT".contains(c)))
+        .map(|(dna, aa1)| ([dna[0], dna[1], dna[2]], aa1))
+        .collect()
+}
+
+/// Translate a single codon to its 1-letter amino acid.
+///
+/// # Args
+///
+/// * `codon` -- A DNA or RNA codon, upper or lower case.
+/// * `table` -- Indicates which codon to amino acid translation table to use.
+///
+/// # Returns
+///
+/// The amino acid as ASCII byte, `b'*'` for stop codons, or `None` if the codon contains
+/// `N` or other IUPAC ambiguity codes.
+pub fn translate_codon(codon: [u8; 3], table: TranslationTable) -> Option<u8> {
+    if !codon.iter().all(|c| b"ACGTUacgtu".contains(c)) {
+        return None;
+    }
+    CodonTranslator::new(table).translate(&codon).ok()
+}
+
+/// Return whether `codon` is a start codon in `table`.
+///
+/// The start codons are those of the NCBI genetic codes, i.e., `ATG`, `CTG`, and `TTG` for
+/// the standard code and `ATT`, `ATC`, `ATA`, `ATG`, and `GTG` for the vertebrate
+/// mitochondrial code.
+pub fn is_start_codon(codon: [u8; 3], table: TranslationTable) -> bool {
+    let start_codons: &[&[u8; 3]] = match table {
+        TranslationTable::Standard | TranslationTable::Selenocysteine => &[b"ATG", b"CTG", b"TTG"],
+        TranslationTable::VertebrateMitochondrial => &[b"ATT", b"ATC", b"ATA", b"ATG", b"GTG"],
+    };
+    let codon = codon.map(|c| DNA_ASCII_MAP[c as usize]);
+    start_codons.contains(&&codon)
+}
+
+/// Return all codons encoding the 1-letter amino acid `aa` in `table`.
+///
+/// Use `b'*'` for the stop codons.  The codons are upper case DNA in lexicographic order.
+pub fn synonymous_codons(aa: u8, table: TranslationTable) -> Vec<[u8; 3]> {
+    let aa = aa.to_ascii_uppercase();
+    let bases = *b"ACGT";
+    let mut result = Vec::new();
+    for first in bases {
+        for second in bases {
+            for third in bases {
+                let codon = [first, second, third];
+                if translate_codon(codon, table) == Some(aa) {
+                    result.push(codon);
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Return the 0-based position of a CDS base within its codon.
+///
+/// Bases upstream of the CDS (`c.-1` and smaller) continue the reading frame backwards,
+/// such that `c.-1` is at position 2.
+///
+/// # Args
+///
+/// * `cds_base` -- The 1-based position relative to the CDS start, e.g., `4` for `c.4`.
+pub fn reading_frame_offset(cds_base: i32) -> u8 {
+    cds_index(cds_base).rem_euclid(3) as u8
+}
+
+/// Return the 1-based number of the codon that a CDS base falls into.
+///
+/// Bases upstream of the CDS yield codon numbers below 1, i.e., `c.-3_-1` is codon `0`.
+///
+/// # Args
+///
+/// * `cds_base` -- The 1-based position relative to the CDS start, e.g., `4` for `c.4`.
+pub fn codon_number(cds_base: i32) -> i32 {
+    cds_index(cds_base).div_euclid(3) + 1
+}
+
+/// Convert CDS base to a 0-based index, there is no `c.0`.
+fn cds_index(cds_base: i32) -> i32 {
+    if cds_base > 0 {
+        cds_base - 1
+    } else {
+        cds_base
+    }
+}
+
 /// Converts sequence to normalized representation for hashing.
 ///
 /// Essentially, removes whitespace and asterisks, and uppercases the string.
@@ -567,6 +895,109 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn gc_content_examples() {
+        assert!(gc_content("").is_nan());
+        assert_eq!(gc_content("NNNN"), 0.0);
+        assert_eq!(gc_content("GCGC"), 1.0);
+        assert_eq!(gc_content("acgt"), 0.5);
+        assert_eq!(gc_content("ATGN"), 0.25);
+    }
+
+    #[test]
+    fn is_valid_sequence_examples() {
+        assert!(is_valid_dna(""));
+        assert!(is_valid_dna("NNNN"));
+        assert!(is_valid_dna("ACGTRYMKWSBDHVN"));
+        assert!(is_valid_dna("acgtn"));
+        assert!(!is_valid_dna("ACGU"));
+        assert!(!is_valid_dna("ACGX"));
+        assert!(!is_valid_dna("ACG T"));
+
+        assert!(is_valid_rna(""));
+        assert!(is_valid_rna("NNNN"));
+        assert!(is_valid_rna("acgun"));
+        assert!(!is_valid_rna("ACGT"));
+        assert!(!is_valid_rna("ACG-"));
+
+        assert!(is_valid_protein(""));
+        assert!(is_valid_protein("MKLV*"));
+        assert!(is_valid_protein("NNNN"));
+        assert!(!is_valid_protein("mklv"));
+        assert!(!is_valid_protein("MKJ"));
+        assert!(!is_valid_protein("Met"));
+    }
+
+    #[test]
+    fn reverse_complement_examples() {
+        assert_eq!(reverse_complement(""), "");
+        assert_eq!(reverse_complement("ACGTN"), "NACGT");
+        assert_eq!(reverse_complement("acgt"), "acgt");
+        assert_eq!(revcomp("AAC"), "GTT");
+    }
+
+    #[test]
+    fn complement_iupac() {
+        let pairs = [
+            (b'A', b'T'),
+            (b'C', b'G'),
+            (b'R', b'Y'),
+            (b'S', b'S'),
+            (b'W', b'W'),
+            (b'K', b'M'),
+            (b'B', b'V'),
+            (b'D', b'H'),
+            (b'N', b'N'),
+        ];
+        for (a, b) in pairs {
+            assert_eq!(complement_byte(a), b);
+            assert_eq!(complement_byte(b), a);
+            assert_eq!(
+                complement_byte(a.to_ascii_lowercase()),
+                b.to_ascii_lowercase()
+            );
+        }
+        assert_eq!(complement_byte(b'-'), b'-');
+
+        assert_eq!(complement("ACGTRYSWKMBDHVN"), "TGCAYRSWMKVHDBN");
+        assert_eq!(
+            reverse_complement_bytes(b"ACGTRYSWKMBDHVN"),
+            b"NBDHVKMWSRYACGT".to_vec()
+        );
+        for seq in ["", "ACGTRYSWKMBDHVN", "acgtnNRy"] {
+            assert_eq!(reverse_complement(&reverse_complement(seq)), seq);
+            assert_eq!(complement(&complement(seq)), seq);
+        }
+    }
+
+    #[test]
+    fn amino_acid_group_examples() {
+        assert_eq!(
+            amino_acid_group("Val"),
+            Some(AminoAcidGroup::NonpolarAliphatic)
+        );
+        assert_eq!(
+            amino_acid_group("I"),
+            Some(AminoAcidGroup::NonpolarAliphatic)
+        );
+        assert_eq!(amino_acid_group("Trp"), Some(AminoAcidGroup::Aromatic));
+        assert_eq!(amino_acid_group("N"), Some(AminoAcidGroup::PolarUncharged));
+        assert_eq!(
+            amino_acid_group("His"),
+            Some(AminoAcidGroup::PositivelyCharged)
+        );
+        assert_eq!(
+            amino_acid_group("E"),
+            Some(AminoAcidGroup::NegativelyCharged)
+        );
+        assert_eq!(amino_acid_group("Gly"), None);
+        assert_eq!(amino_acid_group("Ter"), None);
+        assert_eq!(amino_acid_group("*"), None);
+        assert_eq!(amino_acid_group("X"), None);
+        assert_eq!(amino_acid_group("Xaa"), None);
+        assert_eq!(amino_acid_group("ValIle"), None);
+    }
+
     #[test]
     fn translate_cds_examples() -> Result<(), Error> {
         assert_eq!(
@@ -623,6 +1054,256 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn reading_frame_codons() {
+        assert_eq!(
+            reading_frame(b"ATGCGATA", 0).collect::<Vec<_>>(),
+            vec![b"ATG", b"CGA"]
+        );
+        assert_eq!(
+            reading_frame(b"ATGCGATA", 2).collect::<Vec<_>>(),
+            vec![b"GCG", b"ATA"]
+        );
+        assert_eq!(reading_frame(b"ATG", 5).count(), 0);
+    }
+
+    #[test]
+    fn find_stop_codon_examples() {
+        assert_eq!(find_stop_codon(b"", TranslationTable::Standard), None);
+        assert_eq!(find_stop_codon(b"ATGCGA", TranslationTable::Standard), None);
+        assert_eq!(
+            find_stop_codon(b"ATGTAACGATGA", TranslationTable::Standard),
+            Some(3)
+        );
+        // The out-of-frame `TAA` is ignored.
+        assert_eq!(
+            find_stop_codon(b"ATAACGTGA", TranslationTable::Standard),
+            Some(6)
+        );
+        // `TGA` codes for tryptophan in the mitochondrial code.
+        assert_eq!(
+            find_stop_codon(b"ATGTGAAGA", TranslationTable::VertebrateMitochondrial),
+            Some(6)
+        );
+
+        assert!(has_premature_stop(
+            b"ATGTAACGATGA",
+            12,
+            TranslationTable::Standard
+        ));
+        assert!(!has_premature_stop(
+            b"ATGCGATGA",
+            9,
+            TranslationTable::Standard
+        ));
+        assert!(!has_premature_stop(
+            b"ATGCGA",
+            6,
+            TranslationTable::Standard
+        ));
+    }
+
+    #[test]
+    fn find_stop_codon_sanity_transcripts() -> Result<(), anyhow::Error> {
+        #[derive(serde::Deserialize)]
+        struct Record {
+            transcript_sequence: String,
+            cds_start_i: usize,
+            cds_end_i: usize,
+        }
+
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .from_path("tests/data/mapper/sanity_cp.tsv")?;
+        for record in rdr.deserialize() {
+            let record: Record = record?;
+            let cds = &record.transcript_sequence.as_bytes()[record.cds_start_i..record.cds_end_i];
+            let cds_len = record.cds_end_i - record.cds_start_i;
+
+            assert_eq!(
+                find_stop_codon(cds, TranslationTable::Standard),
+                Some(cds_len - 3)
+            );
+            assert!(!has_premature_stop(
+                cds,
+                cds_len,
+                TranslationTable::Standard
+            ));
+            assert_eq!(reading_frame(cds, 0).count(), cds_len / 3);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn translate_examples() -> Result<(), Error> {
+        assert_eq!(translate(b"", TranslationTable::Standard)?, b"");
+        assert_eq!(translate(b"ATGCGA", TranslationTable::Standard)?, b"MR");
+        assert_eq!(
+            translate(b"AUGCGATAACGA", TranslationTable::Standard)?,
+            b"MR"
+        );
+        // TGA, ATA, AGA, and AGG differ between the standard and mitochondrial code.
+        assert_eq!(
+            translate(b"ATGATAAGGTGA", TranslationTable::Standard)?,
+            b"MIR"
+        );
+        assert_eq!(
+            translate(
+                b"ATGTGAATAAGAAGG",
+                TranslationTable::VertebrateMitochondrial
+            )?,
+            b"MWM"
+        );
+        // Start of the MT-ND1 CDS (NC_012920.1:3307-3324).
+        assert_eq!(
+            translate(
+                b"ATACCCATGGCCAACCTC",
+                TranslationTable::VertebrateMitochondrial
+            )?,
+            b"MPMANL"
+        );
+        assert_eq!(
+            translate(b"ATACCCATGGCCAACCTC", TranslationTable::Standard)?,
+            b"IPMANL"
+        );
+        assert!(translate(b"ATGCG", TranslationTable::Standard).is_err());
+        assert!(translate(b"AUGCGQ", TranslationTable::Standard).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn codon_table_standard() {
+        let table = codon_table();
+        assert_eq!(table.len(), 64);
+        assert_eq!(table[b"ATG"], b'M');
+        assert_eq!(table[b"TGG"], b'W');
+        for stop in [b"TAA", b"TAG", b"TGA"] {
+            assert_eq!(table[stop], b'*');
+        }
+    }
+
+    #[test]
+    fn translate_codon_all_tables() {
+        for (codon, aa) in codon_table() {
+            assert_eq!(translate_codon(codon, TranslationTable::Standard), Some(aa));
+            let rna = codon.map(|c| {
+                if c == b'T' {
+                    b'u'
+                } else {
+                    c.to_ascii_lowercase()
+                }
+            });
+            assert_eq!(translate_codon(rna, TranslationTable::Standard), Some(aa));
+
+            let expected_mt = match &codon {
+                b"TGA" => b'W',
+                b"ATA" => b'M',
+                b"AGA" | b"AGG" => b'*',
+                _ => aa,
+            };
+            assert_eq!(
+                translate_codon(codon, TranslationTable::VertebrateMitochondrial),
+                Some(expected_mt),
+                "{:?}",
+                std::str::from_utf8(&codon)
+            );
+        }
+
+        assert_eq!(
+            translate_codon(*b"TGA", TranslationTable::Selenocysteine),
+            Some(b'U')
+        );
+        assert_eq!(translate_codon(*b"CTN", TranslationTable::Standard), None);
+        assert_eq!(translate_codon(*b"AAR", TranslationTable::Standard), None);
+        assert_eq!(translate_codon(*b"AQA", TranslationTable::Standard), None);
+    }
+
+    #[test]
+    fn start_codons() {
+        assert!(is_start_codon(*b"ATG", TranslationTable::Standard));
+        assert!(is_start_codon(*b"aug", TranslationTable::Standard));
+        assert!(is_start_codon(*b"CTG", TranslationTable::Standard));
+        assert!(!is_start_codon(*b"ATA", TranslationTable::Standard));
+        assert!(is_start_codon(
+            *b"ATA",
+            TranslationTable::VertebrateMitochondrial
+        ));
+        assert!(is_start_codon(
+            *b"GTG",
+            TranslationTable::VertebrateMitochondrial
+        ));
+        assert!(!is_start_codon(
+            *b"CTG",
+            TranslationTable::VertebrateMitochondrial
+        ));
+    }
+
+    #[test]
+    fn synonymous_codons_tables() {
+        let standard = TranslationTable::Standard;
+        let mt = TranslationTable::VertebrateMitochondrial;
+
+        assert_eq!(synonymous_codons(b'M', standard), vec![*b"ATG"]);
+        assert_eq!(synonymous_codons(b'M', mt), vec![*b"ATA", *b"ATG"]);
+        assert_eq!(synonymous_codons(b'W', standard), vec![*b"TGG"]);
+        assert_eq!(synonymous_codons(b'w', mt), vec![*b"TGA", *b"TGG"]);
+        assert_eq!(
+            synonymous_codons(b'*', standard),
+            vec![*b"TAA", *b"TAG", *b"TGA"]
+        );
+        assert_eq!(
+            synonymous_codons(b'*', mt),
+            vec![*b"AGA", *b"AGG", *b"TAA", *b"TAG"]
+        );
+        assert_eq!(synonymous_codons(b'L', standard).len(), 6);
+        assert_eq!(synonymous_codons(b'R', mt).len(), 4);
+        assert!(synonymous_codons(b'X', standard).is_empty());
+
+        let total: usize = b"ACDEFGHIKLMNPQRSTVWY*"
+            .iter()
+            .map(|aa| synonymous_codons(*aa, standard).len())
+            .sum();
+        assert_eq!(total, 64);
+    }
+
+    #[test]
+    fn translation_table_for_alt_ac() {
+        assert_eq!(
+            TranslationTable::for_alt_ac("NC_012920.1", false),
+            TranslationTable::VertebrateMitochondrial
+        );
+        assert_eq!(
+            TranslationTable::for_alt_ac("NC_001807.4", false),
+            TranslationTable::VertebrateMitochondrial
+        );
+        assert_eq!(
+            TranslationTable::for_alt_ac("NC_000001.11", false),
+            TranslationTable::Standard
+        );
+        assert_eq!(
+            TranslationTable::for_alt_ac("NC_000001.11", true),
+            TranslationTable::Selenocysteine
+        );
+    }
+
+    #[test]
+    fn reading_frame_offset_examples() {
+        assert_eq!(reading_frame_offset(1), 0);
+        assert_eq!(reading_frame_offset(2), 1);
+        assert_eq!(reading_frame_offset(3), 2);
+        assert_eq!(reading_frame_offset(4), 0);
+        assert_eq!(reading_frame_offset(-1), 2);
+        assert_eq!(reading_frame_offset(-3), 0);
+
+        assert_eq!(codon_number(1), 1);
+        assert_eq!(codon_number(3), 1);
+        assert_eq!(codon_number(4), 2);
+        assert_eq!(codon_number(-1), 0);
+        assert_eq!(codon_number(-4), -1);
+    }
+
     #[test]
     fn seq_md5_examples() -> Result<(), Error> {
         assert_eq!(seq_md5("", true)?, "d41d8cd98f00b204e9800998ecf8427e");
@@ -0,0 +1,244 @@
+//! Lookups on the assembly information embedded in `biocommons_bioutils`.
+//!
+//! The types are defined in another crate, so the functionality is provided by extension
+//! traits that need to be imported, e.g., `use hgvs::static_data::AssemblyExt`.
+
+use biocommons_bioutils::assemblies::{Assembly, Info, ASSEMBLY_INFOS};
+
+/// Assemblies in the order they are searched by `get_assembly_for_accession()`.
+const ACCESSION_SEARCH_ORDER: [Assembly; 3] =
+    [Assembly::Grch38, Assembly::Grch37, Assembly::Grch37p10];
+
+/// Return the assembly that contains the sequence with the RefSeq or GenBank accession `ac`,
+/// e.g., `NC_000001.10` or `CM000663.2`.
+///
+/// Sequences that are part of several assemblies resolve to the most recent one, e.g.,
+/// `NC_012920.1` (chrMT) to GRCh38.  As GRCh37.p10 includes all sequences of GRCh37,
+/// `Assembly::Grch37p10` is only returned for sequences that were added by the patch release.
+pub fn get_assembly_for_accession(ac: &str) -> Option<Assembly> {
+    ACCESSION_SEARCH_ORDER.into_iter().find(|assembly| {
+        ASSEMBLY_INFOS[*assembly]
+            .sequences
+            .iter()
+            .any(|record| record.refseq_ac == ac || record.genbank_ac == ac)
+    })
+}
+
+/// Conversion between `Assembly` values and assembly names.
+pub trait AssemblyExt: Sized {
+    /// Return the assembly for a UCSC genome name, i.e., `"hg19"` or `"hg38"`.
+    ///
+    /// `"hg19"` is resolved to `Assembly::Grch37`.
+    fn from_ucsc_name(name: &str) -> Option<Self>;
+
+    /// Return the UCSC genome name, i.e., `"hg19"` or `"hg38"`.
+    fn to_ucsc_name(&self) -> &'static str;
+
+    /// Return the assembly for a GRC or UCSC name, e.g., `"GRCh37.p10"` or `"hg38"`.
+    ///
+    /// The comparison is case-insensitive.
+    fn from_name(name: &str) -> Option<Self>;
+
+    /// Return the assembly containing the sequence `ac`, see `get_assembly_for_accession()`.
+    fn for_accession(ac: &str) -> Option<Self>;
+}
+
+impl AssemblyExt for Assembly {
+    fn from_ucsc_name(name: &str) -> Option<Self> {
+        match name {
+            "hg19" => Some(Assembly::Grch37),
+            "hg38" => Some(Assembly::Grch38),
+            _ => None,
+        }
+    }
+
+    fn to_ucsc_name(&self) -> &'static str {
+        match self {
+            Assembly::Grch37 | Assembly::Grch37p10 => "hg19",
+            Assembly::Grch38 => "hg38",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "grch37" => Some(Assembly::Grch37),
+            "grch37.p10" => Some(Assembly::Grch37p10),
+            "grch38" => Some(Assembly::Grch38),
+            name => Self::from_ucsc_name(name),
+        }
+    }
+
+    fn for_accession(ac: &str) -> Option<Self> {
+        get_assembly_for_accession(ac)
+    }
+}
+
+/// RefSeq accession of the mitochondrial sequence that is called `chrM` in UCSC hg19.
+///
+/// GRCh37 uses the revised Cambridge Reference Sequence `NC_012920.1` for `MT` but hg19
+/// kept the older `NC_001807.4` for `chrM`.
+pub const HG19_CHRM_ACCESSION: &str = "NC_001807.4";
+
+/// Length of the human mitochondrial genome (rCRS, `NC_012920.1`).
+pub const MITOCHONDRIAL_GENOME_LENGTH: i32 = 16569;
+
+/// Lookup of sequences in the assembly information.
+pub trait InfoExt {
+    /// Return the RefSeq accession of the chromosome `chrom`.
+    ///
+    /// Both `"1"` and `"chr1"` are accepted.  `"MT"` is the mitochondrial genome of the
+    /// assembly while `"M"` and `"chrM"` follow UCSC and resolve to `HG19_CHRM_ACCESSION`
+    /// on GRCh37.
+    fn accession_for_chrom(&self, chrom: &str) -> Option<&str>;
+}
+
+impl InfoExt for Info {
+    fn accession_for_chrom(&self, chrom: &str) -> Option<&str> {
+        let name = chrom.strip_prefix("chr").unwrap_or(chrom);
+        let name = if name == "M" {
+            if self.name.starts_with("GRCh37") {
+                return Some(HG19_CHRM_ACCESSION);
+            }
+            "MT"
+        } else {
+            name
+        };
+        self.sequences
+            .iter()
+            .find(|record| record.name == name)
+            .map(|record| record.refseq_ac.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use biocommons_bioutils::assemblies::{Assembly, ASSEMBLY_INFOS};
+    use pretty_assertions::assert_eq;
+
+    use super::{get_assembly_for_accession, AssemblyExt, InfoExt};
+
+    #[test]
+    fn ucsc_name_roundtrip() {
+        assert_eq!(Assembly::Grch37.to_ucsc_name(), "hg19");
+        assert_eq!(Assembly::Grch37p10.to_ucsc_name(), "hg19");
+        assert_eq!(Assembly::Grch38.to_ucsc_name(), "hg38");
+        for assembly in [Assembly::Grch37, Assembly::Grch37p10, Assembly::Grch38] {
+            let roundtrip =
+                Assembly::from_ucsc_name(assembly.to_ucsc_name()).expect("UCSC name must resolve");
+            assert_eq!(roundtrip.to_ucsc_name(), assembly.to_ucsc_name());
+        }
+        assert_eq!(Assembly::from_ucsc_name("hg18"), None);
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(Assembly::from_name("GRCh37"), Some(Assembly::Grch37));
+        assert_eq!(Assembly::from_name("GRCh37.p10"), Some(Assembly::Grch37p10));
+        assert_eq!(Assembly::from_name("grch38"), Some(Assembly::Grch38));
+        assert_eq!(Assembly::from_name("hg19"), Some(Assembly::Grch37));
+        assert_eq!(Assembly::from_name("hg38"), Some(Assembly::Grch38));
+        assert_eq!(Assembly::from_name("GRCh36"), None);
+    }
+
+    #[test]
+    fn assembly_for_accession() {
+        let info_38 = &ASSEMBLY_INFOS[Assembly::Grch38];
+        let mut count = 0;
+        for record in &ASSEMBLY_INFOS[Assembly::Grch37].sequences {
+            if !record.refseq_ac.starts_with("NC_")
+                || info_38
+                    .sequences
+                    .iter()
+                    .any(|r| r.refseq_ac == record.refseq_ac)
+            {
+                continue;
+            }
+            count += 1;
+            assert_eq!(
+                get_assembly_for_accession(&record.refseq_ac),
+                Some(Assembly::Grch37),
+                "{}",
+                &record.refseq_ac
+            );
+            if !record.genbank_ac.is_empty() {
+                assert_eq!(
+                    Assembly::for_accession(&record.genbank_ac),
+                    Some(Assembly::Grch37),
+                    "{}",
+                    &record.genbank_ac
+                );
+            }
+        }
+        assert!(count >= 24);
+
+        assert_eq!(
+            get_assembly_for_accession("NC_000001.11"),
+            Some(Assembly::Grch38)
+        );
+        assert_eq!(
+            get_assembly_for_accession("NC_012920.1"),
+            Some(Assembly::Grch38)
+        );
+        assert_eq!(get_assembly_for_accession("NC_000001.12"), None);
+        assert_eq!(Assembly::for_accession("NM_000088.3"), None);
+    }
+
+    #[test]
+    fn accession_for_chrom() {
+        // Accession versions of chromosomes 1-22, X, and Y.
+        let versions_37 = [
+            10, 11, 11, 11, 9, 11, 13, 10, 11, 10, 9, 11, 10, 8, 9, 9, 10, 9, 9, 10, 8, 10, 10, 9,
+        ];
+        let versions_38 = [
+            11, 12, 12, 12, 10, 12, 14, 11, 12, 11, 10, 12, 11, 9, 10, 10, 11, 10, 10, 11, 9, 11,
+            11, 10,
+        ];
+        for (assembly, versions) in [
+            (Assembly::Grch37, versions_37),
+            (Assembly::Grch37p10, versions_37),
+            (Assembly::Grch38, versions_38),
+        ] {
+            let info = &ASSEMBLY_INFOS[assembly];
+            for (i, version) in versions.iter().enumerate() {
+                let chrom = match i + 1 {
+                    23 => String::from("X"),
+                    24 => String::from("Y"),
+                    n => n.to_string(),
+                };
+                let expected = format!("NC_{:06}.{}", i + 1, version);
+                assert_eq!(info.accession_for_chrom(&chrom), Some(expected.as_str()));
+                assert_eq!(
+                    info.accession_for_chrom(&format!("chr{}", chrom)),
+                    Some(expected.as_str())
+                );
+            }
+            assert_eq!(info.accession_for_chrom("MT"), Some("NC_012920.1"));
+            assert_eq!(info.accession_for_chrom("chr99"), None);
+        }
+        for (assembly, expected) in [
+            (Assembly::Grch37, "NC_001807.4"),
+            (Assembly::Grch37p10, "NC_001807.4"),
+            (Assembly::Grch38, "NC_012920.1"),
+        ] {
+            let info = &ASSEMBLY_INFOS[assembly];
+            assert_eq!(info.accession_for_chrom("chrM"), Some(expected));
+            assert_eq!(info.accession_for_chrom("M"), Some(expected));
+        }
+    }
+}
+
+// <LICENSE>
+// Copyright 2023 hgvs-rs Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+// </LICENSE>
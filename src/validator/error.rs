@@ -13,13 +13,58 @@ pub enum Error {
     NumAltBasesEmpty(String),
     #[error("number of inverted bases must be positive in {0}")]
     NumInvBasesNotPositive(String),
+    #[error("number of repeat units must be positive in {0}")]
+    NumRepeatUnitsNotPositive(String),
+    #[error("inserted sequence must be non-empty in {0}")]
+    EmptyInsertion(String),
 
     #[error("Length implied by coordinates must equal count: {0}")]
     ImpliedLengthMismatch(String),
+    #[error("Length implied by coordinates must be a multiple of the repeat unit: {0}")]
+    RepeatUnitLengthMismatch(String),
     #[error("start must be >=1 in {0}")]
     StartMustBePositive(String),
     #[error("end must be >=1 in {0}")]
     EndMustBePositive(String),
     #[error("sart <= end must hold in {0}")]
     StartMustBeLessThanEnd(String),
+    #[error("start position must not be after end position in {0}")]
+    InvalidInterval(String),
+    #[error("position {position} is outside of the range {min}..={max}")]
+    PositionOutOfRange { position: i32, min: i32, max: i32 },
+
+    #[error("variant is outside of the transcript bounds: {0}")]
+    TxBoundsViolation(String),
+    #[error("position {position} is {direction} the bound {bound}")]
+    CdsBoundsViolation {
+        position: i32,
+        bound: i32,
+        direction: BoundDirection,
+    },
+    #[error("variant reference ({found}) does not agree with reference sequence ({expected})")]
+    ReferenceMismatch { expected: String, found: String },
+    #[error("protein reference does not agree with sequence: {0}")]
+    ProteinReferenceError(String),
+    #[error("problem accessing data")]
+    DataError(#[from] crate::data::error::Error),
+    #[error("mapping failed")]
+    MappingFailed(#[source] Box<crate::mapper::Error>),
+}
+
+/// Direction in which a position violates a bound, see `Error::CdsBoundsViolation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundDirection {
+    /// The position lies before the first allowed position.
+    Before,
+    /// The position lies after the last allowed position.
+    After,
+}
+
+impl std::fmt::Display for BoundDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundDirection::Before => write!(f, "before"),
+            BoundDirection::After => write!(f, "after"),
+        }
+    }
 }
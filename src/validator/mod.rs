@@ -6,11 +6,12 @@ use std::sync::Arc;
 
 use log::{error, warn};
 
-pub use crate::validator::error::Error;
+pub use crate::validator::error::{BoundDirection, Error};
 use crate::{
     data::interface::Provider,
-    mapper::{variant::Config, variant::Mapper},
-    parser::HgvsVariant,
+    mapper::{altseq::RefTranscriptData, variant::Config, variant::Mapper},
+    parser::{CdsFrom, HgvsVariant, NaEdit, ProtLocEdit},
+    sequences::aa_to_aa1,
 };
 
 /// Trait for validating of variants, locations etc.
@@ -44,7 +45,9 @@ impl ValidationLevel {
 }
 
 /// Trait for validators.
-pub trait Validator {
+///
+/// Validators are shared behind an `Arc` and thus must be `Send + Sync`.
+pub trait Validator: Send + Sync {
     /// Return whether validation is strict.
     ///
     /// Validation is strict if errors cause `Err` results rather than just logging a warning.
@@ -82,10 +85,21 @@ impl Validator for NullValidator {
     }
 }
 
+/// Positions from which on `IntrinsicValidator` warns that they are close to `i32::MAX`, the
+/// largest supported position.
+pub const LARGE_POSITION_THRESHOLD: i64 = 2_000_000_000;
+
+/// Return whether the location of `var` ends at or after `LARGE_POSITION_THRESHOLD`.
+fn is_near_position_limit(var: &HgvsVariant) -> bool {
+    var.loc_range_i64()
+        .is_some_and(|range| range.end >= LARGE_POSITION_THRESHOLD)
+}
+
 /// A validator that only performs intrinsic validation.
 ///
 /// This means that only the variant description itself is checked without considering the
-/// actual sequence.
+/// actual sequence.  Positions close to the largest supported position `i32::MAX` are
+/// reported as a warning but do not fail validation.
 pub struct IntrinsicValidator {
     strict: bool,
 }
@@ -102,6 +116,14 @@ impl Validator for IntrinsicValidator {
     }
 
     fn validate(&self, var: &HgvsVariant) -> Result<(), Error> {
+        if is_near_position_limit(var) {
+            warn!(
+                "Position of {} is close to the largest supported position {}",
+                var,
+                i32::MAX
+            );
+        }
+
         let res = var.validate();
         match (&res, self.is_strict()) {
             (Ok(_), _) => Ok(()),
@@ -120,7 +142,6 @@ impl Validator for IntrinsicValidator {
 /// Attempts to determine if the HGVS name validates against external data sources
 pub struct ExtrinsicValidator {
     strict: bool,
-    #[allow(dead_code)]
     mapper: Mapper,
 }
 
@@ -134,6 +155,7 @@ impl ExtrinsicValidator {
             strict_bounds: true,
             renormalize_g: false,
             genome_seq_available: true,
+            ..Default::default()
         };
         Self {
             strict,
@@ -148,6 +170,11 @@ impl Validator for ExtrinsicValidator {
     }
 
     fn validate(&self, var: &HgvsVariant) -> Result<(), Error> {
+        // Validate alleles variant by variant.
+        if let HgvsVariant::Allele { variants, .. } = var {
+            return variants.iter().try_for_each(|v| self.validate(v));
+        }
+
         // Check transcripts bounds
         match var {
             HgvsVariant::CdsVariant { .. } | HgvsVariant::TxVariant { .. } => {
@@ -190,21 +217,224 @@ impl Validator for ExtrinsicValidator {
             }
         }
 
+        // Check protein reference.
+        {
+            let res = self.check_prot_ref(var);
+            if res.is_err() {
+                if self.is_strict() {
+                    error!("Validation of {} failed: {:?}", var, res);
+                    return res;
+                } else {
+                    warn!("Validation of {} failed: {:?}", var, res);
+                }
+            }
+        }
+
         Ok(())
     }
 }
 
 impl ExtrinsicValidator {
-    fn check_tx_bound(&self, _var: &HgvsVariant) -> Result<(), Error> {
-        Ok(()) // TODO
+    /// Project `c.` variants to `n.` variants, return all other variants unchanged.
+    fn c_to_n_if_cds(&self, var: &HgvsVariant) -> Result<HgvsVariant, Error> {
+        match var {
+            HgvsVariant::CdsVariant { .. } => match self.mapper.c_to_n(var) {
+                Ok(var_n) => Ok(var_n),
+                Err(crate::mapper::Error::CoordinateOutOfBounds(_)) => {
+                    Err(Error::TxBoundsViolation(format!("{}", var)))
+                }
+                Err(e) => Err(Error::MappingFailed(Box::new(e))),
+            },
+            _ => Ok(var.clone()),
+        }
+    }
+
+    /// Check that the positions of a `c.` or `n.` variant are within the transcript.
+    fn check_tx_bound(&self, var: &HgvsVariant) -> Result<(), Error> {
+        let var_n = match var {
+            HgvsVariant::CdsVariant { .. } | HgvsVariant::TxVariant { .. } => {
+                self.c_to_n_if_cds(var)?
+            }
+            _ => return Ok(()),
+        };
+
+        if let HgvsVariant::TxVariant {
+            accession,
+            loc_edit,
+            ..
+        } = &var_n
+        {
+            let id_info = self
+                .mapper
+                .provider()
+                .get_tx_identity_info(&accession.value)?;
+            let tx_len: i32 = id_info.lengths.iter().sum();
+            let loc = loc_edit.loc.inner();
+            if [&loc.start, &loc.end]
+                .iter()
+                .any(|pos| pos.base < 1 || pos.base > tx_len)
+            {
+                return Err(Error::TxBoundsViolation(format!("{}", var)));
+            }
+        }
+
+        Ok(())
     }
 
-    fn check_cds_bound(&self, _var: &HgvsVariant) -> Result<(), Error> {
-        Ok(()) // TODO
+    /// Check that the positions of a `c.` variant are within the region they refer to.
+    ///
+    /// Positions `c.1`, `c.2`, ... must be within the CDS, positions `c.-1`, `c.-2`, ...
+    /// within the 5' UTR, and positions `c.*1`, `c.*2`, ... within the 3' UTR.
+    fn check_cds_bound(&self, var: &HgvsVariant) -> Result<(), Error> {
+        let (accession, loc_edit) = match var {
+            HgvsVariant::CdsVariant {
+                accession,
+                loc_edit,
+                ..
+            } => (accession, loc_edit),
+            _ => return Ok(()),
+        };
+
+        let id_info = self
+            .mapper
+            .provider()
+            .get_tx_identity_info(&accession.value)?;
+        let tx_len: i32 = id_info.lengths.iter().sum();
+        let loc = loc_edit.loc.inner();
+        for pos in [&loc.start, &loc.end] {
+            let (bound, direction) = match pos.cds_from {
+                CdsFrom::Start if pos.base < 0 => (-id_info.cds_start_i, BoundDirection::Before),
+                CdsFrom::Start => (
+                    id_info.cds_end_i - id_info.cds_start_i,
+                    BoundDirection::After,
+                ),
+                CdsFrom::End => (tx_len - id_info.cds_end_i, BoundDirection::After),
+            };
+            let violated = match direction {
+                BoundDirection::Before => pos.base < bound,
+                BoundDirection::After => pos.base > bound,
+            };
+            if violated {
+                return Err(Error::CdsBoundsViolation {
+                    position: pos.base,
+                    bound,
+                    direction,
+                });
+            }
+        }
+
+        Ok(())
     }
 
-    fn check_ref(&self, _var: &HgvsVariant) -> Result<(), Error> {
-        Ok(()) // TODO
+    /// Check that the reference bases given in the variant agree with the sequence.
+    ///
+    /// Insertions, edits without explicit reference bases, and intronic variants are
+    /// not checked.
+    fn check_ref(&self, var: &HgvsVariant) -> Result<(), Error> {
+        let na_edit = match var.na_edit() {
+            Some(na_edit) => na_edit,
+            None => return Ok(()),
+        };
+        let found = match na_edit {
+            NaEdit::RefAlt { reference, .. }
+            | NaEdit::DelRef { reference }
+            | NaEdit::Dup { reference }
+            | NaEdit::InvRef { reference } => reference,
+            NaEdit::Ins { .. }
+            | NaEdit::NumAlt { .. }
+            | NaEdit::DelNum { .. }
+            | NaEdit::InvNum { .. }
+            | NaEdit::Repeat { .. } => return Ok(()),
+        };
+        if found.is_empty() || var.spans_intron() {
+            return Ok(());
+        }
+
+        let var_x = self.c_to_n_if_cds(var)?;
+        let range = match var_x.loc_range_i64() {
+            Some(range) if range.start >= 0 && range.start < range.end => range,
+            _ => return Ok(()),
+        };
+        let seq = self.mapper.provider().get_seq_part_i64(
+            &var_x.accession().value,
+            Some(range.start),
+            Some(range.end),
+        )?;
+        let expected = if let HgvsVariant::RnaVariant { .. } = var {
+            seq.to_lowercase().replace('t', "u")
+        } else {
+            seq
+        };
+
+        if na_edit.reference_equals(&expected) {
+            Ok(())
+        } else {
+            Err(Error::ReferenceMismatch {
+                expected,
+                found: found.clone(),
+            })
+        }
+    }
+
+    /// Fetch the amino acid sequence of the protein `pro_ac`.
+    ///
+    /// Falls back to translating the coding sequence of the transcript encoding the
+    /// protein if the provider does not serve the protein sequence itself.
+    fn get_prot_seq(&self, pro_ac: &str) -> Result<String, Error> {
+        let provider = self.mapper.provider();
+        match provider.get_seq(pro_ac) {
+            Ok(seq) => Ok(seq),
+            Err(e) => match provider.get_tx_ac_for_pro_ac(pro_ac)? {
+                Some(tx_ac) => RefTranscriptData::new(provider, &tx_ac, Some(pro_ac))
+                    .map(|data| data.aa_sequence)
+                    .map_err(|e| Error::MappingFailed(Box::new(e))),
+                None => Err(Error::DataError(e)),
+            },
+        }
+    }
+
+    /// Check that the amino acids given in a `p.` variant agree with the protein sequence.
+    ///
+    /// Only variants with an explicit location are checked, e.g., `p.?` and `p.Met1?`
+    /// are skipped.
+    fn check_prot_ref(&self, var: &HgvsVariant) -> Result<(), Error> {
+        let (accession, loc) = match var {
+            HgvsVariant::ProtVariant {
+                accession,
+                loc_edit: ProtLocEdit::Ordinary { loc, .. },
+                ..
+            } => (accession, loc.inner()),
+            _ => return Ok(()),
+        };
+
+        let seq = self.get_prot_seq(&accession.value)?;
+        // The stop codon may or may not be part of the sequence.
+        let residues = seq.trim_end_matches('*');
+        let len = residues.len() as i32;
+        for pos in [&loc.start, &loc.end] {
+            let aa = aa_to_aa1(&pos.aa).map_err(|e| {
+                Error::ProteinReferenceError(format!("{}{}: {}", pos.aa, pos.number, e))
+            })?;
+            if aa == "*" && pos.number == len + 1 {
+                continue;
+            }
+            if pos.number < 1 || pos.number > len {
+                return Err(Error::PositionOutOfRange {
+                    position: pos.number,
+                    min: 1,
+                    max: len,
+                });
+            }
+            let found = &residues[(pos.number - 1) as usize..pos.number as usize];
+            if aa != found {
+                return Err(Error::ProteinReferenceError(format!(
+                    "{} at position {} of {} but {} given in {}",
+                    found, pos.number, accession.value, aa, var
+                )));
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -234,6 +464,233 @@ impl Validator for FullValidator {
     }
 }
 
+#[cfg(test)]
+mod test {
+    use std::{str::FromStr, sync::Arc};
+
+    use anyhow::Error;
+    use test_log::test;
+
+    use crate::{
+        data::{gff3, uta_sr::test_helpers::build_provider},
+        parser::HgvsVariant,
+    };
+
+    use super::{
+        is_near_position_limit, BoundDirection, ExtrinsicValidator, IntrinsicValidator, Validator,
+    };
+
+    #[test]
+    fn intrinsic_large_position() -> Result<(), Error> {
+        let validator = IntrinsicValidator::new(true);
+
+        let var = HgvsVariant::from_str("NC_000001.11:g.2147000000_2147000010del")?;
+        assert!(is_near_position_limit(&var));
+        assert!(validator.validate(&var).is_ok());
+
+        let var = HgvsVariant::from_str("NC_000001.11:g.1999999999_2000000000del")?;
+        assert!(is_near_position_limit(&var));
+        let var = HgvsVariant::from_str("NC_000001.11:g.100_110del")?;
+        assert!(!is_near_position_limit(&var));
+        assert!(validator.validate(&var).is_ok());
+
+        Ok(())
+    }
+
+    // ENST00000421528 (OMA1) has a CDS of `n.1_985` and a length of 2105 bp.
+
+    #[test]
+    fn check_tx_bound_inside() -> Result<(), Error> {
+        let validator = ExtrinsicValidator::new(true, build_provider()?);
+
+        for s in [
+            "ENST00000421528:n.1del",
+            "ENST00000421528:n.2105del",
+            "ENST00000421528:c.1del",
+            "ENST00000421528:c.*1120del",
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            assert!(validator.check_tx_bound(&var).is_ok(), "{}", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_tx_bound_outside() -> Result<(), Error> {
+        let validator = ExtrinsicValidator::new(true, build_provider()?);
+
+        for s in [
+            "ENST00000421528:n.2106del",
+            "ENST00000421528:n.2105_2106del",
+            "ENST00000421528:c.-1del",
+            "ENST00000421528:c.*1121del",
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            assert!(
+                matches!(
+                    validator.check_tx_bound(&var),
+                    Err(super::Error::TxBoundsViolation(_))
+                ),
+                "{}",
+                s
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_ref_valid() -> Result<(), Error> {
+        let validator = ExtrinsicValidator::new(true, build_provider()?);
+
+        for s in [
+            "NM_022464.4:c.1A>G",
+            "NM_022464.4:c.3G>A",
+            "NM_022464.4:c.1_3delATG",
+            "NM_022464.4:c.1_3del",
+            "NM_022464.4:c.1_3del3",
+            "NM_022464.4:c.1_2insT",
+            "NM_022464.4:c.1-5C>G",
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            assert!(validator.check_ref(&var).is_ok(), "{}", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_ref_mismatch() -> Result<(), Error> {
+        let validator = ExtrinsicValidator::new(true, build_provider()?);
+
+        let var = HgvsVariant::from_str("NM_022464.4:c.1C>G")?;
+        match validator.check_ref(&var) {
+            Err(super::Error::ReferenceMismatch { expected, found }) => {
+                assert_eq!(expected, "A");
+                assert_eq!(found, "C");
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        let var = HgvsVariant::from_str("NM_022464.4:c.1_3delATC")?;
+        match validator.check_ref(&var) {
+            Err(super::Error::ReferenceMismatch { expected, found }) => {
+                assert_eq!(expected, "ATG");
+                assert_eq!(found, "ATC");
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        let var = HgvsVariant::from_str("NM_022464.4:c.1C>G")?;
+        let lenient = ExtrinsicValidator::new(false, build_provider()?);
+        assert!(lenient.validate(&var).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_tx_bound_strict_and_lenient() -> Result<(), Error> {
+        let var = HgvsVariant::from_str("ENST00000421528:n.2106del")?;
+
+        let strict = ExtrinsicValidator::new(true, build_provider()?);
+        assert!(strict.validate(&var).is_err());
+
+        let lenient = ExtrinsicValidator::new(false, build_provider()?);
+        assert!(lenient.validate(&var).is_ok());
+
+        Ok(())
+    }
+
+    // `TX1.1` has a 5' UTR of 20 bp, a CDS of 66 bp, and a 3' UTR of 14 bp.
+
+    #[test]
+    fn check_cds_bound() -> Result<(), Error> {
+        let provider = gff3::Provider::new(gff3::Config {
+            gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+        let validator = ExtrinsicValidator::new(true, Arc::new(provider));
+
+        for s in [
+            "TX1.1:c.-20A>T",
+            "TX1.1:c.1A>T",
+            "TX1.1:c.66A>T",
+            "TX1.1:c.*10A>T",
+            "TX1.1:c.*14A>T",
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            assert!(validator.check_cds_bound(&var).is_ok(), "{}", s);
+        }
+
+        for (s, position, bound, direction) in [
+            ("TX1.1:c.10000A>T", 10000, 66, BoundDirection::After),
+            ("TX1.1:c.60_67del", 67, 66, BoundDirection::After),
+            ("TX1.1:c.-21A>T", -21, -20, BoundDirection::Before),
+            ("TX1.1:c.*15A>T", 15, 14, BoundDirection::After),
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            match validator.check_cds_bound(&var) {
+                Err(super::Error::CdsBoundsViolation {
+                    position: p,
+                    bound: b,
+                    direction: d,
+                }) => assert_eq!((p, b, d), (position, bound, direction), "{}", s),
+                res => panic!("unexpected result for {}: {:?}", s, res),
+            }
+        }
+
+        Ok(())
+    }
+
+    // `PX1.1` is encoded by `TX1.1` and has the sequence `MKGYRQRHVRRPLRQRFRRCL*`.
+
+    #[test]
+    fn check_prot_ref() -> Result<(), Error> {
+        let provider = gff3::Provider::new(gff3::Config {
+            gff3_path: String::from("tests/data/data/gff3/two_versions.gff3"),
+            fasta_path: String::from("tests/data/data/gff3/single_exon.fa"),
+        })?;
+        let validator = ExtrinsicValidator::new(true, Arc::new(provider));
+
+        for s in [
+            "PX1.1:p.Leu13Arg",
+            "PX1.1:p.L13R",
+            "PX1.1:p.Met1_Lys2del",
+            "PX1.1:p.Ter22Gln",
+            "PX1.1:p.?",
+            "PX1.1:p.Met1?",
+        ] {
+            let var = HgvsVariant::from_str(s)?;
+            assert!(validator.validate(&var).is_ok(), "{}", s);
+        }
+
+        let var = HgvsVariant::from_str("PX1.1:p.Pro13Arg")?;
+        assert!(matches!(
+            validator.check_prot_ref(&var),
+            Err(super::Error::ProteinReferenceError(_))
+        ));
+        let var = HgvsVariant::from_str("PX1.1:p.Leu30Arg")?;
+        assert!(matches!(
+            validator.check_prot_ref(&var),
+            Err(super::Error::PositionOutOfRange {
+                position: 30,
+                min: 1,
+                max: 21
+            })
+        ));
+        let var = HgvsVariant::from_str("PX9.1:p.Leu13Arg")?;
+        assert!(validator.check_prot_ref(&var).is_err());
+
+        let lenient = ExtrinsicValidator::new(false, validator.mapper.provider());
+        assert!(lenient
+            .validate(&HgvsVariant::from_str("PX1.1:p.Pro13Arg")?)
+            .is_ok());
+
+        Ok(())
+    }
+}
+
 // <LICENSE>
 // Copyright 2023 hgvs-rs Contributors
 // Copyright 2014 Bioutils Contributors